@@ -1,15 +1,140 @@
+use crate::daily::{self, DailyChallenge};
+use crate::i18n::{Language, Strings};
+use crate::keymap::KeyMap;
+use crate::persistence::{self, SavedBoard, SavedSession, SavedTour};
+use crate::puzzles::eight_queens;
+use crate::puzzles::missionaries_cannibals;
+use crate::puzzles::knights_tour;
+use crate::puzzles::lights_out;
+use crate::puzzles::maze;
+use crate::puzzles::sokoban;
+use crate::puzzles::sudoku;
+use crate::puzzles::tower_of_hanoi;
+use crate::puzzles::water_jug::{self, RIVER};
+use crate::puzzles::xor_tic_tac_toe::{TranspositionTable, FULL_DEPTH};
 use crate::puzzles::{
-    BoatMove, EightPuzzleState, EightQueensState, MissionariesCannibalsState, PlaceQueen, Player, PuzzleId, PuzzleRegistry, SlideMove, XorTicTacToeState, WINNING_LINES,
+    BoatMove, EightPuzzleHeuristic, EightPuzzleState, EightQueensState, HanoiMove, HanoiState, KnightMove, KnightsTourState, LightsOutState, MazeMove, MazeState, MissionariesCannibalsState, MoveError, PlaceQueen, Player, PuzzleId, PuzzleRegistry, SlideMove, SokobanMove, SokobanState, SudokuMove, SudokuState, WaterJugMove, WaterJugState, XorTicTacToeState,
 };
 use crate::search::{
-    solver::{astar, SearchReport},
+    solver::{astar, astar_cancellable, astar_limited, CancellationToken, astar_with_limits, greedy_best_first, ida_star, uniform_cost, verify_solution, AStarSearch, AstarConfig, SearchOutcome, SearchReport, StepResult, DEFAULT_NODE_LIMIT},
     SearchState,
 };
-use rand::thread_rng;
+use crate::settings::{Settings, SettingsField};
+use crate::stats::{self, BestStats, SolveRecord, StatsError};
+use crate::theme::Theme;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
 use std::hash::{Hash, Hasher};
-use std::collections::{BinaryHeap, HashMap};
-use std::cmp::Ordering;
-use std::time::Instant;
+use std::path::Path;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long a hint stays highlighted on the board.
+const HINT_DURATION: Duration = Duration::from_millis(1500);
+
+/// How long the status line stays green after a personal best.
+const BEST_FLASH_DURATION: Duration = Duration::from_secs(3);
+
+/// Input poll timeout without `--fps`, i.e. 20 frames per second.
+pub const DEFAULT_FRAME_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Range accepted by `--fps`.
+pub const MIN_FPS: u32 = 1;
+pub const MAX_FPS: u32 = 240;
+
+/// Reseeds `rng` for a shuffle, from `seed` when one is pinned or from a fresh
+/// seed otherwise, and returns the seed used so the shuffle can be repeated.
+fn reseed_for_shuffle(rng: &mut StdRng, seed: Option<u64>) -> u64 {
+    use rand::Rng;
+    let seed = seed.unwrap_or_else(|| rng.gen());
+    *rng = StdRng::seed_from_u64(seed);
+    seed
+}
+
+/// Moves a row or column by `delta` on a board `size` cells wide, wrapping
+/// past the edges when `wrap` is set and stopping at them otherwise.
+fn step_cursor(position: isize, delta: isize, size: isize, wrap: bool) -> isize {
+    if wrap {
+        (position + delta).rem_euclid(size)
+    } else {
+        (position + delta).clamp(0, size - 1)
+    }
+}
+
+fn seed_status(seed: Option<u64>) -> String {
+    match seed {
+        Some(seed) => format!("Seed set to {}; every shuffle now starts from it.", seed),
+        None => "Seed cleared; shuffles are random again.".into(),
+    }
+}
+
+/// Why `mv` cannot leave `state`, e.g. "Cannot move 2 missionaries — only 1
+/// on the left bank."
+fn crossing_refusal(state: &MissionariesCannibalsState, mv: BoatMove, err: MoveError) -> String {
+    let (bank, waiting_m, waiting_c) = if state.boat_left {
+        ("left", state.left_m, state.left_c)
+    } else {
+        ("right", state.right_m(), state.right_c())
+    };
+    let load = mv.missionaries + mv.cannibals;
+    let short = |count: u8, waiting: u8, one: &str, many: &str| {
+        format!(
+            "Cannot move {} {} — only {} on the {} bank.",
+            count,
+            if count == 1 { one } else { many },
+            waiting,
+            bank
+        )
+    };
+    match err {
+        MoveError::InvalidQuantity if load == 0 => "The boat cannot cross empty.".into(),
+        MoveError::InvalidQuantity if load > state.boat_capacity => {
+            format!("Cannot move {} people — the boat holds {}.", load, state.boat_capacity)
+        }
+        MoveError::InvalidQuantity if mv.missionaries > waiting_m => {
+            short(mv.missionaries, waiting_m, "missionary", "missionaries")
+        }
+        MoveError::InvalidQuantity if mv.cannibals > waiting_c => short(mv.cannibals, waiting_c, "cannibal", "cannibals"),
+        MoveError::ConflictsExist => {
+            // The bank the boat leaves loses the load; the other one gains it
+            let (left_m, left_c) = if state.boat_left {
+                (state.left_m - mv.missionaries, state.left_c - mv.cannibals)
+            } else {
+                (state.left_m + mv.missionaries, state.left_c + mv.cannibals)
+            };
+            let outnumbered = if left_m > 0 && left_c > left_m { "left" } else { "right" };
+            format!(
+                "Cannot move {} — cannibals would outnumber missionaries on the {} bank.",
+                mv.passengers(),
+                outnumbered
+            )
+        }
+        err => format!("Cannot move {}: {}.", mv.passengers(), err),
+    }
+}
+
+/// Status line for a search that ended without a solution: `no_solution`
+/// when the search ran to completion, otherwise why it stopped early.
+fn unsolved_status<S: SearchState>(report: &SearchReport<S>, no_solution: &str) -> String {
+    match report.outcome {
+        SearchOutcome::NodeLimit => format!(
+            "Node limit reached: gave up after expanding {} nodes without a solution.",
+            report.expanded_nodes
+        ),
+        SearchOutcome::TimedOut => format!(
+            "Timed out after {:.0}s ({} nodes expanded) without a solution.",
+            report.elapsed.as_secs_f64(),
+            report.expanded_nodes
+        ),
+        SearchOutcome::Cancelled => "Solver cancelled.".into(),
+        SearchOutcome::Solved | SearchOutcome::NoSolution => no_solution.into(),
+    }
+}
 
 fn format_player(player: Player) -> &'static str {
     match player {
@@ -18,11 +143,24 @@ fn format_player(player: Player) -> &'static str {
     }
 }
 
+/// Missionaries & Cannibals state as a small table for solution exports.
+fn mc_table(state: &MissionariesCannibalsState) -> String {
+    let row = |bank: &str, m, c, boat_here| {
+        format!("{:<7}{}  {}{}", bank, m, c, if boat_here { "  boat" } else { "" })
+    };
+    format!(
+        "       M  C\n{}\n{}",
+        row("Left", state.left_m, state.left_c, state.boat_left),
+        row("Right", state.right_m(), state.right_c(), !state.boat_left)
+    )
+}
+
 // Wrapper for EightPuzzleState with custom goal
 #[derive(Debug, Clone)]
 struct CustomGoalState {
     state: EightPuzzleState,
     goal: EightPuzzleState,
+    heuristic: EightPuzzleHeuristic,
 }
 
 impl PartialEq for CustomGoalState {
@@ -43,24 +181,11 @@ impl SearchState for CustomGoalState {
     type Move = SlideMove;
 
     fn is_goal(&self) -> bool {
-        self.state.tiles == self.goal.tiles
+        self.state == self.goal
     }
 
     fn heuristic(&self) -> u32 {
-        // Manhattan distance to custom goal
-        self.state
-            .tiles
-            .iter()
-            .enumerate()
-            .filter(|(_, &tile)| tile != 0)
-            .map(|(idx, &tile)| {
-                // Find where this tile should be in goal
-                let goal_idx = self.goal.tiles.iter().position(|&t| t == tile).unwrap_or(idx);
-                let (row, col) = (idx / 3, idx % 3);
-                let (goal_row, goal_col) = (goal_idx / 3, goal_idx % 3);
-                (row.abs_diff(goal_row) + col.abs_diff(goal_col)) as u32
-            })
-            .sum()
+        self.heuristic.estimate(&self.state, &self.goal)
     }
 
     fn successors(&self) -> Vec<(Self::Move, Self)> {
@@ -70,13 +195,14 @@ impl SearchState for CustomGoalState {
         let mut next_states = Vec::new();
 
         let mut push_state = |mv: SlideMove, target_idx: usize| {
-            let mut new_tiles = self.state.tiles;
-            new_tiles.swap(blank, target_idx);
+            let mut next = self.state;
+            next.swap_tiles(blank, target_idx);
             next_states.push((
                 mv,
                 CustomGoalState {
-                    state: EightPuzzleState { tiles: new_tiles },
+                    state: next,
                     goal: self.goal,
+                    heuristic: self.heuristic,
                 },
             ));
         };
@@ -98,395 +224,3797 @@ impl SearchState for CustomGoalState {
     }
 }
 
-fn astar_custom_goal(start: CustomGoalState) -> SearchReport<CustomGoalState> {
-    let start_time = Instant::now();
-    let mut open = BinaryHeap::new();
-    let mut came_from: HashMap<CustomGoalState, (Option<CustomGoalState>, u32)> = HashMap::new();
-
-    open.push(FrontierEntry {
-        g_cost: 0,
-        h_cost: start.heuristic(),
-        state: start.clone(),
-    });
-    came_from.insert(start.clone(), (None, 0));
-
-    let mut expanded = 0usize;
-
-    #[derive(Clone)]
-    struct FrontierEntry {
-        state: CustomGoalState,
-        g_cost: u32,
-        h_cost: u32,
+/// One IDA* pass: extends `path` depth-first while g + h stays within `bound`,
+/// collecting up to `max` paths that reach the goal. Returns the smallest f
+/// that went over the bound, the next bound to try, if any.
+fn collect_paths_within(
+    path: &mut Vec<CustomGoalState>,
+    bound: u32,
+    max: usize,
+    found: &mut Vec<Vec<EightPuzzleState>>,
+) -> Option<u32> {
+    let node = path.last()?.clone();
+    let f_cost = (path.len() - 1) as u32 + node.heuristic();
+    if f_cost > bound {
+        return Some(f_cost);
     }
-
-    impl FrontierEntry {
-        fn f_cost(&self) -> u32 {
-            self.g_cost + self.h_cost
-        }
+    if node.is_goal() {
+        found.push(path.iter().map(|step| step.state).collect());
+        return None;
     }
-
-    impl Eq for FrontierEntry {}
-    impl PartialEq for FrontierEntry {
-        fn eq(&self, other: &Self) -> bool {
-            self.f_cost() == other.f_cost() && self.h_cost == other.h_cost
+    let mut next_bound: Option<u32> = None;
+    for (_, successor) in node.successors() {
+        if found.len() >= max {
+            break;
         }
-    }
-
-    impl Ord for FrontierEntry {
-        fn cmp(&self, other: &Self) -> Ordering {
-            other
-                .f_cost()
-                .cmp(&self.f_cost())
-                .then_with(|| other.h_cost.cmp(&self.h_cost))
+        if path.contains(&successor) {
+            continue;
         }
-    }
-
-    impl PartialOrd for FrontierEntry {
-        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-            Some(self.cmp(other))
+        path.push(successor);
+        if let Some(over) = collect_paths_within(path, bound, max, found) {
+            next_bound = Some(next_bound.map_or(over, |known| known.min(over)));
         }
+        path.pop();
     }
+    next_bound
+}
+
+/// Slides that turn each board of `path` into the next one.
+fn slides_along(path: &[EightPuzzleState]) -> Vec<SlideMove> {
+    path.windows(2)
+        .filter_map(|pair| {
+            pair[0]
+                .successors()
+                .into_iter()
+                .find(|(_, next)| *next == pair[1])
+                .map(|(mv, _)| mv)
+        })
+        .collect()
+}
 
-    while let Some(entry) = open.pop() {
-        let current_state = entry.state;
+/// Status messages kept per puzzle so ones that flash by can be read back.
+pub const STATUS_LOG_LEN: usize = 10;
 
-        let (_, recorded_cost) = came_from
-            .get(&current_state)
-            .cloned()
-            .unwrap_or((None, u32::MAX));
+/// The last [`STATUS_LOG_LEN`] status messages of one puzzle, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct StatusLog {
+    entries: VecDeque<String>,
+    /// How many messages back from the newest the Status block is showing.
+    scroll: usize,
+}
 
-        if entry.g_cost > recorded_cost {
-            continue;
+impl StatusLog {
+    /// Appends `status` unless it is already the newest message, and jumps
+    /// back to it.
+    pub fn record(&mut self, status: &str) {
+        if self.entries.back().is_some_and(|last| last == status) {
+            return;
         }
-
-        if current_state.is_goal() {
-            return SearchReport {
-                path: reconstruct_path_custom(&came_from, current_state),
-                expanded_nodes: expanded,
-                visited_states: came_from.len(),
-                goal_found: true,
-                elapsed: start_time.elapsed(),
-            };
+        if self.entries.len() == STATUS_LOG_LEN {
+            self.entries.pop_front();
         }
+        self.entries.push_back(status.to_string());
+        self.scroll = 0;
+    }
 
-        expanded += 1;
+    pub fn scroll_back(&mut self) {
+        self.scroll = (self.scroll + 1).min(self.entries.len().saturating_sub(1));
+    }
 
-        for (_, successor) in current_state.successors() {
-            let tentative_cost = entry.g_cost.saturating_add(1);
-            let needs_update = match came_from.get(&successor) {
-                Some((_, known_cost)) => tentative_cost < *known_cost,
-                None => true,
-            };
+    pub fn scroll_forward(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
 
-            if needs_update {
-                came_from.insert(successor.clone(), (Some(current_state.clone()), tentative_cost));
-                open.push(FrontierEntry {
-                    h_cost: successor.heuristic(),
-                    g_cost: tentative_cost,
-                    state: successor,
-                });
-            }
-        }
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.scroll = 0;
     }
 
-    SearchReport {
-        path: Vec::new(),
-        expanded_nodes: expanded,
-        visited_states: came_from.len(),
-        goal_found: false,
-        elapsed: start_time.elapsed(),
+    pub fn len(&self) -> usize {
+        self.entries.len()
     }
-}
 
-fn reconstruct_path_custom(
-    came_from: &HashMap<CustomGoalState, (Option<CustomGoalState>, u32)>,
-    mut current: CustomGoalState,
-) -> Vec<CustomGoalState> {
-    let mut path = vec![current.clone()];
-    while let Some((Some(parent), _)) = came_from.get(&current) {
-        current = parent.clone();
-        path.push(current.clone());
+    /// Older message being read back, with how many messages newer ones
+    /// follow it; `None` while the newest message is shown.
+    pub fn scrolled_entry(&self) -> Option<(usize, &str)> {
+        if self.scroll == 0 {
+            return None;
+        }
+        let index = self.entries.len().checked_sub(self.scroll + 1)?;
+        self.entries.get(index).map(|entry| (self.scroll, entry.as_str()))
     }
-    path.reverse();
-    path
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AppRoute {
     MainMenu,
+    Settings,
+    Statistics,
     Puzzle(PuzzleId),
     Quit,
 }
 
 pub struct App {
     pub registry: PuzzleRegistry,
-    pub route: AppRoute,
+    /// Screens opened on top of each other; the last one is shown, and
+    /// [`App::pop_route`] goes back to the one below it.
+    pub route_stack: Vec<AppRoute>,
     pub eight_puzzle: EightPuzzleSession,
     pub xor_ttt: XorTicTacToeSession,
     pub missionaries_cannibals: MissionariesCannibalsSession,
     pub eight_queens: EightQueensSession,
+    pub tower_of_hanoi: HanoiSession,
+    pub knights_tour: KnightsTourSession,
+    pub water_jug: WaterJugSession,
+    pub lights_out: LightsOutSession,
+    pub sokoban: SokobanSession,
+    pub sudoku: SudokuSession,
+    pub maze: MazeSession,
+    /// Digits typed so far while asking for a shuffle seed.
+    pub seed_prompt: Option<String>,
+    pub keymap: KeyMap,
+    /// Key reference popup drawn over the current screen.
+    pub show_help: bool,
+    pub theme: Theme,
+    /// Language of menus and panel titles; starts from the settings and
+    /// `--lang` overrides it for one run.
+    pub language: Language,
+    /// Draw boards with plain ASCII (`+---+`, `|`) for terminals without
+    /// Unicode fonts.
+    pub ascii_mode: bool,
+    /// "Quit? (y/n)" dialog shown after Q, before actually exiting.
+    pub confirm_quit: bool,
+    /// Preferences from `config.toml`, edited on the Settings screen.
+    pub settings: Settings,
+    /// Board cursors wrap to the opposite edge instead of stopping there.
+    pub wrap_cursor: bool,
+    /// Recent status messages of each puzzle, filled by [`App::log_status`].
+    pub status_logs: HashMap<PuzzleId, StatusLog>,
+    /// All-time records, saved to `stats.json` on exit.
+    pub stats: HashMap<PuzzleId, BestStats>,
+    /// Files that could not be read at startup, listed on the main menu.
+    pub startup_warnings: Vec<String>,
+    /// When a personal best was last beaten, for the green status flash.
+    pub best_beaten_at: Option<Instant>,
+    pub app_start: Instant,
+    /// Solves counted since the app started, over all puzzles.
+    pub session_solves: usize,
+    /// Up to when [`App::track_time_spent`] has counted time.
+    time_tracked_at: Instant,
+    /// Challenge opened from the menu today, if any.
+    pub daily: Option<DailyChallenge>,
+    /// How long the event loop waits for input before checking for animation;
+    /// set from `--fps`.
+    pub frame_interval: Duration,
 }
 
 impl Default for App {
     fn default() -> Self {
-        Self {
+        let settings = Settings::load();
+        let mut app = Self {
             registry: PuzzleRegistry::default(),
-            route: AppRoute::MainMenu,
+            route_stack: vec![AppRoute::MainMenu],
             eight_puzzle: EightPuzzleSession::randomized(),
             xor_ttt: XorTicTacToeSession::default(),
             missionaries_cannibals: MissionariesCannibalsSession::default(),
             eight_queens: EightQueensSession::default(),
-        }
+            tower_of_hanoi: HanoiSession::default(),
+            knights_tour: KnightsTourSession::default(),
+            water_jug: WaterJugSession::default(),
+            lights_out: LightsOutSession::default(),
+            sokoban: SokobanSession::default(),
+            sudoku: SudokuSession::default(),
+            maze: MazeSession::default(),
+            seed_prompt: None,
+            keymap: KeyMap::default(),
+            show_help: false,
+            theme: Theme::from_kind(settings.theme),
+            language: settings.language,
+            ascii_mode: false,
+            confirm_quit: false,
+            settings,
+            wrap_cursor: false,
+            status_logs: HashMap::new(),
+            stats: HashMap::new(),
+            startup_warnings: Vec::new(),
+            best_beaten_at: None,
+            app_start: Instant::now(),
+            session_solves: 0,
+            time_tracked_at: Instant::now(),
+            daily: None,
+            frame_interval: settings.frame_interval(),
+        };
+        app.eight_queens.algorithm = settings.default_algorithm;
+        app
     }
 }
 
 impl App {
-    pub fn select_main_menu(&mut self) {
-        self.route = AppRoute::MainMenu;
+    /// Like [`App::default`], but every board shuffle is reproducible from `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        use rand::Rng;
+        let mut seeds = StdRng::seed_from_u64(seed);
+        let mut next_rng = || StdRng::seed_from_u64(seeds.gen());
+        let mut app = Self {
+            eight_puzzle: EightPuzzleSession::with_rng(next_rng()),
+            ..Self::default()
+        };
+        app.xor_ttt.rng = next_rng();
+        app.missionaries_cannibals.rng = next_rng();
+        app.eight_queens.rng = next_rng();
+        app.lights_out = LightsOutSession::with_rng(next_rng());
+        app.maze = MazeSession::with_rng(next_rng());
+        app
     }
 
-    pub fn select_puzzle(&mut self, puzzle: PuzzleId) {
-        self.route = AppRoute::Puzzle(puzzle);
+    /// Screen on top of the stack; [`AppRoute::Quit`] once it is empty.
+    pub fn current_route(&self) -> AppRoute {
+        self.route_stack.last().copied().unwrap_or(AppRoute::Quit)
     }
 
-    pub fn request_quit(&mut self) {
-        self.route = AppRoute::Quit;
+    pub fn push_route(&mut self, route: AppRoute) {
+        self.route_stack.push(route);
     }
 
-    pub fn should_exit(&self) -> bool {
-        self.route == AppRoute::Quit
+    /// Closes the current screen and returns to the one it was opened from.
+    pub fn pop_route(&mut self) -> Option<AppRoute> {
+        self.route_stack.pop()
     }
-}
 
-#[derive(Debug, Clone)]
-pub struct EightPuzzleSolution {
-    pub report: SearchReport<EightPuzzleState>,
-    pub step: usize,
-}
-
-impl EightPuzzleSolution {
-    pub fn total_steps(&self) -> usize {
-        self.report.path.len().saturating_sub(1)
+    /// Opens `puzzle`. One puzzle replaces another instead of stacking on
+    /// it, so Back still leads to the screen the first was opened from.
+    pub fn select_puzzle(&mut self, puzzle: PuzzleId) {
+        if puzzle == PuzzleId::DailyChallenge {
+            self.start_daily_challenge();
+            return;
+        }
+        if matches!(self.current_route(), AppRoute::Puzzle(_)) {
+            self.pop_route();
+        }
+        self.push_route(AppRoute::Puzzle(puzzle));
     }
-}
 
-#[derive(Debug)]
-pub struct EightPuzzleSession {
-    pub start: EightPuzzleState,
-    pub current: EightPuzzleState,
-    pub goal_state: EightPuzzleState,
-    pub moves_made: usize,
-    pub status: String,
-    pub solution: Option<EightPuzzleSolution>,
-    pub selected_cell: usize,
-    pub goal_selected_cell: usize,
-    pub editing_goal: bool,
-}
+    /// Loads today's board, the same for everyone on that date, and opens
+    /// its puzzle. The puzzle is one of [`daily::PUZZLES`], drawn from the
+    /// date seed like the board itself.
+    pub fn start_daily_challenge(&mut self) {
+        use rand::seq::SliceRandom;
+        let date = daily::today();
+        let mut rng = StdRng::seed_from_u64(daily::seed_for(date));
+        let puzzle = *daily::PUZZLES.choose(&mut rng).expect("daily puzzle list is not empty");
+        let (board, par) = match puzzle {
+            PuzzleId::LightsOut => {
+                let state = LightsOutState::random_solvable(lights_out::DEFAULT_SIZE, &mut rng);
+                self.lights_out.load(state, 0);
+                let par = state.solve_gaussian_elimination().map(|presses| presses.len());
+                (SavedBoard::LightsOut(state), par)
+            }
+            PuzzleId::Maze => {
+                let state = MazeState::generate(maze::DEFAULT_SIZE, &mut rng);
+                self.maze.load(state.clone(), 0);
+                let report = astar(state.clone());
+                (SavedBoard::Maze(state), report.goal_found.then_some(report.actions.len()))
+            }
+            _ => {
+                let state = EightPuzzleState::random_solvable(&mut rng);
+                self.eight_puzzle.load(state, 0);
+                self.eight_puzzle.goal_state = EightPuzzleState::default();
+                let report = astar(state);
+                (SavedBoard::EightPuzzle(state), report.goal_found.then_some(report.actions.len()))
+            }
+        };
+        let challenge = DailyChallenge { date, board, par };
+        if let Some(status) = self.status_mut(puzzle) {
+            *status = match par {
+                Some(par) => format!("{}: solve this board by hand. Par is {} moves.", challenge.title(), par),
+                None => format!("{}: solve this board by hand.", challenge.title()),
+            };
+        }
+        self.daily = Some(challenge);
+        self.select_puzzle(puzzle);
+    }
 
-impl EightPuzzleSession {
-    fn base_message() -> String {
-        "Use arrows to select cell, 1-8 to place number. Tab switches boards. R resets, N shuffles, S solves, Space replays.".into()
+    /// Today's challenge, while `puzzle` still shows the board it started
+    /// from; a new or edited board leaves the challenge.
+    pub fn daily_in_play(&self, puzzle: PuzzleId) -> Option<&DailyChallenge> {
+        let challenge = self.daily.as_ref()?;
+        let in_play = match &challenge.board {
+            SavedBoard::EightPuzzle(start) => {
+                let session = &self.eight_puzzle;
+                // Shuffling keeps `start`, so check the moves began there too
+                puzzle == PuzzleId::EightPuzzle
+                    && session.start == *start
+                    && session.goal_state == EightPuzzleState::default()
+                    && session.history.first().is_none_or(|first| first == start)
+            }
+            SavedBoard::LightsOut(start) => puzzle == PuzzleId::LightsOut && self.lights_out.start == *start,
+            SavedBoard::Maze(start) => puzzle == PuzzleId::Maze && self.maze.start == *start,
+            _ => false,
+        };
+        in_play.then_some(challenge)
     }
 
-    fn random_state() -> EightPuzzleState {
-        let mut rng = thread_rng();
-        EightPuzzleState::random_solvable(&mut rng)
+    fn timer(&self, puzzle: PuzzleId) -> Option<&MoveTimer> {
+        match puzzle {
+            PuzzleId::EightPuzzle => Some(&self.eight_puzzle.timer),
+            PuzzleId::LightsOut => Some(&self.lights_out.timer),
+            PuzzleId::Maze => Some(&self.maze.timer),
+            _ => None,
+        }
     }
 
-    pub fn randomized() -> Self {
-        let state = Self::random_state();
-        Self {
-            start: state,
-            current: state,
-            goal_state: EightPuzzleState::default(), // Default goal [1,2,3,4,5,6,7,8,0]
-            moves_made: 0,
-            status: Self::base_message(),
-            solution: None,
-            selected_cell: 0,
-            goal_selected_cell: 0,
-            editing_goal: false,
+    /// Congratulates a hand solve of today's board and keeps its time under
+    /// [`PuzzleId::DailyChallenge`] in the records.
+    fn finish_daily_challenge(&mut self, puzzle: PuzzleId, solve: SolveRecord) {
+        let Some(challenge) = self.daily_in_play(puzzle) else {
+            return;
+        };
+        let time = self.timer(puzzle).and_then(MoveTimer::elapsed).unwrap_or_default();
+        let moves = solve.moves.unwrap_or_default();
+        let versus_par = match challenge.par {
+            Some(par) if moves == par => " That is par!".to_string(),
+            Some(par) if moves > par => format!(" {} over par ({}).", moves - par, par),
+            Some(par) => format!(" {} under par ({}).", par - moves, par),
+            None => String::new(),
+        };
+        let message = format!(
+            "Daily challenge complete in {}.{:03} s and {} moves!{}",
+            time.as_secs(),
+            time.subsec_millis(),
+            moves,
+            versus_par
+        );
+        if let Some(status) = self.status_mut(puzzle) {
+            *status = message;
+        }
+        let daily = SolveRecord {
+            moves: solve.moves,
+            time: Some(time),
+        };
+        if self.stats.entry(PuzzleId::DailyChallenge).or_default().record(daily) {
+            self.best_beaten_at = Some(Instant::now());
+            if let Some(status) = self.status_mut(puzzle) {
+                status.push_str(" New best daily time!");
+            }
         }
     }
 
-    pub fn reset(&mut self) {
-        self.current = self.start;
-        self.moves_made = 0;
-        self.solution = None;
-        self.selected_cell = 0;
-        self.editing_goal = false;
-        self.status = "Reset to starting arrangement.".into();
+    pub fn strings(&self) -> &'static Strings {
+        self.language.strings()
     }
 
-    pub fn new_board(&mut self) {
-        let state = Self::random_state();
-        self.start = state;
-        self.current = state;
-        self.moves_made = 0;
-        self.solution = None;
-        self.selected_cell = 0;
-        self.status = "Generated a new solvable board.".into();
+    /// Moves `field` on to its next value, applies it straight away and saves
+    /// `config.toml`. Returns a status line for the Settings screen.
+    pub fn cycle_setting(&mut self, field: SettingsField) -> String {
+        self.settings.cycle(field);
+        let saved = self.save_settings();
+        match field {
+            SettingsField::DefaultAlgorithm => self.eight_queens.algorithm = self.settings.default_algorithm,
+            SettingsField::AnimationSpeed => self.frame_interval = self.settings.frame_interval(),
+            SettingsField::Theme => self.theme = Theme::from_kind(self.settings.theme),
+            SettingsField::Language => self.language = self.settings.language,
+            SettingsField::ConfirmQuit => {}
+        }
+        let value = self.settings.value(field);
+        match saved {
+            Ok(()) => format!("{}: {}.", field.label(), value),
+            Err(err) => format!("{}: {} (not saved: {}).", field.label(), value, err),
+        }
     }
 
-    pub fn shuffle(&mut self) {
-        if self.editing_goal {
-            let state = Self::random_state();
-            self.goal_state = state;
-            self.goal_selected_cell = 0;
-            self.status = "Goal board shuffled randomly.".into();
-        } else {
-            let state = Self::random_state();
-            self.current = state;
-            self.moves_made = 0;
-            self.solution = None;
-            self.selected_cell = 0;
-            self.status = "Board shuffled randomly.".into();
+    fn save_settings(&self) -> Result<(), String> {
+        match Settings::config_path() {
+            Some(path) => persistence::save_config(&self.settings, &path).map_err(|err| err.to_string()),
+            None => Ok(()),
         }
     }
 
-    pub fn toggle_editing_goal(&mut self) {
-        self.editing_goal = !self.editing_goal;
-        if self.editing_goal {
-            self.status = "Now editing goal board. Use Tab to switch back.".into();
-        } else {
-            self.status = "Now editing current board. Use Tab to edit goal.".into();
+    pub fn cycle_theme(&mut self) {
+        self.theme = self.theme.next();
+        self.settings.theme = self.theme.kind;
+        let message = match self.save_settings() {
+            Ok(()) => format!("Theme: {}.", self.theme.kind.label()),
+            Err(err) => format!("Theme: {} (not saved: {}).", self.theme.kind.label(), err),
+        };
+        if let Some(status) = self.active_puzzle().and_then(|puzzle| self.status_mut(puzzle)) {
+            *status = message;
         }
     }
 
-    pub fn move_cursor(&mut self, row_delta: isize, col_delta: isize) {
-        if self.editing_goal {
-            let row = (self.goal_selected_cell / 3) as isize;
-            let col = (self.goal_selected_cell % 3) as isize;
-            let new_row = (row + row_delta).clamp(0, 2);
-            let new_col = (col + col_delta).clamp(0, 2);
-            self.goal_selected_cell = (new_row * 3 + new_col) as usize;
-        } else {
-            let row = (self.selected_cell / 3) as isize;
-            let col = (self.selected_cell % 3) as isize;
-            let new_row = (row + row_delta).clamp(0, 2);
-            let new_col = (col + col_delta).clamp(0, 2);
-            self.selected_cell = (new_row * 3 + new_col) as usize;
+    pub fn toggle_ascii_mode(&mut self) {
+        self.ascii_mode = !self.ascii_mode;
+        let message = if self.ascii_mode { "ASCII boards on." } else { "ASCII boards off." };
+        if let Some(status) = self.active_puzzle().and_then(|puzzle| self.status_mut(puzzle)) {
+            *status = message.into();
         }
     }
 
-    pub fn place_number(&mut self, number: u8) -> bool {
-        if number == 0 || number > 8 {
-            self.status = format!("Invalid number: {}. Use 1-8.", number);
-            return false;
+    pub fn toggle_wrap_cursor(&mut self) {
+        self.wrap_cursor = !self.wrap_cursor;
+        let message = if self.wrap_cursor { "Cursor wraps at board edges." } else { "Cursor stops at board edges." };
+        if let Some(status) = self.active_puzzle().and_then(|puzzle| self.status_mut(puzzle)) {
+            *status = message.into();
         }
+    }
 
-        if self.editing_goal {
-            let current_value = self.goal_state.tiles[self.goal_selected_cell];
-            
-            if current_value == number {
-                self.status = format!("Goal cell already contains {}.", number);
-                return false;
-            }
+    pub fn toggle_help(&mut self) {
+        self.show_help = !self.show_help;
+    }
 
-            if let Some(existing_idx) = self.goal_state.tiles.iter().position(|&t| t == number) {
-                self.goal_state.tiles[self.goal_selected_cell] = number;
-                self.goal_state.tiles[existing_idx] = current_value;
-                self.solution = None;
-                self.status = format!("Goal: Swapped {} with cell {}.", number, existing_idx + 1);
-                return true;
-            }
+    /// Asks for confirmation, unless turned off in the settings; the app only
+    /// exits once [`App::quit`] is called.
+    pub fn request_quit(&mut self) {
+        if self.settings.confirm_quit {
+            self.confirm_quit = true;
+        } else {
+            self.quit();
+        }
+    }
 
-            if current_value == 0 {
-                self.goal_state.tiles[self.goal_selected_cell] = number;
-                self.solution = None;
-                self.status = format!("Goal: Placed {} in cell {}.", number, self.goal_selected_cell + 1);
-                return true;
-            }
+    pub fn cancel_quit(&mut self) {
+        self.confirm_quit = false;
+    }
 
-            self.goal_state.tiles[self.goal_selected_cell] = number;
-            self.solution = None;
-            self.status = format!("Goal: Replaced {} with {} in cell {}.", current_value, number, self.goal_selected_cell + 1);
-            true
-        } else {
-            let current_value = self.current.tiles[self.selected_cell];
-            
-            if current_value == number {
-                self.status = format!("Cell already contains {}.", number);
-                return false;
-            }
+    pub fn quit(&mut self) {
+        self.confirm_quit = false;
+        self.push_route(AppRoute::Quit);
+    }
 
-            if let Some(existing_idx) = self.current.tiles.iter().position(|&t| t == number) {
-                self.current.tiles[self.selected_cell] = number;
-                self.current.tiles[existing_idx] = current_value;
-                self.moves_made += 1;
-                self.solution = None;
-                self.status = format!("Swapped {} with cell {}.", number, existing_idx + 1);
-                return true;
-            }
+    pub fn should_exit(&self) -> bool {
+        self.route_stack.is_empty() || self.route_stack.contains(&AppRoute::Quit)
+    }
 
-            if current_value == 0 {
-                self.current.tiles[self.selected_cell] = number;
-                self.moves_made += 1;
-                self.solution = None;
-                self.status = format!("Placed {} in cell {}.", number, self.selected_cell + 1);
-                return true;
+    /// Moves solves the sessions reported since the last call into
+    /// [`App::stats`].
+    pub fn collect_solves(&mut self) {
+        let solves = [
+            (PuzzleId::EightPuzzle, self.eight_puzzle.new_solve.take()),
+            (PuzzleId::MissionariesCannibals, self.missionaries_cannibals.new_solve.take()),
+            (PuzzleId::EightQueens, self.eight_queens.new_solve.take()),
+            (PuzzleId::TowerOfHanoi, self.tower_of_hanoi.new_solve.take()),
+            (PuzzleId::KnightsTour, self.knights_tour.new_solve.take()),
+            (PuzzleId::WaterJug, self.water_jug.new_solve.take()),
+            (PuzzleId::LightsOut, self.lights_out.new_solve.take()),
+            (PuzzleId::Sokoban, self.sokoban.new_solve.take()),
+            (PuzzleId::Sudoku, self.sudoku.new_solve.take()),
+            (PuzzleId::Maze, self.maze.new_solve.take()),
+        ];
+        for (puzzle, solve) in solves {
+            let Some(solve) = solve else {
+                continue;
+            };
+            self.session_solves += 1;
+            if solve.time.is_none() {
+                self.finish_daily_challenge(puzzle, solve);
             }
+            if self.stats.entry(puzzle).or_default().record(solve) {
+                self.best_beaten_at = Some(Instant::now());
+                if let Some(status) = self.status_mut(puzzle) {
+                    status.push_str(" New personal best!");
+                }
+            }
+        }
+    }
 
-            self.current.tiles[self.selected_cell] = number;
-            self.moves_made += 1;
-            self.solution = None;
-            self.status = format!("Replaced {} with {} in cell {}.", current_value, number, self.selected_cell + 1);
-            true
+    /// Adds the time since the last call to the open puzzle's
+    /// [`BestStats::time_spent`].
+    pub fn track_time_spent(&mut self) {
+        let now = Instant::now();
+        let elapsed = now - self.time_tracked_at;
+        self.time_tracked_at = now;
+        if let Some(puzzle) = self.active_puzzle() {
+            self.stats.entry(puzzle).or_default().time_spent += elapsed;
         }
     }
 
+    /// Forgets every record and saves the empty table straight away.
+    pub fn reset_stats(&mut self) -> Result<(), StatsError> {
+        self.stats.clear();
+        stats::save(&self.stats)
+    }
 
-    pub fn is_solved(&self) -> bool {
-        self.current.tiles == self.goal_state.tiles
+    /// Adds the active puzzle's status message to its log when it changed
+    /// since the last frame.
+    pub fn log_status(&mut self) {
+        let Some(puzzle) = self.active_puzzle() else {
+            return;
+        };
+        let Some(status) = self.status_mut(puzzle).map(|status| status.clone()) else {
+            return;
+        };
+        self.status_logs.entry(puzzle).or_default().record(&status);
     }
 
-    pub fn solve_current(&mut self) {
-        // Create a wrapper state with custom goal
-        let start_state = CustomGoalState {
-            state: self.current,
-            goal: self.goal_state,
+    pub fn scroll_status_log(&mut self, back: bool) {
+        let Some(puzzle) = self.active_puzzle() else {
+            return;
         };
-        let report = astar_custom_goal(start_state);
-        if report.goal_found && !report.path.is_empty() {
-            // Extract the actual states from the wrapper
-            let actual_path: Vec<EightPuzzleState> = report.path.iter().map(|s| s.state).collect();
-            let actual_report = SearchReport {
-                path: actual_path,
-                expanded_nodes: report.expanded_nodes,
-                visited_states: report.visited_states,
-                goal_found: report.goal_found,
-                elapsed: report.elapsed,
-            };
-            self.solution = Some(EightPuzzleSolution { report: actual_report, step: 0 });
-            self.moves_made = 0;
-            if let Some(solution) = &self.solution {
-                if let Some(first) = solution.report.path.first() {
-                    self.current = *first;
-                }
-                self.status = format!(
-                    "Solution ready ({} moves). Press Space to step.",
-                    solution.total_steps()
-                );
-            }
+        let log = self.status_logs.entry(puzzle).or_default();
+        if back {
+            log.scroll_back();
         } else {
-            self.solution = None;
-            self.status = "No solution found (should never happen).".into();
+            log.scroll_forward();
         }
     }
 
-    pub fn advance_solution(&mut self) -> bool {
-        if let Some(solution) = &mut self.solution {
-            if solution.step + 1 < solution.report.path.len() {
-                solution.step += 1;
-                if let Some(state) = solution.report.path.get(solution.step) {
-                    self.current = *state;
-                    self.moves_made = solution.step;
-                    if solution.step == solution.report.path.len() - 1 {
-                        self.status = "Solution complete! Board solved.".into();
+    /// Switches the Solver panel between the latest run and the comparison table.
+    pub fn toggle_comparison(&mut self) {
+        let (mode, runs, status) = match self.active_puzzle() {
+            Some(PuzzleId::EightPuzzle) => {
+                let session = &mut self.eight_puzzle;
+                (session.compare_reports.toggle(), session.compare_reports.rows.len(), &mut session.status)
+            }
+            Some(PuzzleId::MissionariesCannibals) => {
+                let session = &mut self.missionaries_cannibals;
+                (session.compare_reports.toggle(), session.compare_reports.rows.len(), &mut session.status)
+            }
+            Some(PuzzleId::EightQueens) => {
+                let session = &mut self.eight_queens;
+                (session.compare_reports.toggle(), session.compare_reports.rows.len(), &mut session.status)
+            }
+            Some(PuzzleId::TowerOfHanoi) => {
+                let session = &mut self.tower_of_hanoi;
+                (session.compare_reports.toggle(), session.compare_reports.rows.len(), &mut session.status)
+            }
+            Some(PuzzleId::KnightsTour) => {
+                let session = &mut self.knights_tour;
+                (session.compare_reports.toggle(), session.compare_reports.rows.len(), &mut session.status)
+            }
+            Some(PuzzleId::WaterJug) => {
+                let session = &mut self.water_jug;
+                (session.compare_reports.toggle(), session.compare_reports.rows.len(), &mut session.status)
+            }
+            Some(PuzzleId::Sokoban) => {
+                let session = &mut self.sokoban;
+                (session.compare_reports.toggle(), session.compare_reports.rows.len(), &mut session.status)
+            }
+            Some(PuzzleId::Sudoku) => {
+                let session = &mut self.sudoku;
+                (session.compare_reports.toggle(), session.compare_reports.rows.len(), &mut session.status)
+            }
+            Some(PuzzleId::Maze) => {
+                let session = &mut self.maze;
+                (session.compare_reports.toggle(), session.compare_reports.rows.len(), &mut session.status)
+            }
+            _ => return,
+        };
+        *status = match mode {
+            ComparisonMode::Table if runs == 0 => {
+                "Comparison table: solve this board to add a row.".into()
+            }
+            ComparisonMode::Table => format!("Comparison table: {} runs from this start state.", runs),
+            ComparisonMode::Solver => "Showing the latest solver run.".into(),
+        };
+    }
+
+    pub fn clear_status_log(&mut self) {
+        let Some(puzzle) = self.active_puzzle() else {
+            return;
+        };
+        self.status_logs.entry(puzzle).or_default().clear();
+        if let Some(status) = self.status_mut(puzzle) {
+            *status = "Status log cleared.".into();
+        }
+    }
+
+    fn autoplay_mut(&mut self, puzzle: PuzzleId) -> Option<&mut Autoplay> {
+        match puzzle {
+            PuzzleId::EightPuzzle => Some(&mut self.eight_puzzle.autoplay),
+            PuzzleId::MissionariesCannibals => Some(&mut self.missionaries_cannibals.autoplay),
+            PuzzleId::EightQueens => Some(&mut self.eight_queens.autoplay),
+            PuzzleId::TowerOfHanoi => Some(&mut self.tower_of_hanoi.autoplay),
+            PuzzleId::WaterJug => Some(&mut self.water_jug.autoplay),
+            PuzzleId::Sokoban => Some(&mut self.sokoban.autoplay),
+            PuzzleId::Sudoku => Some(&mut self.sudoku.autoplay),
+            PuzzleId::Maze => Some(&mut self.maze.autoplay),
+            _ => None,
+        }
+    }
+
+    /// Auto-play state of the open puzzle, for puzzles that replay a solution.
+    pub fn autoplay(&self) -> Option<&Autoplay> {
+        match self.active_puzzle()? {
+            PuzzleId::EightPuzzle => Some(&self.eight_puzzle.autoplay),
+            PuzzleId::MissionariesCannibals => Some(&self.missionaries_cannibals.autoplay),
+            PuzzleId::EightQueens => Some(&self.eight_queens.autoplay),
+            PuzzleId::TowerOfHanoi => Some(&self.tower_of_hanoi.autoplay),
+            PuzzleId::WaterJug => Some(&self.water_jug.autoplay),
+            PuzzleId::Sokoban => Some(&self.sokoban.autoplay),
+            PuzzleId::Sudoku => Some(&self.sudoku.autoplay),
+            PuzzleId::Maze => Some(&self.maze.autoplay),
+            _ => None,
+        }
+    }
+
+    /// Replays one step of `puzzle`'s solution. Returns whether there was a
+    /// step to take and whether the board is solved after it.
+    fn advance_solution(&mut self, puzzle: PuzzleId) -> (bool, bool) {
+        match puzzle {
+            PuzzleId::EightPuzzle => (self.eight_puzzle.advance_solution(), self.eight_puzzle.is_solved()),
+            PuzzleId::MissionariesCannibals => (
+                self.missionaries_cannibals.advance_solution(),
+                self.missionaries_cannibals.is_solved(),
+            ),
+            PuzzleId::EightQueens => (self.eight_queens.advance_solution(), self.eight_queens.is_solved()),
+            PuzzleId::TowerOfHanoi => (self.tower_of_hanoi.advance_solution(), self.tower_of_hanoi.is_solved()),
+            PuzzleId::WaterJug => (self.water_jug.advance_solution(), self.water_jug.is_solved()),
+            PuzzleId::Sokoban => (self.sokoban.advance_solution(), self.sokoban.is_solved()),
+            PuzzleId::Sudoku => (self.sudoku.advance_solution(), self.sudoku.is_solved()),
+            PuzzleId::Maze => (self.maze.advance_solution(), self.maze.is_solved()),
+            _ => (false, false),
+        }
+    }
+
+    /// Starts replaying the open puzzle's solution on a timer, taking the
+    /// first step straight away, or stops a replay already running.
+    pub fn toggle_autoplay(&mut self) {
+        let Some(puzzle) = self.active_puzzle() else {
+            return;
+        };
+        let Some(autoplay) = self.autoplay_mut(puzzle) else {
+            return;
+        };
+        if autoplay.playing {
+            autoplay.playing = false;
+            if let Some(status) = self.status_mut(puzzle) {
+                *status = "Auto-play stopped.".into();
+            }
+            return;
+        }
+        autoplay.playing = true;
+        self.step_autoplay(puzzle);
+    }
+
+    /// Stops the open puzzle's auto-play, e.g. before a key changes the board.
+    pub fn stop_autoplay(&mut self) {
+        if let Some(autoplay) = self.active_puzzle().and_then(|puzzle| self.autoplay_mut(puzzle)) {
+            autoplay.playing = false;
+        }
+    }
+
+    /// Makes the open puzzle's auto-play faster or slower. Returns whether
+    /// it was playing, so `+` / `-` keep their usual meaning otherwise.
+    pub fn change_autoplay_speed(&mut self, faster: bool) -> bool {
+        let Some(puzzle) = self.active_puzzle() else {
+            return false;
+        };
+        match self.autoplay_mut(puzzle) {
+            Some(autoplay) if autoplay.playing => {
+                autoplay.change_speed(faster);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Takes the open puzzle's next auto-played step once it is due.
+    pub fn poll_autoplay(&mut self) {
+        let Some(puzzle) = self.active_puzzle() else {
+            return;
+        };
+        if self.autoplay_mut(puzzle).is_some_and(|autoplay| autoplay.is_due()) {
+            self.step_autoplay(puzzle);
+        }
+    }
+
+    fn step_autoplay(&mut self, puzzle: PuzzleId) {
+        let (advanced, solved) = self.advance_solution(puzzle);
+        if let Some(autoplay) = self.autoplay_mut(puzzle) {
+            autoplay.last_advance = Instant::now();
+            // The last step leaves its message up rather than "already at the end"
+            autoplay.playing = advanced && !solved;
+        }
+    }
+
+    /// Freezes the move timer of every board that has just been solved.
+    pub fn stop_solved_timers(&mut self) {
+        let solved = [
+            (self.eight_puzzle.is_solved(), &mut self.eight_puzzle.timer),
+            (self.missionaries_cannibals.is_solved(), &mut self.missionaries_cannibals.timer),
+            (self.eight_queens.is_solved(), &mut self.eight_queens.timer),
+            (self.tower_of_hanoi.is_solved(), &mut self.tower_of_hanoi.timer),
+            (self.knights_tour.is_solved(), &mut self.knights_tour.timer),
+            (self.water_jug.is_solved(), &mut self.water_jug.timer),
+            (self.lights_out.is_solved(), &mut self.lights_out.timer),
+            (self.sokoban.is_solved(), &mut self.sokoban.timer),
+            (self.sudoku.is_solved(), &mut self.sudoku.timer),
+            (self.maze.is_solved(), &mut self.maze.timer),
+        ];
+        for (is_solved, timer) in solved {
+            if is_solved {
+                timer.stop();
+            }
+        }
+    }
+
+    pub fn best_stats(&self, puzzle: PuzzleId) -> BestStats {
+        self.stats.get(&puzzle).copied().unwrap_or_default()
+    }
+
+    /// Whether anything on screen changes without input: a background solve,
+    /// a running move timer, a hint highlight or the personal-best flash.
+    pub fn is_animating(&self) -> bool {
+        let timers = [
+            &self.eight_puzzle.timer,
+            &self.missionaries_cannibals.timer,
+            &self.eight_queens.timer,
+            &self.tower_of_hanoi.timer,
+            &self.knights_tour.timer,
+            &self.water_jug.timer,
+            &self.lights_out.timer,
+            &self.sokoban.timer,
+            &self.sudoku.timer,
+            &self.maze.timer,
+        ];
+        self.eight_puzzle.is_solving()
+            || self.autoplay().is_some_and(|autoplay| autoplay.playing)
+            || self.eight_puzzle.active_hint().is_some()
+            || self.eight_queens.active_hint().is_some()
+            || self.best_flash_active()
+            || timers.iter().any(|timer| timer.is_running())
+    }
+
+    /// Whether the status line should still be flashing for a new record.
+    pub fn best_flash_active(&self) -> bool {
+        self.best_beaten_at
+            .is_some_and(|at| at.elapsed() < BEST_FLASH_DURATION)
+    }
+
+    fn active_puzzle(&self) -> Option<PuzzleId> {
+        match self.current_route() {
+            AppRoute::Puzzle(PuzzleId::About | PuzzleId::DailyChallenge) => None,
+            AppRoute::Puzzle(puzzle) => Some(puzzle),
+            _ => None,
+        }
+    }
+
+    fn status_mut(&mut self, puzzle: PuzzleId) -> Option<&mut String> {
+        match puzzle {
+            PuzzleId::EightPuzzle => Some(&mut self.eight_puzzle.status),
+            PuzzleId::XorTicTacToe => Some(&mut self.xor_ttt.status),
+            PuzzleId::MissionariesCannibals => Some(&mut self.missionaries_cannibals.status),
+            PuzzleId::EightQueens => Some(&mut self.eight_queens.status),
+            PuzzleId::TowerOfHanoi => Some(&mut self.tower_of_hanoi.status),
+            PuzzleId::KnightsTour => Some(&mut self.knights_tour.status),
+            PuzzleId::WaterJug => Some(&mut self.water_jug.status),
+            PuzzleId::LightsOut => Some(&mut self.lights_out.status),
+            PuzzleId::Sokoban => Some(&mut self.sokoban.status),
+            PuzzleId::Sudoku => Some(&mut self.sudoku.status),
+            PuzzleId::Maze => Some(&mut self.maze.status),
+            PuzzleId::About | PuzzleId::DailyChallenge => None,
+        }
+    }
+
+    /// Active puzzle whose shuffles can be pinned to a seed.
+    fn seeded_puzzle(&self) -> Option<PuzzleId> {
+        match self.active_puzzle()? {
+            puzzle @ (PuzzleId::EightPuzzle
+            | PuzzleId::MissionariesCannibals
+            | PuzzleId::EightQueens) => Some(puzzle),
+            _ => None,
+        }
+    }
+
+    /// Starts asking for a seed, prefilled with the pinned seed or the last one used.
+    pub fn open_seed_prompt(&mut self) {
+        let Some(puzzle) = self.seeded_puzzle() else {
+            return;
+        };
+        let (seed, last_seed) = match puzzle {
+            PuzzleId::EightPuzzle => (self.eight_puzzle.seed, self.eight_puzzle.last_seed),
+            PuzzleId::MissionariesCannibals => (
+                self.missionaries_cannibals.seed,
+                self.missionaries_cannibals.last_seed,
+            ),
+            _ => (self.eight_queens.seed, self.eight_queens.last_seed),
+        };
+        self.seed_prompt = Some(seed.or(last_seed).map(|seed| seed.to_string()).unwrap_or_default());
+        self.refresh_seed_prompt();
+    }
+
+    pub fn seed_prompt_push(&mut self, digit: char) {
+        if let Some(input) = &mut self.seed_prompt {
+            if digit.is_ascii_digit() && input.len() < 20 {
+                input.push(digit);
+            }
+        }
+        self.refresh_seed_prompt();
+    }
+
+    pub fn seed_prompt_pop(&mut self) {
+        if let Some(input) = &mut self.seed_prompt {
+            input.pop();
+        }
+        self.refresh_seed_prompt();
+    }
+
+    /// Pins the typed seed; an empty prompt goes back to random shuffles.
+    pub fn submit_seed_prompt(&mut self) {
+        let Some(input) = self.seed_prompt.take() else {
+            return;
+        };
+        let seed = if input.is_empty() {
+            None
+        } else {
+            match input.parse() {
+                Ok(seed) => Some(seed),
+                Err(_) => {
+                    if let Some(status) = self.seeded_puzzle().and_then(|puzzle| self.status_mut(puzzle)) {
+                        *status = format!("'{}' is not a valid seed.", input);
+                    }
+                    return;
+                }
+            }
+        };
+        match self.seeded_puzzle() {
+            Some(PuzzleId::EightPuzzle) => self.eight_puzzle.set_seed(seed),
+            Some(PuzzleId::MissionariesCannibals) => self.missionaries_cannibals.set_seed(seed),
+            Some(PuzzleId::EightQueens) => self.eight_queens.set_seed(seed),
+            _ => {}
+        }
+    }
+
+    pub fn cancel_seed_prompt(&mut self) {
+        self.seed_prompt = None;
+        if let Some(status) = self.seeded_puzzle().and_then(|puzzle| self.status_mut(puzzle)) {
+            *status = "Seed unchanged.".into();
+        }
+    }
+
+    fn refresh_seed_prompt(&mut self) {
+        let Some(input) = self.seed_prompt.clone() else {
+            return;
+        };
+        if let Some(status) = self.seeded_puzzle().and_then(|puzzle| self.status_mut(puzzle)) {
+            *status = format!(
+                "Seed: {}_  (Enter pins it, empty for random shuffles, Esc cancels)",
+                input
+            );
+        }
+    }
+
+    /// Writes the active puzzle's board to `path` as JSON, reporting the outcome
+    /// in that puzzle's status panel.
+    pub fn save_session(&mut self, path: &Path) {
+        let Some(puzzle) = self.active_puzzle() else {
+            return;
+        };
+        let (board, moves_made, status) = match puzzle {
+            PuzzleId::EightPuzzle => (
+                SavedBoard::EightPuzzle(self.eight_puzzle.current),
+                self.eight_puzzle.moves_made,
+                self.eight_puzzle.status.clone(),
+            ),
+            PuzzleId::XorTicTacToe => (
+                SavedBoard::XorTicTacToe(self.xor_ttt.state),
+                0,
+                self.xor_ttt.status.clone(),
+            ),
+            PuzzleId::MissionariesCannibals => (
+                SavedBoard::MissionariesCannibals(self.missionaries_cannibals.state),
+                self.missionaries_cannibals.moves_made,
+                self.missionaries_cannibals.status.clone(),
+            ),
+            PuzzleId::EightQueens => (
+                SavedBoard::EightQueens(self.eight_queens.state),
+                0,
+                self.eight_queens.status.clone(),
+            ),
+            PuzzleId::TowerOfHanoi => (
+                SavedBoard::TowerOfHanoi(self.tower_of_hanoi.state.clone()),
+                self.tower_of_hanoi.moves_made,
+                self.tower_of_hanoi.status.clone(),
+            ),
+            PuzzleId::KnightsTour => (
+                SavedBoard::KnightsTour(SavedTour::Sized {
+                    size: self.knights_tour.state.size,
+                    squares: self.knights_tour.tour.clone(),
+                }),
+                self.knights_tour.tour.len().saturating_sub(1),
+                self.knights_tour.status.clone(),
+            ),
+            PuzzleId::WaterJug => (
+                SavedBoard::WaterJug(self.water_jug.state.clone()),
+                0,
+                self.water_jug.status.clone(),
+            ),
+            PuzzleId::LightsOut => (
+                SavedBoard::LightsOut(self.lights_out.state),
+                self.lights_out.moves_made,
+                self.lights_out.status.clone(),
+            ),
+            PuzzleId::Sokoban => (
+                SavedBoard::Sokoban(self.sokoban.state.clone()),
+                self.sokoban.moves_made,
+                self.sokoban.status.clone(),
+            ),
+            PuzzleId::Sudoku => (
+                SavedBoard::Sudoku(self.sudoku.state),
+                self.sudoku.moves_made,
+                self.sudoku.status.clone(),
+            ),
+            PuzzleId::Maze => (
+                SavedBoard::Maze(self.maze.state.clone()),
+                self.maze.moves_made,
+                self.maze.status.clone(),
+            ),
+            PuzzleId::DailyChallenge | PuzzleId::About => return,
+        };
+        let session = SavedSession {
+            board,
+            moves_made,
+            status,
+        };
+        let message = match persistence::write_session(&session, path) {
+            Ok(()) => format!("State saved to {}.", persistence::display_path(path)),
+            Err(err) => format!("Save failed: {}.", err),
+        };
+        if let Some(status) = self.status_mut(puzzle) {
+            *status = message;
+        }
+    }
+
+    /// Writes the active puzzle's solver trace to `./solution_<puzzle>_<timestamp>.txt`.
+    pub fn export_solution(&mut self) {
+        let Some(puzzle) = self.active_puzzle() else {
+            return;
+        };
+        let path = persistence::export_path(puzzle);
+        let name = self
+            .registry
+            .descriptor(puzzle)
+            .map(|descriptor| descriptor.name)
+            .unwrap_or_default();
+        let result = match puzzle {
+            PuzzleId::EightPuzzle => self.eight_puzzle.solution.as_ref().map(|solution| {
+                persistence::export_solution(
+                    name,
+                    &solution.report,
+                    EightPuzzleState::to_string,
+                    |mv| format!("blank {}", mv.label().to_lowercase()),
+                    &path,
+                )
+            }),
+            PuzzleId::MissionariesCannibals => {
+                self.missionaries_cannibals.solution.as_ref().map(|solution| {
+                    persistence::export_solution(
+                        name,
+                        &solution.report,
+                        mc_table,
+                        |mv| format!("boat carries {}M {}C", mv.missionaries, mv.cannibals),
+                        &path,
+                    )
+                })
+            }
+            PuzzleId::EightQueens => self.eight_queens.solution.as_ref().map(|solution| {
+                persistence::export_solution(
+                    name,
+                    &solution.report,
+                    EightQueensState::to_string,
+                    |mv| format!("queen on row {}, column {}", mv.row + 1, mv.col + 1),
+                    &path,
+                )
+            }),
+            PuzzleId::TowerOfHanoi => self.tower_of_hanoi.solution.as_ref().map(|solution| {
+                persistence::export_solution(name, &solution.report, HanoiState::to_string, HanoiMove::label, &path)
+            }),
+            PuzzleId::KnightsTour => self.knights_tour.solution.as_ref().map(|report| {
+                persistence::export_solution(
+                    name,
+                    report,
+                    KnightsTourState::to_string,
+                    |mv| format!("knight to row {}, column {}", mv.row + 1, mv.col + 1),
+                    &path,
+                )
+            }),
+            PuzzleId::Sokoban => self.sokoban.solution.as_ref().map(|solution| {
+                persistence::export_solution(
+                    name,
+                    &solution.report,
+                    SokobanState::to_string,
+                    |mv| format!("player {}", mv.label().to_lowercase()),
+                    &path,
+                )
+            }),
+            PuzzleId::Sudoku => self.sudoku.solution.as_ref().map(|solution| {
+                persistence::export_solution(
+                    name,
+                    &solution.report,
+                    SudokuState::to_string,
+                    |mv| format!("try {}, then fill every forced cell", mv.label()),
+                    &path,
+                )
+            }),
+            PuzzleId::Maze => self.maze.solution.as_ref().map(|solution| {
+                persistence::export_solution(
+                    name,
+                    &solution.report,
+                    MazeState::to_string,
+                    |mv: &MazeMove| format!("step {}", mv.label().to_lowercase()),
+                    &path,
+                )
+            }),
+            _ => return,
+        };
+        let message = match result {
+            None => "Nothing to export yet: press S to solve first.".into(),
+            Some(Ok(())) => {
+                let full_path = std::env::current_dir()
+                    .map(|dir| dir.join(&path))
+                    .unwrap_or(path);
+                format!("Solution exported to {}.", full_path.display())
+            }
+            Some(Err(err)) => format!("Export failed: {}.", err),
+        };
+        if let Some(status) = self.status_mut(puzzle) {
+            *status = message;
+        }
+    }
+
+    /// Used when the platform has no data directory for Ctrl+S / Ctrl+L saves.
+    pub fn report_no_data_dir(&mut self) {
+        if let Some(status) = self.active_puzzle().and_then(|puzzle| self.status_mut(puzzle)) {
+            *status = "No data directory on this system; use F2/F3 to save next to the app.".into();
+        }
+    }
+
+    /// Restores a board saved by [`App::save_session`] and switches to its puzzle.
+    pub fn load_session(&mut self, path: &Path) {
+        let Some(active) = self.active_puzzle() else {
+            return;
+        };
+        let session = match persistence::read_session(path) {
+            Ok(session) => session,
+            Err(err) => {
+                if let Some(status) = self.status_mut(active) {
+                    *status = if err.is_not_found() {
+                        format!("No saved state found at {}.", persistence::display_path(path))
+                    } else {
+                        format!("Load failed: {}.", err)
+                    };
+                }
+                return;
+            }
+        };
+        let puzzle = session.board.puzzle();
+        match session.board {
+            SavedBoard::EightPuzzle(state) => {
+                self.eight_puzzle.load(state, session.moves_made);
+            }
+            SavedBoard::XorTicTacToe(state) => {
+                self.xor_ttt.state = state;
+                self.xor_ttt.cursor = 4;
+                self.xor_ttt.setup_mode = false;
+            }
+            SavedBoard::MissionariesCannibals(state) => {
+                self.missionaries_cannibals.load(state, session.moves_made);
+            }
+            SavedBoard::EightQueens(state) => {
+                self.eight_queens.reset();
+                self.eight_queens.state = state;
+            }
+            SavedBoard::TowerOfHanoi(state) => {
+                self.tower_of_hanoi.load(state, session.moves_made);
+            }
+            SavedBoard::KnightsTour(tour) => {
+                self.knights_tour.load(tour.size(), tour.squares());
+            }
+            SavedBoard::WaterJug(state) => {
+                self.water_jug.load(state);
+            }
+            SavedBoard::LightsOut(state) => {
+                self.lights_out.load(state, session.moves_made);
+            }
+            SavedBoard::Sokoban(state) => {
+                self.sokoban.load(state, session.moves_made);
+            }
+            SavedBoard::Sudoku(state) => {
+                self.sudoku.load(state, session.moves_made);
+            }
+            SavedBoard::Maze(state) => {
+                self.maze.load(state, session.moves_made);
+            }
+        }
+        self.select_puzzle(puzzle);
+        if let Some(status) = self.status_mut(puzzle) {
+            *status = format!("Loaded session from {}. {}", persistence::display_path(path), session.status);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EightPuzzleSolution {
+    pub report: SearchReport<EightPuzzleState>,
+    pub step: usize,
+    /// Heuristic the search that found this solution ran with.
+    pub heuristic: EightPuzzleHeuristic,
+}
+
+impl EightPuzzleSolution {
+    pub fn total_steps(&self) -> usize {
+        self.report.path.len().saturating_sub(1)
+    }
+
+    /// Whether this replays the best incomplete path of a search that
+    /// stopped early rather than a route to the goal.
+    pub fn is_partial(&self) -> bool {
+        !self.report.goal_found
+    }
+
+    /// Status line for the replay step shown.
+    fn replay_status(&self) -> String {
+        let label = self.move_label(self.step).unwrap_or("?");
+        if self.is_partial() {
+            format!(
+                "Partial solution ({} steps, not optimal): step {} / {} ({})",
+                self.total_steps(),
+                self.step,
+                self.total_steps(),
+                label
+            )
+        } else {
+            format!("Replaying solution: step {} / {} ({})", self.step, self.total_steps(), label)
+        }
+    }
+
+    /// Label of the move that led to replay step `step` (1-based).
+    pub fn move_label(&self, step: usize) -> Option<&'static str> {
+        step.checked_sub(1)
+            .and_then(|idx| self.report.actions.get(idx))
+            .map(SlideMove::label)
+    }
+}
+
+/// Name a solver run is listed under in the comparison table.
+pub type AlgorithmLabel = &'static str;
+
+/// What the Solver panel shows; `C` switches between the two.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ComparisonMode {
+    /// Details of the latest run.
+    #[default]
+    Solver,
+    /// One row per run from the current start state.
+    Table,
+}
+
+/// Every solver run from one start state, so different algorithms can be
+/// compared side by side.
+#[derive(Clone)]
+pub struct CompareReports<S: SearchState> {
+    start: Option<S>,
+    pub rows: Vec<(AlgorithmLabel, SearchReport<S>)>,
+    pub mode: ComparisonMode,
+}
+
+impl<S: SearchState> std::fmt::Debug for CompareReports<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompareReports")
+            .field("rows", &self.rows.len())
+            .field("mode", &self.mode)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: SearchState> Default for CompareReports<S> {
+    fn default() -> Self {
+        Self {
+            start: None,
+            rows: Vec::new(),
+            mode: ComparisonMode::default(),
+        }
+    }
+}
+
+impl<S: SearchState> CompareReports<S> {
+    /// Appends a run, first dropping the rows of an earlier start state.
+    pub fn record(&mut self, start: &S, label: AlgorithmLabel, report: &SearchReport<S>) {
+        if self.start.as_ref() != Some(start) {
+            self.rows.clear();
+            self.start = Some(start.clone());
+        }
+        self.rows.push((label, report.clone()));
+    }
+
+    pub fn clear(&mut self) {
+        self.start = None;
+        self.rows.clear();
+    }
+
+    pub fn toggle(&mut self) -> ComparisonMode {
+        self.mode = match self.mode {
+            ComparisonMode::Solver => ComparisonMode::Table,
+            ComparisonMode::Table => ComparisonMode::Solver,
+        };
+        self.mode
+    }
+
+    pub fn showing_table(&self) -> bool {
+        self.mode == ComparisonMode::Table
+    }
+}
+
+/// Stopwatch for solving a board by hand: starts on the first manual move
+/// and freezes once the board is solved.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MoveTimer {
+    pub started_at: Option<Instant>,
+    /// Time on the clock when it was stopped.
+    frozen: Option<Duration>,
+}
+
+impl MoveTimer {
+    /// Starts the clock unless it is already running or stopped.
+    pub fn start(&mut self) {
+        if self.started_at.is_none() {
+            self.started_at = Some(Instant::now());
+        }
+    }
+
+    /// Started and not yet stopped, so the clock on screen is ticking.
+    pub fn is_running(&self) -> bool {
+        self.started_at.is_some() && self.frozen.is_none()
+    }
+
+    pub fn stop(&mut self) {
+        if self.frozen.is_none() {
+            self.frozen = self.started_at.map(|start| start.elapsed());
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Time shown on the clock; `None` before the first move.
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.frozen
+            .or_else(|| self.started_at.map(|start| start.elapsed()))
+    }
+}
+
+/// Waits between auto-played steps offered by `+` / `-`, fastest first.
+pub const AUTOPLAY_INTERVALS_MS: [u64; 6] = [125, 250, 500, 1_000, 1_500, 2_000];
+
+/// Steps a loaded solution forward on its own, one step per `interval`,
+/// until the path ends or a key stops it.
+#[derive(Debug, Clone, Copy)]
+pub struct Autoplay {
+    pub playing: bool,
+    pub interval: Duration,
+    pub last_advance: Instant,
+}
+
+impl Default for Autoplay {
+    fn default() -> Self {
+        Self {
+            playing: false,
+            interval: Duration::from_millis(500),
+            last_advance: Instant::now(),
+        }
+    }
+}
+
+impl Autoplay {
+    /// Whether the next step is due.
+    pub fn is_due(&self) -> bool {
+        self.playing && self.last_advance.elapsed() >= self.interval
+    }
+
+    /// Moves to the next shorter (`faster`) or longer wait on
+    /// [`AUTOPLAY_INTERVALS_MS`], stopping at either end.
+    pub fn change_speed(&mut self, faster: bool) {
+        let current = self.interval.as_millis() as u64;
+        let next = if faster {
+            AUTOPLAY_INTERVALS_MS.into_iter().rev().find(|&ms| ms < current)
+        } else {
+            AUTOPLAY_INTERVALS_MS.into_iter().find(|&ms| ms > current)
+        };
+        if let Some(ms) = next {
+            self.interval = Duration::from_millis(ms);
+        }
+    }
+
+    /// E.g. "▶ Auto-playing (0.5s/step)".
+    pub fn label(&self) -> String {
+        format!("▶ Auto-playing ({}s/step)", self.interval.as_secs_f64())
+    }
+}
+
+#[derive(Debug)]
+pub struct EightPuzzleSession {
+    pub start: EightPuzzleState,
+    pub current: EightPuzzleState,
+    pub goal_state: EightPuzzleState,
+    pub moves_made: usize,
+    pub status: String,
+    pub solution: Option<EightPuzzleSolution>,
+    pub selected_cell: usize,
+    pub goal_selected_cell: usize,
+    pub editing_goal: bool,
+    pub history: Vec<EightPuzzleState>,
+    pub redo_stack: Vec<EightPuzzleState>,
+    pub rng: StdRng,
+    /// Pinned shuffle seed; `None` draws a fresh seed for every shuffle.
+    pub seed: Option<u64>,
+    /// Seed behind the most recent shuffle.
+    pub last_seed: Option<u64>,
+    /// One line per manual move or replayed solution step, oldest first.
+    pub move_history: Vec<String>,
+    /// Entry of `move_history` kept in view by the history panel.
+    pub history_scroll: usize,
+    /// When the current board was set up; history timestamps count from here.
+    pub started_at: Instant,
+    /// Result of the A* search running on a background thread, if any.
+    pub solver_rx: Option<Receiver<SearchReport<EightPuzzleState>>>,
+    /// Set to stop the background search early.
+    pub cancel_token: Option<CancellationToken>,
+    pub solve_start: Option<Instant>,
+    /// Nodes expanded so far by the background search.
+    pub solver_expanded: Arc<AtomicUsize>,
+    /// Tile suggested by the last hint and when it was asked for.
+    pub hint: Option<(usize, Instant)>,
+    /// A* run one expansion per Space or Enter while step mode is on.
+    step_solver: Option<AStarSearch<CustomGoalState>>,
+    /// Solve not yet counted by [`App::collect_solves`].
+    pub new_solve: Option<SolveRecord>,
+    pub timer: MoveTimer,
+    pub autoplay: Autoplay,
+    pub compare_reports: CompareReports<EightPuzzleState>,
+    /// Equally short solutions of the loaded one, the A* path first; filled
+    /// the first time N is pressed after solving.
+    pub all_paths: Vec<Vec<EightPuzzleState>>,
+    /// Entry of `all_paths` being replayed.
+    pub path_index: usize,
+    /// Text typed so far while pasting a board with V.
+    pub board_prompt: Option<String>,
+    /// What A* estimates the remaining slides with; M cycles it.
+    pub heuristic: EightPuzzleHeuristic,
+}
+
+impl EightPuzzleSession {
+    /// Entries moved per PageUp/PageDown in the history panel.
+    const HISTORY_PAGE: usize = 5;
+    /// Most optimal solutions N cycles through.
+    const MAX_PATHS: usize = 10;
+
+    fn base_message() -> String {
+        "Use arrows to select cell, 1-8 to place number. Tab switches boards. R resets, N shuffles, S solves, Space replays, U undoes.".into()
+    }
+
+    fn random_state(&mut self) -> EightPuzzleState {
+        self.last_seed = Some(reseed_for_shuffle(&mut self.rng, self.seed));
+        EightPuzzleState::random_solvable(&mut self.rng)
+    }
+
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+        self.status = seed_status(seed);
+    }
+
+    pub fn randomized() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+
+    /// Session whose starting board and later shuffles are drawn from `rng`.
+    pub fn with_rng(mut rng: StdRng) -> Self {
+        let state = EightPuzzleState::random_solvable(&mut rng);
+        Self {
+            start: state,
+            current: state,
+            goal_state: EightPuzzleState::default(), // Default goal [1,2,3,4,5,6,7,8,0]
+            moves_made: 0,
+            status: Self::base_message(),
+            solution: None,
+            selected_cell: 0,
+            goal_selected_cell: 0,
+            editing_goal: false,
+            history: Vec::new(),
+            redo_stack: Vec::new(),
+            rng,
+            seed: None,
+            last_seed: None,
+            move_history: Vec::new(),
+            history_scroll: 0,
+            started_at: Instant::now(),
+            solver_rx: None,
+            cancel_token: None,
+            solve_start: None,
+            solver_expanded: Arc::new(AtomicUsize::new(0)),
+            hint: None,
+            step_solver: None,
+            new_solve: None,
+            timer: MoveTimer::default(),
+            autoplay: Autoplay::default(),
+            compare_reports: CompareReports::default(),
+            all_paths: Vec::new(),
+            path_index: 0,
+            board_prompt: None,
+            heuristic: EightPuzzleHeuristic::default(),
+        }
+    }
+
+    fn clear_move_history(&mut self) {
+        self.move_history.clear();
+        self.history_scroll = 0;
+        self.started_at = Instant::now();
+    }
+
+    /// Appends a numbered, timestamped entry and scrolls the panel to it.
+    fn record_move(&mut self, description: String) {
+        let elapsed = self.started_at.elapsed().as_secs();
+        self.move_history.push(format!(
+            "{}. +{}:{:02} {}",
+            self.move_history.len() + 1,
+            elapsed / 60,
+            elapsed % 60,
+            description
+        ));
+        self.history_scroll = self.move_history.len() - 1;
+    }
+
+    pub fn scroll_history_up(&mut self) {
+        self.history_scroll = self.history_scroll.saturating_sub(Self::HISTORY_PAGE);
+    }
+
+    pub fn scroll_history_down(&mut self) {
+        self.history_scroll = (self.history_scroll + Self::HISTORY_PAGE)
+            .min(self.move_history.len().saturating_sub(1));
+    }
+
+    pub fn reset(&mut self) {
+        self.current = self.start;
+        self.moves_made = 0;
+        self.solution = None;
+        self.selected_cell = 0;
+        self.editing_goal = false;
+        self.history.clear();
+        self.redo_stack.clear();
+        self.clear_move_history();
+        self.timer.clear();
+        self.compare_reports.clear();
+        self.all_paths.clear();
+        self.path_index = 0;
+        self.status = "Reset to starting arrangement.".into();
+    }
+
+    /// Replaces the board with a saved one, keeping the goal board.
+    pub fn load(&mut self, state: EightPuzzleState, moves_made: usize) {
+        self.stop_solver();
+        self.step_solver = None;
+        self.start = state;
+        self.current = state;
+        self.moves_made = moves_made;
+        self.solution = None;
+        self.selected_cell = 0;
+        self.editing_goal = false;
+        self.history.clear();
+        self.redo_stack.clear();
+        self.clear_move_history();
+        self.timer.clear();
+    }
+
+    /// Starts asking for a board typed or pasted as text, e.g. `123456780`.
+    pub fn open_board_prompt(&mut self) {
+        self.board_prompt = Some(String::new());
+        self.refresh_board_prompt();
+    }
+
+    pub fn board_prompt_push(&mut self, ch: char) {
+        if let Some(input) = &mut self.board_prompt {
+            if input.len() < 40 {
+                input.push(ch);
+            }
+        }
+        self.refresh_board_prompt();
+    }
+
+    pub fn board_prompt_pop(&mut self) {
+        if let Some(input) = &mut self.board_prompt {
+            input.pop();
+        }
+        self.refresh_board_prompt();
+    }
+
+    /// Loads the typed board, or leaves the prompt open with the reason it
+    /// could not be read.
+    pub fn submit_board_prompt(&mut self) {
+        let Some(input) = &self.board_prompt else {
+            return;
+        };
+        match input.parse::<EightPuzzleState>() {
+            Ok(state) => {
+                self.board_prompt = None;
+                self.load(state, 0);
+                self.status = if state.is_solvable_to(&self.goal_state) {
+                    "Board loaded. Press S to solve.".into()
+                } else {
+                    "Board loaded, but its inversion parity differs from the goal's: it cannot be solved.".into()
+                };
+            }
+            Err(err) => {
+                self.status = format!(
+                    "Board: {}_  ({}; Enter loads, Esc cancels)",
+                    input, err
+                );
+            }
+        }
+    }
+
+    pub fn cancel_board_prompt(&mut self) {
+        self.board_prompt = None;
+        self.status = "Board unchanged.".into();
+    }
+
+    fn refresh_board_prompt(&mut self) {
+        if let Some(input) = &self.board_prompt {
+            self.status = format!(
+                "Board: {}_  (9 tiles row by row, 0 for the blank, e.g. 1 2 3 / 4 5 6 / 7 8 0; Enter loads, Esc cancels)",
+                input
+            );
+        }
+    }
+
+    pub fn new_board(&mut self) {
+        let state = self.random_state();
+        self.start = state;
+        self.current = state;
+        self.moves_made = 0;
+        self.solution = None;
+        self.selected_cell = 0;
+        self.history.clear();
+        self.redo_stack.clear();
+        self.clear_move_history();
+        self.timer.clear();
+        self.status = format!(
+            "Generated a new solvable board. Seed: {}.",
+            self.last_seed.unwrap_or_default()
+        );
+    }
+
+    pub fn shuffle(&mut self) {
+        if self.editing_goal {
+            let state = self.random_state();
+            self.goal_state = state;
+            self.goal_selected_cell = 0;
+            self.status = format!(
+                "Goal board shuffled randomly. Seed: {}.",
+                self.last_seed.unwrap_or_default()
+            );
+        } else {
+            let state = self.random_state();
+            self.current = state;
+            self.moves_made = 0;
+            self.solution = None;
+            self.selected_cell = 0;
+            self.history.clear();
+            self.redo_stack.clear();
+            self.clear_move_history();
+            self.timer.clear();
+            self.status = format!(
+                "Board shuffled randomly. Seed: {}.",
+                self.last_seed.unwrap_or_default()
+            );
+        }
+    }
+
+    pub fn toggle_editing_goal(&mut self) {
+        self.editing_goal = !self.editing_goal;
+        if self.editing_goal {
+            self.status = "Now editing goal board. Use Tab to switch back.".into();
+        } else {
+            self.status = "Now editing current board. Use Tab to edit goal.".into();
+        }
+    }
+
+    pub fn move_cursor(&mut self, row_delta: isize, col_delta: isize, wrap: bool) {
+        if self.editing_goal {
+            let row = (self.goal_selected_cell / 3) as isize;
+            let col = (self.goal_selected_cell % 3) as isize;
+            let new_row = step_cursor(row, row_delta, 3, wrap);
+            let new_col = step_cursor(col, col_delta, 3, wrap);
+            self.goal_selected_cell = (new_row * 3 + new_col) as usize;
+        } else {
+            let row = (self.selected_cell / 3) as isize;
+            let col = (self.selected_cell % 3) as isize;
+            let new_row = step_cursor(row, row_delta, 3, wrap);
+            let new_col = step_cursor(col, col_delta, 3, wrap);
+            self.selected_cell = (new_row * 3 + new_col) as usize;
+        }
+    }
+
+    /// Moves the cursor to `index` on the current board, or the goal board when `goal` is set.
+    pub fn select_cell(&mut self, index: usize, goal: bool) {
+        if goal != self.editing_goal {
+            self.toggle_editing_goal();
+        }
+        if goal {
+            self.goal_selected_cell = index;
+        } else {
+            self.selected_cell = index;
+        }
+    }
+
+    pub fn place_number(&mut self, number: u8) -> bool {
+        if number == 0 || number > 8 {
+            self.status = format!("Invalid number: {}. Use 1-8.", number);
+            return false;
+        }
+
+        if self.editing_goal {
+            let current_value = self.goal_state.get_tile(self.goal_selected_cell);
+            
+            if current_value == number {
+                self.status = format!("Goal cell already contains {}.", number);
+                return false;
+            }
+
+            if let Some(existing_idx) = self.goal_state.tiles().iter().position(|&t| t == number) {
+                self.goal_state.set_tile(self.goal_selected_cell, number);
+                self.goal_state.set_tile(existing_idx, current_value);
+                self.solution = None;
+                self.status = format!("Goal: Swapped {} with cell {}.", number, existing_idx + 1);
+                return true;
+            }
+
+            if current_value == 0 {
+                self.goal_state.set_tile(self.goal_selected_cell, number);
+                self.solution = None;
+                self.status = format!("Goal: Placed {} in cell {}.", number, self.goal_selected_cell + 1);
+                return true;
+            }
+
+            self.goal_state.set_tile(self.goal_selected_cell, number);
+            self.solution = None;
+            self.status = format!("Goal: Replaced {} with {} in cell {}.", current_value, number, self.goal_selected_cell + 1);
+            true
+        } else {
+            let current_value = self.current.get_tile(self.selected_cell);
+            
+            if current_value == number {
+                self.status = format!("Cell already contains {}.", number);
+                return false;
+            }
+
+            self.history.push(self.current);
+            self.redo_stack.clear();
+            self.timer.start();
+
+            if let Some(existing_idx) = self.current.tiles().iter().position(|&t| t == number) {
+                self.current.set_tile(self.selected_cell, number);
+                self.current.set_tile(existing_idx, current_value);
+                self.moves_made += 1;
+                self.solution = None;
+                self.status = format!("Swapped {} with cell {}.", number, existing_idx + 1);
+                self.record_move(self.status.clone());
+                self.check_hand_solve();
+                return true;
+            }
+
+            if current_value == 0 {
+                self.current.set_tile(self.selected_cell, number);
+                self.moves_made += 1;
+                self.solution = None;
+                self.status = format!("Placed {} in cell {}.", number, self.selected_cell + 1);
+                self.record_move(self.status.clone());
+                self.check_hand_solve();
+                return true;
+            }
+
+            self.current.set_tile(self.selected_cell, number);
+            self.moves_made += 1;
+            self.solution = None;
+            self.status = format!("Replaced {} with {} in cell {}.", current_value, number, self.selected_cell + 1);
+            self.record_move(self.status.clone());
+            self.check_hand_solve();
+            true
+        }
+    }
+
+
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(previous) => {
+                self.redo_stack.push(self.current);
+                self.current = previous;
+                self.moves_made = self.moves_made.saturating_sub(1);
+                self.solution = None;
+                self.status = format!("Undid last move ({} more available).", self.history.len());
+                self.record_move("Undo".into());
+                true
+            }
+            None => {
+                self.status = "Nothing to undo.".into();
+                false
+            }
+        }
+    }
+
+    pub fn redo(&mut self) -> bool {
+        match self.redo_stack.pop() {
+            Some(next) => {
+                self.history.push(self.current);
+                self.current = next;
+                self.moves_made += 1;
+                self.solution = None;
+                self.status = format!("Redid move ({} more available).", self.redo_stack.len());
+                self.record_move("Redo".into());
+                true
+            }
+            None => {
+                self.status = "Nothing to redo.".into();
+                false
+            }
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.current == self.goal_state
+    }
+
+    fn check_hand_solve(&mut self) {
+        if self.is_solved() {
+            self.new_solve = Some(SolveRecord::by_hand(Some(self.moves_made)));
+        }
+    }
+
+    /// First move of an optimal path from the current board to the goal.
+    pub fn hint(&self) -> Option<SlideMove> {
+        if self.is_solved() {
+            return None;
+        }
+        let start_state = CustomGoalState {
+            state: self.current,
+            goal: self.goal_state,
+            heuristic: EightPuzzleHeuristic::Manhattan,
+        };
+        let report = astar_limited(start_state, DEFAULT_NODE_LIMIT);
+        report.actions.first().copied()
+    }
+
+    /// Highlights the tile that [`EightPuzzleSession::hint`] would slide.
+    pub fn show_hint(&mut self) {
+        match self.hint() {
+            Some(mv) => {
+                let blank = self.current.blank_index();
+                let tile_cell = match mv {
+                    SlideMove::Up => blank - 3,
+                    SlideMove::Down => blank + 3,
+                    SlideMove::Left => blank - 1,
+                    SlideMove::Right => blank + 1,
+                };
+                self.hint = Some((tile_cell, Instant::now()));
+                self.status = format!(
+                    "Hint: slide tile {} into the blank ({}).",
+                    self.current.get_tile(tile_cell),
+                    mv.label()
+                );
+            }
+            None if self.is_solved() => self.status = "Already solved.".into(),
+            None => self.status = "No hint: this board cannot reach the goal.".into(),
+        }
+    }
+
+    /// Cell of the hinted tile while its highlight is still showing.
+    pub fn active_hint(&self) -> Option<usize> {
+        self.hint
+            .filter(|(_, shown)| shown.elapsed() < HINT_DURATION)
+            .map(|(cell, _)| cell)
+    }
+
+    pub fn is_stepping(&self) -> bool {
+        self.step_solver.is_some()
+    }
+
+    /// Frontier and closed list sizes of the search being stepped through.
+    pub fn step_counts(&self) -> Option<(usize, usize)> {
+        self.step_solver
+            .as_ref()
+            .map(|search| (search.frontier_len(), search.closed_len()))
+    }
+
+    /// Board taken off the frontier by the latest step, with its g and h costs.
+    pub fn step_node(&self) -> Option<(EightPuzzleState, u32, u32)> {
+        let (node, g, h) = self.step_solver.as_ref()?.current()?;
+        Some((node.state, *g, *h))
+    }
+
+    /// Turns step mode on or off. While it is on, each
+    /// [`EightPuzzleSession::step_search`] expands a single A* node.
+    pub fn toggle_step_mode(&mut self) {
+        if self.step_solver.take().is_some() {
+            self.status = "Left step mode.".into();
+            return;
+        }
+        if !self.check_solvable() {
+            return;
+        }
+        self.step_solver = Some(AStarSearch::new(CustomGoalState {
+            state: self.current,
+            goal: self.goal_state,
+            heuristic: self.heuristic,
+        }));
+        self.status = "Step mode: press Space or Enter to expand one A* node, I to leave.".into();
+    }
+
+    pub fn step_search(&mut self) {
+        let Some(step_solver) = &mut self.step_solver else {
+            return;
+        };
+        match step_solver.step() {
+            StepResult::Finished(report) => {
+                self.step_solver = None;
+                self.redo_stack.clear();
+                self.finish_solve("A* (stepped)", SearchReport {
+                    path: report.path.iter().map(|s| s.state).collect(),
+                    actions: report.actions,
+                    expanded_nodes: report.expanded_nodes,
+                    visited_states: report.visited_states,
+                    goal_found: report.goal_found,
+                    outcome: report.outcome,
+                    elapsed: report.elapsed,
+                    peak_memory_bytes: report.peak_memory_bytes,
+                    max_open_size: report.max_open_size,
+                    solution_depth: report.solution_depth,
+                    effective_branching_factor: report.effective_branching_factor,
+                    edges: Vec::new(),
+                    partial_path: report.partial_path.iter().map(|s| s.state).collect(),
+                    partial_path_cost: report.partial_path_cost,
+                });
+            }
+            StepResult::Expanded => {
+                if let Some((node, g, h)) = step_solver.current() {
+                    self.status = format!(
+                        "Expanded #{}: f = {} (g = {}, h = {}), blank at cell {}. Frontier: {}, closed: {}.",
+                        step_solver.closed_len(),
+                        g + h,
+                        g,
+                        h,
+                        node.state.blank_index() + 1,
+                        step_solver.frontier_len(),
+                        step_solver.closed_len()
+                    );
+                }
+            }
+        }
+    }
+
+    pub fn is_solving(&self) -> bool {
+        self.solver_rx.is_some()
+    }
+
+    /// Switches A* to the next heuristic. A loaded solution keeps the one
+    /// it was found with; solve again and press C to compare the two.
+    pub fn cycle_heuristic(&mut self) {
+        self.heuristic = self.heuristic.next();
+        self.status = format!(
+            "Heuristic: {} (h = {} on this board). Press S to solve with it.",
+            self.heuristic.label(),
+            self.heuristic_estimate()
+        );
+    }
+
+    /// The active heuristic's estimate from the current board to the goal board.
+    pub fn heuristic_estimate(&self) -> u32 {
+        self.heuristic.estimate(&self.current, &self.goal_state)
+    }
+
+    /// Refuses to search between boards of different inversion parity, which
+    /// A* would otherwise explore until it times out.
+    fn check_solvable(&mut self) -> bool {
+        let solvable = self.current.is_solvable_to(&self.goal_state);
+        if !solvable {
+            self.status = "This configuration has no solution (odd number of inversions). Shuffle or manually rearrange.".into();
+        }
+        solvable
+    }
+
+    /// Starts A* on a background thread; [`EightPuzzleSession::poll_solver`]
+    /// picks up the result.
+    pub fn solve_current(&mut self) {
+        if self.is_solving() || !self.check_solvable() {
+            return;
+        }
+        if self.is_solved() {
+            self.status = "Already solved — nothing to do.".into();
+            return;
+        }
+        self.redo_stack.clear();
+        // Create a wrapper state with custom goal
+        let start_state = CustomGoalState {
+            state: self.current,
+            goal: self.goal_state,
+            heuristic: self.heuristic,
+        };
+        let cancel = CancellationToken::new();
+        let expanded = Arc::new(AtomicUsize::new(0));
+        let (tx, rx) = mpsc::channel();
+        let thread_cancel = cancel.clone();
+        let thread_expanded = Arc::clone(&expanded);
+        thread::spawn(move || {
+            let report = astar_cancellable(
+                start_state,
+                AstarConfig::with_node_limit(DEFAULT_NODE_LIMIT),
+                &thread_cancel,
+                &thread_expanded,
+            );
+            // Extract the actual states from the wrapper
+            let actual_report = SearchReport {
+                path: report.path.iter().map(|s| s.state).collect(),
+                actions: report.actions,
+                expanded_nodes: report.expanded_nodes,
+                visited_states: report.visited_states,
+                goal_found: report.goal_found,
+                outcome: report.outcome,
+                elapsed: report.elapsed,
+                peak_memory_bytes: report.peak_memory_bytes,
+                max_open_size: report.max_open_size,
+                solution_depth: report.solution_depth,
+                effective_branching_factor: report.effective_branching_factor,
+                edges: Vec::new(),
+                partial_path: report.partial_path.iter().map(|s| s.state).collect(),
+                partial_path_cost: report.partial_path_cost,
+            };
+            // The session may have stopped listening; nothing to do then
+            let _ = tx.send(actual_report);
+        });
+        self.solver_rx = Some(rx);
+        self.cancel_token = Some(cancel);
+        self.solver_expanded = expanded;
+        self.solve_start = Some(Instant::now());
+        self.status = "Solving… press X or Esc to cancel.".into();
+    }
+
+    /// Takes the background search result once it is ready.
+    pub fn poll_solver(&mut self) {
+        let Some(rx) = &self.solver_rx else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(report) => {
+                self.solver_rx = None;
+                self.cancel_token = None;
+                self.solve_start = None;
+                self.finish_solve(self.heuristic.search_label(), report);
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => {
+                self.solver_rx = None;
+                self.cancel_token = None;
+                self.solve_start = None;
+                self.status = "Solver stopped unexpectedly.".into();
+            }
+        }
+    }
+
+    fn finish_solve(&mut self, label: AlgorithmLabel, report: SearchReport<EightPuzzleState>) {
+        self.compare_reports.record(&self.current, label, &report);
+        self.all_paths.clear();
+        self.path_index = 0;
+        if report.goal_found && !report.path.is_empty() {
+            self.new_solve = Some(SolveRecord::by_solver(report.actions.len(), report.elapsed));
+            self.solution = Some(EightPuzzleSolution {
+                report,
+                step: 0,
+                heuristic: self.heuristic,
+            });
+            self.moves_made = 0;
+            if let Some(solution) = &self.solution {
+                if let Some(first) = solution.report.path.first() {
+                    self.current = *first;
+                }
+                self.status = format!(
+                    "Solution ready ({} moves). Press Space to step, N for other shortest paths.",
+                    solution.total_steps()
+                );
+            }
+        } else if report.outcome != SearchOutcome::NoSolution && report.partial_path.len() > 1 {
+            // Stopped early: offer the path to the closest board reached instead
+            let stopped = unsolved_status(&report, "");
+            let path = report.partial_path.clone();
+            self.solution = Some(EightPuzzleSolution {
+                report: SearchReport {
+                    actions: slides_along(&path),
+                    solution_depth: path.len() - 1,
+                    path,
+                    ..report
+                },
+                step: 0,
+                heuristic: self.heuristic,
+            });
+            self.moves_made = 0;
+            if let Some(solution) = &self.solution {
+                if let Some(first) = solution.report.path.first() {
+                    self.current = *first;
+                }
+                self.status = format!(
+                    "{} Partial solution ({} steps, not optimal). Press Space to step.",
+                    stopped,
+                    solution.total_steps()
+                );
+            }
+        } else {
+            self.solution = None;
+            self.status = unsolved_status(&report, "No solution found (should never happen).");
+        }
+    }
+
+    /// Tells the search thread to stop and stops listening for its report.
+    fn stop_solver(&mut self) {
+        if let Some(token) = self.cancel_token.take() {
+            token.cancel();
+        }
+        self.solver_rx = None;
+        self.solve_start = None;
+    }
+
+    /// Tells the search thread to stop but keeps listening, so the best
+    /// incomplete path it reached still arrives through [`Self::poll_solver`].
+    pub fn cancel_solve(&mut self) {
+        if let Some(token) = &self.cancel_token {
+            token.cancel();
+            self.status = "Cancelling…".into();
+        }
+    }
+
+    /// Nodes the running search has expanded so far.
+    pub fn expanded_so_far(&self) -> usize {
+        self.solver_expanded.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Spinner frame for the status panel while the solver runs.
+    pub fn spinner(&self) -> Option<char> {
+        const FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+        self.solve_start
+            .map(|start| FRAMES[(start.elapsed().as_millis() / 100) as usize % FRAMES.len()])
+    }
+
+    /// Up to `max` distinct shortest paths from the current board to the goal
+    /// board, found by iterative deepening on f = g + h (IDA*). Which of them
+    /// A* returns depends only on how its heap breaks ties.
+    pub fn enumerate_solutions(&self, max: usize) -> Vec<Vec<EightPuzzleState>> {
+        if max == 0 || !self.current.is_solvable_to(&self.goal_state) {
+            return Vec::new();
+        }
+        let start = CustomGoalState {
+            state: self.current,
+            goal: self.goal_state,
+            heuristic: EightPuzzleHeuristic::Manhattan,
+        };
+        let mut bound = start.heuristic();
+        let mut path = vec![start];
+        loop {
+            let mut found = Vec::new();
+            let next_bound = collect_paths_within(&mut path, bound, max, &mut found);
+            match next_bound {
+                _ if !found.is_empty() => return found,
+                Some(next) => bound = next,
+                None => return Vec::new(),
+            }
+        }
+    }
+
+    /// Loads the next equally short solution for replay.
+    pub fn next_path(&mut self) -> bool {
+        let Some(solution) = &self.solution else {
+            self.status = "Run the solver with 'S' first.".into();
+            return false;
+        };
+        if solution.is_partial() {
+            self.status = "A partial solution has no other shortest paths; solve again to finish.".into();
+            return false;
+        }
+        if self.all_paths.is_empty() {
+            let astar_path = solution.report.path.clone();
+            // Enumerate from the start of the solution, not the replayed board
+            if let Some(first) = astar_path.first() {
+                self.current = *first;
+            }
+            let others = self.enumerate_solutions(Self::MAX_PATHS);
+            self.all_paths = std::iter::once(astar_path.clone())
+                .chain(others.into_iter().filter(|path| *path != astar_path))
+                .take(Self::MAX_PATHS)
+                .collect();
+            self.path_index = 0;
+        }
+        self.path_index = (self.path_index + 1) % self.all_paths.len();
+        let path = self.all_paths[self.path_index].clone();
+        if let Some(solution) = &mut self.solution {
+            solution.report.actions = slides_along(&path);
+            solution.report.path = path;
+            solution.step = 0;
+            if let Some(first) = solution.report.path.first() {
+                self.current = *first;
+            }
+        }
+        self.moves_made = 0;
+        self.status = format!(
+            "Path {} of {}{}. Press Space to step.",
+            self.path_index + 1,
+            self.all_paths.len(),
+            if self.all_paths.len() == 1 {
+                " (the only shortest solution)"
+            } else {
+                ""
+            }
+        );
+        true
+    }
+
+    pub fn advance_solution(&mut self) -> bool {
+        if let Some(solution) = &mut self.solution {
+            if !verify_solution(&solution.report.path) {
+                self.status = "WARNING: Invalid solution path detected.".into();
+                return false;
+            }
+            if solution.step + 1 < solution.report.path.len() {
+                solution.step += 1;
+                let entry = format!(
+                    "Solution step {}: {}",
+                    solution.step,
+                    solution.move_label(solution.step).unwrap_or("?")
+                );
+                if let Some(state) = solution.report.path.get(solution.step) {
+                    self.current = *state;
+                    self.moves_made = solution.step;
+                    if solution.step == solution.report.path.len() - 1 && !solution.is_partial() {
+                        self.status = "Solution complete! Board solved.".into();
+                    } else {
+                        self.status = solution.replay_status();
+                    }
+                }
+                self.record_move(entry);
+                return true;
+            } else {
+                self.status = "Already at final solution state.".into();
+                return false;
+            }
+        }
+        self.status = "Run the solver with 'S' first.".into();
+        false
+    }
+
+    pub fn rewind_solution(&mut self) -> bool {
+        if let Some(solution) = &mut self.solution {
+            if solution.step > 0 {
+                let entry = format!(
+                    "Back from step {}: {}",
+                    solution.step,
+                    solution.move_label(solution.step).unwrap_or("?")
+                );
+                solution.step -= 1;
+                if let Some(state) = solution.report.path.get(solution.step) {
+                    self.current = *state;
+                    self.moves_made = solution.step;
+                    if solution.step == 0 {
+                        self.status = "Back at the starting state.".into();
+                    } else {
+                        self.status = solution.replay_status();
+                    }
+                }
+                self.record_move(entry);
+                return true;
+            } else {
+                self.status = "Already at the start of the solution.".into();
+                return false;
+            }
+        }
+        self.status = "Run the solver with 'S' first.".into();
+        false
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AiDifficulty {
+    Easy,
+    Medium,
+    Hard,
+    Perfect,
+}
+
+impl AiDifficulty {
+    /// Plies searched by the Hard difficulty.
+    const HARD_DEPTH: u8 = 3;
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AiDifficulty::Easy => "Easy",
+            AiDifficulty::Medium => "Medium",
+            AiDifficulty::Hard => "Hard",
+            AiDifficulty::Perfect => "Perfect",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            AiDifficulty::Easy => AiDifficulty::Medium,
+            AiDifficulty::Medium => AiDifficulty::Hard,
+            AiDifficulty::Hard => AiDifficulty::Perfect,
+            AiDifficulty::Perfect => AiDifficulty::Easy,
+        }
+    }
+}
+
+/// Who plays the O side (or X, when the human picked O) of a tic-tac-toe game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    VsAi,
+    /// Two people take turns on the same keyboard.
+    TwoPlayer,
+}
+
+impl GameMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameMode::VsAi => "vs AI",
+            GameMode::TwoPlayer => "Two players",
+        }
+    }
+}
+
+/// Outcome tally of an AI-vs-AI tournament.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TournamentResult {
+    pub x_wins: u32,
+    pub o_wins: u32,
+    pub draws: u32,
+    /// Most moves played in a single game.
+    pub longest_game: u32,
+}
+
+#[derive(Debug)]
+pub struct XorTicTacToeSession {
+    pub state: XorTicTacToeState,
+    pub cursor: usize,
+    pub status: String,
+    pub human_symbol: Player,
+    pub setup_mode: bool,
+    /// Text typed so far while entering a position with V in setup mode.
+    pub board_prompt: Option<String>,
+    pub ai_difficulty: AiDifficulty,
+    pub mode: GameMode,
+    pub rng: StdRng,
+}
+
+impl Default for XorTicTacToeSession {
+    fn default() -> Self {
+        Self {
+            state: XorTicTacToeState::default(),
+            cursor: 4,
+            status: Self::base_status(),
+            human_symbol: Player::X,
+            setup_mode: false,
+            board_prompt: None,
+            ai_difficulty: AiDifficulty::Perfect,
+            mode: GameMode::VsAi,
+            rng: StdRng::from_entropy(),
+        }
+    }
+}
+
+impl XorTicTacToeSession {
+    fn base_status() -> String {
+        "Arrows move cursor, X/O place pieces, Tab setup mode, H shuffle, S auto-move, L difficulty, F swap sides, M two-player, T tournament, R restart.".into()
+    }
+
+    /// Plays `games` best-move-vs-best-move games from an empty board, alternating
+    /// the starting player. The current board is left untouched.
+    pub fn run_tournament(&mut self, games: u32) -> TournamentResult {
+        let saved = self.state;
+        let mut table = TranspositionTable::new();
+        let mut result = TournamentResult::default();
+        for game in 0..games {
+            self.state = XorTicTacToeState::default();
+            if game % 2 == 1 {
+                self.state.to_move = Player::O;
+            }
+            let mut moves = 0;
+            while !self.is_locked() {
+                let player = self.state.to_move;
+                let Some(index) = self.pick_best_move_cached(player, &mut table) else {
+                    break;
+                };
+                self.state.cells[index] = Some(player);
+                self.state.to_move = player.opponent();
+                moves += 1;
+            }
+            match self.state.winner() {
+                Some(Player::X) => result.x_wins += 1,
+                Some(Player::O) => result.o_wins += 1,
+                None => result.draws += 1,
+            }
+            result.longest_game = result.longest_game.max(moves);
+        }
+        self.state = saved;
+        result
+    }
+
+    pub fn start_tournament(&mut self) {
+        let result = self.run_tournament(Self::TOURNAMENT_GAMES);
+        self.status = format!(
+            "X: {} wins, O: {} wins, {} draws (longest game: {} moves)",
+            result.x_wins, result.o_wins, result.draws, result.longest_game
+        );
+    }
+
+    pub fn cycle_difficulty(&mut self) {
+        self.ai_difficulty = self.ai_difficulty.next();
+        self.status = format!("AI difficulty: {}.", self.ai_difficulty.label());
+    }
+
+    const TOURNAMENT_GAMES: u32 = 100;
+
+    pub fn reset(&mut self) {
+        self.state = XorTicTacToeState::default();
+        self.cursor = 4;
+        self.setup_mode = false;
+        self.status = Self::base_status();
+        // X always opens, so the AI does when the human plays O
+        self.ai_auto_move();
+    }
+
+    pub fn toggle_mode(&mut self) {
+        self.mode = match self.mode {
+            GameMode::VsAi => GameMode::TwoPlayer,
+            GameMode::TwoPlayer => GameMode::VsAi,
+        };
+        self.status = match self.mode {
+            GameMode::TwoPlayer => format!(
+                "Two-player mode: take turns on this keyboard. {} to move.",
+                format_player(self.state.to_move)
+            ),
+            GameMode::VsAi => format!("Playing against the AI as {}.", format_player(self.human_symbol)),
+        };
+        self.ai_auto_move();
+    }
+
+    /// Status line naming who moves next, for two-player games.
+    fn announce_turn(&mut self) {
+        if self.mode == GameMode::TwoPlayer && !self.is_locked() {
+            self.status
+                .push_str(&format!(" {} to move.", format_player(self.state.to_move)));
+        }
+    }
+
+    /// Lets the human play the other symbol, starting a fresh game. Only
+    /// allowed before the human has placed a piece (the AI may have opened).
+    pub fn swap_sides(&mut self) {
+        if self.mode == GameMode::TwoPlayer {
+            self.status = "Both sides are human in two-player mode (M switches back).".into();
+            return;
+        }
+        if self.state.cells.contains(&Some(self.human_symbol)) {
+            self.status = "Sides can only be swapped before your first move (R restarts).".into();
+            return;
+        }
+        self.human_symbol = self.human_symbol.opponent();
+        self.state = XorTicTacToeState::default();
+        self.setup_mode = false;
+        self.status = format!("You now play {}.", format_player(self.human_symbol));
+        self.ai_auto_move();
+    }
+
+    pub fn toggle_setup_mode(&mut self) {
+        self.setup_mode = !self.setup_mode;
+        if self.setup_mode {
+            self.status = "Setup mode: Place X/O manually or V to type a board. Tab to exit setup.".into();
+        } else {
+            self.status = format!("Game mode: {}. Tab to enter setup.", self.mode.label());
+        }
+    }
+
+    /// Starts asking for a whole position as text; only setup mode allows it.
+    pub fn open_board_prompt(&mut self) {
+        if !self.setup_mode {
+            self.status = "Enter setup mode (Tab) to type a board.".into();
+            return;
+        }
+        self.board_prompt = Some(String::new());
+        self.refresh_board_prompt();
+    }
+
+    pub fn board_prompt_push(&mut self, ch: char) {
+        if let Some(input) = &mut self.board_prompt {
+            if input.len() < 20 {
+                input.push(ch);
+            }
+        }
+        self.refresh_board_prompt();
+    }
+
+    pub fn board_prompt_pop(&mut self) {
+        if let Some(input) = &mut self.board_prompt {
+            input.pop();
+        }
+        self.refresh_board_prompt();
+    }
+
+    /// Loads the typed position, or leaves the prompt open with the reason it
+    /// could not be read.
+    pub fn submit_board_prompt(&mut self) {
+        let Some(input) = &self.board_prompt else {
+            return;
+        };
+        match input.parse::<XorTicTacToeState>() {
+            Ok(state) => {
+                self.board_prompt = None;
+                self.state = state;
+                self.cursor = 4;
+                self.status = format!(
+                    "Board loaded; {} to move. Tab leaves setup mode.",
+                    format_player(state.to_move)
+                );
+            }
+            Err(err) => {
+                self.status = format!("Board: {}_  ({}; Enter loads, Esc cancels)", input, err);
+            }
+        }
+    }
+
+    pub fn cancel_board_prompt(&mut self) {
+        self.board_prompt = None;
+        self.status = "Board unchanged.".into();
+    }
+
+    fn refresh_board_prompt(&mut self) {
+        if let Some(input) = &self.board_prompt {
+            self.status = format!(
+                "Board: {}_  (9 cells row by row: X, O, . for empty, e.g. X.O/.X./...; Enter loads, Esc cancels)",
+                input
+            );
+        }
+    }
+
+    pub fn shuffle(&mut self) {
+        use rand::Rng;
+        let rng = &mut self.rng;
+        let mut cells = [None; 9];
+        let mut x_count = 0;
+        let mut o_count = 0;
+        
+        // Randomly place 3-4 pieces of each type
+        let total_pieces = rng.gen_range(0..=4);
+        for _ in 0..total_pieces {
+            let empty_cells: Vec<usize> = (0..9).filter(|&i| cells[i].is_none()).collect();
+            if !empty_cells.is_empty() {
+                let idx = empty_cells[rng.gen_range(0..empty_cells.len())];
+                if x_count <= o_count {
+                    cells[idx] = Some(Player::X);
+                    x_count += 1;
+                } else {
+                    cells[idx] = Some(Player::O);
+                    o_count += 1;
+                }
+            }
+        }
+        
+        self.state.cells = cells;
+        self.state.to_move = if x_count <= o_count { Player::X } else { Player::O };
+        self.cursor = 4;
+        self.status = "Board shuffled randomly.".into();
+    }
+
+    pub fn place_manual(&mut self, player: Player) -> bool {
+        if self.setup_mode {
+            // In setup mode, allow placing any piece
+            if self.state.cells[self.cursor].is_some() && self.state.cells[self.cursor] == Some(player) {
+                // Remove if same piece
+                self.state.cells[self.cursor] = None;
+                self.status = format!("Removed {} from cell {}.", format_player(player), self.cursor + 1);
+                return true;
+            }
+            self.state.cells[self.cursor] = Some(player);
+            self.status = format!("Placed {} in cell {}.", format_player(player), self.cursor + 1);
+            true
+        } else {
+            // In game mode, only allow placing human symbol on their turn
+            if self.is_locked() {
+                self.status = "Game over. Press R to restart.".into();
+                return false;
+            }
+            if player != self.state.to_move {
+                self.status = match self.mode {
+                    GameMode::TwoPlayer => format!("It's {}'s turn.", format_player(self.state.to_move)),
+                    GameMode::VsAi => format!("You are playing as {}.", format_player(self.human_symbol)),
+                };
+                return false;
+            }
+            self.place_cell(self.cursor)
+        }
+    }
+
+    pub fn move_cursor(&mut self, row_delta: isize, col_delta: isize, wrap: bool) {
+        let row = (self.cursor / 3) as isize;
+        let col = (self.cursor % 3) as isize;
+        let new_row = step_cursor(row, row_delta, 3, wrap);
+        let new_col = step_cursor(col, col_delta, 3, wrap);
+        self.cursor = (new_row * 3 + new_col) as usize;
+    }
+
+    pub fn quick_place(&mut self, index: usize) -> bool {
+        if index < 9 {
+            self.cursor = index;
+            return self.place_cell(index);
+        }
+        false
+    }
+
+    pub fn place_cursor(&mut self) -> bool {
+        self.place_cell(self.cursor)
+    }
+
+    pub fn place_cell(&mut self, index: usize) -> bool {
+        if index >= 9 {
+            return false;
+        }
+        self.cursor = index;
+        if self.setup_mode {
+            // In setup mode, toggle between X, O, and empty
+            match self.state.cells[index] {
+                None => {
+                    self.state.cells[index] = Some(Player::X);
+                    self.status = format!("Placed X in cell {}.", index + 1);
+                }
+                Some(Player::X) => {
+                    self.state.cells[index] = Some(Player::O);
+                    self.status = format!("Changed to O in cell {}.", index + 1);
+                }
+                Some(Player::O) => {
+                    self.state.cells[index] = None;
+                    self.status = format!("Cleared cell {}.", index + 1);
+                }
+            }
+            return true;
+        }
+        
+        if self.is_locked() {
+            self.status = "Game over. Press R to restart.".into();
+            return false;
+        }
+        if self.mode == GameMode::VsAi && self.state.to_move != self.human_symbol {
+            self.status = "Wait for the AI to finish its move.".into();
+            return false;
+        }
+        if self.state.cells[index].is_some() {
+            self.status = format!("Cell {} is already occupied.", index + 1);
+            return false;
+        }
+
+        let player = self.state.to_move;
+        self.state.cells[index] = Some(player);
+        self.state.to_move = player.opponent();
+        self.status = format!("Placed {} in cell {}.", format_player(player), index + 1);
+        self.update_outcome();
+        self.announce_turn();
+
+        if !self.is_locked() {
+            self.ai_auto_move();
+        }
+        true
+    }
+
+    pub fn auto_player_move(&mut self) -> bool {
+        if self.mode == GameMode::VsAi && self.state.to_move != self.human_symbol {
+            self.status = "It's not your turn.".into();
+            return false;
+        }
+        if self.is_locked() {
+            self.status = "Game over. Press R to restart.".into();
+            return false;
+        }
+        if let Some(index) = self.pick_best_move(self.state.to_move) {
+            return self.place_cell(index);
+        }
+        self.status = "No legal moves available.".into();
+        false
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.state.winner().is_some() || self.state.is_full()
+    }
+
+    fn update_outcome(&mut self) {
+        if let Some(winner) = self.state.winner() {
+            self.status = if self.mode == GameMode::TwoPlayer {
+                format!("{} wins! Press R to play again.", format_player(winner))
+            } else if winner == self.human_symbol {
+                format!("You win as {}! Press R to play again.", format_player(winner))
+            } else {
+                format!("AI wins as {}. Press R to try again.", format_player(winner))
+            };
+        } else if self.state.is_full() {
+            self.status = "It's a draw. Press R to restart.".into();
+        }
+    }
+
+    fn ai_auto_move(&mut self) {
+        let ai = self.human_symbol.opponent();
+        if self.mode == GameMode::TwoPlayer || self.state.to_move != ai || self.is_locked() {
+            return;
+        }
+        if let Some(index) = self.pick_ai_move(ai) {
+            self.state.cells[index] = Some(ai);
+            self.state.to_move = self.human_symbol;
+            self.cursor = index;
+            self.status = format!("AI placed {} in cell {}.", format_player(ai), index + 1);
+            self.update_outcome();
+        }
+    }
+
+    fn pick_best_move(&self, player: Player) -> Option<usize> {
+        self.pick_move_with_depth(player, FULL_DEPTH)
+    }
+
+    /// Full-depth best move that reuses `table`, so repeated searches stay cheap.
+    fn pick_best_move_cached(&self, player: Player, table: &mut TranspositionTable) -> Option<usize> {
+        let mut state = self.state;
+        state.to_move = player;
+        state.best_move_with_table(FULL_DEPTH, table)
+    }
+
+    fn pick_move_with_depth(&self, player: Player, depth: u8) -> Option<usize> {
+        let mut state = self.state;
+        state.to_move = player;
+        state.best_move(depth)
+    }
+
+    fn pick_random_move(&mut self) -> Option<usize> {
+        use rand::seq::SliceRandom;
+        let empty: Vec<usize> = (0..9).filter(|&idx| self.state.cells[idx].is_none()).collect();
+        empty.choose(&mut self.rng).copied()
+    }
+
+    fn pick_ai_move(&mut self, player: Player) -> Option<usize> {
+        use rand::Rng;
+        match self.ai_difficulty {
+            AiDifficulty::Easy => self.pick_random_move(),
+            AiDifficulty::Medium => {
+                if self.rng.gen_bool(0.5) {
+                    self.pick_best_move(player)
+                } else {
+                    self.pick_random_move()
+                }
+            }
+            AiDifficulty::Hard => self.pick_move_with_depth(player, AiDifficulty::HARD_DEPTH),
+            AiDifficulty::Perfect => self.pick_best_move(player),
+        }
+    }
+
+    /// Minimax score of placing the next piece in `index`, from the mover's point of view.
+    pub fn cell_score(&self, index: usize) -> Option<i32> {
+        if self.setup_mode || self.is_locked() || self.state.cells.get(index)?.is_some() {
+            return None;
+        }
+        self.state.move_scores(FULL_DEPTH)[index]
+    }
+}
+
+#[derive(Debug)]
+pub struct MissionariesCannibalsSession {
+    pub state: MissionariesCannibalsState,
+    pub status: String,
+    pub solution: Option<MissionariesCannibalsSolution>,
+    pub selected_move: usize,
+    pub rng: StdRng,
+    /// Pinned shuffle seed; `None` draws a fresh seed for every shuffle.
+    pub seed: Option<u64>,
+    /// Seed behind the most recent shuffle.
+    pub last_seed: Option<u64>,
+    /// Solve not yet counted by [`App::collect_solves`].
+    pub new_solve: Option<SolveRecord>,
+    pub timer: MoveTimer,
+    pub autoplay: Autoplay,
+    pub compare_reports: CompareReports<MissionariesCannibalsState>,
+    /// A* run one expansion per Space or Enter while step mode is on.
+    pub step_solver: Option<AStarSearch<MissionariesCannibalsState>>,
+    /// Bank the current attempt started from.
+    pub start: MissionariesCannibalsState,
+    /// Crossings made by hand since `start`.
+    pub moves_made: usize,
+    /// Fewest crossings from `start`, known once the solver has run.
+    pub optimal_moves: Option<usize>,
+    /// Load of the crossing that led to the bank shown, and whether the boat
+    /// went from the left bank to the right one.
+    pub last_crossing: Option<(BoatMove, bool)>,
+    /// Banks before each hand crossing since `start`, newest last. Not
+    /// capped: the puzzle has only a few dozen banks.
+    pub history: Vec<MissionariesCannibalsState>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MissionariesCannibalsSolution {
+    pub report: SearchReport<MissionariesCannibalsState>,
+    pub step: usize,
+}
+
+impl MissionariesCannibalsSolution {
+    pub fn total_steps(&self) -> usize {
+        self.report.path.len().saturating_sub(1)
+    }
+
+    /// The crossing that led to replay step `step` (1-based), read off the
+    /// banks before and after it, and whether the boat went from the left
+    /// bank to the right one.
+    pub fn step_move(&self, step: usize) -> Option<(BoatMove, bool)> {
+        let before = self.report.path.get(step.checked_sub(1)?)?;
+        let mv = before.crossing_to(self.report.path.get(step)?)?;
+        Some((mv, before.boat_left))
+    }
+}
+
+impl Default for MissionariesCannibalsSession {
+    fn default() -> Self {
+        Self {
+            state: MissionariesCannibalsState::default(),
+            status: Self::base_status(),
+            solution: None,
+            selected_move: 0,
+            rng: StdRng::from_entropy(),
+            seed: None,
+            last_seed: None,
+            new_solve: None,
+            timer: MoveTimer::default(),
+            autoplay: Autoplay::default(),
+            compare_reports: CompareReports::default(),
+            step_solver: None,
+            start: MissionariesCannibalsState::default(),
+            moves_made: 0,
+            optimal_moves: None,
+            last_crossing: None,
+            history: Vec::new(),
+        }
+    }
+}
+
+impl MissionariesCannibalsSession {
+    fn base_status() -> String {
+        "Use S to solve, Space to step through solution. H shuffles, R resets, +/- group size, </> boat size.".into()
+    }
+
+    pub fn reset(&mut self) {
+        self.state = MissionariesCannibalsState::new(self.state.n, self.state.boat_capacity);
+        self.status = Self::base_status();
+        self.solution = None;
+        self.step_solver = None;
+        self.selected_move = 0;
+        self.timer.clear();
+        self.compare_reports.clear();
+        self.begin_attempt();
+    }
+
+    pub fn load(&mut self, state: MissionariesCannibalsState, moves_made: usize) {
+        self.state = state;
+        self.solution = None;
+        self.selected_move = 0;
+        self.timer.clear();
+        self.begin_attempt();
+        self.moves_made = moves_made;
+    }
+
+    /// Counts crossings afresh from the current bank. The optimal count is
+    /// kept when the attempt starts from the same bank as the last one.
+    fn begin_attempt(&mut self) {
+        if self.state != self.start {
+            self.optimal_moves = None;
+        }
+        self.start = self.state;
+        self.moves_made = 0;
+        self.last_crossing = None;
+        self.history.clear();
+    }
+
+    pub fn set_group_size(&mut self, n: u8) {
+        let n = n.clamp(
+            missionaries_cannibals::MIN_GROUP_SIZE,
+            missionaries_cannibals::MAX_GROUP_SIZE,
+        );
+        self.state = MissionariesCannibalsState::new(n, self.state.boat_capacity);
+        self.solution = None;
+        self.selected_move = 0;
+        self.timer.clear();
+        self.begin_attempt();
+        self.status = format!(
+            "{} missionaries and {} cannibals, boat holds {}.",
+            n, n, self.state.boat_capacity
+        );
+        if n > 4 {
+            self.status.push_str(" Large groups grow the search space quickly.");
+        }
+    }
+
+    pub fn set_boat_capacity(&mut self, capacity: u8) {
+        let capacity = capacity.clamp(
+            missionaries_cannibals::MIN_BOAT_CAPACITY,
+            missionaries_cannibals::MAX_BOAT_CAPACITY,
+        );
+        self.state = MissionariesCannibalsState::new(self.state.n, capacity);
+        self.solution = None;
+        self.selected_move = 0;
+        self.timer.clear();
+        self.begin_attempt();
+        self.status = format!(
+            "{} missionaries and {} cannibals, boat holds {}.",
+            self.state.n, self.state.n, capacity
+        );
+    }
+
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+        self.status = seed_status(seed);
+    }
+
+    pub fn shuffle(&mut self) {
+        use rand::Rng;
+        let seed = reseed_for_shuffle(&mut self.rng, self.seed);
+        self.last_seed = Some(seed);
+        self.timer.clear();
+        let rng = &mut self.rng;
+        
+        // Generate random valid states by trying different configurations
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            if attempts > 100 {
+                // Fallback to the starting bank if we can't find a valid random state
+                self.state = MissionariesCannibalsState::new(self.state.n, self.state.boat_capacity);
+                self.status = "Shuffled to default state.".into();
+                break;
+            }
+            
+            // Randomly distribute missionaries and cannibals
+            let left_m = rng.gen_range(0..=self.state.n);
+            let left_c = rng.gen_range(0..=self.state.n);
+            let boat_left = rng.gen_bool(0.5);
+            
+            let new_state = MissionariesCannibalsState {
+                left_m,
+                left_c,
+                boat_left,
+                ..self.state
+            };
+            
+            // Check if state is valid
+            if new_state.is_valid() {
+                // Also check if it's not the goal state (too easy)
+                if !new_state.is_goal() {
+                    self.state = new_state;
+                    self.solution = None;
+                    self.selected_move = 0;
+                    self.status = format!(
+                        "Shuffled: Left M={} C={}, Boat on {}",
+                        left_m,
+                        left_c,
+                        if boat_left { "left" } else { "right" }
+                    );
+                    break;
+                }
+            }
+        }
+        self.begin_attempt();
+        self.status.push_str(&format!(" Seed: {}.", seed));
+    }
+
+    pub fn is_stepping(&self) -> bool {
+        self.step_solver.is_some()
+    }
+
+    /// Turns step mode on or off. While it is on, each
+    /// [`MissionariesCannibalsSession::step_search`] expands a single A* node.
+    pub fn toggle_step_mode(&mut self) {
+        if self.step_solver.take().is_some() {
+            self.status = "Left step mode.".into();
+            return;
+        }
+        self.step_solver = Some(AStarSearch::new(self.state));
+        self.status = "Step mode: press Space or Enter to expand one A* node, I to leave.".into();
+    }
+
+    pub fn step_search(&mut self) {
+        let Some(step_solver) = &mut self.step_solver else {
+            return;
+        };
+        match step_solver.step() {
+            StepResult::Finished(report) => {
+                self.step_solver = None;
+                self.finish_solve("A* (stepped)", report);
+            }
+            StepResult::Expanded => {
+                if let Some((node, g, h)) = step_solver.current() {
+                    self.status = format!(
+                        "Expanded #{}: f = {} (g = {}, h = {}), {}M {}C on the left bank. Frontier: {}, closed: {}.",
+                        step_solver.closed_len(),
+                        g + h,
+                        g,
+                        h,
+                        node.left_m,
+                        node.left_c,
+                        step_solver.frontier_len(),
+                        step_solver.closed_len()
+                    );
+                }
+            }
+        }
+    }
+
+    pub fn solve(&mut self) {
+        if self.is_solved() {
+            self.status = "Already solved — nothing to do.".into();
+            return;
+        }
+        let report = astar_limited(self.state, DEFAULT_NODE_LIMIT);
+        self.finish_solve("A*", report);
+    }
+
+    /// Runs A* again from the solution's start (or the current bank), this
+    /// time recording every edge, and writes the graph to `search.dot`.
+    pub fn export_search_graph(&mut self) {
+        let start = self
+            .solution
+            .as_ref()
+            .and_then(|solution| solution.report.path.first().copied())
+            .unwrap_or(self.state);
+        let config = AstarConfig {
+            record_edges: true,
+            ..AstarConfig::with_node_limit(DEFAULT_NODE_LIMIT)
+        };
+        let report = astar_with_limits(start, config);
+        let path = Path::new("search.dot");
+        self.status = match persistence::export_search_graph(&report, path) {
+            Ok(()) => format!(
+                "Search graph ({} states, {} edges) written to search.dot; render it with dot -Tsvg search.dot.",
+                report.visited_states,
+                report.edges.len()
+            ),
+            Err(err) => format!("Export failed: {}.", err),
+        };
+    }
+
+    fn finish_solve(&mut self, label: AlgorithmLabel, report: SearchReport<MissionariesCannibalsState>) {
+        self.compare_reports.record(&self.state, label, &report);
+        if report.goal_found && !report.path.is_empty() {
+            self.new_solve = Some(SolveRecord::by_solver(report.actions.len(), report.elapsed));
+            // The solver may have started partway through the attempt
+            self.optimal_moves = if report.path.first() == Some(&self.start) {
+                Some(report.actions.len())
+            } else {
+                let from_start = astar_limited(self.start, DEFAULT_NODE_LIMIT);
+                from_start.goal_found.then_some(from_start.actions.len())
+            };
+            self.solution = Some(MissionariesCannibalsSolution { report, step: 0 });
+            if let Some(solution) = &self.solution {
+                if let Some(first) = solution.report.path.first() {
+                    self.state = *first;
+                }
+                self.status = format!(
+                    "Solution ready ({} moves). Press Space to step.",
+                    solution.total_steps()
+                );
+            }
+        } else {
+            self.solution = None;
+            let no_solution = format!(
+                "No solution found for {} pairs with a boat of {}.",
+                self.state.n, self.state.boat_capacity
+            );
+            self.status = unsolved_status(&report, &no_solution);
+        }
+        if self.state.n > 4 {
+            self.status.push_str(" Warning: N > 4 grows the state space quickly; allow a longer timeout for bigger groups.");
+        }
+    }
+
+    pub fn advance_solution(&mut self) -> bool {
+        if let Some(solution) = &mut self.solution {
+            if solution.step + 1 < solution.report.path.len() {
+                solution.step += 1;
+                self.last_crossing = solution.step_move(solution.step);
+                if let Some(state) = solution.report.path.get(solution.step) {
+                    self.state = *state;
+                    if solution.step == solution.report.path.len() - 1 {
+                        self.status = "Solution complete! Everyone crossed safely.".into();
+                    } else {
+                        self.status = format!(
+                            "Step {} / {}",
+                            solution.step,
+                            solution.total_steps()
+                        );
+                    }
+                }
+                return true;
+            } else {
+                self.status = "Already at final solution state.".into();
+                return false;
+            }
+        }
+        self.status = "Run the solver with 'S' first.".into();
+        false
+    }
+
+    pub fn rewind_solution(&mut self) -> bool {
+        if let Some(solution) = &mut self.solution {
+            if solution.step > 0 {
+                solution.step -= 1;
+                self.last_crossing = solution.step_move(solution.step);
+                if let Some(state) = solution.report.path.get(solution.step) {
+                    self.state = *state;
+                    if solution.step == 0 {
+                        self.status = "Back at the starting state.".into();
+                    } else {
+                        self.status = format!(
+                            "Step {} / {}",
+                            solution.step,
+                            solution.total_steps()
+                        );
+                    }
+                }
+                return true;
+            } else {
+                self.status = "Already at the start of the solution.".into();
+                return false;
+            }
+        }
+        self.status = "Run the solver with 'S' first.".into();
+        false
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.state.is_goal()
+    }
+
+    /// Whether a hand solve matched the fewest crossings the solver found.
+    pub fn is_optimal(&self) -> bool {
+        self.is_solved() && self.optimal_moves == Some(self.moves_made)
+    }
+
+    pub fn get_valid_moves(&self) -> Vec<BoatMove> {
+        self.state
+            .possible_moves()
+            .into_iter()
+            .filter(|mv| self.state.apply_move(*mv).is_ok())
+            .collect()
+    }
+
+    pub fn apply_move(&mut self, mv: BoatMove) -> bool {
+        match self.state.apply_move(mv) {
+            Ok(new_state) => {
+                self.history.push(self.state);
+                self.last_crossing = Some((mv, self.state.boat_left));
+                self.state = new_state;
+                self.solution = None;
+                self.moves_made += 1;
+                self.timer.start();
+                self.status = format!(
+                    "Moved {}M {}C {}",
+                    mv.missionaries,
+                    mv.cannibals,
+                    if self.state.boat_left { "to left" } else { "to right" }
+                );
+                if self.is_solved() {
+                    self.status = format!("Solved in {} crossings! Everyone crossed safely.", self.moves_made);
+                    if self.is_optimal() {
+                        self.status.push_str(" That is optimal.");
+                    }
+                    self.new_solve = Some(SolveRecord::by_hand(Some(self.moves_made)));
+                }
+                true
+            }
+            Err(err) => {
+                self.status = crossing_refusal(&self.state, mv, err);
+                false
+            }
+        }
+    }
+
+    /// Takes back the last hand crossing. Off while a solution is loaded,
+    /// whose steps Backspace walks back instead.
+    pub fn undo(&mut self) -> bool {
+        if self.solution.is_some() {
+            self.status = "Undo is off while a solution is loaded; Backspace steps back through it.".into();
+            return false;
+        }
+        match self.history.pop() {
+            Some(previous) => {
+                self.state = previous;
+                self.moves_made = self.moves_made.saturating_sub(1);
+                self.last_crossing = self
+                    .history
+                    .last()
+                    .and_then(|before| Some((before.crossing_to(&previous)?, before.boat_left)));
+                self.status = format!("Undid the last crossing ({} more available).", self.history.len());
+                true
+            }
+            None => {
+                self.status = "Nothing to undo.".into();
+                false
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct EightQueensSession {
+    pub state: EightQueensState,
+    pub status: String,
+    pub solution: Option<EightQueensSolution>,
+    pub selected_row: usize,
+    pub selected_col: usize,
+    pub algorithm: QueensAlgorithm,
+    pub all_solutions: Vec<EightQueensState>,
+    pub solution_index: usize,
+    pub rng: StdRng,
+    /// Pinned shuffle seed; `None` draws a fresh seed for every shuffle.
+    pub seed: Option<u64>,
+    /// Seed behind the most recent shuffle.
+    pub last_seed: Option<u64>,
+    /// Square suggested by the last hint and when it was asked for.
+    pub hint: Option<((usize, usize), Instant)>,
+    pub astar_config: AstarConfig,
+    /// Solve not yet counted by [`App::collect_solves`].
+    pub new_solve: Option<SolveRecord>,
+    pub timer: MoveTimer,
+    pub autoplay: Autoplay,
+    pub compare_reports: CompareReports<EightQueensState>,
+    /// Mark the empty squares a queen attacks; X turns it off and on.
+    pub show_attacks: bool,
+}
+
+/// Node limits cycled through with L on the 8 Queens screen.
+pub const NODE_LIMIT_PRESETS: [Option<usize>; 4] = [Some(10_000), Some(100_000), Some(1_000_000), None];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueensAlgorithm {
+    #[serde(rename = "astar")]
+    AStar,
+    MinConflicts,
+    UniformCost,
+    Greedy,
+    IdaStar,
+    SimulatedAnnealing,
+}
+
+impl QueensAlgorithm {
+    pub fn label(&self) -> &'static str {
+        match self {
+            QueensAlgorithm::AStar => "A*",
+            QueensAlgorithm::MinConflicts => "Min-conflicts",
+            QueensAlgorithm::UniformCost => "Uniform cost",
+            QueensAlgorithm::Greedy => "Greedy best-first",
+            QueensAlgorithm::IdaStar => "IDA*",
+            QueensAlgorithm::SimulatedAnnealing => "Simulated annealing",
+        }
+    }
+
+    /// The algorithm after this one in the M cycle. Simulated annealing has its
+    /// own key and is not part of the cycle, so it leads back to A*.
+    pub fn next(&self) -> Self {
+        match self {
+            QueensAlgorithm::AStar => QueensAlgorithm::MinConflicts,
+            QueensAlgorithm::MinConflicts => QueensAlgorithm::UniformCost,
+            QueensAlgorithm::UniformCost => QueensAlgorithm::Greedy,
+            QueensAlgorithm::Greedy => QueensAlgorithm::IdaStar,
+            QueensAlgorithm::IdaStar | QueensAlgorithm::SimulatedAnnealing => QueensAlgorithm::AStar,
+        }
+    }
+
+    /// Whether the path this algorithm returns may be longer than necessary.
+    pub fn is_optimal(&self) -> bool {
+        !matches!(self, QueensAlgorithm::Greedy)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EightQueensSolution {
+    pub report: SearchReport<EightQueensState>,
+    pub step: usize,
+    pub algorithm: QueensAlgorithm,
+}
+
+impl EightQueensSolution {
+    pub fn total_steps(&self) -> usize {
+        self.report.path.len().saturating_sub(1)
+    }
+
+    /// About b^d nodes for the last IDA* pass, where b is the start board's
+    /// branching estimate and d the depth that pass reached.
+    pub fn estimated_final_pass_nodes(&self) -> u64 {
+        let branching = self
+            .report
+            .path
+            .first()
+            .map_or(0.0, |start| start.branching_factor_estimate());
+        branching.powi(self.report.solution_depth as i32).round() as u64
+    }
+}
+
+impl Default for EightQueensSession {
+    fn default() -> Self {
+        Self {
+            state: EightQueensState::default(),
+            status: Self::base_status(),
+            solution: None,
+            selected_row: 0,
+            selected_col: 0,
+            algorithm: QueensAlgorithm::AStar,
+            all_solutions: Vec::new(),
+            solution_index: 0,
+            rng: StdRng::from_entropy(),
+            seed: None,
+            last_seed: None,
+            hint: None,
+            astar_config: AstarConfig::default(),
+            new_solve: None,
+            timer: MoveTimer::default(),
+            autoplay: Autoplay::default(),
+            compare_reports: CompareReports::default(),
+            show_attacks: true,
+        }
+    }
+}
+
+impl EightQueensSession {
+    fn base_status() -> String {
+        "Use arrows to select cell, Enter to place/remove queen. S solves, Space replays, M switches algorithm, A anneals, R resets, H shuffles.".into()
+    }
+
+    pub fn toggle_algorithm(&mut self) {
+        self.algorithm = self.algorithm.next();
+        self.status = format!("Solver algorithm: {}. Press S to solve.", self.algorithm.label());
+    }
+
+    pub fn toggle_attack_overlay(&mut self) {
+        self.show_attacks = !self.show_attacks;
+        self.status = if self.show_attacks {
+            "Attacked squares marked with ×. Press X to hide them.".into()
+        } else {
+            "Attacked squares hidden. Press X to mark them again.".into()
+        };
+    }
+
+    pub fn reset(&mut self) {
+        self.state = EightQueensState::default();
+        self.status = Self::base_status();
+        self.solution = None;
+        self.selected_row = 0;
+        self.selected_col = 0;
+        self.all_solutions.clear();
+        self.solution_index = 0;
+        self.timer.clear();
+        self.compare_reports.clear();
+    }
+
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+        self.status = seed_status(seed);
+    }
+
+    pub fn shuffle(&mut self) {
+        use rand::Rng;
+        let seed = reseed_for_shuffle(&mut self.rng, self.seed);
+        self.last_seed = Some(seed);
+        self.timer.clear();
+        let queens = self.rng.gen_range(1..=4);
+        self.state = EightQueensState::random_solvable(queens, &mut self.rng);
+        self.solution = None;
+        self.status = format!(
+            "Shuffled: {} queens kept from a random solution. State is solvable. Seed: {}.",
+            queens, seed
+        );
+    }
+
+    pub fn solve(&mut self) {
+        self.solve_with(self.algorithm);
+    }
+
+    /// Runs `algorithm` once without making it the one S uses.
+    pub fn solve_with(&mut self, algorithm: QueensAlgorithm) {
+        if self.is_solved() {
+            self.status = "Already solved — nothing to do.".into();
+            return;
+        }
+        // Enumerate from the board as the user left it so locked-in queens are kept.
+        self.all_solutions = self.state.all_solutions();
+        self.solution_index = 0;
+        match algorithm {
+            QueensAlgorithm::AStar | QueensAlgorithm::UniformCost | QueensAlgorithm::Greedy | QueensAlgorithm::IdaStar => {
+                self.solve_astar(algorithm)
+            }
+            QueensAlgorithm::MinConflicts => self.solve_min_conflicts(),
+            QueensAlgorithm::SimulatedAnnealing => self.solve_simulated_annealing(),
+        }
+        if let Some(solution) = &self.solution {
+            if let Some(last) = solution.report.path.last() {
+                if let Some(idx) = self.all_solutions.iter().position(|s| s == last) {
+                    self.solution_index = idx;
+                }
+            }
+        }
+    }
+
+    /// 1-based number of the enumerated solution currently on the board, if any.
+    pub fn current_solution_number(&self) -> Option<usize> {
+        self.all_solutions
+            .get(self.solution_index)
+            .filter(|solution| **solution == self.state)
+            .map(|_| self.solution_index + 1)
+    }
+
+    pub fn next_solution(&mut self) -> bool {
+        self.cycle_solution(1)
+    }
+
+    pub fn previous_solution(&mut self) -> bool {
+        self.cycle_solution(self.all_solutions.len().saturating_sub(1))
+    }
+
+    fn cycle_solution(&mut self, offset: usize) -> bool {
+        if self.all_solutions.is_empty() {
+            self.status = "Run the solver with 'S' first.".into();
+            return false;
+        }
+        let total = self.all_solutions.len();
+        self.solution_index = (self.solution_index + offset) % total;
+        self.state = self.all_solutions[self.solution_index];
+        self.solution = None;
+        self.status = format!("Solution {} / {}", self.solution_index + 1, total);
+        true
+    }
+
+    /// Moves to the next entry of [`NODE_LIMIT_PRESETS`].
+    pub fn cycle_node_limit(&mut self) {
+        let current = NODE_LIMIT_PRESETS
+            .iter()
+            .position(|limit| *limit == self.astar_config.node_limit)
+            .unwrap_or(NODE_LIMIT_PRESETS.len() - 1);
+        self.astar_config.node_limit = NODE_LIMIT_PRESETS[(current + 1) % NODE_LIMIT_PRESETS.len()];
+        self.status = match self.astar_config.node_limit {
+            Some(limit) => format!("A* node limit: {} nodes.", limit),
+            None => "A* node limit: unlimited.".into(),
+        };
+    }
+
+    /// A*, or with `UniformCost` / `Greedy` the same search without its
+    /// heuristic / path cost. The node limit only applies to A*.
+    fn solve_astar(&mut self, algorithm: QueensAlgorithm) {
+        let report = match (algorithm, self.astar_config.node_limit) {
+            (QueensAlgorithm::UniformCost, _) => uniform_cost(self.state),
+            (QueensAlgorithm::Greedy, _) => greedy_best_first(self.state),
+            (QueensAlgorithm::IdaStar, limit) => ida_star(self.state, limit.unwrap_or(DEFAULT_NODE_LIMIT)),
+            (_, Some(limit)) => astar_limited(self.state, limit),
+            (_, None) => astar(self.state),
+        };
+        self.compare_reports.record(&self.state, algorithm.label(), &report);
+        if report.goal_found && !report.path.is_empty() {
+            self.new_solve = Some(SolveRecord::by_solver(report.actions.len(), report.elapsed));
+            self.solution = Some(EightQueensSolution {
+                report,
+                step: 0,
+                algorithm,
+            });
+            if let Some(solution) = &self.solution {
+                if let Some(first) = solution.report.path.first() {
+                    self.state = *first;
+                }
+                self.status = format!(
+                    "Solution ready ({} steps). Press Space to step.",
+                    solution.total_steps()
+                );
+            }
+        } else {
+            self.solution = None;
+            let elapsed_secs = report.elapsed.as_secs();
+            if report.outcome == SearchOutcome::NodeLimit {
+                self.status = format!(
+                    "Stopped at the node limit ({} nodes expanded). Raise it with L, or try shuffling (H).",
+                    report.expanded_nodes
+                );
+            } else if report.outcome == SearchOutcome::TimedOut {
+                self.status = format!(
+                    "Search timed out after 1 hour ({} nodes explored). The puzzle may be unsolvable from this state, or try shuffling (H).",
+                    report.expanded_nodes
+                );
+            } else if report.expanded_nodes == 0 {
+                self.status = "No valid moves available. Try shuffling (H) for a different starting state.".into();
+            } else if report.expanded_nodes < 10 {
+                self.status = format!(
+                    "Search terminated early ({} states). This may indicate the starting state has no valid successors. Try shuffling (H).",
+                    report.expanded_nodes
+                );
+            } else {
+                self.status = format!(
+                    "No solution found after exploring {} states in {:.1}s. Still searching... Try shuffling (H) for a different starting state, or wait longer.",
+                    report.expanded_nodes,
+                    elapsed_secs as f64 + report.elapsed.subsec_millis() as f64 / 1000.0
+                );
+            }
+        }
+    }
+
+    fn solve_min_conflicts(&mut self) {
+        let report = self
+            .state
+            .min_conflicts_report(eight_queens::MIN_CONFLICTS_MAX_STEPS, &mut self.rng);
+        self.compare_reports.record(&self.state, QueensAlgorithm::MinConflicts.label(), &report);
+        if report.goal_found && !report.path.is_empty() {
+            self.new_solve = Some(SolveRecord::by_solver(report.actions.len(), report.elapsed));
+            self.solution = Some(EightQueensSolution {
+                report,
+                step: 0,
+                algorithm: QueensAlgorithm::MinConflicts,
+            });
+            if let Some(solution) = &self.solution {
+                if let Some(first) = solution.report.path.first() {
+                    self.state = *first;
+                }
+                self.status = format!(
+                    "Min-conflicts solved in {} repair steps. Press Space to step.",
+                    solution.report.expanded_nodes
+                );
+            }
+        } else {
+            self.solution = None;
+            self.status = format!(
+                "Min-conflicts gave up after {} repair steps. Press S to try again.",
+                report.expanded_nodes
+            );
+        }
+    }
+
+    fn solve_simulated_annealing(&mut self) {
+        let report = eight_queens::simulated_annealing(
+            self.state,
+            eight_queens::ANNEALING_INITIAL_TEMP,
+            eight_queens::ANNEALING_COOLING,
+            eight_queens::ANNEALING_MAX_ITER,
+            &mut self.rng,
+        );
+        self.compare_reports.record(&self.state, QueensAlgorithm::SimulatedAnnealing.label(), &report);
+        if report.goal_found && !report.path.is_empty() {
+            self.new_solve = Some(SolveRecord::by_solver(report.actions.len(), report.elapsed));
+            self.solution = Some(EightQueensSolution {
+                report,
+                step: 0,
+                algorithm: QueensAlgorithm::SimulatedAnnealing,
+            });
+            if let Some(solution) = &self.solution {
+                if let Some(first) = solution.report.path.first() {
+                    self.state = *first;
+                }
+                self.status = format!(
+                    "Simulated annealing solved in {} iterations ({} accepted moves). Press Space to step.",
+                    solution.report.expanded_nodes,
+                    solution.report.actions.len()
+                );
+            }
+        } else {
+            self.solution = None;
+            self.status = format!(
+                "Simulated annealing cooled down after {} iterations with conflicts left. Press A to try again.",
+                report.expanded_nodes
+            );
+        }
+    }
+
+    pub fn advance_solution(&mut self) -> bool {
+        if let Some(solution) = &mut self.solution {
+            if solution.step + 1 < solution.report.path.len() {
+                solution.step += 1;
+                if let Some(state) = solution.report.path.get(solution.step) {
+                    self.state = *state;
+                    if solution.step == solution.report.path.len() - 1 {
+                        self.status = "Solution complete! All 8 queens placed safely.".into();
+                    } else {
+                        self.status = format!(
+                            "Step {} / {}",
+                            solution.step,
+                            solution.total_steps()
+                        );
+                    }
+                }
+                return true;
+            } else {
+                self.status = "Already at final solution state.".into();
+                return false;
+            }
+        }
+        self.status = "Run the solver with 'S' first.".into();
+        false
+    }
+
+    pub fn rewind_solution(&mut self) -> bool {
+        if let Some(solution) = &mut self.solution {
+            if solution.step > 0 {
+                solution.step -= 1;
+                if let Some(state) = solution.report.path.get(solution.step) {
+                    self.state = *state;
+                    if solution.step == 0 {
+                        self.status = "Back at the starting state.".into();
+                    } else {
+                        self.status = format!(
+                            "Step {} / {}",
+                            solution.step,
+                            solution.total_steps()
+                        );
+                    }
+                }
+                return true;
+            } else {
+                self.status = "Already at the start of the solution.".into();
+                return false;
+            }
+        }
+        self.status = "Run the solver with 'S' first.".into();
+        false
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.state.is_goal()
+    }
+
+    pub fn move_cursor(&mut self, row_delta: isize, col_delta: isize, wrap: bool) {
+        let new_row = step_cursor(self.selected_row as isize, row_delta, 8, wrap) as usize;
+        let new_col = step_cursor(self.selected_col as isize, col_delta, 8, wrap) as usize;
+        self.selected_row = new_row;
+        self.selected_col = new_col;
+    }
+
+    /// A safe square in the first empty row, preferring one that still leads
+    /// to a full solution.
+    pub fn hint(&self) -> Option<(usize, usize)> {
+        let row = self.state.queens.iter().position(|queen| queen.is_none())?;
+        let completes = self
+            .state
+            .all_solutions()
+            .first()
+            .and_then(|solution| solution.queens[row])
+            .map(|col| (row, col as usize));
+        completes.or_else(|| {
+            (0..8)
+                .find(|&col| self.state.is_valid_placement(row as u8, col))
+                .map(|col| (row, col as usize))
+        })
+    }
+
+    pub fn show_hint(&mut self) {
+        match self.hint() {
+            Some((row, col)) => {
+                self.hint = Some(((row, col), Instant::now()));
+                self.status = format!("Hint: a queen is safe at row {}, col {}.", row + 1, col + 1);
+            }
+            None if self.is_solved() => self.status = "Already solved.".into(),
+            None => self.status = "No hint: every square in the next empty row is attacked.".into(),
+        }
+    }
+
+    /// Hinted square while its highlight is still showing.
+    pub fn active_hint(&self) -> Option<(usize, usize)> {
+        self.hint
+            .filter(|(_, shown)| shown.elapsed() < HINT_DURATION)
+            .map(|(square, _)| square)
+    }
+
+    pub fn toggle_queen(&mut self) -> bool {
+        let row = self.selected_row as u8;
+        let col = self.selected_col as u8;
+        self.timer.start();
+        
+        if self.state.queens[self.selected_row].is_some() {
+            // Remove queen
+            self.state = self.state.remove_queen(row);
+            self.solution = None;
+            self.status = format!("Removed queen from row {}, col {}.", row + 1, col + 1);
+            true
+        } else {
+            // Try to place queen
+            match self.state.apply_placement(PlaceQueen { row, col }) {
+                Ok(new_state) => {
+                    self.state = new_state;
+                    self.solution = None;
+                    let conflicts = self.state.count_conflicts();
+                    if conflicts == 0 && self.is_solved() {
+                        self.status = "Perfect! All 8 queens placed with no conflicts.".into();
+                        self.new_solve = Some(SolveRecord::by_hand(None));
+                    } else if conflicts == 0 {
+                        self.status = format!("Placed queen at row {}, col {}. No conflicts yet.", row + 1, col + 1);
+                    } else {
+                        self.status = format!("Placed queen at row {}, col {}. Conflicts: {}.", row + 1, col + 1, conflicts);
+                    }
+                    true
+                }
+                Err(MoveError::DeadEnd) => {
+                    self.status = format!("Cannot place queen at row {}, col {} (it would leave another row with no safe square).", row + 1, col + 1);
+                    false
+                }
+                Err(MoveError::ConflictsExist) => {
+                    self.status = format!("Cannot place queen at row {}, col {} (conflicts with existing queens).", row + 1, col + 1);
+                    false
+                }
+                Err(err) => {
+                    self.status = format!("Cannot place queen at row {}, col {}: {}.", row + 1, col + 1, err);
+                    false
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct HanoiSession {
+    pub state: HanoiState,
+    pub status: String,
+    pub solution: Option<HanoiSolution>,
+    pub selected_peg: usize,
+    pub held_peg: Option<usize>,
+    pub moves_made: usize,
+    /// Solve not yet counted by [`App::collect_solves`].
+    pub new_solve: Option<SolveRecord>,
+    pub timer: MoveTimer,
+    pub autoplay: Autoplay,
+    pub compare_reports: CompareReports<HanoiState>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HanoiSolution {
+    pub report: SearchReport<HanoiState>,
+    pub step: usize,
+}
+
+impl HanoiSolution {
+    pub fn total_steps(&self) -> usize {
+        self.report.path.len().saturating_sub(1)
+    }
+
+    /// Label of the move that leads into replay step `step` (1-based).
+    pub fn move_label(&self, step: usize) -> Option<String> {
+        step.checked_sub(1)
+            .and_then(|idx| self.report.actions.get(idx))
+            .map(HanoiMove::label)
+    }
+}
+
+impl Default for HanoiSession {
+    fn default() -> Self {
+        Self {
+            state: HanoiState::default(),
+            status: Self::base_status(),
+            solution: None,
+            selected_peg: 0,
+            held_peg: None,
+            moves_made: 0,
+            new_solve: None,
+            timer: MoveTimer::default(),
+            autoplay: Autoplay::default(),
+            compare_reports: CompareReports::default(),
+        }
+    }
+}
+
+impl HanoiSession {
+    fn base_status() -> String {
+        "1-3 or Enter pick up / drop a disk, S solves, Space steps, +/- disk count, R resets.".into()
+    }
+
+    pub fn reset(&mut self) {
+        self.state = HanoiState::new(self.state.disks);
+        self.status = Self::base_status();
+        self.solution = None;
+        self.held_peg = None;
+        self.moves_made = 0;
+        self.timer.clear();
+        self.compare_reports.clear();
+    }
+
+    pub fn set_disks(&mut self, disks: u8) {
+        let disks = disks.clamp(tower_of_hanoi::MIN_DISKS, tower_of_hanoi::MAX_DISKS);
+        self.state = HanoiState::new(disks);
+        self.solution = None;
+        self.held_peg = None;
+        self.moves_made = 0;
+        self.timer.clear();
+        self.status = format!(
+            "{} disks: the optimal solution takes {} moves.",
+            disks,
+            HanoiState::optimal_moves(disks)
+        );
+    }
+
+    pub fn load(&mut self, state: HanoiState, moves_made: usize) {
+        self.state = state;
+        self.solution = None;
+        self.held_peg = None;
+        self.moves_made = moves_made;
+        self.timer.clear();
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        let pegs = tower_of_hanoi::PEG_COUNT as isize;
+        self.selected_peg = (self.selected_peg as isize + delta).clamp(0, pegs - 1) as usize;
+    }
+
+    /// Picks up the top disk of `peg`, or drops the held disk onto it.
+    pub fn select_peg(&mut self, peg: usize) -> bool {
+        if peg >= tower_of_hanoi::PEG_COUNT {
+            return false;
+        }
+        self.selected_peg = peg;
+        let Some(from) = self.held_peg else {
+            if self.state.top(peg).is_none() {
+                self.status = format!("Peg {} is empty.", peg + 1);
+                return false;
+            }
+            self.held_peg = Some(peg);
+            self.status = format!("Holding the top disk of peg {}. Choose a destination.", peg + 1);
+            return true;
+        };
+        self.held_peg = None;
+        if from == peg {
+            self.status = "Put the disk back.".into();
+            return false;
+        }
+        let mv = HanoiMove {
+            from: from as u8,
+            to: peg as u8,
+        };
+        match self.state.apply_move(mv) {
+            Some(next) => {
+                self.state = next;
+                self.solution = None;
+                self.moves_made += 1;
+                self.timer.start();
+                if self.is_solved() {
+                    self.new_solve = Some(SolveRecord::by_hand(Some(self.moves_made)));
+                }
+                self.status = if self.is_solved() {
+                    format!("Solved in {} moves!", self.moves_made)
+                } else {
+                    format!("Moved {}.", mv.label())
+                };
+                true
+            }
+            None => {
+                self.status = "A larger disk cannot go on a smaller one.".into();
+                false
+            }
+        }
+    }
+
+    pub fn solve(&mut self) {
+        let report = astar_limited(self.state.clone(), DEFAULT_NODE_LIMIT);
+        self.compare_reports.record(&self.state, "A*", &report);
+        if report.goal_found && !report.path.is_empty() {
+            self.new_solve = Some(SolveRecord::by_solver(report.actions.len(), report.elapsed));
+            let solution = HanoiSolution { report, step: 0 };
+            if let Some(first) = solution.report.path.first() {
+                self.state = first.clone();
+            }
+            self.held_peg = None;
+            self.status = format!(
+                "Solution ready ({} moves; 2^{} - 1 = {} from the start). Press Space to step.",
+                solution.total_steps(),
+                self.state.disks,
+                HanoiState::optimal_moves(self.state.disks)
+            );
+            self.solution = Some(solution);
+        } else {
+            self.solution = None;
+            self.status = unsolved_status(&report, "No solution found.");
+        }
+    }
+
+    pub fn advance_solution(&mut self) -> bool {
+        if let Some(solution) = &mut self.solution {
+            if solution.step + 1 < solution.report.path.len() {
+                solution.step += 1;
+                if let Some(state) = solution.report.path.get(solution.step) {
+                    self.state = state.clone();
+                    if solution.step == solution.report.path.len() - 1 {
+                        self.status = "Solution complete! The tower has moved.".into();
                     } else {
                         self.status = format!(
-                            "Replaying solution: step {} / {}",
+                            "Step {} / {} ({})",
                             solution.step,
-                            solution.total_steps()
+                            solution.total_steps(),
+                            solution.move_label(solution.step).unwrap_or_default()
                         );
                     }
                 }
@@ -499,379 +4027,970 @@ impl EightPuzzleSession {
         self.status = "Run the solver with 'S' first.".into();
         false
     }
+
+    pub fn rewind_solution(&mut self) -> bool {
+        if let Some(solution) = &mut self.solution {
+            if solution.step > 0 {
+                solution.step -= 1;
+                if let Some(state) = solution.report.path.get(solution.step) {
+                    self.state = state.clone();
+                    if solution.step == 0 {
+                        self.status = "Back at the starting state.".into();
+                    } else {
+                        self.status = format!(
+                            "Step {} / {} ({})",
+                            solution.step,
+                            solution.total_steps(),
+                            solution.move_label(solution.step).unwrap_or_default()
+                        );
+                    }
+                }
+                return true;
+            } else {
+                self.status = "Already at the start of the solution.".into();
+                return false;
+            }
+        }
+        self.status = "Run the solver with 'S' first.".into();
+        false
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.state.is_goal()
+    }
 }
 
 #[derive(Debug)]
-pub struct XorTicTacToeSession {
-    pub state: XorTicTacToeState,
-    pub cursor: usize,
+pub struct KnightsTourSession {
+    pub state: KnightsTourState,
     pub status: String,
-    pub human_symbol: Player,
-    pub setup_mode: bool,
+    pub solution: Option<SearchReport<KnightsTourState>>,
+    pub cursor: (u8, u8),
+    /// Squares in visiting order; the knight stands on the last one.
+    pub tour: Vec<(u8, u8)>,
+    /// Solve not yet counted by [`App::collect_solves`].
+    pub new_solve: Option<SolveRecord>,
+    pub timer: MoveTimer,
+    pub compare_reports: CompareReports<KnightsTourState>,
 }
 
-impl Default for XorTicTacToeSession {
+impl Default for KnightsTourSession {
     fn default() -> Self {
         Self {
-            state: XorTicTacToeState::default(),
-            cursor: 4,
+            state: KnightsTourState::default(),
             status: Self::base_status(),
-            human_symbol: Player::X,
-            setup_mode: false,
+            solution: None,
+            cursor: (0, 0),
+            tour: vec![(0, 0)],
+            new_solve: None,
+            timer: MoveTimer::default(),
+            compare_reports: CompareReports::default(),
         }
     }
 }
 
-impl XorTicTacToeSession {
+impl KnightsTourSession {
     fn base_status() -> String {
-        "Arrows move cursor, X/O place pieces, Tab setup mode, H shuffle, S auto-move, R restart.".into()
+        "Arrows move cursor, Enter jumps there (or restarts the tour from it). S completes the tour, +/- resize, M switches heuristic, R resets.".into()
     }
 
     pub fn reset(&mut self) {
-        self.state = XorTicTacToeState::default();
-        self.cursor = 4;
-        self.setup_mode = false;
+        let (row, col) = self.tour.first().copied().unwrap_or((0, 0));
+        self.start_at(row, col);
+        self.compare_reports.clear();
         self.status = Self::base_status();
     }
 
-    pub fn toggle_setup_mode(&mut self) {
-        self.setup_mode = !self.setup_mode;
-        if self.setup_mode {
-            self.status = "Setup mode: Place X/O manually. Tab to exit setup.".into();
-        } else {
-            self.status = "Game mode: Playing against AI. Tab to enter setup.".into();
-        }
+    fn start_at(&mut self, row: u8, col: u8) {
+        self.state = KnightsTourState::new(self.state.size, row, col).with_heuristic(self.state.heuristic);
+        self.tour = vec![(row, col)];
+        self.solution = None;
+        self.timer.clear();
     }
 
-    pub fn shuffle(&mut self) {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
-        let mut cells = [None; 9];
-        let mut x_count = 0;
-        let mut o_count = 0;
-        
-        // Randomly place 3-4 pieces of each type
-        let total_pieces = rng.gen_range(0..=4);
-        for _ in 0..total_pieces {
-            let empty_cells: Vec<usize> = (0..9).filter(|&i| cells[i].is_none()).collect();
-            if !empty_cells.is_empty() {
-                let idx = empty_cells[rng.gen_range(0..empty_cells.len())];
-                if x_count <= o_count {
-                    cells[idx] = Some(Player::X);
-                    x_count += 1;
-                } else {
-                    cells[idx] = Some(Player::O);
-                    o_count += 1;
+    /// Replays a saved tour on a `size`×`size` board, stopping at the first
+    /// illegal jump.
+    pub fn load(&mut self, size: u8, tour: &[(u8, u8)]) {
+        let Some(&(row, col)) = tour.first() else {
+            return;
+        };
+        self.state.size = size;
+        self.start_at(row, col);
+        for &(row, col) in &tour[1..] {
+            match self.state.apply_move(KnightMove { row, col }) {
+                Some(next) => {
+                    self.state = next;
+                    self.tour.push((row, col));
                 }
+                None => break,
             }
         }
-        
-        self.state.cells = cells;
-        self.state.to_move = if x_count <= o_count { Player::X } else { Player::O };
-        self.cursor = 4;
-        self.status = "Board shuffled randomly.".into();
+        self.cursor = self.state.position;
     }
 
-    pub fn place_manual(&mut self, player: Player) -> bool {
-        if self.setup_mode {
-            // In setup mode, allow placing any piece
-            if self.state.cells[self.cursor].is_some() && self.state.cells[self.cursor] == Some(player) {
-                // Remove if same piece
-                self.state.cells[self.cursor] = None;
-                self.status = format!("Removed {} from cell {}.", format_player(player), self.cursor + 1);
-                return true;
+    pub fn move_cursor(&mut self, row_delta: isize, col_delta: isize, wrap: bool) {
+        let size = self.state.size as isize;
+        let row = step_cursor(self.cursor.0 as isize, row_delta, size, wrap);
+        let col = step_cursor(self.cursor.1 as isize, col_delta, size, wrap);
+        self.cursor = (row as u8, col as u8);
+    }
+
+    /// Starts a new tour from the top-left corner of a board `size` squares
+    /// wide, kept between [`knights_tour::MIN_SIZE`] and [`knights_tour::MAX_SIZE`].
+    pub fn set_size(&mut self, size: u8) {
+        self.state.size = size.clamp(knights_tour::MIN_SIZE, knights_tour::MAX_SIZE);
+        self.start_at(0, 0);
+        self.cursor = (0, 0);
+        self.compare_reports.clear();
+        self.status = format!("{0}×{0} board: the tour takes {1} jumps.", self.state.size, self.state.squares() - 1);
+    }
+
+    pub fn cycle_heuristic(&mut self) {
+        self.state.heuristic = self.state.heuristic.next();
+        self.solution = None;
+        self.status = format!(
+            "Heuristic: {} (h = {} here). Press S to solve with it.",
+            self.state.heuristic.label(),
+            self.state.heuristic()
+        );
+    }
+
+    /// Jumps to the cursor square if it is a legal knight move, otherwise
+    /// restarts the tour there.
+    pub fn select_cursor(&mut self) {
+        let (row, col) = self.cursor;
+        if let Some(next) = self.state.apply_move(KnightMove { row, col }) {
+            self.state = next;
+            self.tour.push((row, col));
+            self.solution = None;
+            self.timer.start();
+            if self.is_solved() {
+                self.new_solve = Some(SolveRecord::by_hand(Some(self.tour.len() - 1)));
             }
-            self.state.cells[self.cursor] = Some(player);
-            self.status = format!("Placed {} in cell {}.", format_player(player), self.cursor + 1);
-            true
+            self.status = if self.is_solved() {
+                "Tour complete! Every square visited once.".into()
+            } else {
+                format!("Move {}: jumped to row {}, column {}.", self.tour.len(), row + 1, col + 1)
+            };
         } else {
-            // In game mode, only allow placing human symbol on their turn
-            if self.is_locked() {
-                self.status = "Game over. Press R to restart.".into();
-                return false;
+            self.start_at(row, col);
+            self.status = format!(
+                "Tour starts at row {}, column {}. Press S to complete it.",
+                row + 1,
+                col + 1
+            );
+        }
+    }
+
+    /// Move number (1-based) at which the knight reached `(row, col)`.
+    pub fn move_number(&self, row: u8, col: u8) -> Option<usize> {
+        self.tour
+            .iter()
+            .position(|&square| square == (row, col))
+            .map(|idx| idx + 1)
+    }
+
+    pub fn solve(&mut self) {
+        let report = astar_limited(self.state, DEFAULT_NODE_LIMIT);
+        self.compare_reports.record(&self.state, self.state.heuristic.search_label(), &report);
+        if report.goal_found {
+            self.new_solve = Some(SolveRecord::by_solver(report.actions.len(), report.elapsed));
+            self.tour
+                .extend(report.actions.iter().map(|mv| (mv.row, mv.col)));
+            if let Some(last) = report.path.last() {
+                self.state = *last;
             }
-            if self.state.to_move != self.human_symbol {
-                self.status = "Wait for the AI to finish its move.".into();
-                return false;
+            self.status = format!(
+                "Tour complete in {} moves after expanding {} nodes.",
+                self.tour.len() - 1,
+                report.expanded_nodes
+            );
+        } else {
+            self.status = unsolved_status(&report, "No tour exists from this partial path. Press R to restart.");
+        }
+        self.solution = Some(report);
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.state.is_goal()
+    }
+}
+
+#[derive(Debug)]
+pub struct WaterJugSession {
+    pub state: WaterJugState,
+    pub status: String,
+    pub solution: Option<WaterJugSolution>,
+    pub selected_jug: usize,
+    /// Jug chosen as the source of the next manual pour.
+    pub pour_from: Option<usize>,
+    pub editing: bool,
+    /// Field being edited: a jug index, or `jugs.len()` for the target.
+    pub edit_field: usize,
+    /// Solve not yet counted by [`App::collect_solves`].
+    pub new_solve: Option<SolveRecord>,
+    pub timer: MoveTimer,
+    pub autoplay: Autoplay,
+    pub compare_reports: CompareReports<WaterJugState>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WaterJugSolution {
+    pub report: SearchReport<WaterJugState>,
+    pub step: usize,
+}
+
+impl WaterJugSolution {
+    pub fn total_steps(&self) -> usize {
+        self.report.path.len().saturating_sub(1)
+    }
+
+    /// Label of the move that leads into replay step `step` (1-based).
+    pub fn move_label(&self, step: usize) -> Option<String> {
+        step.checked_sub(1)
+            .and_then(|idx| self.report.actions.get(idx))
+            .map(WaterJugMove::label)
+    }
+}
+
+impl Default for WaterJugSession {
+    fn default() -> Self {
+        Self {
+            state: WaterJugState::default(),
+            status: Self::base_status(),
+            solution: None,
+            selected_jug: 0,
+            pour_from: None,
+            editing: false,
+            edit_field: 0,
+            new_solve: None,
+            timer: MoveTimer::default(),
+            autoplay: Autoplay::default(),
+            compare_reports: CompareReports::default(),
+        }
+    }
+}
+
+impl WaterJugSession {
+    fn base_status() -> String {
+        "←→ select jug, F fills, X empties, Enter pours. S solves, Space steps, 1-3 presets, E edits jugs.".into()
+    }
+
+    pub fn reset(&mut self) {
+        self.state = WaterJugState::new(self.state.capacities.clone(), self.state.target);
+        self.solution = None;
+        self.pour_from = None;
+        self.timer.clear();
+        self.compare_reports.clear();
+        self.status = Self::base_status();
+    }
+
+    pub fn load(&mut self, state: WaterJugState) {
+        self.state = state;
+        self.solution = None;
+        self.pour_from = None;
+        self.editing = false;
+        self.selected_jug = 0;
+        self.timer.clear();
+    }
+
+    pub fn load_preset(&mut self, index: usize) {
+        if let Some(state) = WaterJugState::preset(index) {
+            self.load(state);
+            self.status = format!("Preset {}: {}", index + 1, self.describe());
+        }
+    }
+
+    fn describe(&self) -> String {
+        let capacities: Vec<String> = self
+            .state
+            .capacities
+            .iter()
+            .map(|capacity| format!("{}L", capacity))
+            .collect();
+        format!("jugs {} targeting {}L.", capacities.join(" / "), self.state.target)
+    }
+
+    pub fn toggle_editing(&mut self) {
+        self.editing = !self.editing;
+        self.pour_from = None;
+        if self.editing {
+            self.edit_field = 0;
+            self.status = "Editing: ←→ choose a jug or the target, ↑↓ adjust, +/- add or remove a jug, E or Enter to finish.".into();
+        } else {
+            self.status = format!("Ready: {}", self.describe());
+        }
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        if self.editing {
+            let last = self.state.jugs.len() as isize;
+            self.edit_field = (self.edit_field as isize + delta).clamp(0, last) as usize;
+        } else {
+            let last = self.state.jugs.len() as isize - 1;
+            self.selected_jug = (self.selected_jug as isize + delta).clamp(0, last) as usize;
+        }
+    }
+
+    /// Changes the capacity or target under the edit cursor by `delta` liters.
+    pub fn adjust_field(&mut self, delta: i32) {
+        let mut capacities = self.state.capacities.clone();
+        let mut target = self.state.target;
+        let adjust = |value: u32| {
+            (value as i32 + delta).clamp(water_jug::MIN_CAPACITY as i32, water_jug::MAX_CAPACITY as i32)
+                as u32
+        };
+        match capacities.get_mut(self.edit_field) {
+            Some(capacity) => *capacity = adjust(*capacity),
+            None => target = adjust(target),
+        }
+        self.apply_setup(capacities, target);
+    }
+
+    pub fn add_jug(&mut self) {
+        if self.state.jugs.len() >= water_jug::MAX_JUGS {
+            self.status = format!("At most {} jugs.", water_jug::MAX_JUGS);
+            return;
+        }
+        let mut capacities = self.state.capacities.clone();
+        capacities.push(water_jug::MIN_CAPACITY);
+        self.apply_setup(capacities, self.state.target);
+    }
+
+    pub fn remove_jug(&mut self) {
+        if self.state.jugs.len() <= water_jug::MIN_JUGS {
+            self.status = format!("At least {} jugs are needed.", water_jug::MIN_JUGS);
+            return;
+        }
+        let mut capacities = self.state.capacities.clone();
+        capacities.pop();
+        self.apply_setup(capacities, self.state.target);
+    }
+
+    fn apply_setup(&mut self, capacities: Vec<u32>, target: u32) {
+        self.state = WaterJugState::new(capacities, target);
+        self.solution = None;
+        self.edit_field = self.edit_field.min(self.state.jugs.len());
+        self.selected_jug = self.selected_jug.min(self.state.jugs.len() - 1);
+        self.status = format!("Editing: {}", self.describe());
+    }
+
+    pub fn fill_selected(&mut self) -> bool {
+        self.apply_move(WaterJugMove {
+            from: RIVER,
+            to: self.selected_jug,
+        })
+    }
+
+    pub fn empty_selected(&mut self) -> bool {
+        self.apply_move(WaterJugMove {
+            from: self.selected_jug,
+            to: RIVER,
+        })
+    }
+
+    /// First press picks the source jug, the second pours it into the selected jug.
+    pub fn pour_selected(&mut self) -> bool {
+        match self.pour_from.take() {
+            None => {
+                self.pour_from = Some(self.selected_jug);
+                self.status = format!(
+                    "Pouring from jug {}. Select the destination and press Enter.",
+                    self.selected_jug + 1
+                );
+                true
             }
-            if player != self.human_symbol {
-                self.status = format!("You are playing as {}.", format_player(self.human_symbol));
-                return false;
+            Some(from) if from == self.selected_jug => {
+                self.status = "Pour cancelled.".into();
+                false
+            }
+            Some(from) => self.apply_move(WaterJugMove {
+                from,
+                to: self.selected_jug,
+            }),
+        }
+    }
+
+    fn apply_move(&mut self, mv: WaterJugMove) -> bool {
+        if self.editing {
+            self.status = "Finish editing (E) before pouring.".into();
+            return false;
+        }
+        match self.state.apply_move(mv) {
+            Some(next) => {
+                self.state = next;
+                self.solution = None;
+                self.timer.start();
+                if self.is_solved() {
+                    self.new_solve = Some(SolveRecord::by_hand(None));
+                }
+                self.status = if self.is_solved() {
+                    format!("Solved! A jug holds exactly {}L.", self.state.target)
+                } else {
+                    format!("{}.", mv.label())
+                };
+                true
+            }
+            None => {
+                self.status = format!("Cannot {}.", mv.label().to_lowercase());
+                false
             }
-            if self.state.cells[self.cursor].is_some() {
-                self.status = format!("Cell {} is already occupied.", self.cursor + 1);
+        }
+    }
+
+    pub fn solve(&mut self) {
+        self.editing = false;
+        self.pour_from = None;
+        let report = astar_limited(self.state.clone(), DEFAULT_NODE_LIMIT);
+        self.compare_reports.record(&self.state, "A*", &report);
+        if report.goal_found && !report.path.is_empty() {
+            self.new_solve = Some(SolveRecord::by_solver(report.actions.len(), report.elapsed));
+            let solution = WaterJugSolution { report, step: 0 };
+            self.status = format!(
+                "Solution ready ({} moves). Press Space to step.",
+                solution.total_steps()
+            );
+            self.solution = Some(solution);
+        } else {
+            self.solution = None;
+            let no_solution = format!(
+                "No sequence of fills, pours and empties reaches {}L with these jugs.",
+                self.state.target
+            );
+            self.status = unsolved_status(&report, &no_solution);
+        }
+    }
+
+    pub fn advance_solution(&mut self) -> bool {
+        if let Some(solution) = &mut self.solution {
+            if solution.step + 1 < solution.report.path.len() {
+                solution.step += 1;
+                if let Some(state) = solution.report.path.get(solution.step) {
+                    self.state = state.clone();
+                    self.status = if solution.step == solution.report.path.len() - 1 {
+                        format!("Solution complete! A jug holds exactly {}L.", self.state.target)
+                    } else {
+                        format!(
+                            "Step {} / {} ({})",
+                            solution.step,
+                            solution.total_steps(),
+                            solution.move_label(solution.step).unwrap_or_default()
+                        )
+                    };
+                }
+                return true;
+            } else {
+                self.status = "Already at final solution state.".into();
                 return false;
             }
-            
-            self.state.cells[self.cursor] = Some(self.human_symbol);
-            self.state.to_move = self.human_symbol.opponent();
-            self.status = format!("Placed {} in cell {}.", format_player(self.human_symbol), self.cursor + 1);
-            self.update_outcome();
-            
-            if !self.is_locked() {
-                self.ai_auto_move();
+        }
+        self.status = "Run the solver with 'S' first.".into();
+        false
+    }
+
+    pub fn rewind_solution(&mut self) -> bool {
+        if let Some(solution) = &mut self.solution {
+            if solution.step > 0 {
+                solution.step -= 1;
+                if let Some(state) = solution.report.path.get(solution.step) {
+                    self.state = state.clone();
+                    self.status = if solution.step == 0 {
+                        "Back at the starting state.".into()
+                    } else {
+                        format!(
+                            "Step {} / {} ({})",
+                            solution.step,
+                            solution.total_steps(),
+                            solution.move_label(solution.step).unwrap_or_default()
+                        )
+                    };
+                }
+                return true;
+            } else {
+                self.status = "Already at the start of the solution.".into();
+                return false;
             }
-            true
         }
+        self.status = "Run the solver with 'S' first.".into();
+        false
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.state.is_goal()
+    }
+}
+
+#[derive(Debug)]
+pub struct LightsOutSession {
+    pub state: LightsOutState,
+    pub start: LightsOutState,
+    pub status: String,
+    /// Cells to press, shown on the board once the solver has run.
+    pub solution: Option<Vec<usize>>,
+    pub cursor: usize,
+    pub moves_made: usize,
+    pub rng: StdRng,
+    /// Solve not yet counted by [`App::collect_solves`].
+    pub new_solve: Option<SolveRecord>,
+    pub timer: MoveTimer,
+}
+
+impl Default for LightsOutSession {
+    fn default() -> Self {
+        Self::with_rng(StdRng::from_entropy())
     }
+}
 
-    pub fn move_cursor(&mut self, row_delta: isize, col_delta: isize) {
-        let row = (self.cursor / 3) as isize;
-        let col = (self.cursor % 3) as isize;
-        let new_row = (row + row_delta).clamp(0, 2);
-        let new_col = (col + col_delta).clamp(0, 2);
-        self.cursor = (new_row * 3 + new_col) as usize;
+impl LightsOutSession {
+    fn base_status() -> String {
+        "Arrows move, Space/Enter presses a light. S shows the fewest presses, H new board, +/- size, R resets.".into()
     }
 
-    pub fn quick_place(&mut self, index: usize) -> bool {
-        if index < 9 {
-            self.cursor = index;
-            return self.place_cell(index);
+    pub fn with_rng(mut rng: StdRng) -> Self {
+        let state = LightsOutState::random_solvable(lights_out::DEFAULT_SIZE, &mut rng);
+        Self {
+            state,
+            start: state,
+            status: Self::base_status(),
+            solution: None,
+            cursor: 0,
+            moves_made: 0,
+            rng,
+            new_solve: None,
+            timer: MoveTimer::default(),
         }
-        false
     }
 
-    pub fn place_cursor(&mut self) -> bool {
-        self.place_cell(self.cursor)
+    pub fn reset(&mut self) {
+        self.state = self.start;
+        self.solution = None;
+        self.moves_made = 0;
+        self.timer.clear();
+        self.status = "Reset to the starting pattern.".into();
     }
 
-    pub fn place_cell(&mut self, index: usize) -> bool {
-        if index >= 9 {
-            return false;
-        }
-        self.cursor = index;
-        if self.setup_mode {
-            // In setup mode, toggle between X, O, and empty
-            match self.state.cells[index] {
-                None => {
-                    self.state.cells[index] = Some(Player::X);
-                    self.status = format!("Placed X in cell {}.", index + 1);
-                }
-                Some(Player::X) => {
-                    self.state.cells[index] = Some(Player::O);
-                    self.status = format!("Changed to O in cell {}.", index + 1);
-                }
-                Some(Player::O) => {
-                    self.state.cells[index] = None;
-                    self.status = format!("Cleared cell {}.", index + 1);
-                }
-            }
-            return true;
-        }
-        
-        if self.is_locked() {
-            self.status = "Game over. Press R to restart.".into();
+    pub fn load(&mut self, state: LightsOutState, moves_made: usize) {
+        self.state = state;
+        self.start = state;
+        self.solution = None;
+        self.cursor = 0;
+        self.moves_made = moves_made;
+        self.timer.clear();
+    }
+
+    pub fn shuffle(&mut self) {
+        let state = LightsOutState::random_solvable(self.state.size, &mut self.rng);
+        self.load(state, 0);
+        self.status = format!("New {}×{} board with {} lights on.", state.size, state.size, state.lit_count());
+    }
+
+    pub fn set_size(&mut self, size: u8) {
+        let size = size.clamp(lights_out::MIN_SIZE, lights_out::MAX_SIZE);
+        let state = LightsOutState::random_solvable(size, &mut self.rng);
+        self.load(state, 0);
+        self.status = format!("Board size {}×{}.", size, size);
+    }
+
+    pub fn move_cursor(&mut self, row_delta: isize, col_delta: isize, wrap: bool) {
+        let size = self.state.size as isize;
+        let row = step_cursor(self.cursor as isize / size, row_delta, size, wrap);
+        let col = step_cursor(self.cursor as isize % size, col_delta, size, wrap);
+        self.cursor = (row * size + col) as usize;
+    }
+
+    pub fn press_cursor(&mut self) -> bool {
+        let Some(next) = self.state.press(self.cursor) else {
             return false;
+        };
+        self.state = next;
+        self.moves_made += 1;
+        self.timer.start();
+        if self.solution.is_some() {
+            self.solution = self.state.solve_gaussian_elimination();
         }
-        if self.state.to_move != self.human_symbol {
-            self.status = "Wait for the AI to finish its move.".into();
-            return false;
+        if self.is_solved() {
+            self.new_solve = Some(SolveRecord::by_hand(Some(self.moves_made)));
         }
-        if self.state.cells[index].is_some() {
-            self.status = format!("Cell {} is already occupied.", index + 1);
-            return false;
+        self.status = if self.is_solved() {
+            format!("All lights off in {} presses!", self.moves_made)
+        } else {
+            format!("{} lights still on.", self.state.lit_count())
+        };
+        true
+    }
+
+    /// Marks the fewest presses that clear the board. No A* here: the GF(2)
+    /// system already gives the exact answer.
+    pub fn solve(&mut self) {
+        let started = Instant::now();
+        self.solution = self.state.solve_gaussian_elimination();
+        if let Some(presses) = self.solution.as_ref().filter(|presses| !presses.is_empty()) {
+            self.new_solve = Some(SolveRecord::by_solver(presses.len(), started.elapsed()));
         }
+        self.status = match &self.solution {
+            Some(presses) if presses.is_empty() => "Every light is already off.".into(),
+            Some(presses) => format!(
+                "Press the {} marked cells (in any order) to switch every light off.",
+                presses.len()
+            ),
+            None => "This pattern cannot be cleared on this board size.".into(),
+        };
+    }
 
-        self.state.cells[index] = Some(self.human_symbol);
-        self.state.to_move = self.human_symbol.opponent();
-        self.status = format!("Placed {} in cell {}.", format_player(self.human_symbol), index + 1);
-        self.update_outcome();
+    pub fn is_in_solution(&self, index: usize) -> bool {
+        self.solution
+            .as_ref()
+            .is_some_and(|presses| presses.contains(&index))
+    }
 
-        if !self.is_locked() {
-            self.ai_auto_move();
-        }
-        true
+    pub fn is_solved(&self) -> bool {
+        self.state.is_goal()
     }
+}
 
-    pub fn auto_player_move(&mut self) -> bool {
-        if self.state.to_move != self.human_symbol {
-            self.status = "It's not your turn.".into();
-            return false;
-        }
-        if self.is_locked() {
-            self.status = "Game over. Press R to restart.".into();
-            return false;
-        }
-        if let Some(index) = self.pick_best_move(self.human_symbol) {
-            return self.place_cell(index);
+#[derive(Debug)]
+pub struct SokobanSession {
+    pub state: SokobanState,
+    /// Board that R returns to: the level as loaded.
+    pub start: SokobanState,
+    /// Index into [`sokoban::LEVELS`], or `None` for a board loaded from a save.
+    pub level: Option<usize>,
+    pub status: String,
+    pub solution: Option<SokobanSolution>,
+    pub moves_made: usize,
+    pub pushes: usize,
+    /// Solve not yet counted by [`App::collect_solves`].
+    pub new_solve: Option<SolveRecord>,
+    pub timer: MoveTimer,
+    pub autoplay: Autoplay,
+    pub compare_reports: CompareReports<SokobanState>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SokobanSolution {
+    pub report: SearchReport<SokobanState>,
+    pub step: usize,
+}
+
+impl SokobanSolution {
+    pub fn total_steps(&self) -> usize {
+        self.report.path.len().saturating_sub(1)
+    }
+
+    /// Box pushes along the whole solution.
+    pub fn push_count(&self) -> usize {
+        self.report
+            .path
+            .windows(2)
+            .filter(|pair| pair[0].boxes != pair[1].boxes)
+            .count()
+    }
+}
+
+impl Default for SokobanSession {
+    fn default() -> Self {
+        let state = SokobanState::default();
+        Self {
+            start: state.clone(),
+            state,
+            level: Some(0),
+            status: Self::base_status(),
+            solution: None,
+            moves_made: 0,
+            pushes: 0,
+            new_solve: None,
+            timer: MoveTimer::default(),
+            autoplay: Autoplay::default(),
+            compare_reports: CompareReports::default(),
         }
-        self.status = "No legal moves available.".into();
-        false
     }
+}
 
-    pub fn is_locked(&self) -> bool {
-        self.state.winner().is_some() || self.state.is_full()
+impl SokobanSession {
+    fn base_status() -> String {
+        "Arrows move and push boxes onto the goals. S solves, Space steps, 1-5 pick a level, R restarts.".into()
     }
 
-    fn update_outcome(&mut self) {
-        if let Some(winner) = self.state.winner() {
-            self.status = match winner {
-                Player::X => "You win! Press R to play again.".into(),
-                Player::O => "AI wins. Press R to try again.".into(),
-            };
-        } else if self.state.is_full() {
-            self.status = "It's a draw. Press R to restart.".into();
+    pub fn reset(&mut self) {
+        self.state = self.start.clone();
+        self.solution = None;
+        self.moves_made = 0;
+        self.pushes = 0;
+        self.timer.clear();
+        self.compare_reports.clear();
+        self.status = "Level restarted.".into();
+    }
+
+    pub fn load(&mut self, state: SokobanState, moves_made: usize) {
+        self.start = state.clone();
+        self.state = state;
+        self.level = None;
+        self.solution = None;
+        self.moves_made = moves_made;
+        self.pushes = 0;
+        self.timer.clear();
+        self.compare_reports.clear();
+    }
+
+    pub fn load_level(&mut self, index: usize) {
+        if let Some(state) = SokobanState::level(index) {
+            self.load(state, 0);
+            self.level = Some(index);
+            self.status = format!(
+                "Level {} of {}: {} boxes.",
+                index + 1,
+                sokoban::LEVELS.len(),
+                self.state.boxes.len()
+            );
         }
     }
 
-    fn ai_auto_move(&mut self) {
-        if self.state.to_move != Player::O || self.is_locked() {
-            return;
+    pub fn move_player(&mut self, mv: SokobanMove) -> bool {
+        let push = self.state.is_push(mv);
+        let Some(next) = self.state.apply_move(mv) else {
+            self.status = format!("Cannot move {}.", mv.label().to_lowercase());
+            return false;
+        };
+        self.state = next;
+        self.solution = None;
+        self.moves_made += 1;
+        if push {
+            self.pushes += 1;
         }
-        if let Some(index) = self.pick_best_move(Player::O) {
-            self.state.cells[index] = Some(Player::O);
-            self.state.to_move = Player::X;
-            self.cursor = index;
-            self.status = format!("AI placed O in cell {}.", index + 1);
-            self.update_outcome();
+        self.timer.start();
+        if self.is_solved() {
+            self.new_solve = Some(SolveRecord::by_hand(Some(self.moves_made)));
+            self.status = format!(
+                "Solved in {} moves and {} pushes!",
+                self.moves_made, self.pushes
+            );
+        } else {
+            self.status = format!(
+                "{} of {} boxes on goals.",
+                self.state.boxes_on_goals(),
+                self.state.boxes.len()
+            );
         }
+        true
     }
 
-    fn pick_best_move(&self, player: Player) -> Option<usize> {
-        self.find_winning_move(player)
-            .or_else(|| self.find_winning_move(player.opponent()))
-            .or_else(|| {
-                if self.state.cells[4].is_none() {
-                    Some(4)
-                } else {
-                    None
-                }
-            })
-            .or_else(|| {
-                [0, 2, 6, 8]
-                    .into_iter()
-                    .find(|&idx| self.state.cells[idx].is_none())
-            })
-            .or_else(|| (0..9).find(|&idx| self.state.cells[idx].is_none()))
-    }
-
-    fn find_winning_move(&self, player: Player) -> Option<usize> {
-        for line in WINNING_LINES {
-            let mut player_marks = 0;
-            let mut empty_spot = None;
-            for &idx in &line {
-                match self.state.cells[idx] {
-                    Some(mark) if mark == player => player_marks += 1,
-                    Some(_) => {}
-                    None => empty_spot = Some(idx),
+    pub fn solve(&mut self) {
+        let report = astar_limited(self.state.clone(), DEFAULT_NODE_LIMIT);
+        self.compare_reports.record(&self.state, "A*", &report);
+        if report.goal_found && !report.path.is_empty() {
+            self.new_solve = Some(SolveRecord::by_solver(report.actions.len(), report.elapsed));
+            let solution = SokobanSolution { report, step: 0 };
+            self.status = format!(
+                "Solution ready ({} moves, {} pushes). Press Space to step.",
+                solution.total_steps(),
+                solution.push_count()
+            );
+            self.solution = Some(solution);
+        } else {
+            self.solution = None;
+            self.status = unsolved_status(&report, "No solution from here: a box is stuck. Press R to restart the level.");
+        }
+    }
+
+    pub fn advance_solution(&mut self) -> bool {
+        if let Some(solution) = &mut self.solution {
+            if solution.step + 1 < solution.report.path.len() {
+                solution.step += 1;
+                if let Some(state) = solution.report.path.get(solution.step) {
+                    self.state = state.clone();
+                    self.status = if solution.step == solution.report.path.len() - 1 {
+                        "Solution complete! Every box is on a goal.".into()
+                    } else {
+                        format!("Step {} / {}", solution.step, solution.total_steps())
+                    };
                 }
+                return true;
+            } else {
+                self.status = "Already at final solution state.".into();
+                return false;
             }
-            if player_marks == 2 {
-                if let Some(idx) = empty_spot {
-                    if self.state.cells[idx].is_none() {
-                        return Some(idx);
-                    }
+        }
+        self.status = "Run the solver with 'S' first.".into();
+        false
+    }
+
+    pub fn rewind_solution(&mut self) -> bool {
+        if let Some(solution) = &mut self.solution {
+            if solution.step > 0 {
+                solution.step -= 1;
+                if let Some(state) = solution.report.path.get(solution.step) {
+                    self.state = state.clone();
+                    self.status = if solution.step == 0 {
+                        "Back at the starting state.".into()
+                    } else {
+                        format!("Step {} / {}", solution.step, solution.total_steps())
+                    };
                 }
+                return true;
+            } else {
+                self.status = "Already at the start of the solution.".into();
+                return false;
             }
         }
-        None
+        self.status = "Run the solver with 'S' first.".into();
+        false
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.state.is_goal()
     }
 }
 
 #[derive(Debug)]
-pub struct MissionariesCannibalsSession {
-    pub state: MissionariesCannibalsState,
+pub struct SudokuSession {
+    pub state: SudokuState,
+    /// Board as loaded; its filled cells are the givens, which cannot be changed.
+    pub start: SudokuState,
+    /// Index into [`sudoku::PUZZLES`], or `None` for a board loaded from a save.
+    pub puzzle: Option<usize>,
+    pub cursor: usize,
     pub status: String,
-    pub solution: Option<MissionariesCannibalsSolution>,
-    pub selected_move: usize,
+    pub solution: Option<SudokuSolution>,
+    pub moves_made: usize,
+    /// Solve not yet counted by [`App::collect_solves`].
+    pub new_solve: Option<SolveRecord>,
+    pub timer: MoveTimer,
+    pub autoplay: Autoplay,
+    pub compare_reports: CompareReports<SudokuState>,
 }
 
 #[derive(Debug, Clone)]
-pub struct MissionariesCannibalsSolution {
-    pub report: SearchReport<MissionariesCannibalsState>,
+pub struct SudokuSolution {
+    pub report: SearchReport<SudokuState>,
     pub step: usize,
 }
 
-impl MissionariesCannibalsSolution {
+impl SudokuSolution {
     pub fn total_steps(&self) -> usize {
         self.report.path.len().saturating_sub(1)
     }
+
+    /// Label of the guess that leads into replay step `step` (1-based).
+    pub fn move_label(&self, step: usize) -> Option<String> {
+        step.checked_sub(1)
+            .and_then(|idx| self.report.actions.get(idx))
+            .map(SudokuMove::label)
+    }
 }
 
-impl Default for MissionariesCannibalsSession {
+impl Default for SudokuSession {
     fn default() -> Self {
+        let state = SudokuState::default();
         Self {
-            state: MissionariesCannibalsState::default(),
+            state,
+            start: state,
+            puzzle: Some(0),
+            cursor: 0,
             status: Self::base_status(),
             solution: None,
-            selected_move: 0,
+            moves_made: 0,
+            new_solve: None,
+            timer: MoveTimer::default(),
+            autoplay: Autoplay::default(),
+            compare_reports: CompareReports::default(),
         }
     }
 }
 
-impl MissionariesCannibalsSession {
+impl SudokuSession {
     fn base_status() -> String {
-        "Use S to solve, Space to step through solution. H shuffles, R resets.".into()
+        "Arrows move, 1-9 fill a cell, 0/Delete clears it. S solves, Space steps, N next puzzle, R resets.".into()
     }
 
     pub fn reset(&mut self) {
-        self.state = MissionariesCannibalsState::default();
-        self.status = Self::base_status();
+        self.state = self.start;
         self.solution = None;
-        self.selected_move = 0;
+        self.moves_made = 0;
+        self.timer.clear();
+        self.compare_reports.clear();
+        self.status = "Back to the starting grid.".into();
     }
 
-    pub fn shuffle(&mut self) {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
-        
-        // Generate random valid states by trying different configurations
-        let mut attempts = 0;
-        loop {
-            attempts += 1;
-            if attempts > 100 {
-                // Fallback to default if we can't find a valid random state
-                self.state = MissionariesCannibalsState::default();
-                self.status = "Shuffled to default state.".into();
-                break;
-            }
-            
-            // Randomly distribute missionaries and cannibals
-            let left_m = rng.gen_range(0..=3);
-            let left_c = rng.gen_range(0..=3);
-            let boat_left = rng.gen_bool(0.5);
-            
-            let new_state = MissionariesCannibalsState {
-                left_m,
-                left_c,
-                boat_left,
-            };
-            
-            // Check if state is valid
-            if new_state.is_valid() {
-                // Also check if it's not the goal state (too easy)
-                if !new_state.is_goal() {
-                    self.state = new_state;
-                    self.solution = None;
-                    self.selected_move = 0;
-                    self.status = format!(
-                        "Shuffled: Left M={} C={}, Boat on {}",
-                        left_m,
-                        left_c,
-                        if boat_left { "left" } else { "right" }
-                    );
-                    break;
-                }
-            }
+    pub fn load(&mut self, state: SudokuState, moves_made: usize) {
+        self.state = state;
+        self.start = state;
+        self.puzzle = None;
+        self.solution = None;
+        self.moves_made = moves_made;
+        self.timer.clear();
+        self.compare_reports.clear();
+    }
+
+    pub fn load_puzzle(&mut self, index: usize) {
+        if let Some(state) = SudokuState::puzzle(index) {
+            self.load(state, 0);
+            self.puzzle = Some(index);
+            self.status = format!(
+                "Puzzle {} of {} ({}): {} empty cells.",
+                index + 1,
+                sudoku::PUZZLES.len(),
+                sudoku::PUZZLES[index].0,
+                state.empty_cells()
+            );
+        }
+    }
+
+    /// Moves on to the next built-in puzzle, wrapping after the last one.
+    pub fn next_puzzle(&mut self) {
+        let next = self.puzzle.map_or(0, |index| (index + 1) % sudoku::PUZZLES.len());
+        self.load_puzzle(next);
+    }
+
+    pub fn move_cursor(&mut self, row_delta: isize, col_delta: isize, wrap: bool) {
+        let row = step_cursor(self.cursor as isize / 9, row_delta, 9, wrap);
+        let col = step_cursor(self.cursor as isize % 9, col_delta, 9, wrap);
+        self.cursor = (row * 9 + col) as usize;
+    }
+
+    pub fn is_given(&self, index: usize) -> bool {
+        self.start.grid[index] != 0
+    }
+
+    /// Writes `digit` under the cursor, or clears the cell for 0.
+    pub fn set_digit(&mut self, digit: u8) -> bool {
+        if self.is_given(self.cursor) {
+            self.status = "That digit is part of the puzzle and cannot be changed.".into();
+            return false;
+        }
+        if digit != 0 && !self.state.allows(self.cursor, digit) {
+            self.status = format!("{} already appears in this row, column or box.", digit);
+            return false;
+        }
+        if self.state.grid[self.cursor] == digit {
+            return false;
+        }
+        self.state.grid[self.cursor] = digit;
+        self.solution = None;
+        self.moves_made += 1;
+        self.timer.start();
+        if self.is_solved() {
+            self.new_solve = Some(SolveRecord::by_hand(Some(self.moves_made)));
+            self.status = format!("Solved in {} entries!", self.moves_made);
+        } else {
+            self.status = format!("{} empty cells left.", self.state.empty_cells());
         }
+        true
     }
 
     pub fn solve(&mut self) {
-        let report = astar(self.state);
+        let report = astar_limited(self.state, DEFAULT_NODE_LIMIT);
+        self.compare_reports.record(&self.state, "A*", &report);
         if report.goal_found && !report.path.is_empty() {
-            self.solution = Some(MissionariesCannibalsSolution { report, step: 0 });
-            if let Some(solution) = &self.solution {
-                if let Some(first) = solution.report.path.first() {
-                    self.state = *first;
-                }
-                self.status = format!(
-                    "Solution ready ({} moves). Press Space to step.",
-                    solution.total_steps()
-                );
-            }
+            self.new_solve = Some(SolveRecord::by_solver(report.actions.len(), report.elapsed));
+            let solution = SudokuSolution { report, step: 0 };
+            self.status = format!(
+                "Solution ready ({} guesses after propagation). Press Space to step.",
+                solution.total_steps()
+            );
+            self.solution = Some(solution);
         } else {
             self.solution = None;
-            self.status = "No solution found.".into();
+            self.status = unsolved_status(&report, "No solution from here: clear some of your entries (0) or press R.");
         }
     }
 
@@ -881,15 +5000,16 @@ impl MissionariesCannibalsSession {
                 solution.step += 1;
                 if let Some(state) = solution.report.path.get(solution.step) {
                     self.state = *state;
-                    if solution.step == solution.report.path.len() - 1 {
-                        self.status = "Solution complete! Everyone crossed safely.".into();
+                    self.status = if solution.step == solution.report.path.len() - 1 {
+                        "Solution complete! Every row, column and box holds 1-9.".into()
                     } else {
-                        self.status = format!(
-                            "Step {} / {}",
+                        format!(
+                            "Step {} / {} (try {})",
                             solution.step,
-                            solution.total_steps()
-                        );
-                    }
+                            solution.total_steps(),
+                            solution.move_label(solution.step).unwrap_or_default()
+                        )
+                    };
                 }
                 return true;
             } else {
@@ -901,245 +5021,173 @@ impl MissionariesCannibalsSession {
         false
     }
 
-    pub fn is_solved(&self) -> bool {
-        self.state.is_goal()
-    }
-
-    pub fn get_valid_moves(&self) -> Vec<BoatMove> {
-        let mut moves = Vec::new();
-        let possible_moves = vec![
-            BoatMove { missionaries: 1, cannibals: 0 },
-            BoatMove { missionaries: 2, cannibals: 0 },
-            BoatMove { missionaries: 0, cannibals: 1 },
-            BoatMove { missionaries: 0, cannibals: 2 },
-            BoatMove { missionaries: 1, cannibals: 1 },
-        ];
-
-        for mv in possible_moves {
-            if self.state.apply_move(mv).is_some() {
-                moves.push(mv);
+    pub fn rewind_solution(&mut self) -> bool {
+        if let Some(solution) = &mut self.solution {
+            if solution.step > 0 {
+                solution.step -= 1;
+                if let Some(state) = solution.report.path.get(solution.step) {
+                    self.state = *state;
+                    self.status = if solution.step == 0 {
+                        "Back at the starting state.".into()
+                    } else {
+                        format!("Step {} / {}", solution.step, solution.total_steps())
+                    };
+                }
+                return true;
+            } else {
+                self.status = "Already at the start of the solution.".into();
+                return false;
             }
         }
-        moves
+        self.status = "Run the solver with 'S' first.".into();
+        false
     }
 
-    pub fn apply_move(&mut self, mv: BoatMove) -> bool {
-        if let Some(new_state) = self.state.apply_move(mv) {
-            self.state = new_state;
-            self.solution = None;
-            self.status = format!(
-                "Moved {}M {}C {}",
-                mv.missionaries,
-                mv.cannibals,
-                if self.state.boat_left { "to left" } else { "to right" }
-            );
-            if self.is_solved() {
-                self.status = "Solved! Everyone crossed safely.".into();
-            }
-            true
-        } else {
-            self.status = "Invalid move!".into();
-            false
-        }
+    pub fn is_solved(&self) -> bool {
+        self.state.is_goal()
     }
 }
 
 #[derive(Debug)]
-pub struct EightQueensSession {
-    pub state: EightQueensState,
+pub struct MazeSession {
+    pub state: MazeState,
+    /// Board that R returns to: the maze with the player back on Start.
+    pub start: MazeState,
+    /// Width and height, in cells, of the next maze G generates.
+    pub maze_size: (u8, u8),
+    pub rng: StdRng,
     pub status: String,
-    pub solution: Option<EightQueensSolution>,
-    pub selected_row: usize,
-    pub selected_col: usize,
+    pub solution: Option<MazeSolution>,
+    pub moves_made: usize,
+    /// Solve not yet counted by [`App::collect_solves`].
+    pub new_solve: Option<SolveRecord>,
+    pub timer: MoveTimer,
+    pub autoplay: Autoplay,
+    pub compare_reports: CompareReports<MazeState>,
 }
 
 #[derive(Debug, Clone)]
-pub struct EightQueensSolution {
-    pub report: SearchReport<EightQueensState>,
+pub struct MazeSolution {
+    pub report: SearchReport<MazeState>,
     pub step: usize,
 }
 
-impl EightQueensSolution {
+impl MazeSolution {
     pub fn total_steps(&self) -> usize {
         self.report.path.len().saturating_sub(1)
     }
+
+    /// Whether the solution path passes through `cell`.
+    pub fn visits(&self, cell: (u8, u8)) -> bool {
+        self.report.path.iter().any(|state| state.player == cell)
+    }
 }
 
-impl Default for EightQueensSession {
+impl Default for MazeSession {
     fn default() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+}
+
+impl MazeSession {
+    fn base_status() -> String {
+        "Arrows walk from S to E. S solves, Space steps, G new maze, +/- size, R restarts.".into()
+    }
+
+    pub fn with_rng(mut rng: StdRng) -> Self {
+        let state = MazeState::generate(maze::DEFAULT_SIZE, &mut rng);
         Self {
-            state: EightQueensState::default(),
+            start: state.clone(),
+            state,
+            maze_size: maze::DEFAULT_SIZE,
+            rng,
             status: Self::base_status(),
             solution: None,
-            selected_row: 0,
-            selected_col: 0,
+            moves_made: 0,
+            new_solve: None,
+            timer: MoveTimer::default(),
+            autoplay: Autoplay::default(),
+            compare_reports: CompareReports::default(),
         }
     }
-}
 
-impl EightQueensSession {
-    fn base_status() -> String {
-        "Use arrows to select cell, Space to place/remove queen. S solves, R resets, H shuffles.".into()
+    pub fn reset(&mut self) {
+        self.state = self.start.clone();
+        self.solution = None;
+        self.moves_made = 0;
+        self.timer.clear();
+        self.compare_reports.clear();
+        self.status = "Back at the start.".into();
     }
 
-    pub fn reset(&mut self) {
-        self.state = EightQueensState::default();
-        self.status = Self::base_status();
+    pub fn load(&mut self, state: MazeState, moves_made: usize) {
+        self.maze_size = (state.width(), state.height());
+        self.start = MazeState {
+            player: state.start().unwrap_or(state.player),
+            ..state.clone()
+        };
+        self.state = state;
         self.solution = None;
-        self.selected_row = 0;
-        self.selected_col = 0;
+        self.moves_made = moves_made;
+        self.timer.clear();
+        self.compare_reports.clear();
     }
 
-    pub fn shuffle(&mut self) {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
-        
-        // Use backtracking to generate a solvable partial solution
-        // This ensures the state always has a solution
-        let num_queens = rng.gen_range(1..=4);
-        let mut new_state = EightQueensState::default();
-        
-        // Generate a valid partial solution using backtracking
-        // This ensures the state is always solvable
-        let mut rows_used = [false; 8];
-        let mut placed = 0;
-        
-        // Try to place queens using a randomized backtracking approach
-        // that ensures solvability
-        while placed < num_queens {
-            let mut best_row: Option<usize> = None;
-            let mut best_options = Vec::new();
-            
-            // Find rows with the most valid placement options
-            for row in 0..8 {
-                if rows_used[row] {
-                    continue;
-                }
-                
-                let mut valid_cols = Vec::new();
-                for col in 0..8 {
-                    if new_state.is_valid_placement(row as u8, col) {
-                        valid_cols.push(col);
-                    }
-                }
-                
-                if !valid_cols.is_empty() {
-                    if best_options.is_empty() || valid_cols.len() >= best_options.len() {
-                        best_row = Some(row);
-                        best_options = valid_cols;
-                    }
-                }
-            }
-            
-            // Place a queen in the row with most options (or any valid row)
-            if let Some(row) = best_row {
-                if !best_options.is_empty() {
-                    let col = best_options[rng.gen_range(0..best_options.len())];
-                    if let Some(updated_state) = new_state.apply_placement(PlaceQueen { 
-                        row: row as u8, 
-                        col 
-                    }) {
-                        new_state = updated_state;
-                        rows_used[row] = true;
-                        placed += 1;
-                    }
-                } else {
-                    break; // No more valid placements
-                }
-            } else {
-                break; // No more valid rows
-            }
-        }
-        
-        // If we couldn't place the desired number, try a different approach:
-        // Use a known solvable pattern or start from a solved state and remove queens
-        if placed == 0 {
-            // Fallback: use a known valid partial solution
-            // Place queens in a pattern that's known to be solvable
-            let known_solutions = vec![
-                vec![(0, 0), (1, 4), (2, 7), (3, 5)],
-                vec![(0, 1), (1, 3), (2, 5), (3, 7)],
-                vec![(0, 2), (1, 5), (2, 1), (3, 6)],
-                vec![(0, 3), (1, 6), (2, 0), (3, 2)],
-            ];
-            let solution = &known_solutions[rng.gen_range(0..known_solutions.len())];
-            let to_place = rng.gen_range(1..=solution.len().min(4));
-            
-            for i in 0..to_place {
-                let (row, col) = solution[i];
-                if let Some(updated_state) = new_state.apply_placement(PlaceQueen { 
-                    row: row as u8, 
-                    col: col as u8 
-                }) {
-                    new_state = updated_state;
-                }
-            }
+    /// Carves a new maze of [`MazeSession::maze_size`].
+    pub fn generate(&mut self) {
+        let state = MazeState::generate(self.maze_size, &mut self.rng);
+        self.load(state, 0);
+        self.status = format!("New {}×{} maze.", self.maze_size.0, self.maze_size.1);
+    }
+
+    /// Grows (`steps > 0`) or shrinks the maze by [`maze::SIZE_STEP`] cells
+    /// per side and generates a new one.
+    pub fn resize(&mut self, steps: i16) {
+        let change = |size: u8| {
+            (size as i16 + steps * maze::SIZE_STEP as i16).clamp(maze::MIN_SIZE as i16, maze::MAX_SIZE as i16) as u8
+        };
+        let size = (change(self.maze_size.0), change(self.maze_size.1));
+        if size == self.maze_size {
+            self.status = format!("Maze size stays {}×{}.", size.0, size.1);
+            return;
         }
-        
-        self.state = new_state;
+        self.maze_size = size;
+        self.generate();
+    }
+
+    pub fn move_player(&mut self, mv: MazeMove) -> bool {
+        let Some(next) = self.state.apply_move(mv) else {
+            self.status = format!("A wall blocks the way {}.", mv.label().to_lowercase());
+            return false;
+        };
+        self.state = next;
         self.solution = None;
-        let conflicts = self.state.count_conflicts();
-        let queens_placed = self.state.queens.iter().filter(|q| q.is_some()).count();
-        
-        // Verify the state has valid successors
-        let has_successors = !self.state.successors().is_empty();
-        
-        if conflicts == 0 && has_successors {
-            self.status = format!("Shuffled: {} queens placed with no conflicts. State is solvable.", queens_placed);
-        } else if has_successors {
-            self.status = format!("Shuffled: {} queens placed. Conflicts: {}. State is solvable.", queens_placed, conflicts);
+        self.moves_made += 1;
+        self.timer.start();
+        self.status = if self.is_solved() {
+            self.new_solve = Some(SolveRecord::by_hand(Some(self.moves_made)));
+            format!("Reached the exit in {} moves!", self.moves_made)
         } else {
-            // If no successors, try one more time with a simpler approach
-            self.state = EightQueensState::default();
-            // Place just 1 queen in a random valid position
-            let row = rng.gen_range(0..8) as u8;
-            let col = rng.gen_range(0..8) as u8;
-            if let Some(updated_state) = self.state.apply_placement(PlaceQueen { row, col }) {
-                self.state = updated_state;
-                self.status = "Shuffled: 1 queen placed. State is solvable.".into();
-            } else {
-                // Last resort: empty board (always solvable)
-                self.state = EightQueensState::default();
-                self.status = "Shuffled: Empty board (always solvable).".into();
-            }
-        }
+            format!("{} steps from the exit as the crow flies.", self.state.heuristic())
+        };
+        true
     }
 
     pub fn solve(&mut self) {
-        let report = astar(self.state);
+        let report = astar_limited(self.state.clone(), DEFAULT_NODE_LIMIT);
+        self.compare_reports.record(&self.state, "A*", &report);
         if report.goal_found && !report.path.is_empty() {
-            self.solution = Some(EightQueensSolution { report, step: 0 });
-            if let Some(solution) = &self.solution {
-                if let Some(first) = solution.report.path.first() {
-                    self.state = *first;
-                }
-                self.status = format!(
-                    "Solution ready ({} steps). Press Space to step.",
-                    solution.total_steps()
-                );
-            }
+            self.new_solve = Some(SolveRecord::by_solver(report.actions.len(), report.elapsed));
+            let solution = MazeSolution { report, step: 0 };
+            self.status = format!(
+                "Cheapest route: {} moves costing {}, marked on the maze. Press Space to step.",
+                solution.total_steps(),
+                solution.report.path_cost()
+            );
+            self.solution = Some(solution);
         } else {
             self.solution = None;
-            let elapsed_secs = report.elapsed.as_secs();
-            if elapsed_secs >= 3600 {
-                self.status = format!(
-                    "Search timed out after 1 hour ({} nodes explored). The puzzle may be unsolvable from this state, or try shuffling (H).",
-                    report.expanded_nodes
-                );
-            } else if report.expanded_nodes == 0 {
-                self.status = "No valid moves available. Try shuffling (H) for a different starting state.".into();
-            } else if report.expanded_nodes < 10 {
-                self.status = format!(
-                    "Search terminated early ({} states). This may indicate the starting state has no valid successors. Try shuffling (H).",
-                    report.expanded_nodes
-                );
-            } else {
-                self.status = format!(
-                    "No solution found after exploring {} states in {:.1}s. Still searching... Try shuffling (H) for a different starting state, or wait longer.",
-                    report.expanded_nodes,
-                    elapsed_secs as f64 + report.elapsed.subsec_millis() as f64 / 1000.0
-                );
-            }
+            self.status = unsolved_status(&report, "No route to the exit from here.");
         }
     }
 
@@ -1148,16 +5196,12 @@ impl EightQueensSession {
             if solution.step + 1 < solution.report.path.len() {
                 solution.step += 1;
                 if let Some(state) = solution.report.path.get(solution.step) {
-                    self.state = *state;
-                    if solution.step == solution.report.path.len() - 1 {
-                        self.status = "Solution complete! All 8 queens placed safely.".into();
+                    self.state = state.clone();
+                    self.status = if solution.step == solution.report.path.len() - 1 {
+                        "Solution complete! The exit is reached.".into()
                     } else {
-                        self.status = format!(
-                            "Step {} / {}",
-                            solution.step,
-                            solution.total_steps()
-                        );
-                    }
+                        format!("Step {} / {}", solution.step, solution.total_steps())
+                    };
                 }
                 return true;
             } else {
@@ -1169,45 +5213,318 @@ impl EightQueensSession {
         false
     }
 
+    pub fn rewind_solution(&mut self) -> bool {
+        if let Some(solution) = &mut self.solution {
+            if solution.step > 0 {
+                solution.step -= 1;
+                if let Some(state) = solution.report.path.get(solution.step) {
+                    self.state = state.clone();
+                    self.status = if solution.step == 0 {
+                        "Back at the starting state.".into()
+                    } else {
+                        format!("Step {} / {}", solution.step, solution.total_steps())
+                    };
+                }
+                return true;
+            } else {
+                self.status = "Already at the start of the solution.".into();
+                return false;
+            }
+        }
+        self.status = "Run the solver with 'S' first.".into();
+        false
+    }
+
     pub fn is_solved(&self) -> bool {
         self.state.is_goal()
     }
+}
 
-    pub fn move_cursor(&mut self, row_delta: isize, col_delta: isize) {
-        let new_row = (self.selected_row as isize + row_delta).clamp(0, 7) as usize;
-        let new_col = (self.selected_col as isize + col_delta).clamp(0, 7) as usize;
-        self.selected_row = new_row;
-        self.selected_col = new_col;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Polls the background 8-Puzzle solver until it has reported back.
+    fn wait_for_solver(session: &mut EightPuzzleSession) {
+        while session.is_solving() {
+            thread::sleep(Duration::from_millis(5));
+            session.poll_solver();
+        }
     }
 
-    pub fn toggle_queen(&mut self) -> bool {
-        let row = self.selected_row as u8;
-        let col = self.selected_col as u8;
-        
-        if self.state.queens[self.selected_row].is_some() {
-            // Remove queen
-            self.state = self.state.remove_queen(row);
-            self.solution = None;
-            self.status = format!("Removed queen from row {}, col {}.", row + 1, col + 1);
-            true
-        } else {
-            // Try to place queen
-            if let Some(new_state) = self.state.apply_placement(PlaceQueen { row, col }) {
-                self.state = new_state;
-                self.solution = None;
-                let conflicts = self.state.count_conflicts();
-                if conflicts == 0 && self.is_solved() {
-                    self.status = "Perfect! All 8 queens placed with no conflicts.".into();
-                } else if conflicts == 0 {
-                    self.status = format!("Placed queen at row {}, col {}. No conflicts yet.", row + 1, col + 1);
-                } else {
-                    self.status = format!("Placed queen at row {}, col {}. Conflicts: {}.", row + 1, col + 1, conflicts);
-                }
-                true
-            } else {
-                self.status = format!("Cannot place queen at row {}, col {} (conflicts with existing queens).", row + 1, col + 1);
-                false
-            }
+    /// With the standard goal the wrapper must walk exactly the path that the
+    /// plain 8-puzzle search finds.
+    #[test]
+    fn custom_goal_search_matches_plain_astar() {
+        for seed in 0..20 {
+            let start = EightPuzzleState::random_solvable(&mut StdRng::seed_from_u64(seed));
+            let plain = astar(start);
+            let wrapped = astar(CustomGoalState {
+                state: start,
+                goal: EightPuzzleState::default(),
+                heuristic: EightPuzzleHeuristic::Manhattan,
+            });
+            let wrapped_path: Vec<EightPuzzleState> = wrapped.path.iter().map(|step| step.state).collect();
+            assert_eq!(wrapped_path, plain.path);
+            assert_eq!(wrapped.expanded_nodes, plain.expanded_nodes);
+        }
+    }
+
+    #[test]
+    fn background_solve_reaches_a_custom_goal() {
+        let mut session = EightPuzzleSession::with_rng(StdRng::seed_from_u64(1));
+        session.goal_state = EightPuzzleState::random_solvable(&mut StdRng::seed_from_u64(2));
+        let (start, goal) = (session.current, session.goal_state);
+        session.solve_current();
+        wait_for_solver(&mut session);
+
+        let solution = session.solution.expect("solvable boards share a parity");
+        assert_eq!(solution.report.path.first(), Some(&start));
+        assert_eq!(solution.report.path.last(), Some(&goal));
+        // Slides are reversible, so the way back is just as long
+        let reverse = astar(CustomGoalState {
+            state: goal,
+            goal: start,
+            heuristic: EightPuzzleHeuristic::Manhattan,
+        });
+        assert_eq!(solution.total_steps(), reverse.path.len() - 1);
+    }
+
+    /// Every heuristic finds an equally short path from the same seed, and
+    /// the comparison table shows how much work each one took.
+    #[test]
+    fn heuristics_trade_expanded_nodes_for_the_same_path_length() {
+        let mut session = EightPuzzleSession::with_rng(StdRng::seed_from_u64(7));
+        for _ in 0..4 {
+            session.solve_current();
+            wait_for_solver(&mut session);
+            session.cycle_heuristic();
+        }
+
+        let rows = &session.compare_reports.rows;
+        let labels: Vec<AlgorithmLabel> = rows.iter().map(|(label, _)| *label).collect();
+        assert_eq!(
+            labels,
+            ["A* (Manhattan)", "A* (Misplaced)", "A* (Linear conflict)", "A* (Pattern database)"]
+        );
+        let (manhattan, misplaced, linear, pattern) = (&rows[0].1, &rows[1].1, &rows[2].1, &rows[3].1);
+        assert_eq!(manhattan.actions.len(), misplaced.actions.len());
+        assert_eq!(manhattan.actions.len(), linear.actions.len());
+        assert_eq!(manhattan.actions.len(), pattern.actions.len());
+        assert!(misplaced.expanded_nodes > manhattan.expanded_nodes);
+        assert!(linear.expanded_nodes <= manhattan.expanded_nodes);
+        assert!(pattern.expanded_nodes <= manhattan.expanded_nodes);
+    }
+
+    /// A search stopped by its node limit still leaves a path to replay,
+    /// one that never claims to reach the goal.
+    #[test]
+    fn stopped_searches_replay_their_partial_path() {
+        let mut session = EightPuzzleSession::with_rng(StdRng::seed_from_u64(7));
+        let start = session.current;
+        let report = astar_limited(start, 20);
+        session.finish_solve("A*", report);
+
+        let solution = session.solution.clone().expect("a partial solution");
+        assert!(solution.is_partial());
+        let steps = solution.total_steps();
+        assert!(session.status.contains(&format!("Partial solution ({} steps, not optimal)", steps)));
+        assert!(!session.next_path());
+        for _ in 0..steps {
+            assert!(session.advance_solution());
+        }
+        assert!(session.status.starts_with("Partial solution"));
+        assert!(!session.is_solved());
+    }
+
+    /// Replaying the solver's crossings by hand matches the optimal count.
+    #[test]
+    fn hand_solves_are_compared_with_the_optimal_crossings() {
+        let mut session = MissionariesCannibalsSession::default();
+        session.solve();
+        assert_eq!(session.optimal_moves, Some(11));
+        let crossings = session.solution.as_ref().expect("a solution").report.actions.clone();
+
+        session.reset();
+        assert_eq!(session.optimal_moves, Some(11));
+        for mv in crossings {
+            assert!(session.apply_move(mv));
+        }
+        assert_eq!(session.moves_made, 11);
+        assert!(session.is_optimal());
+
+        session.set_group_size(2);
+        assert_eq!((session.moves_made, session.optimal_moves), (0, None));
+    }
+
+    #[test]
+    fn autoplay_steps_until_the_path_ends() {
+        let mut app = App::with_seed(1);
+        app.select_puzzle(PuzzleId::MissionariesCannibals);
+        app.toggle_autoplay();
+        assert!(!app.missionaries_cannibals.autoplay.playing, "nothing to play before solving");
+
+        app.missionaries_cannibals.solve();
+        app.toggle_autoplay();
+        app.change_autoplay_speed(true);
+        assert_eq!(app.autoplay().map(Autoplay::label).as_deref(), Some("▶ Auto-playing (0.25s/step)"));
+        let mut polls = 0;
+        while app.missionaries_cannibals.autoplay.playing {
+            app.missionaries_cannibals.autoplay.last_advance -= Duration::from_secs(1);
+            app.poll_autoplay();
+            polls += 1;
+        }
+        assert_eq!(polls, 10);
+        assert!(app.missionaries_cannibals.is_solved());
+        assert_eq!(app.missionaries_cannibals.status, "Solution complete! Everyone crossed safely.");
+    }
+
+    #[test]
+    fn undo_takes_back_hand_crossings_only() {
+        let mut session = MissionariesCannibalsSession::default();
+        assert!(!session.undo());
+        let start = session.state;
+        let first = session.get_valid_moves()[0];
+        assert!(session.apply_move(first));
+        let middle = session.state;
+        let second = session.get_valid_moves()[0];
+        assert!(session.apply_move(second));
+
+        assert!(session.undo());
+        assert_eq!((session.state, session.moves_made), (middle, 1));
+        assert!(session.last_crossing.is_some());
+        assert!(session.undo());
+        assert_eq!((session.state, session.moves_made), (start, 0));
+        assert!(!session.undo());
+
+        session.apply_move(first);
+        session.solve();
+        assert!(!session.undo(), "undo waits until the solution is dropped");
+        assert_eq!(session.state, middle);
+    }
+
+    #[test]
+    fn refused_crossings_say_why() {
+        let mut session = MissionariesCannibalsSession::default();
+        session.load(MissionariesCannibalsState { left_m: 1, left_c: 1, ..Default::default() }, 0);
+        assert!(!session.apply_move(BoatMove { missionaries: 2, cannibals: 0 }));
+        assert_eq!(session.status, "Cannot move 2 missionaries — only 1 on the left bank.");
+        assert!(!session.apply_move(BoatMove { missionaries: 0, cannibals: 1 }));
+        assert_eq!(
+            session.status,
+            "Cannot move 1 cannibal — cannibals would outnumber missionaries on the right bank."
+        );
+        assert!(!session.apply_move(BoatMove { missionaries: 0, cannibals: 3 }));
+        assert_eq!(session.status, "Cannot move 3 people — the boat holds 2.");
+    }
+
+    #[test]
+    fn replay_narrates_each_crossing() {
+        let mut session = MissionariesCannibalsSession::default();
+        session.solve();
+        let actions = session.solution.as_ref().expect("a solution").report.actions.clone();
+        for (step, expected) in actions.iter().enumerate() {
+            assert!(session.advance_solution());
+            let (mv, rightward) = session.last_crossing.expect("a crossing");
+            assert_eq!((mv.missionaries, mv.cannibals), (expected.missionaries, expected.cannibals));
+            assert_eq!(rightward, step % 2 == 0);
+        }
+        assert_eq!(BoatMove { missionaries: 1, cannibals: 1 }.passengers(), "1 missionary and 1 cannibal");
+        assert_eq!(BoatMove { missionaries: 0, cannibals: 2 }.passengers(), "2 cannibals");
+    }
+
+    #[test]
+    fn daily_challenge_is_the_same_board_for_everyone() {
+        let mut app = App::with_seed(1);
+        let mut other = App::with_seed(2);
+        app.start_daily_challenge();
+        other.start_daily_challenge();
+        let board = |app: &App| serde_json::to_string(&app.daily.as_ref().expect("a challenge").board).unwrap();
+        assert_eq!(board(&app), board(&other));
+
+        let AppRoute::Puzzle(puzzle) = app.current_route() else {
+            panic!("the challenge opens a puzzle");
+        };
+        assert!(daily::PUZZLES.contains(&puzzle));
+        let par = app.daily_in_play(puzzle).and_then(|daily| daily.par);
+        let solve = Some(SolveRecord::by_hand(par));
+        match puzzle {
+            PuzzleId::EightPuzzle => app.eight_puzzle.new_solve = solve,
+            PuzzleId::LightsOut => app.lights_out.new_solve = solve,
+            _ => app.maze.new_solve = solve,
         }
+        app.collect_solves();
+        assert_eq!(app.best_stats(PuzzleId::DailyChallenge).total_solves, 1);
+        assert!(app.status_mut(puzzle).is_some_and(|status| status.contains("That is par!")));
+    }
+
+    /// A board that already matches the goal never reaches the solver.
+    #[test]
+    fn solved_boards_skip_the_search() {
+        let mut eight = EightPuzzleSession::with_rng(StdRng::seed_from_u64(1));
+        eight.current = eight.goal_state;
+        eight.solve_current();
+        assert!(!eight.is_solving());
+        assert_eq!(eight.expanded_so_far(), 0);
+        assert!(eight.solution.is_none());
+        assert_eq!(eight.status, "Already solved — nothing to do.");
+
+        let mut missionaries = MissionariesCannibalsSession::default();
+        missionaries.state.left_m = 0;
+        missionaries.state.left_c = 0;
+        missionaries.state.boat_left = false;
+        missionaries.solve();
+        assert!(missionaries.solution.is_none());
+        assert!(missionaries.compare_reports.rows.is_empty());
+
+        let mut queens = EightQueensSession::default();
+        queens.state = queens.state.all_solutions()[0];
+        queens.solve();
+        assert!(queens.solution.is_none());
+        assert!(queens.compare_reports.rows.is_empty());
+    }
+
+    /// A path that jumps between boards is refused instead of replayed.
+    #[test]
+    fn replay_refuses_an_illegal_path() {
+        let mut eight = EightPuzzleSession::with_rng(StdRng::seed_from_u64(1));
+        eight.solve_current();
+        wait_for_solver(&mut eight);
+        assert!(eight.advance_solution());
+
+        let solution = eight.solution.as_mut().unwrap();
+        let last = solution.report.path.len() - 1;
+        solution.report.path.swap(1, last);
+        let before = eight.current;
+        assert!(!eight.advance_solution());
+        assert_eq!(eight.current, before);
+        assert_eq!(eight.status, "WARNING: Invalid solution path detected.");
+    }
+
+    /// Sessions saved before board sizes existed still load, on 8×8.
+    #[test]
+    fn saved_tours_keep_their_board_size() {
+        let old: SavedBoard = serde_json::from_str(r#"{"puzzle":"KnightsTour","board":[[0,0],[1,2]]}"#).unwrap();
+        let SavedBoard::KnightsTour(tour) = old else {
+            panic!("expected a Knight's Tour board");
+        };
+        assert_eq!((tour.size(), tour.squares().len()), (8, 2));
+
+        let mut knights = KnightsTourSession::default();
+        knights.set_size(6);
+        knights.cursor = (1, 2);
+        knights.select_cursor();
+        let saved = SavedBoard::KnightsTour(SavedTour::Sized {
+            size: knights.state.size,
+            squares: knights.tour.clone(),
+        });
+        let json = serde_json::to_string(&saved).unwrap();
+        let SavedBoard::KnightsTour(tour) = serde_json::from_str(&json).unwrap() else {
+            panic!("expected a Knight's Tour board");
+        };
+        let mut restored = KnightsTourSession::default();
+        restored.load(tour.size(), tour.squares());
+        assert_eq!(restored.state, knights.state);
+        assert_eq!(restored.tour, vec![(0, 0), (1, 2)]);
     }
 }
@@ -0,0 +1,35 @@
+use crate::persistence::SavedBoard;
+use crate::puzzles::PuzzleId;
+use chrono::{Datelike, Local, NaiveDate};
+
+/// Puzzles the daily challenge picks from: those with a random start board
+/// and a solver that gives the fewest moves to compare against.
+pub const PUZZLES: [PuzzleId; 3] = [PuzzleId::EightPuzzle, PuzzleId::LightsOut, PuzzleId::Maze];
+
+/// Today's challenge, the same for everyone who opens it on that date.
+#[derive(Debug, Clone)]
+pub struct DailyChallenge {
+    pub date: NaiveDate,
+    /// Board the challenge starts from; solving another board of the same
+    /// puzzle does not count.
+    pub board: SavedBoard,
+    /// Fewest moves the solver needs, when it found a solution.
+    pub par: Option<usize>,
+}
+
+impl DailyChallenge {
+    /// Header shown on the puzzle screen while the challenge is open.
+    pub fn title(&self) -> String {
+        format!("Daily Challenge — {}", self.date.format("%Y-%m-%d"))
+    }
+}
+
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// Seed for the boards of `date`: the day of the year plus the year times 1000,
+/// so 2024-02-01 gives 2024032.
+pub fn seed_for(date: NaiveDate) -> u64 {
+    date.year() as u64 * 1_000 + u64::from(date.ordinal())
+}
@@ -0,0 +1,224 @@
+use crate::app::App;
+use crate::puzzles::{HanoiState, KnightsTourState, PuzzleId, SokobanState, SudokuState, WaterJugState};
+use crate::puzzles::PuzzleRegistry;
+use crate::search::solver::{astar, greedy_best_first, ida_star, SearchReport, DEFAULT_NODE_LIMIT};
+use crate::search::SearchState;
+use clap::ValueEnum;
+use color_eyre::eyre::bail;
+use color_eyre::Result;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::time::Instant;
+
+/// Search run by `--solve` and `--benchmark`, picked with `--algorithm`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum SearchAlgorithm {
+    #[default]
+    #[value(name = "astar")]
+    AStar,
+    #[value(name = "idastar")]
+    IdaStar,
+    Greedy,
+}
+
+impl SearchAlgorithm {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SearchAlgorithm::AStar => "A*",
+            SearchAlgorithm::IdaStar => "IDA*",
+            SearchAlgorithm::Greedy => "Greedy best-first",
+        }
+    }
+
+    fn run<S: SearchState>(&self, start: S) -> SearchReport<S> {
+        match self {
+            SearchAlgorithm::AStar => astar(start),
+            SearchAlgorithm::IdaStar => ida_star(start, DEFAULT_NODE_LIMIT),
+            SearchAlgorithm::Greedy => greedy_best_first(start),
+        }
+    }
+}
+
+/// Solver metrics printed by `--solve`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SolveMetrics {
+    pub puzzle: &'static str,
+    pub seed: Option<u64>,
+    pub goal_found: bool,
+    pub moves: usize,
+    pub expanded_nodes: usize,
+    pub visited_states: usize,
+    pub elapsed_ms: f64,
+}
+
+impl SolveMetrics {
+    fn from_report<S: SearchState>(puzzle: PuzzleId, seed: Option<u64>, report: &SearchReport<S>) -> Self {
+        Self {
+            puzzle: puzzle.slug(),
+            seed,
+            goal_found: report.goal_found,
+            moves: report.path.len().saturating_sub(1),
+            expanded_nodes: report.expanded_nodes,
+            visited_states: report.visited_states,
+            elapsed_ms: report.elapsed.as_secs_f64() * 1_000.0,
+        }
+    }
+}
+
+impl Display for SolveMetrics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Puzzle:         {}", self.puzzle)?;
+        if let Some(seed) = self.seed {
+            writeln!(f, "Seed:           {}", seed)?;
+        }
+        writeln!(f, "Goal found:     {}", if self.goal_found { "yes" } else { "no" })?;
+        writeln!(f, "Moves:          {}", self.moves)?;
+        writeln!(f, "Expanded nodes: {}", self.expanded_nodes)?;
+        writeln!(f, "Visited states: {}", self.visited_states)?;
+        write!(f, "Elapsed:        {:.2} ms", self.elapsed_ms)
+    }
+}
+
+/// Runs `algorithm` on the puzzle's starting board, built exactly as the TUI
+/// would build it for the same seed.
+pub fn solve(puzzle: PuzzleId, seed: Option<u64>, algorithm: SearchAlgorithm) -> Result<SolveMetrics> {
+    let app = match seed {
+        Some(seed) => App::with_seed(seed),
+        None => App::default(),
+    };
+    solve_board(&app, puzzle, seed, algorithm)
+}
+
+fn solve_board(app: &App, puzzle: PuzzleId, seed: Option<u64>, algorithm: SearchAlgorithm) -> Result<SolveMetrics> {
+    let metrics = match puzzle {
+        PuzzleId::EightPuzzle => {
+            SolveMetrics::from_report(puzzle, seed, &algorithm.run(app.eight_puzzle.current))
+        }
+        PuzzleId::MissionariesCannibals => {
+            SolveMetrics::from_report(puzzle, seed, &algorithm.run(app.missionaries_cannibals.state))
+        }
+        PuzzleId::EightQueens => {
+            SolveMetrics::from_report(puzzle, seed, &algorithm.run(app.eight_queens.state))
+        }
+        PuzzleId::TowerOfHanoi => {
+            SolveMetrics::from_report(puzzle, seed, &algorithm.run(HanoiState::default()))
+        }
+        PuzzleId::KnightsTour => {
+            SolveMetrics::from_report(puzzle, seed, &algorithm.run(KnightsTourState::default()))
+        }
+        PuzzleId::WaterJug => {
+            SolveMetrics::from_report(puzzle, seed, &algorithm.run(WaterJugState::default()))
+        }
+        PuzzleId::Sokoban => {
+            SolveMetrics::from_report(puzzle, seed, &algorithm.run(SokobanState::default()))
+        }
+        PuzzleId::Sudoku => {
+            SolveMetrics::from_report(puzzle, seed, &algorithm.run(SudokuState::default()))
+        }
+        PuzzleId::Maze => {
+            SolveMetrics::from_report(puzzle, seed, &algorithm.run(app.maze.state.clone()))
+        }
+        PuzzleId::LightsOut => {
+            // Solved exactly by Gaussian elimination; A* over 2^25 boards is not practical.
+            let start = Instant::now();
+            let presses = app.lights_out.state.solve_gaussian_elimination();
+            SolveMetrics {
+                puzzle: puzzle.slug(),
+                seed,
+                goal_found: presses.is_some(),
+                moves: presses.map_or(0, |presses| presses.len()),
+                expanded_nodes: 0,
+                visited_states: 0,
+                elapsed_ms: start.elapsed().as_secs_f64() * 1_000.0,
+            }
+        }
+        PuzzleId::XorTicTacToe | PuzzleId::DailyChallenge | PuzzleId::About => {
+            bail!("'{}' has no A* solver to run headless", puzzle.slug())
+        }
+    };
+    Ok(metrics)
+}
+
+/// Expanded nodes and times over the `--benchmark` runs of one puzzle.
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchmarkSummary {
+    pub puzzle: &'static str,
+    pub runs: usize,
+    pub solved: usize,
+    pub avg_expanded: f64,
+    pub min_expanded: usize,
+    pub max_expanded: usize,
+    pub avg_ms: f64,
+    pub min_ms: f64,
+    pub max_ms: f64,
+}
+
+impl BenchmarkSummary {
+    /// Summary of `runs`, which must not be empty.
+    fn from_runs(puzzle: PuzzleId, runs: &[SolveMetrics]) -> Self {
+        let expanded = runs.iter().map(|run| run.expanded_nodes);
+        let times = runs.iter().map(|run| run.elapsed_ms);
+        let count = runs.len() as f64;
+        Self {
+            puzzle: puzzle.slug(),
+            runs: runs.len(),
+            solved: runs.iter().filter(|run| run.goal_found).count(),
+            avg_expanded: expanded.clone().sum::<usize>() as f64 / count,
+            min_expanded: expanded.clone().min().unwrap_or(0),
+            max_expanded: expanded.max().unwrap_or(0),
+            avg_ms: times.clone().sum::<f64>() / count,
+            min_ms: times.clone().fold(f64::INFINITY, f64::min),
+            max_ms: times.fold(0.0, f64::max),
+        }
+    }
+}
+
+impl Display for BenchmarkSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:<24} {:>4}/{:<4} {:>12.1} {:>10} {:>10} {:>10.2} {:>10.2} {:>10.2}",
+            self.puzzle,
+            self.solved,
+            self.runs,
+            self.avg_expanded,
+            self.min_expanded,
+            self.max_expanded,
+            self.avg_ms,
+            self.min_ms,
+            self.max_ms
+        )
+    }
+}
+
+/// Column headings matching [`BenchmarkSummary`]'s `Display` output.
+pub const BENCHMARK_HEADER: &str =
+    "Puzzle                   Solved      Avg nodes  Min nodes  Max nodes     Avg ms     Min ms     Max ms";
+
+/// Solves every searchable puzzle `runs` times with `algorithm`. Each run
+/// starts from the board the TUI would deal for a fresh seed, drawn from
+/// `seed` when one is given so the whole benchmark can be repeated. Puzzles
+/// with a fixed starting board solve the same board every run.
+pub fn benchmark(runs: usize, seed: Option<u64>, algorithm: SearchAlgorithm) -> Result<Vec<BenchmarkSummary>> {
+    let mut seeds = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let apps: Vec<App> = (0..runs).map(|_| App::with_seed(seeds.gen())).collect();
+    let mut summaries = Vec::new();
+    for descriptor in PuzzleRegistry::initialize().descriptors {
+        let puzzle = descriptor.id;
+        // Lights Out is solved by elimination rather than search, and the others have no solver
+        if matches!(puzzle, PuzzleId::LightsOut | PuzzleId::XorTicTacToe | PuzzleId::DailyChallenge | PuzzleId::About) {
+            continue;
+        }
+        let results = apps
+            .iter()
+            .map(|app| solve_board(app, puzzle, None, algorithm))
+            .collect::<Result<Vec<_>>>()?;
+        summaries.push(BenchmarkSummary::from_runs(puzzle, &results));
+    }
+    Ok(summaries)
+}
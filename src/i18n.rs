@@ -0,0 +1,214 @@
+use crate::puzzles::{Difficulty, PuzzleDescriptor, PuzzleId};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Language of the screen chrome, picked on the Settings screen or with `--lang`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en")]
+    #[value(name = "en")]
+    English,
+    #[serde(rename = "ar")]
+    #[value(name = "ar")]
+    Arabic,
+}
+
+/// Fixed text of the menus, panel titles and dialogs in one language.
+#[derive(Debug)]
+pub struct Strings {
+    pub app_title: &'static str,
+    pub puzzles: &'static str,
+    pub filter_hint: &'static str,
+    pub filtering: &'static str,
+    pub no_match: &'static str,
+    pub description: &'static str,
+    pub difficulty: &'static str,
+    pub all: &'static str,
+    pub change_difficulty: &'static str,
+    pub main_menu: &'static str,
+    pub settings: &'static str,
+    pub statistics: &'static str,
+    pub help: &'static str,
+    pub status: &'static str,
+    pub best: &'static str,
+    pub solver: &'static str,
+    pub board: &'static str,
+    pub general: &'static str,
+    pub solved: &'static str,
+    pub in_progress: &'static str,
+    pub quit_prompt: &'static str,
+    pub puzzle: &'static str,
+    pub times_solved: &'static str,
+    pub fastest_solve: &'static str,
+    pub fewest_moves: &'static str,
+    pub total_time_spent: &'static str,
+}
+
+const ENGLISH: Strings = Strings {
+    app_title: "AI Puzzle Suite (TUI)",
+    puzzles: "Puzzles",
+    filter_hint: "/ to filter",
+    filtering: "filtering",
+    no_match: "No puzzles match",
+    description: "Description",
+    difficulty: "Difficulty",
+    all: "All",
+    change_difficulty: "F to change",
+    main_menu: "Main Menu",
+    settings: "Settings",
+    statistics: "Statistics",
+    help: "Help",
+    status: "Status",
+    best: "Best",
+    solver: "Solver",
+    board: "Board",
+    general: "General",
+    solved: "Solved",
+    in_progress: "In progress",
+    quit_prompt: "Quit? (y/n)",
+    puzzle: "Puzzle",
+    times_solved: "Times Solved",
+    fastest_solve: "Fastest Solve",
+    fewest_moves: "Fewest Moves",
+    total_time_spent: "Total Time Spent",
+};
+
+const ARABIC: Strings = Strings {
+    app_title: "مجموعة ألغاز الذكاء الاصطناعي (TUI)",
+    puzzles: "الألغاز",
+    filter_hint: "/ للتصفية",
+    filtering: "تصفية",
+    no_match: "لا توجد ألغاز مطابقة",
+    description: "الوصف",
+    difficulty: "الصعوبة",
+    all: "الكل",
+    change_difficulty: "F للتغيير",
+    main_menu: "القائمة الرئيسية",
+    settings: "الإعدادات",
+    statistics: "الإحصائيات",
+    help: "المساعدة",
+    status: "الحالة",
+    best: "الأفضل",
+    solver: "الحل الآلي",
+    board: "اللوحة",
+    general: "عام",
+    solved: "محلولة",
+    in_progress: "قيد الحل",
+    quit_prompt: "خروج؟ (y/n)",
+    puzzle: "اللغز",
+    times_solved: "مرات الحل",
+    fastest_solve: "أسرع حل",
+    fewest_moves: "أقل عدد حركات",
+    total_time_spent: "إجمالي الوقت",
+};
+
+impl Language {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Arabic => "العربية",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Language::English => Language::Arabic,
+            Language::Arabic => Language::English,
+        }
+    }
+
+    pub fn strings(&self) -> &'static Strings {
+        match self {
+            Language::English => &ENGLISH,
+            Language::Arabic => &ARABIC,
+        }
+    }
+
+    /// Written right to left, so titles and lists line up on the right.
+    pub fn is_rtl(&self) -> bool {
+        matches!(self, Language::Arabic)
+    }
+
+    pub fn difficulty(&self, difficulty: Difficulty) -> &'static str {
+        match (self, difficulty) {
+            (Language::English, difficulty) => difficulty.label(),
+            (Language::Arabic, Difficulty::Beginner) => "مبتدئ",
+            (Language::Arabic, Difficulty::Intermediate) => "متوسط",
+            (Language::Arabic, Difficulty::Advanced) => "متقدم",
+            (Language::Arabic, Difficulty::Expert) => "خبير",
+        }
+    }
+
+    /// Menu name of `descriptor`; the registry holds the English one.
+    pub fn puzzle_name(&self, descriptor: &PuzzleDescriptor) -> &'static str {
+        match self {
+            Language::English => descriptor.name,
+            Language::Arabic => arabic_puzzle_text(descriptor.id).0,
+        }
+    }
+
+    pub fn puzzle_summary(&self, descriptor: &PuzzleDescriptor) -> &'static str {
+        match self {
+            Language::English => descriptor.summary,
+            Language::Arabic => arabic_puzzle_text(descriptor.id).1,
+        }
+    }
+}
+
+fn arabic_puzzle_text(id: PuzzleId) -> (&'static str, &'static str) {
+    match id {
+        PuzzleId::EightPuzzle => (
+            "لغز الثمانية",
+            "حرّك البلاطات إلى أماكنها وراقب إحصائيات البحث الموجَّه بالدالة التقديرية.",
+        ),
+        PuzzleId::XorTicTacToe => (
+            "إكس-أو بقاعدة XOR",
+            "العب بأفضل شكل مع تلميحات A* في نسخة غير مألوفة من إكس-أو.",
+        ),
+        PuzzleId::MissionariesCannibals => (
+            "المبشّرون وآكلو لحوم البشر",
+            "انقل 3 مبشّرين و3 من آكلي لحوم البشر عبر النهر بأمان باستخدام بحث A*.",
+        ),
+        PuzzleId::EightQueens => (
+            "مسألة الملكات الثماني",
+            "ضع 8 ملكات على رقعة الشطرنج دون أن تهاجم إحداها الأخرى، وشاهد A* يحلّها!",
+        ),
+        PuzzleId::TowerOfHanoi => (
+            "برج هانوي",
+            "انقل الكومة كاملة إلى العمود الأخير دون أن تضع قرصًا أكبر فوق قرص أصغر.",
+        ),
+        PuzzleId::KnightsTour => (
+            "جولة الحصان",
+            "زُر كل مربعات الرقعة مرة واحدة بالحصان، مسترشدًا بقاعدة وارنسدورف.",
+        ),
+        PuzzleId::WaterJug => (
+            "مسألة أباريق الماء",
+            "املأ الأباريق وأفرغها واسكب بينها حتى يحوي أحدها الكمية المطلوبة بالضبط.",
+        ),
+        PuzzleId::LightsOut => (
+            "أطفئ الأضواء",
+            "الضغط على مصباح يبدّل حالته وحالة جيرانه. أطفئ كل المصابيح؛ يجد حذف غاوس الحل الدقيق.",
+        ),
+        PuzzleId::Sokoban => (
+            "سوكوبان",
+            "ادفع كل صندوق إلى مربع هدف. يخطط A* للدفعات بمسافات الصناديق إلى الأهداف.",
+        ),
+        PuzzleId::Sudoku => (
+            "سودوكو",
+            "املأ الشبكة بحيث يحوي كل صف وعمود ومربع الأرقام 1-9. لا يخمّن A* إلا بعد أن يستنفد انتشار القيود.",
+        ),
+        PuzzleId::Maze => (
+            "المتاهة",
+            "جِد الطريق من S إلى E في متاهة عشوائية. يتبع A* مسافة مانهاتن إلى المخرج.",
+        ),
+        PuzzleId::DailyChallenge => (
+            "تحدي اليوم",
+            "لوحة اليوم، هي نفسها لكل من يلعب في هذا التاريخ. حلّها بيدك وقارن حركاتك بالمعدل.",
+        ),
+        PuzzleId::About => (
+            "عن البرنامج",
+            "تعرّف على مجموعة الألغاز هذه وخوارزمية A* والشكر والتقدير.",
+        ),
+    }
+}
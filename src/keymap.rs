@@ -0,0 +1,221 @@
+use crate::persistence;
+use crossterm::event::KeyCode;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Logical input actions shared by the puzzle screens. Keys that only make
+/// sense for one puzzle (placing X or O, filling a jug, …) stay hardcoded in
+/// that puzzle's handler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Select,
+    StepForward,
+    StepBack,
+    Solve,
+    Reset,
+    Shuffle,
+    Back,
+    Quit,
+}
+
+impl Action {
+    pub const ALL: [Action; 12] = [
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Select,
+        Action::StepForward,
+        Action::StepBack,
+        Action::Solve,
+        Action::Reset,
+        Action::Shuffle,
+        Action::Back,
+        Action::Quit,
+    ];
+
+    /// Name used for this action in `keybindings.toml`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::MoveUp => "MoveUp",
+            Action::MoveDown => "MoveDown",
+            Action::MoveLeft => "MoveLeft",
+            Action::MoveRight => "MoveRight",
+            Action::Select => "Select",
+            Action::StepForward => "StepForward",
+            Action::StepBack => "StepBack",
+            Action::Solve => "Solve",
+            Action::Reset => "Reset",
+            Action::Shuffle => "Shuffle",
+            Action::Back => "Back",
+            Action::Quit => "Quit",
+        }
+    }
+
+    fn default_keys(&self) -> Vec<KeyCode> {
+        match self {
+            Action::MoveUp => vec![KeyCode::Up, KeyCode::Char('w')],
+            Action::MoveDown => vec![KeyCode::Down, KeyCode::Char('j')],
+            Action::MoveLeft => vec![KeyCode::Left, KeyCode::Char('a')],
+            Action::MoveRight => vec![KeyCode::Right, KeyCode::Char('d')],
+            Action::Select => vec![KeyCode::Enter],
+            Action::StepForward => vec![KeyCode::Char(' ')],
+            Action::StepBack => vec![KeyCode::Backspace, KeyCode::Char('p')],
+            Action::Solve => vec![KeyCode::Char('s')],
+            Action::Reset => vec![KeyCode::Char('r')],
+            Action::Shuffle => vec![KeyCode::Char('h')],
+            Action::Back => vec![KeyCode::Esc, KeyCode::Char('b')],
+            Action::Quit => vec![KeyCode::Char('q')],
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum KeyMapError {
+    #[error("could not read {path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("invalid keybindings file {path}: {source}")]
+    Format {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    #[error("unknown key '{key}' bound to {action} in {path}")]
+    UnknownKey {
+        path: PathBuf,
+        action: &'static str,
+        key: String,
+    },
+}
+
+/// Keys bound to each [`Action`]. Letters are matched case-insensitively.
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    bindings: HashMap<Action, Vec<KeyCode>>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self {
+            bindings: Action::ALL
+                .iter()
+                .map(|action| (*action, action.default_keys()))
+                .collect(),
+        }
+    }
+}
+
+impl KeyMap {
+    /// `keybindings.toml` inside the platform config directory.
+    pub fn config_path() -> Option<PathBuf> {
+        persistence::config_file("keybindings.toml")
+    }
+
+    /// Loads the user's keybindings, or the defaults when there is no file.
+    pub fn load() -> Result<Self, KeyMapError> {
+        match Self::config_path() {
+            Some(path) => Self::from_file(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Reads a file of `Action = ["Key", …]` lines. Actions it leaves out keep
+    /// their default keys; a missing file means all defaults.
+    pub fn from_file(path: &Path) -> Result<Self, KeyMapError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(source) => {
+                return Err(KeyMapError::Io {
+                    path: path.to_path_buf(),
+                    source,
+                })
+            }
+        };
+        let overrides: HashMap<Action, Vec<String>> =
+            toml::from_str(&text).map_err(|source| KeyMapError::Format {
+                path: path.to_path_buf(),
+                source,
+            })?;
+
+        let mut keymap = Self::default();
+        // Walk actions in a fixed order so conflicting overrides resolve the same way every run
+        for action in Action::ALL {
+            let Some(names) = overrides.get(&action) else {
+                continue;
+            };
+            let keys = names
+                .iter()
+                .map(|name| {
+                    parse_key(name).ok_or_else(|| KeyMapError::UnknownKey {
+                        path: path.to_path_buf(),
+                        action: action.name(),
+                        key: name.clone(),
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            keymap.bind(action, keys);
+        }
+        Ok(keymap)
+    }
+
+    /// Binds `keys` to `action`, taking them away from any other action.
+    pub fn bind(&mut self, action: Action, keys: Vec<KeyCode>) {
+        for bound in self.bindings.values_mut() {
+            bound.retain(|key| !keys.contains(key));
+        }
+        self.bindings.insert(action, keys);
+    }
+
+    pub fn action(&self, code: KeyCode) -> Option<Action> {
+        let code = normalize(code);
+        Action::ALL.into_iter().find(|action| {
+            self.bindings
+                .get(action)
+                .is_some_and(|keys| keys.contains(&code))
+        })
+    }
+}
+
+fn normalize(code: KeyCode) -> KeyCode {
+    match code {
+        KeyCode::Char(ch) => KeyCode::Char(ch.to_ascii_lowercase()),
+        other => other,
+    }
+}
+
+/// Parses a key name such as `"Up"`, `"Enter"`, `"F5"` or a single character.
+fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        return Some(normalize(KeyCode::Char(ch)));
+    }
+    let code = match name.to_ascii_lowercase().as_str() {
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "enter" => KeyCode::Enter,
+        "space" => KeyCode::Char(' '),
+        "backspace" => KeyCode::Backspace,
+        "tab" => KeyCode::Tab,
+        "esc" | "escape" => KeyCode::Esc,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "delete" => KeyCode::Delete,
+        other => {
+            let number = other.strip_prefix('f')?.parse().ok()?;
+            KeyCode::F(number)
+        }
+    };
+    Some(code)
+}
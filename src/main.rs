@@ -1,12 +1,133 @@
 mod app;
+mod daily;
+mod headless;
+mod i18n;
+mod keymap;
+mod persistence;
 mod puzzles;
 mod search;
+mod settings;
+mod stats;
+mod theme;
 mod ui;
 
+use clap::Parser;
 use color_eyre::Result;
+use headless::SearchAlgorithm;
+use puzzles::PuzzleId;
+use std::time::Duration;
+
+const PUZZLE_NAMES: &str =
+    "Puzzles: eight, xor, missionaries, queens, hanoi, knights, jugs, lights, sokoban, sudoku, maze, daily, about";
+
+#[derive(Debug, Parser)]
+#[command(version, about, after_help = PUZZLE_NAMES)]
+struct Cli {
+    /// Open this puzzle instead of the main menu.
+    #[arg(short, long, value_name = "NAME", value_parser = parse_puzzle)]
+    puzzle: Option<PuzzleId>,
+    /// Solve this puzzle without starting the TUI.
+    #[arg(long, value_name = "NAME", value_parser = parse_puzzle, conflicts_with = "puzzle")]
+    solve: Option<PuzzleId>,
+    /// Seed for the starting boards and every shuffle.
+    #[arg(short, long)]
+    seed: Option<u64>,
+    /// Print --solve or --benchmark results as JSON.
+    #[arg(long)]
+    json: bool,
+    /// Input checks per second, 1-240 (default 20).
+    #[arg(long, value_parser = clap::value_parser!(u32).range(i64::from(app::MIN_FPS)..=i64::from(app::MAX_FPS)))]
+    fps: Option<u32>,
+    /// Language of menus and panel titles for this run.
+    #[arg(long, value_enum)]
+    lang: Option<i18n::Language>,
+    /// Search used by --solve and --benchmark.
+    #[arg(long, value_enum, default_value = "astar")]
+    algorithm: SearchAlgorithm,
+    /// Solve every searchable puzzle N times, print node and time statistics and exit.
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..), conflicts_with_all = ["puzzle", "solve"])]
+    benchmark: Option<u32>,
+}
+
+fn parse_puzzle(name: &str) -> Result<PuzzleId, String> {
+    PuzzleId::from_name(name).ok_or_else(|| format!("unknown puzzle '{name}'"))
+}
 
 fn main() -> Result<()> {
     color_eyre::install()?;
-    let mut application = app::App::default();
-    ui::run(&mut application)
+    let cli = Cli::parse();
+    if let Some(runs) = cli.benchmark {
+        let summaries = headless::benchmark(runs as usize, cli.seed, cli.algorithm)?;
+        if cli.json {
+            println!("{}", serde_json::to_string_pretty(&summaries)?);
+        } else {
+            println!("{} benchmark, {} runs per puzzle", cli.algorithm.label(), runs);
+            println!("{}", headless::BENCHMARK_HEADER);
+            for summary in &summaries {
+                println!("{}", summary);
+            }
+        }
+        return Ok(());
+    }
+    if let Some(puzzle) = cli.solve {
+        let metrics = headless::solve(puzzle, cli.seed, cli.algorithm)?;
+        if cli.json {
+            println!("{}", serde_json::to_string_pretty(&metrics)?);
+        } else {
+            println!("{}", metrics);
+        }
+        std::process::exit(if metrics.goal_found { 0 } else { 1 });
+    }
+    let mut application = match cli.seed {
+        Some(seed) => app::App::with_seed(seed),
+        None => app::App::default(),
+    };
+    match keymap::KeyMap::load() {
+        Ok(keymap) => application.keymap = keymap,
+        Err(err) => application
+            .startup_warnings
+            .push(format!("Default keys in use: {}.", err)),
+    }
+    application.ascii_mode = !ui::unicode_locale();
+    if let Some(language) = cli.lang {
+        application.language = language;
+    }
+    if let Some(fps) = cli.fps {
+        application.frame_interval = Duration::from_secs_f64(1.0 / f64::from(fps));
+    }
+    if let Some(puzzle) = cli.puzzle {
+        application.select_puzzle(puzzle);
+    }
+    // Only write stats.json on exit if it loaded or the bad file was moved aside.
+    let mut stats_loaded_ok = true;
+    match stats::load() {
+        Ok(records) => application.stats = records,
+        Err(err) => {
+            let warning = match stats::back_up() {
+                Ok(Some(backup)) => format!(
+                    "Records not loaded, starting fresh: {}. The old file is kept as {}.",
+                    err,
+                    persistence::display_path(&backup)
+                ),
+                Ok(None) => format!("Records not loaded, starting fresh: {}.", err),
+                Err(backup_err) => {
+                    stats_loaded_ok = false;
+                    format!(
+                        "Records not loaded and will not be saved this run: {}; {}.",
+                        err, backup_err
+                    )
+                }
+            };
+            application.startup_warnings.push(warning);
+        }
+    }
+    let outcome = ui::run(&mut application);
+    let saved = if stats_loaded_ok {
+        stats::save(&application.stats)
+    } else {
+        Ok(())
+    };
+    outcome?;
+    saved?;
+    Ok(())
 }
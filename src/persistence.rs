@@ -0,0 +1,390 @@
+use crate::puzzles::{
+    knights_tour, lights_out, maze, missionaries_cannibals, sokoban, tower_of_hanoi, water_jug,
+    EightPuzzleState, EightQueensState, HanoiState, LightsOutState, MazeState, MissionariesCannibalsState,
+    Player, PuzzleId, SokobanState, SudokuState, WaterJugState, XorTicTacToeState,
+};
+use crate::search::solver::{to_dot, SearchReport};
+use crate::settings::Settings;
+use crate::search::SearchState;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PersistenceError {
+    #[error("could not access {path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("invalid session file {path}: {source}")]
+    Format {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[error("rejected board in {path}: {reason}")]
+    Invalid { path: PathBuf, reason: String },
+    #[error("invalid config file {path}: {source}")]
+    Config {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    #[error("could not encode config for {path}: {source}")]
+    Encode {
+        path: PathBuf,
+        source: toml::ser::Error,
+    },
+}
+
+impl PersistenceError {
+    /// Whether the file simply has not been written yet.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, PersistenceError::Io { source, .. } if source.kind() == io::ErrorKind::NotFound)
+    }
+}
+
+/// Board snapshot for whichever puzzle was active when the session was saved.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "puzzle", content = "board")]
+pub enum SavedBoard {
+    EightPuzzle(EightPuzzleState),
+    XorTicTacToe(XorTicTacToeState),
+    MissionariesCannibals(MissionariesCannibalsState),
+    EightQueens(EightQueensState),
+    TowerOfHanoi(HanoiState),
+    KnightsTour(SavedTour),
+    WaterJug(WaterJugState),
+    LightsOut(LightsOutState),
+    Sokoban(SokobanState),
+    Sudoku(SudokuState),
+    Maze(MazeState),
+}
+
+/// A Knight's Tour in progress: the squares in the order the knight visited them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SavedTour {
+    Sized { size: u8, squares: Vec<(u8, u8)> },
+    /// Written before boards other than 8×8 existed.
+    Squares(Vec<(u8, u8)>),
+}
+
+impl SavedTour {
+    pub fn size(&self) -> u8 {
+        match self {
+            SavedTour::Sized { size, .. } => *size,
+            SavedTour::Squares(_) => knights_tour::DEFAULT_SIZE,
+        }
+    }
+
+    pub fn squares(&self) -> &[(u8, u8)] {
+        match self {
+            SavedTour::Sized { squares, .. } | SavedTour::Squares(squares) => squares,
+        }
+    }
+}
+
+impl SavedBoard {
+    pub fn puzzle(&self) -> PuzzleId {
+        match self {
+            SavedBoard::EightPuzzle(_) => PuzzleId::EightPuzzle,
+            SavedBoard::XorTicTacToe(_) => PuzzleId::XorTicTacToe,
+            SavedBoard::MissionariesCannibals(_) => PuzzleId::MissionariesCannibals,
+            SavedBoard::EightQueens(_) => PuzzleId::EightQueens,
+            SavedBoard::TowerOfHanoi(_) => PuzzleId::TowerOfHanoi,
+            SavedBoard::KnightsTour(_) => PuzzleId::KnightsTour,
+            SavedBoard::WaterJug(_) => PuzzleId::WaterJug,
+            SavedBoard::LightsOut(_) => PuzzleId::LightsOut,
+            SavedBoard::Sokoban(_) => PuzzleId::Sokoban,
+            SavedBoard::Sudoku(_) => PuzzleId::Sudoku,
+            SavedBoard::Maze(_) => PuzzleId::Maze,
+        }
+    }
+}
+
+impl SavedBoard {
+    /// Checks what the JSON format cannot: tiles form a permutation, counts
+    /// stay within the puzzle's limits, and so on.
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            SavedBoard::EightPuzzle(state) => {
+                let mut tiles = state.tiles();
+                tiles.sort_unstable();
+                if tiles != [0, 1, 2, 3, 4, 5, 6, 7, 8] {
+                    return Err("tiles must be 0-8, each exactly once".into());
+                }
+            }
+            SavedBoard::XorTicTacToe(state) => {
+                let count = |player| state.cells.iter().filter(|cell| **cell == Some(player)).count();
+                if count(Player::X).abs_diff(count(Player::O)) > 1 {
+                    return Err("X and O piece counts differ by more than one".into());
+                }
+            }
+            SavedBoard::MissionariesCannibals(state) => {
+                if !(missionaries_cannibals::MIN_GROUP_SIZE..=missionaries_cannibals::MAX_GROUP_SIZE)
+                    .contains(&state.n)
+                    || !(missionaries_cannibals::MIN_BOAT_CAPACITY..=missionaries_cannibals::MAX_BOAT_CAPACITY)
+                        .contains(&state.boat_capacity)
+                {
+                    return Err("group size or boat capacity out of range".into());
+                }
+                if !state.is_valid() {
+                    return Err("cannibals outnumber missionaries on a bank".into());
+                }
+            }
+            SavedBoard::EightQueens(state) => {
+                if state.queens.iter().flatten().any(|&col| col >= 8) {
+                    return Err("queen column off the board".into());
+                }
+                if state.count_conflicts() > 0 {
+                    return Err("queens attack each other".into());
+                }
+            }
+            SavedBoard::TowerOfHanoi(state) => {
+                if !(tower_of_hanoi::MIN_DISKS..=tower_of_hanoi::MAX_DISKS).contains(&state.disks) {
+                    return Err("disk count out of range".into());
+                }
+                let mut disks: Vec<u8> = state.pegs.iter().flatten().copied().collect();
+                disks.sort_unstable();
+                if !disks.iter().copied().eq(1..=state.disks) {
+                    return Err("every disk must be on exactly one peg".into());
+                }
+                if state.pegs.iter().any(|peg| peg.windows(2).any(|pair| pair[0] < pair[1])) {
+                    return Err("a larger disk sits on a smaller one".into());
+                }
+            }
+            SavedBoard::KnightsTour(tour) => {
+                let size = tour.size();
+                if !(knights_tour::MIN_SIZE..=knights_tour::MAX_SIZE).contains(&size) {
+                    return Err("board size out of range".into());
+                }
+                if tour.squares().is_empty() {
+                    return Err("tour has no starting square".into());
+                }
+                if tour.squares().iter().any(|&(row, col)| row >= size || col >= size) {
+                    return Err("square off the board".into());
+                }
+            }
+            SavedBoard::WaterJug(state) => {
+                if !(water_jug::MIN_JUGS..=water_jug::MAX_JUGS).contains(&state.jugs.len())
+                    || state.jugs.len() != state.capacities.len()
+                {
+                    return Err("jug count out of range".into());
+                }
+                if state.capacities.iter().any(|capacity| {
+                    !(water_jug::MIN_CAPACITY..=water_jug::MAX_CAPACITY).contains(capacity)
+                }) || state.jugs.iter().zip(&state.capacities).any(|(liters, capacity)| liters > capacity)
+                {
+                    return Err("jug holds more than its capacity".into());
+                }
+            }
+            SavedBoard::LightsOut(state) => {
+                if !(lights_out::MIN_SIZE..=lights_out::MAX_SIZE).contains(&state.size) {
+                    return Err("board size out of range".into());
+                }
+                if state.grid >> state.cell_count() != 0 {
+                    return Err("lights outside the board".into());
+                }
+            }
+            SavedBoard::Sokoban(state) => {
+                if state.width > sokoban::MAX_SIZE || state.height > sokoban::MAX_SIZE {
+                    return Err("level larger than 20×20".into());
+                }
+                let on_board = |&(row, col): &(u8, u8)| row < state.height && col < state.width;
+                if !on_board(&state.player)
+                    || !state.boxes.iter().all(on_board)
+                    || !state.goals.iter().all(on_board)
+                {
+                    return Err("player, box or goal off the board".into());
+                }
+                if state.boxes.len() != state.goals.len() {
+                    return Err("box and goal counts differ".into());
+                }
+                let ordered = |cells: &[(u8, u8)]| cells.windows(2).all(|pair| pair[0] < pair[1]);
+                if !ordered(&state.boxes) || !ordered(&state.goals) || !ordered(&state.walls) {
+                    return Err("cells must be listed once each, in order".into());
+                }
+                if state.is_wall(state.player) || state.is_box(state.player) {
+                    return Err("player stands on a wall or box".into());
+                }
+                if state.boxes.iter().any(|&cell| state.is_wall(cell)) {
+                    return Err("box inside a wall".into());
+                }
+            }
+            SavedBoard::Sudoku(state) => {
+                if !state.is_consistent() {
+                    return Err("a digit repeats in a row, column or box".into());
+                }
+            }
+            SavedBoard::Maze(state) => {
+                let height = state.grid.len();
+                let width = state.grid.first().map_or(0, Vec::len);
+                let max = maze::MAX_SIZE as usize;
+                if height < 3 || width < 3 || height > max || width > max {
+                    return Err(format!("maze must be between 3×3 and {}×{}", max, max));
+                }
+                if state.grid.iter().any(|row| row.len() != width) {
+                    return Err("maze rows differ in length".into());
+                }
+                if state.is_wall(state.player) || state.is_wall(state.goal) {
+                    return Err("player or goal inside a wall or off the maze".into());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedSession {
+    #[serde(flatten)]
+    pub board: SavedBoard,
+    /// Manual moves made so far, for the puzzles that count them.
+    pub moves_made: usize,
+    pub status: String,
+}
+
+/// `name` inside the platform config directory, e.g.
+/// `~/.config/ai-puzzle-suite/<name>` on Linux.
+pub fn config_file(name: &str) -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("ai-puzzle-suite").join(name))
+}
+
+/// `name` inside the platform data directory, e.g.
+/// `~/.local/share/ai-puzzle-suite/<name>` on Linux.
+pub fn data_file(name: &str) -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("ai-puzzle-suite").join(name))
+}
+
+/// Ctrl+S save slot for `puzzle`, e.g.
+/// `~/.local/share/ai-puzzle-suite/eight_puzzle.json` on Linux.
+pub fn state_path(puzzle: PuzzleId) -> Option<PathBuf> {
+    data_file(&format!("{}.json", puzzle.slug()))
+}
+
+/// `path` with the home directory shortened to `~`, for status messages.
+pub fn display_path(path: &Path) -> String {
+    match dirs::home_dir().and_then(|home| path.strip_prefix(home).ok().map(Path::to_path_buf)) {
+        Some(rest) => format!("~/{}", rest.display()),
+        None => path.display().to_string(),
+    }
+}
+
+/// Default session file for `puzzle`, relative to the working directory.
+pub fn session_path(puzzle: PuzzleId) -> PathBuf {
+    PathBuf::from(format!("{}_session.json", puzzle.slug()))
+}
+
+pub fn write_session(session: &SavedSession, path: &Path) -> Result<(), PersistenceError> {
+    let json = serde_json::to_string_pretty(session).map_err(|source| PersistenceError::Format {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let io_error = |source| PersistenceError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    fs::write(path, json).map_err(io_error)
+}
+
+pub fn read_session(path: &Path) -> Result<SavedSession, PersistenceError> {
+    let json = fs::read_to_string(path).map_err(|source| PersistenceError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let session: SavedSession =
+        serde_json::from_str(&json).map_err(|source| PersistenceError::Format {
+            path: path.to_path_buf(),
+            source,
+        })?;
+    session
+        .board
+        .validate()
+        .map_err(|reason| PersistenceError::Invalid {
+            path: path.to_path_buf(),
+            reason,
+        })?;
+    Ok(session)
+}
+
+/// Writes `settings` to `path` as TOML, creating its directory if needed.
+pub fn save_config(settings: &Settings, path: &Path) -> Result<(), PersistenceError> {
+    let text = toml::to_string(settings).map_err(|source| PersistenceError::Encode {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let io_error = |source| PersistenceError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    fs::write(path, text).map_err(io_error)
+}
+
+pub fn load_config(path: &Path) -> Result<Settings, PersistenceError> {
+    let text = fs::read_to_string(path).map_err(|source| PersistenceError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    toml::from_str(&text).map_err(|source| PersistenceError::Config {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Default export file for `puzzle`, e.g. `./solution_eight_puzzle_20240131-142500.txt`.
+pub fn export_path(puzzle: PuzzleId) -> PathBuf {
+    PathBuf::from(format!(
+        "solution_{}_{}.txt",
+        puzzle.slug(),
+        Local::now().format("%Y%m%d-%H%M%S")
+    ))
+}
+
+/// Writes a plain-text trace of `report`: a header with the solver statistics,
+/// then every state on the path, numbered, with the move taken in between.
+pub fn export_solution<S: SearchState>(
+    puzzle_name: &str,
+    report: &SearchReport<S>,
+    render_state: impl Fn(&S) -> String,
+    move_label: impl Fn(&S::Move) -> String,
+    path: &Path,
+) -> Result<(), PersistenceError> {
+    let mut text = format!(
+        "{}\nExported: {}\nSteps: {}\nExpanded nodes: {}\nVisited states: {}\nElapsed: {:.3}s\n",
+        puzzle_name,
+        Local::now().format("%Y-%m-%d %H:%M:%S %:z"),
+        report.path.len().saturating_sub(1),
+        report.expanded_nodes,
+        report.visited_states,
+        report.elapsed.as_secs_f64()
+    );
+    for (step, state) in report.path.iter().enumerate() {
+        if let Some(mv) = step.checked_sub(1).and_then(|idx| report.actions.get(idx)) {
+            text.push_str(&format!("\nMove: {}\n", move_label(mv)));
+        }
+        let heading = if step == 0 { "Step 0 (start)".to_string() } else { format!("Step {}", step) };
+        let rendered = render_state(state);
+        let board: Vec<&str> = rendered.lines().map(str::trim_end).collect();
+        text.push_str(&format!("\n{}\n{}\n", heading, board.join("\n")));
+    }
+    fs::write(path, text).map_err(|source| PersistenceError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Writes the edges A* recorded in `report` to `path` as a Graphviz DOT
+/// digraph, the solution path in red.
+pub fn export_search_graph<S: SearchState + Display>(report: &SearchReport<S>, path: &Path) -> Result<(), PersistenceError> {
+    fs::write(path, to_dot(&report.edges, &report.path)).map_err(|source| PersistenceError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
@@ -1,19 +1,35 @@
 use rand::seq::SliceRandom;
 use rand::Rng;
+use std::collections::VecDeque;
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use thiserror::Error;
 
 use crate::search::SearchState;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 const GOAL: [u8; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 0];
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Tiles per pattern; the eight tiles split into two disjoint patterns.
+const PATTERN_TILES: usize = 4;
+
+/// Pattern databases by the goal's blank cell, each built the first time a
+/// goal with its blank there is searched for.
+static PATTERN_DATABASES: [OnceLock<PatternDatabase>; 9] = [const { OnceLock::new() }; 9];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct EightPuzzleState {
-    pub tiles: [u8; 9],
+    // Tile of cell `i`, row by row, in bits 4i..4i+3 (0 is the blank). One
+    // word instead of a 9-byte array keeps search entries small, and
+    // equality and hashing compare a single integer. Saved as the array.
+    #[serde(rename = "tiles", serialize_with = "write_tiles", deserialize_with = "read_tiles")]
+    packed: u64,
 }
 
 impl Default for EightPuzzleState {
     fn default() -> Self {
-        Self { tiles: GOAL }
+        Self::from_tiles(GOAL)
     }
 }
 
@@ -36,28 +52,160 @@ impl SlideMove {
     }
 }
 
+/// Estimate of the remaining slides that A* orders the frontier by. All
+/// four never overestimate, so every one finds a shortest solution; the
+/// closer the estimate, the fewer boards A* expands on the way.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EightPuzzleHeuristic {
+    #[default]
+    Manhattan,
+    /// Tiles off their goal cell; the textbook baseline, far weaker.
+    Misplaced,
+    /// Manhattan distance plus the detours tiles need to pass each other.
+    LinearConflict,
+    /// Exact slides for two disjoint groups of four tiles, looked up in
+    /// tables built by breadth-first search the first time they are needed.
+    PatternDatabase,
+}
+
+impl EightPuzzleHeuristic {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EightPuzzleHeuristic::Manhattan => "Manhattan",
+            EightPuzzleHeuristic::Misplaced => "Misplaced tiles",
+            EightPuzzleHeuristic::LinearConflict => "Linear conflict",
+            EightPuzzleHeuristic::PatternDatabase => "Pattern database",
+        }
+    }
+
+    /// Name of an A* run with this heuristic in the comparison table.
+    pub fn search_label(&self) -> &'static str {
+        match self {
+            EightPuzzleHeuristic::Manhattan => "A* (Manhattan)",
+            EightPuzzleHeuristic::Misplaced => "A* (Misplaced)",
+            EightPuzzleHeuristic::LinearConflict => "A* (Linear conflict)",
+            EightPuzzleHeuristic::PatternDatabase => "A* (Pattern database)",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            EightPuzzleHeuristic::Manhattan => EightPuzzleHeuristic::Misplaced,
+            EightPuzzleHeuristic::Misplaced => EightPuzzleHeuristic::LinearConflict,
+            EightPuzzleHeuristic::LinearConflict => EightPuzzleHeuristic::PatternDatabase,
+            EightPuzzleHeuristic::PatternDatabase => EightPuzzleHeuristic::Manhattan,
+        }
+    }
+
+    /// Slides this heuristic expects between `state` and `goal`.
+    pub fn estimate(&self, state: &EightPuzzleState, goal: &EightPuzzleState) -> u32 {
+        match self {
+            EightPuzzleHeuristic::Manhattan => state.manhattan_distance_to(goal),
+            EightPuzzleHeuristic::Misplaced => state.misplaced_tiles_to(goal),
+            EightPuzzleHeuristic::LinearConflict => state.linear_conflict_to(goal),
+            EightPuzzleHeuristic::PatternDatabase => state.pattern_db_distance_to(goal),
+        }
+    }
+}
+
 impl EightPuzzleState {
+    /// The board with `tiles[i]` in cell `i`, row by row.
+    pub fn from_tiles(tiles: [u8; 9]) -> Self {
+        let packed = tiles
+            .iter()
+            .rev()
+            .fold(0, |packed, &tile| packed << 4 | u64::from(tile & 0xF));
+        Self { packed }
+    }
+
+    /// Every cell's tile, row by row.
+    pub fn tiles(&self) -> [u8; 9] {
+        std::array::from_fn(|idx| self.get_tile(idx))
+    }
+
+    pub fn get_tile(&self, idx: usize) -> u8 {
+        (self.packed >> (4 * idx) & 0xF) as u8
+    }
+
+    /// Puts tile `val` (0-8) in cell `idx`, whatever was there before.
+    pub fn set_tile(&mut self, idx: usize, val: u8) {
+        let shift = 4 * idx;
+        self.packed = self.packed & !(0xF << shift) | u64::from(val & 0xF) << shift;
+    }
+
+    pub fn swap_tiles(&mut self, a: usize, b: usize) {
+        let (tile_a, tile_b) = (self.get_tile(a), self.get_tile(b));
+        self.set_tile(a, tile_b);
+        self.set_tile(b, tile_a);
+    }
+
     pub fn random_solvable(rng: &mut impl Rng) -> Self {
         let mut tiles = GOAL;
         loop {
             tiles.shuffle(rng);
-            if is_solvable(&tiles) {
-                return Self { tiles };
+            let state = Self::from_tiles(tiles);
+            if state.is_solvable() {
+                return state;
             }
         }
     }
 
+    /// Pairs of tiles, blank left out, where the larger one comes first in
+    /// reading order. Slides only ever change it by an even amount.
+    pub fn inversion_count(&self) -> usize {
+        let tiles = self.tiles();
+        let mut inversions = 0;
+        for i in 0..tiles.len() {
+            for j in i + 1..tiles.len() {
+                if tiles[i] != 0 && tiles[j] != 0 && tiles[i] > tiles[j] {
+                    inversions += 1;
+                }
+            }
+        }
+        inversions
+    }
+
+    /// Whether the standard goal can be reached: the number of inversions must be even.
+    pub fn is_solvable(&self) -> bool {
+        self.inversion_count().is_multiple_of(2)
+    }
+
+    /// Whether both boards have the same inversion parity, which slides never change.
+    pub fn same_parity_as(&self, other: &Self) -> bool {
+        self.is_solvable() == other.is_solvable()
+    }
+
+    /// Whether `goal` can be reached from this board by sliding tiles.
+    pub fn is_solvable_to(&self, goal: &Self) -> bool {
+        self.same_parity_as(goal)
+    }
+
     pub fn blank_index(&self) -> usize {
-        self.tiles.iter().position(|&t| t == 0).unwrap_or(8)
+        (0..9).find(|&idx| self.get_tile(idx) == 0).unwrap_or(8)
+    }
+
+    /// Cell of every tile, indexed by tile number.
+    fn cells(&self) -> [usize; 9] {
+        let mut cells = [0; 9];
+        for (idx, tile) in self.tiles().into_iter().enumerate() {
+            cells[tile as usize] = idx;
+        }
+        cells
     }
 
     pub fn manhattan_distance(&self) -> u32 {
-        self.tiles
-            .iter()
+        self.manhattan_distance_to(&Self::default())
+    }
+
+    /// Rows plus columns each tile is away from its cell on `goal`.
+    pub fn manhattan_distance_to(&self, goal: &Self) -> u32 {
+        let goal_cells = goal.cells();
+        self.tiles()
+            .into_iter()
             .enumerate()
-            .filter(|(_, &tile)| tile != 0)
-            .map(|(idx, &tile)| {
-                let goal_idx = (tile - 1) as usize;
+            .filter(|&(_, tile)| tile != 0)
+            .map(|(idx, tile)| {
+                let goal_idx = goal_cells[tile as usize];
                 let (row, col) = (idx / 3, idx % 3);
                 let (goal_row, goal_col) = (goal_idx / 3, goal_idx % 3);
                 (row.abs_diff(goal_row) + col.abs_diff(goal_col)) as u32
@@ -65,6 +213,72 @@ impl EightPuzzleState {
             .sum()
     }
 
+    /// Tiles, the blank not counted, that are off their cell on the standard goal.
+    #[allow(dead_code)]
+    pub fn misplaced_tiles(&self) -> u32 {
+        self.misplaced_tiles_to(&Self::default())
+    }
+
+    pub fn misplaced_tiles_to(&self, goal: &Self) -> u32 {
+        self.tiles()
+            .into_iter()
+            .zip(goal.tiles())
+            .filter(|&(tile, goal_tile)| tile != 0 && tile != goal_tile)
+            .count() as u32
+    }
+
+    /// Manhattan distance plus two slides for each tile that must step out
+    /// of its goal row or column to let another tile of that line past:
+    /// per line, its tiles minus the longest run already in goal order.
+    pub fn linear_conflict_to(&self, goal: &Self) -> u32 {
+        let goal_cells = goal.cells();
+        let mut detours = 0;
+        for line in 0..3 {
+            // Goal columns of the tiles in row `line` that belong in it, and
+            // goal rows of the tiles in column `line` that belong in it
+            let in_row: Vec<usize> = (0..3)
+                .map(|col| goal_cells[self.get_tile(line * 3 + col) as usize])
+                .filter(|&goal_idx| goal_idx / 3 == line && goal_idx != goal_cells[0])
+                .map(|goal_idx| goal_idx % 3)
+                .collect();
+            let in_col: Vec<usize> = (0..3)
+                .map(|row| goal_cells[self.get_tile(row * 3 + line) as usize])
+                .filter(|&goal_idx| goal_idx % 3 == line && goal_idx != goal_cells[0])
+                .map(|goal_idx| goal_idx / 3)
+                .collect();
+            detours += in_row.len() - longest_ordered_run(&in_row);
+            detours += in_col.len() - longest_ordered_run(&in_col);
+        }
+        self.manhattan_distance_to(goal) + 2 * detours as u32
+    }
+
+    /// [`Self::pattern_db_distance_to`] the standard goal.
+    #[allow(dead_code)]
+    pub fn pattern_db_distance(&self) -> u32 {
+        self.pattern_db_distance_to(&Self::default())
+    }
+
+    /// Slides the tiles of the first four goal cells need, counting only their
+    /// own moves, plus the same for the other four. No slide moves tiles of
+    /// both groups, so the sum never overestimates. The first call for a goal
+    /// blank cell builds that cell's tables, which takes a few milliseconds.
+    pub fn pattern_db_distance_to(&self, goal: &Self) -> u32 {
+        let blank = goal.blank_index();
+        let database = PATTERN_DATABASES[blank].get_or_init(|| PatternDatabase::build(blank));
+        let cells = self.cells();
+        let goal_tiles = goal.tiles();
+        database
+            .groups
+            .iter()
+            .zip(&database.tables)
+            .map(|(group, table)| {
+                let positions = group.map(|goal_cell| cells[goal_tiles[goal_cell] as usize]);
+                u32::from(table[placement_index(&positions)])
+            })
+            .sum()
+    }
+
+    #[allow(dead_code)]
     pub fn apply_move(&self, mv: SlideMove) -> Option<Self> {
         let blank = self.blank_index();
         let row = blank / 3;
@@ -77,19 +291,155 @@ impl EightPuzzleState {
             _ => None,
         }?;
 
-        let mut tiles = self.tiles;
-        tiles.swap(blank, target);
-        Some(Self { tiles })
+        let mut next = *self;
+        next.swap_tiles(blank, target);
+        Some(next)
+    }
+}
+
+/// Disjoint pattern database for goals with the blank on one cell. Tiles are
+/// named by their goal cell, so the tables serve every goal with that blank.
+struct PatternDatabase {
+    /// Goal cells of each pattern's tiles.
+    groups: [[usize; PATTERN_TILES]; 2],
+    /// Fewest slides of a pattern's own tiles from each placement of them,
+    /// indexed by [`placement_index`].
+    tables: [Vec<u8>; 2],
+}
+
+impl PatternDatabase {
+    fn build(blank: usize) -> Self {
+        let cells: Vec<usize> = (0..9).filter(|&cell| cell != blank).collect();
+        let groups: [[usize; PATTERN_TILES]; 2] =
+            std::array::from_fn(|group| std::array::from_fn(|idx| cells[group * PATTERN_TILES + idx]));
+        let tables = groups.map(|group| pattern_table(group, blank));
+        Self { groups, tables }
     }
 }
 
+/// Position of each of a pattern's tiles, packed in base 9.
+fn placement_index(positions: &[usize; PATTERN_TILES]) -> usize {
+    positions.iter().rev().fold(0, |index, &cell| index * 9 + cell)
+}
+
+/// Breadth-first search back from the goal over the pattern's tiles and the
+/// blank. Sliding one of the pattern's tiles costs 1 and any other tile 0,
+/// so the search keeps a deque and puts free slides at the front.
+fn pattern_table(goal_cells: [usize; PATTERN_TILES], blank: usize) -> Vec<u8> {
+    const PLACEMENTS: usize = 9usize.pow(PATTERN_TILES as u32);
+    let mut distance = vec![u8::MAX; PLACEMENTS * 9];
+    let mut queue = VecDeque::from([(goal_cells, blank)]);
+    distance[placement_index(&goal_cells) * 9 + blank] = 0;
+    while let Some((positions, blank)) = queue.pop_front() {
+        let here = distance[placement_index(&positions) * 9 + blank];
+        let (row, col) = (blank / 3, blank % 3);
+        let neighbors = [
+            (row > 0).then(|| blank - 3),
+            (row < 2).then(|| blank + 3),
+            (col > 0).then(|| blank - 1),
+            (col < 2).then(|| blank + 1),
+        ];
+        for next_blank in neighbors.into_iter().flatten() {
+            let mut next = positions;
+            let moved = next.iter().position(|&cell| cell == next_blank);
+            if let Some(tile) = moved {
+                next[tile] = blank;
+            }
+            let cost = here + u8::from(moved.is_some());
+            let slot = &mut distance[placement_index(&next) * 9 + next_blank];
+            if cost < *slot {
+                *slot = cost;
+                if moved.is_some() {
+                    queue.push_back((next, next_blank));
+                } else {
+                    queue.push_front((next, next_blank));
+                }
+            }
+        }
+    }
+    // The blank is not part of the pattern: keep its best cell per placement
+    distance
+        .chunks(9)
+        .map(|by_blank| by_blank.iter().copied().min().unwrap_or(u8::MAX))
+        .collect()
+}
+
+/// Length of the longest increasing subsequence of `positions`.
+fn longest_ordered_run(positions: &[usize]) -> usize {
+    let mut best = vec![1; positions.len()];
+    for later in 0..positions.len() {
+        for earlier in 0..later {
+            if positions[earlier] < positions[later] {
+                best[later] = best[later].max(best[earlier] + 1);
+            }
+        }
+    }
+    best.into_iter().max().unwrap_or(0)
+}
+
+/// Why a board string could not be read by [`EightPuzzleState::from_str`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseBoardError {
+    #[error("unexpected '{0}': use the digits 0-8, with 0 or . for the blank")]
+    InvalidChar(char),
+    #[error("expected 9 tiles, found {0}")]
+    WrongLength(usize),
+    #[error("tile {0} appears more than once")]
+    Duplicate(u8),
+}
+
+/// Reads 9 tiles row by row, e.g. `"123456780"` or `"1 2 3 / 4 5 6 / 7 8 0"`.
+/// `0` or `.` marks the blank; whitespace, `/`, `,` and `|` only separate
+/// tiles, so the [`Display`] output parses back to the same board.
+impl FromStr for EightPuzzleState {
+    type Err = ParseBoardError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let tiles = text
+            .chars()
+            .filter(|ch| !ch.is_whitespace() && !matches!(ch, '/' | ',' | '|'))
+            .map(|ch| match ch {
+                '.' => Ok(0),
+                '0'..='8' => Ok(ch as u8 - b'0'),
+                other => Err(ParseBoardError::InvalidChar(other)),
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+        let tiles: [u8; 9] = tiles
+            .as_slice()
+            .try_into()
+            .map_err(|_| ParseBoardError::WrongLength(tiles.len()))?;
+        let mut seen = [false; 9];
+        for &tile in &tiles {
+            if std::mem::replace(&mut seen[tile as usize], true) {
+                return Err(ParseBoardError::Duplicate(tile));
+            }
+        }
+        Ok(Self::from_tiles(tiles))
+    }
+}
+
+fn write_tiles<S: Serializer>(packed: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+    EightPuzzleState { packed: *packed }.tiles().serialize(serializer)
+}
+
+/// Reads the 9-tile array. Tiles over 8 are refused here, since four bits
+/// would silently wrap them; whether the tiles form a permutation is left
+/// to the caller.
+fn read_tiles<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    let tiles = <[u8; 9]>::deserialize(deserializer)?;
+    if let Some(tile) = tiles.iter().find(|&&tile| tile > 8) {
+        return Err(serde::de::Error::custom(format!("tile {} is not 0-8", tile)));
+    }
+    Ok(EightPuzzleState::from_tiles(tiles).packed)
+}
+
 impl Display for EightPuzzleState {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         for row in 0..3 {
             for col in 0..3 {
-                let tile = self.tiles[row * 3 + col];
+                let tile = self.get_tile(row * 3 + col);
                 if tile == 0 {
-                    write!(f, "   ")?;
+                    write!(f, " . ")?;
                 } else {
                     write!(f, "{:>2} ", tile)?;
                 }
@@ -106,13 +456,18 @@ impl SearchState for EightPuzzleState {
     type Move = SlideMove;
 
     fn is_goal(&self) -> bool {
-        self.tiles == GOAL
+        *self == Self::default()
     }
 
     fn heuristic(&self) -> u32 {
         self.manhattan_distance()
     }
 
+    /// 2 with the blank in a corner, 3 on an edge, 4 in the centre.
+    fn branching_factor_estimate(&self) -> f32 {
+        self.successors().len() as f32
+    }
+
     fn successors(&self) -> Vec<(Self::Move, Self)> {
         let mut next_states = Vec::new();
         let blank = self.blank_index();
@@ -120,9 +475,9 @@ impl SearchState for EightPuzzleState {
         let col = blank % 3;
 
         let mut push_state = |mv: SlideMove, target_idx: usize| {
-            let mut new_tiles = self.tiles;
-            new_tiles.swap(blank, target_idx);
-            next_states.push((mv, Self { tiles: new_tiles }));
+            let mut next = *self;
+            next.swap_tiles(blank, target_idx);
+            next_states.push((mv, next));
         };
 
         if row > 0 {
@@ -142,14 +497,180 @@ impl SearchState for EightPuzzleState {
     }
 }
 
-fn is_solvable(tiles: &[u8; 9]) -> bool {
-    let mut inversions = 0;
-    for i in 0..tiles.len() {
-        for j in i + 1..tiles.len() {
-            if tiles[i] != 0 && tiles[j] != 0 && tiles[i] > tiles[j] {
-                inversions += 1;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::solver::astar_limited;
+    use proptest::prelude::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn display_output_parses_back() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..20 {
+            let state = EightPuzzleState::random_solvable(&mut rng);
+            assert_eq!(state.to_string().parse(), Ok(state));
+        }
+        assert_eq!("1 2 3 / 4 5 6 / 7 8 0".parse(), Ok(EightPuzzleState::default()));
+    }
+
+    #[test]
+    fn inversions_skip_the_blank() {
+        assert_eq!(EightPuzzleState::default().inversion_count(), 0);
+        let swapped: EightPuzzleState = "2 1 3 / 4 5 6 / 7 8 0".parse().expect("valid board");
+        assert_eq!(swapped.inversion_count(), 1);
+        assert!(!swapped.is_solvable());
+        let blank_first: EightPuzzleState = "0 8 7 / 6 5 4 / 3 2 1".parse().expect("valid board");
+        assert_eq!(blank_first.inversion_count(), 28);
+        assert!(blank_first.is_solvable());
+    }
+
+    #[test]
+    fn malformed_boards_are_rejected() {
+        assert_eq!("12345678".parse::<EightPuzzleState>(), Err(ParseBoardError::WrongLength(8)));
+        assert_eq!("123456789".parse::<EightPuzzleState>(), Err(ParseBoardError::InvalidChar('9')));
+        assert_eq!("123456788".parse::<EightPuzzleState>(), Err(ParseBoardError::Duplicate(8)));
+    }
+
+    /// Any arrangement of the tiles 0-8, solvable or not.
+    fn any_board() -> impl Strategy<Value = EightPuzzleState> {
+        Just((0..9).collect::<Vec<u8>>())
+            .prop_shuffle()
+            .prop_map(|tiles| EightPuzzleState::from_tiles(tiles.try_into().expect("nine tiles")))
+    }
+
+    fn opposite(mv: SlideMove) -> SlideMove {
+        match mv {
+            SlideMove::Up => SlideMove::Down,
+            SlideMove::Down => SlideMove::Up,
+            SlideMove::Left => SlideMove::Right,
+            SlideMove::Right => SlideMove::Left,
+        }
+    }
+
+    /// Every solvable arrangement of `[u8; 9]`, in lexicographic order.
+    fn solvable_arrangements() -> Vec<[u8; 9]> {
+        let mut tiles = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+        let mut all = Vec::new();
+        loop {
+            if EightPuzzleState::from_tiles(tiles).is_solvable() {
+                all.push(tiles);
+            }
+            // Next permutation: bump the last ascent, then reverse the tail
+            let Some(pivot) = (0..8).rev().find(|&idx| tiles[idx] < tiles[idx + 1]) else {
+                return all;
+            };
+            let swap = (pivot + 1..9).rev().find(|&idx| tiles[idx] > tiles[pivot]).expect("an ascent has a larger tile after it");
+            tiles.swap(pivot, swap);
+            tiles[pivot + 1..].reverse();
+        }
+    }
+
+    /// The packed board must answer exactly what the plain array would, for
+    /// all 9!/2 solvable boards.
+    #[test]
+    fn packed_boards_agree_with_their_tile_arrays() {
+        let boards = solvable_arrangements();
+        assert_eq!(boards.len(), 181_440);
+        for tiles in boards {
+            let state = EightPuzzleState::from_tiles(tiles);
+            assert_eq!(state.tiles(), tiles);
+            assert!((0..9).all(|idx| state.get_tile(idx) == tiles[idx]));
+            assert_eq!(state.blank_index(), tiles.iter().position(|&tile| tile == 0).unwrap());
+            let manhattan: u32 = (0..9)
+                .filter(|&idx| tiles[idx] != 0)
+                .map(|idx| {
+                    let goal_idx = tiles[idx] as usize - 1;
+                    ((idx / 3).abs_diff(goal_idx / 3) + (idx % 3).abs_diff(goal_idx % 3)) as u32
+                })
+                .sum();
+            assert_eq!(state.manhattan_distance(), manhattan);
+            for (_, next) in state.successors() {
+                let mut swapped = tiles;
+                swapped.swap(state.blank_index(), next.blank_index());
+                assert_eq!(next.tiles(), swapped);
+            }
+            let mut edited = state;
+            edited.set_tile(0, 8);
+            assert_eq!(edited.get_tile(0), 8);
+            assert!((1..9).all(|idx| edited.get_tile(idx) == tiles[idx]));
+        }
+    }
+
+    #[test]
+    fn boards_save_as_tile_arrays() {
+        let state: EightPuzzleState = "123456780".parse().unwrap();
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(json, r#"{"tiles":[1,2,3,4,5,6,7,8,0]}"#);
+        assert_eq!(serde_json::from_str::<EightPuzzleState>(&json).unwrap(), state);
+        assert!(serde_json::from_str::<EightPuzzleState>(r#"{"tiles":[1,2,3,4,5,6,7,8,16]}"#).is_err());
+    }
+
+    #[test]
+    fn solved_board_has_zero_distance() {
+        assert_eq!(EightPuzzleState::default().manhattan_distance(), 0);
+    }
+
+    proptest! {
+        #[test]
+        fn moves_are_undone_by_their_opposite(state in any_board()) {
+            for mv in [SlideMove::Up, SlideMove::Down, SlideMove::Left, SlideMove::Right] {
+                if let Some(next) = state.apply_move(mv) {
+                    prop_assert_eq!(next.apply_move(opposite(mv)), Some(state));
+                }
             }
         }
+
+        #[test]
+        fn random_boards_are_solvable(seed in any::<u64>()) {
+            prop_assert!(EightPuzzleState::random_solvable(&mut StdRng::seed_from_u64(seed)).is_solvable());
+        }
+
+        #[test]
+        fn successors_never_repeat_the_parent(state in any_board()) {
+            let successors = state.successors();
+            prop_assert!((2..=4).contains(&successors.len()));
+            for (mv, next) in successors {
+                prop_assert_ne!(next, state);
+                prop_assert_eq!(state.apply_move(mv), Some(next));
+            }
+        }
+
+        /// Also guards the heuristic: counting the blank would overestimate
+        /// and send the search the long way round. The hardest boards take
+        /// about 15,600 expansions, so the limit leaves some room above that.
+        #[test]
+        fn solvable_boards_are_solved_within_the_node_limit(
+            state in any_board().prop_filter("solvable", EightPuzzleState::is_solvable)
+        ) {
+            let report = astar_limited(state, 20_000);
+            prop_assert!(report.goal_found, "{:?} after {} nodes", report.outcome, report.expanded_nodes);
+            prop_assert!(report.path.last().is_some_and(EightPuzzleState::is_goal));
+            prop_assert!(state.manhattan_distance() <= report.actions.len() as u32);
+        }
+
+        /// Each heuristic is at least as informed as the one before it and
+        /// none overestimates the shortest solution.
+        #[test]
+        fn heuristics_are_ordered_and_admissible(
+            state in any_board().prop_filter("solvable", EightPuzzleState::is_solvable)
+        ) {
+            let goal = EightPuzzleState::default();
+            let optimal = astar_limited(state, 20_000).actions.len() as u32;
+            prop_assert!(state.misplaced_tiles() <= state.manhattan_distance());
+            prop_assert!(state.manhattan_distance() <= state.linear_conflict_to(&goal));
+            prop_assert!(state.linear_conflict_to(&goal) <= optimal);
+            prop_assert!(state.manhattan_distance() <= state.pattern_db_distance());
+            prop_assert!(state.pattern_db_distance() <= optimal);
+        }
+
+        /// Goals with the blank elsewhere get tables of their own, which
+        /// still count at least the Manhattan distance.
+        #[test]
+        fn pattern_database_serves_any_goal(state in any_board(), goal in any_board()) {
+            prop_assert_eq!(goal.pattern_db_distance_to(&goal), 0);
+            prop_assert!(state.manhattan_distance_to(&goal) <= state.pattern_db_distance_to(&goal));
+        }
     }
-    inversions % 2 == 0
 }
@@ -1,21 +1,31 @@
-use crate::search::SearchState;
+use crate::puzzles::MoveError;
+use crate::search::solver::{SearchOutcome, SearchReport};
+use crate::search::{LocalSearchState, SearchState};
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
+use std::time::Instant;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Repair steps the M-cycle min-conflicts solver gets before giving up.
+pub const MIN_CONFLICTS_MAX_STEPS: usize = 1_000;
+
+/// Simulated annealing defaults used by the A key on the 8 Queens screen.
+pub const ANNEALING_INITIAL_TEMP: f64 = 2.0;
+pub const ANNEALING_COOLING: f64 = 0.995;
+pub const ANNEALING_MAX_ITER: u32 = 20_000;
+
+/// Heuristic cost of a board where some empty row has no safe square left.
+/// Added once rather than per row so that g + h cannot overflow.
+const DEAD_END_PENALTY: u32 = u32::MAX / 2;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct EightQueensState {
     // Each element represents the column position of the queen in that row
     // queens[row] = column (0-7)
     pub queens: [Option<u8>; 8],
 }
 
-impl Default for EightQueensState {
-    fn default() -> Self {
-        Self {
-            queens: [None; 8],
-        }
-    }
-}
-
 #[derive(Debug, Clone, Copy)]
 pub struct PlaceQueen {
     pub row: u8,
@@ -47,6 +57,16 @@ impl EightQueensState {
         true
     }
 
+    /// Columns of an empty `row` that no placed queen attacks.
+    pub fn available_columns(&self, row: u8) -> Vec<u8> {
+        (0..8).filter(|&col| self.is_valid_placement(row, col)).collect()
+    }
+
+    /// Whether some empty row has no safe square left.
+    pub fn has_dead_end(&self) -> bool {
+        (0..8u8).any(|row| self.queens[row as usize].is_none() && !(0..8).any(|col| self.is_valid_placement(row, col)))
+    }
+
     pub fn count_conflicts(&self) -> u32 {
         let mut conflicts = 0;
         
@@ -83,18 +103,160 @@ impl EightQueensState {
         conflicts
     }
 
-    pub fn apply_placement(&self, placement: PlaceQueen) -> Option<Self> {
+    /// Every complete placement of 8 non-attacking queens that keeps the queens
+    /// already on this board, found by backtracking.
+    pub fn all_solutions(&self) -> Vec<EightQueensState> {
+        let mut solutions = Vec::new();
+        if self.count_conflicts() == 0 {
+            self.collect_solutions(&mut solutions);
+        }
+        solutions
+    }
+
+    fn collect_solutions(&self, solutions: &mut Vec<EightQueensState>) {
+        match self.queens.iter().position(|q| q.is_none()) {
+            None => solutions.push(*self),
+            Some(row) => {
+                for col in 0..8 {
+                    if let Ok(next) = self.apply_placement(PlaceQueen { row: row as u8, col }) {
+                        next.collect_solutions(solutions);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Number of queens attacking each cell (row-major, `row * 8 + col`) along
+    /// rows, columns and diagonals. A queen does not attack its own cell.
+    pub fn attack_counts(&self) -> [u8; 64] {
+        let mut counts = [0u8; 64];
+        for (q_row, queen) in self.queens.iter().enumerate() {
+            let Some(q_col) = *queen else { continue };
+            for (idx, count) in counts.iter_mut().enumerate() {
+                let (row, col) = (idx / 8, idx % 8);
+                if row == q_row && col == q_col as usize {
+                    continue;
+                }
+                let same_line = row == q_row || col == q_col as usize;
+                let same_diagonal = row.abs_diff(q_row) == col.abs_diff(q_col as usize);
+                if same_line || same_diagonal {
+                    *count += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Whether any queen attacks each cell, indexed like [`Self::attack_counts`].
+    pub fn attacked_cells(&self) -> [bool; 64] {
+        self.attack_counts().map(|count| count > 0)
+    }
+
+    /// A solution found by [`min_conflicts_columns`] within `max_steps` repair
+    /// steps, starting from this board with the empty rows filled at random.
+    #[allow(dead_code)]
+    pub fn solve_min_conflicts(&self, max_steps: usize) -> Option<EightQueensState> {
+        let report = self.min_conflicts_report(max_steps, &mut thread_rng());
+        report.path.last().copied().filter(|_| report.goal_found)
+    }
+
+    /// [`min_conflicts_columns`] on this board, with every repair step in
+    /// `report.path`; `expanded_nodes` counts the repair steps.
+    pub fn min_conflicts_report(&self, max_steps: usize, rng: &mut impl Rng) -> SearchReport<EightQueensState> {
+        let start_time = Instant::now();
+        let start = self.randomized(rng);
+        let mut columns: Vec<usize> = start.queens.iter().map(|q| q.unwrap_or(0) as usize).collect();
+        let repairs = min_conflicts_columns(&mut columns, max_steps, rng);
+        let mut path = vec![start];
+        let mut actions = Vec::new();
+        for &(row, col) in repairs.iter().flatten() {
+            let placement = PlaceQueen {
+                row: row as u8,
+                col: col as u8,
+            };
+            let mut next = path[path.len() - 1];
+            next.queens[row] = Some(placement.col);
+            path.push(next);
+            actions.push(placement);
+        }
+        let goal_found = repairs.is_some();
+        SearchReport {
+            visited_states: path.len(),
+            peak_memory_bytes: path.len() * size_of::<EightQueensState>(),
+            goal_found,
+            outcome: if goal_found {
+                SearchOutcome::Solved
+            } else {
+                SearchOutcome::NoSolution
+            },
+            solution_depth: actions.len(),
+            expanded_nodes: repairs.as_ref().map_or(max_steps, Vec::len),
+            path,
+            actions,
+            elapsed: start_time.elapsed(),
+            max_open_size: 0,
+            effective_branching_factor: 0.0,
+            edges: Vec::new(),
+            partial_path: Vec::new(),
+            partial_path_cost: 0,
+        }
+    }
+
+    /// Places a queen on a safe square of an empty row. Forward checking also
+    /// refuses, as [`MoveError::DeadEnd`], a placement that leaves another
+    /// empty row with no safe square.
+    pub fn apply_placement(&self, placement: PlaceQueen) -> Result<Self, MoveError> {
         if placement.row >= 8 || placement.col >= 8 {
-            return None;
+            return Err(MoveError::OutOfBounds);
+        }
+
+        if self.queens[placement.row as usize].is_some() {
+            return Err(MoveError::Occupied);
         }
 
         if !self.is_valid_placement(placement.row, placement.col) {
-            return None;
+            return Err(MoveError::ConflictsExist);
         }
 
         let mut new_state = *self;
         new_state.queens[placement.row as usize] = Some(placement.col);
-        Some(new_state)
+        if new_state.has_dead_end() {
+            return Err(MoveError::DeadEnd);
+        }
+        Ok(new_state)
+    }
+
+    /// A complete solution picked at random: backtracking row by row, trying
+    /// the columns in a shuffled order.
+    pub fn random_solution(rng: &mut impl Rng) -> Self {
+        Self::default()
+            .complete_randomly(rng)
+            .expect("the empty board has a solution")
+    }
+
+    /// `queens` queens (at most 8) kept from [`Self::random_solution`], so
+    /// the board always extends to a full solution.
+    pub fn random_solvable(queens: usize, rng: &mut impl Rng) -> Self {
+        let mut state = Self::random_solution(rng);
+        let mut rows: Vec<u8> = (0..8).collect();
+        rows.shuffle(rng);
+        for row in rows.into_iter().skip(queens) {
+            state = state.remove_queen(row);
+        }
+        state
+    }
+
+    fn complete_randomly(&self, rng: &mut impl Rng) -> Option<Self> {
+        let Some(row) = self.queens.iter().position(|q| q.is_none()) else {
+            return Some(*self);
+        };
+        let mut cols: Vec<u8> = (0..8).collect();
+        cols.shuffle(rng);
+        cols.into_iter().find_map(|col| {
+            self.apply_placement(PlaceQueen { row: row as u8, col })
+                .ok()?
+                .complete_randomly(rng)
+        })
     }
 
     pub fn remove_queen(&self, row: u8) -> Self {
@@ -144,24 +306,39 @@ impl SearchState for EightQueensState {
         
         // Count how many empty rows have very few valid placements (penalty)
         let mut penalty = 0u32;
+        let mut dead_end = false;
         for row in 0..8 {
             if self.queens[row].is_none() {
-                let mut valid_placements = 0u32;
-                for col in 0..8 {
-                    if self.is_valid_placement(row as u8, col as u8) {
-                        valid_placements += 1;
-                    }
-                }
-                // If a row has 0 or 1 valid placements, add penalty
-                if valid_placements == 0 {
-                    penalty += 10; // Dead end - very bad
-                } else if valid_placements == 1 {
-                    penalty += 2; // Very constrained
+                match self.available_columns(row as u8).len() {
+                    0 => dead_end = true, // Dead end - prune right away
+                    1 => penalty += 2, // Very constrained
+                    _ => {}
                 }
             }
         }
+        if dead_end {
+            penalty = penalty.saturating_add(DEAD_END_PENALTY);
+        }
         
-        conflicts + missing + penalty
+        (conflicts + missing).saturating_add(penalty)
+    }
+
+    /// Safe squares per empty row, averaged; 0 on a full board.
+    fn branching_factor_estimate(&self) -> f32 {
+        let options: Vec<usize> = (0..8u8)
+            .filter(|&row| self.queens[row as usize].is_none())
+            .map(|row| self.available_columns(row).len())
+            .collect();
+        if options.is_empty() {
+            return 0.0;
+        }
+        options.iter().sum::<usize>() as f32 / options.len() as f32
+    }
+
+    /// Only a hand-placed start can be one: forward checking in
+    /// [`EightQueensState::apply_placement`] never produces a dead end.
+    fn is_dead_end(&self) -> bool {
+        self.has_dead_end()
     }
 
     fn successors(&self) -> Vec<(Self::Move, Self)> {
@@ -177,7 +354,7 @@ impl SearchState for EightQueensState {
                     row: row as u8,
                     col,
                 };
-                if let Some(new_state) = self.apply_placement(placement) {
+                if let Ok(new_state) = self.apply_placement(placement) {
                     successors.push((placement, new_state));
                 }
             }
@@ -187,3 +364,322 @@ impl SearchState for EightQueensState {
     }
 }
 
+
+impl LocalSearchState for EightQueensState {
+    fn randomized<R: Rng>(&self, rng: &mut R) -> Self {
+        let mut new_state = *self;
+        for queen in new_state.queens.iter_mut() {
+            if queen.is_none() {
+                *queen = Some(rng.gen_range(0..8));
+            }
+        }
+        new_state
+    }
+}
+
+/// Min-conflicts for N queens, one per row, where `columns[row]` is the
+/// column of that row's queen and N is `columns.len()`. Each step moves the
+/// most attacked queen, other than the one moved last, to the column with the
+/// fewest attackers, breaking ties at random and never leaving it where it was. Returns the `(row, column)`
+/// repairs in order once no queen is attacked, or `None` if some still are
+/// after `max_steps` of them. Attack counts per column and diagonal are kept
+/// up to date, so a step costs O(N).
+pub fn min_conflicts_columns(columns: &mut [usize], max_steps: usize, rng: &mut impl Rng) -> Option<Vec<(usize, usize)>> {
+    let n = columns.len();
+    let mut lines = QueenLines::new(n);
+    for (row, &col) in columns.iter().enumerate() {
+        lines.add(row, col);
+    }
+
+    let mut repairs = Vec::new();
+    loop {
+        // Each queen is counted once on each of its own three lines
+        let scores: Vec<u32> = (0..n).map(|row| lines.queens_on(row, columns[row]) - 3).collect();
+        let worst = scores.iter().copied().max().unwrap_or(0);
+        if worst == 0 {
+            return Some(repairs);
+        }
+        if repairs.len() == max_steps {
+            return None;
+        }
+        // The queen moved last sits out a step so that two queens cannot keep
+        // trading places; attacks are mutual, so another one is attacked too
+        let last = repairs.last().map(|&(row, _)| row);
+        let worst = (0..n).filter(|&row| Some(row) != last).map(|row| scores[row]).max().unwrap_or(0);
+        let rows: Vec<usize> = (0..n).filter(|&row| Some(row) != last && scores[row] == worst).collect();
+        let row = *rows.choose(rng).expect("some queen is attacked");
+        let from = columns[row];
+        lines.remove(row, from);
+        let column_scores: Vec<(usize, u32)> = (0..n)
+            .filter(|&col| col != from)
+            .map(|col| (col, lines.queens_on(row, col)))
+            .collect();
+        let best = column_scores.iter().map(|&(_, score)| score).min().unwrap_or(0);
+        let choices: Vec<usize> = column_scores
+            .iter()
+            .filter(|&&(_, score)| score == best)
+            .map(|&(col, _)| col)
+            .collect();
+        let col = *choices.choose(rng).expect("two queens attacking each other need two columns");
+        lines.add(row, col);
+        columns[row] = col;
+        repairs.push((row, col));
+    }
+}
+
+/// Queens per column and per diagonal of an N×N board.
+struct QueenLines {
+    size: usize,
+    columns: Vec<u32>,
+    /// Indexed by `row + col`.
+    down: Vec<u32>,
+    /// Indexed by `row + size - col`.
+    up: Vec<u32>,
+}
+
+impl QueenLines {
+    fn new(size: usize) -> Self {
+        Self {
+            size,
+            columns: vec![0; size],
+            down: vec![0; 2 * size],
+            up: vec![0; 2 * size],
+        }
+    }
+
+    fn add(&mut self, row: usize, col: usize) {
+        self.columns[col] += 1;
+        self.down[row + col] += 1;
+        self.up[row + self.size - col] += 1;
+    }
+
+    fn remove(&mut self, row: usize, col: usize) {
+        self.columns[col] -= 1;
+        self.down[row + col] -= 1;
+        self.up[row + self.size - col] -= 1;
+    }
+
+    /// Queens on the column and both diagonals through `(row, col)`, summed.
+    fn queens_on(&self, row: usize, col: usize) -> u32 {
+        self.columns[col] + self.down[row + col] + self.up[row + self.size - col]
+    }
+}
+
+/// Simulated annealing over complete boards. Each iteration moves a random
+/// queen to a random column and keeps the move when `e^(−ΔE/T)` beats a
+/// uniform draw, where ΔE is the change in [`EightQueensState::count_conflicts`].
+/// The temperature is multiplied by `cooling` after every iteration, and every
+/// draw comes from `rng`, so a seeded run can be repeated. Every
+/// accepted board ends up in `report.path`; `expanded_nodes` counts iterations.
+pub fn simulated_annealing(
+    start: EightQueensState,
+    initial_temp: f64,
+    cooling: f64,
+    max_iter: u32,
+    rng: &mut impl Rng,
+) -> SearchReport<EightQueensState> {
+    let start_time = Instant::now();
+    let mut current = start.randomized(rng);
+    let mut energy = current.count_conflicts();
+    let mut temperature = initial_temp;
+    let mut path = vec![current];
+    let mut actions = Vec::new();
+    let mut iterations = 0usize;
+
+    while energy > 0 && iterations < max_iter as usize {
+        iterations += 1;
+        let row = rng.gen_range(0..8u8);
+        let col = rng.gen_range(0..8u8);
+        if current.queens[row as usize] != Some(col) {
+            let mut next = current;
+            next.queens[row as usize] = Some(col);
+            let next_energy = next.count_conflicts();
+            let delta = next_energy as f64 - energy as f64;
+            // Always true for downhill moves, since the exponent is then positive
+            let accept = (-delta / temperature.max(f64::MIN_POSITIVE)).exp() > rng.gen::<f64>();
+            if accept {
+                current = next;
+                energy = next_energy;
+                path.push(current);
+                actions.push(PlaceQueen { row, col });
+            }
+        }
+        temperature *= cooling;
+    }
+
+    SearchReport {
+        visited_states: path.len(),
+        peak_memory_bytes: path.len() * size_of::<EightQueensState>(),
+        goal_found: current.is_goal(),
+        outcome: if current.is_goal() {
+            SearchOutcome::Solved
+        } else {
+            SearchOutcome::NoSolution
+        },
+        solution_depth: if current.is_goal() { actions.len() } else { 0 },
+        path,
+        actions,
+        expanded_nodes: iterations,
+        elapsed: start_time.elapsed(),
+        max_open_size: 0,
+        effective_branching_factor: 0.0,
+        edges: Vec::new(),
+        partial_path: Vec::new(),
+        partial_path_cost: 0,
+    }
+}
+
+/// Temperature [`simulated_annealing`] had reached after `iterations` rounds of cooling.
+pub fn annealing_temperature(initial_temp: f64, cooling: f64, iterations: usize) -> f64 {
+    initial_temp * cooling.powi(iterations.min(i32::MAX as usize) as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::solver::astar;
+
+    fn board(queens: &[(u8, u8)]) -> EightQueensState {
+        let mut state = EightQueensState::default();
+        for &(row, col) in queens {
+            state.queens[row as usize] = Some(col);
+        }
+        state
+    }
+
+    /// A queen on (4, 2) is safe itself but, with queens on (0, 1) and
+    /// (1, 6), leaves row 2 without a safe square.
+    #[test]
+    fn dead_end_placement_is_refused() {
+        let state = board(&[(0, 1), (1, 6)]);
+        assert!(state.is_valid_placement(4, 2));
+        assert_eq!(state.apply_placement(PlaceQueen { row: 4, col: 2 }), Err(MoveError::DeadEnd));
+        assert!(board(&[(0, 1), (1, 6), (4, 2)]).available_columns(2).is_empty());
+    }
+
+    /// A* gives up on a dead-end start after one expansion, without successors.
+    #[test]
+    fn dead_end_start_is_not_expanded() {
+        let state = board(&[(0, 1), (1, 6), (4, 2)]);
+        assert!(state.is_dead_end());
+        let report = astar(state);
+        assert_eq!(report.outcome, SearchOutcome::NoSolution);
+        assert_eq!(report.expanded_nodes, 1);
+        assert_eq!(report.visited_states, 1);
+        assert!(!EightQueensState::default().is_dead_end());
+    }
+
+    #[test]
+    fn safe_placement_is_accepted() {
+        let state = board(&[(0, 1), (1, 6)]);
+        let next = state.apply_placement(PlaceQueen { row: 4, col: 0 });
+        assert_eq!(next, Ok(board(&[(0, 1), (1, 6), (4, 0)])));
+    }
+
+    /// Forward checking only removes branches without a completion.
+    #[test]
+    fn forward_checking_keeps_every_solution() {
+        assert_eq!(EightQueensState::default().all_solutions().len(), 92);
+    }
+
+    #[test]
+    fn random_partial_boards_always_extend_to_a_solution() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(5);
+        assert!(EightQueensState::random_solution(&mut rng).is_goal());
+        for queens in 0..=8 {
+            for _ in 0..20 {
+                let state = EightQueensState::random_solvable(queens, &mut rng);
+                assert_eq!(state.queens.iter().flatten().count(), queens);
+                assert!(!state.all_solutions().is_empty());
+            }
+        }
+    }
+
+    fn no_queen_attacked(columns: &[usize]) -> bool {
+        (0..columns.len()).all(|a| {
+            (a + 1..columns.len()).all(|b| {
+                columns[a] != columns[b] && columns[a].abs_diff(columns[b]) != b - a
+            })
+        })
+    }
+
+    /// The column and diagonal counts make each repair O(N), so 100 queens
+    /// from random columns settle well within the step budget.
+    #[test]
+    fn min_conflicts_solves_a_hundred_queens() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        const MAX_STEPS: usize = 2_000;
+        let mut rng = StdRng::seed_from_u64(100);
+        for _ in 0..5 {
+            let mut columns: Vec<usize> = (0..100).map(|_| rng.gen_range(0..100)).collect();
+            let repairs = min_conflicts_columns(&mut columns, MAX_STEPS, &mut rng).expect("solved");
+            assert!(repairs.len() <= MAX_STEPS);
+            assert!(no_queen_attacked(&columns));
+        }
+    }
+
+    /// Three queens have no solution, so the search stops at the step budget.
+    #[test]
+    fn min_conflicts_gives_up_after_max_steps() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut columns = vec![0, 1, 2];
+        assert_eq!(min_conflicts_columns(&mut columns, 50, &mut StdRng::seed_from_u64(3)), None);
+    }
+
+    #[test]
+    fn min_conflicts_keeps_the_repairs_on_an_eight_by_eight_board() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let start = board(&[(0, 0), (3, 3)]);
+        let report = start.min_conflicts_report(MIN_CONFLICTS_MAX_STEPS, &mut StdRng::seed_from_u64(8));
+        assert!(report.goal_found);
+        assert_eq!(report.expanded_nodes, report.actions.len());
+        assert_eq!(report.path.len(), report.actions.len() + 1);
+        assert!(start.solve_min_conflicts(MIN_CONFLICTS_MAX_STEPS).is_some_and(|state| state.is_goal()));
+    }
+
+    #[test]
+    fn annealing_from_the_same_seed_ends_on_the_same_board() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let run = |seed| {
+            simulated_annealing(
+                board(&[(2, 5)]),
+                ANNEALING_INITIAL_TEMP,
+                ANNEALING_COOLING,
+                ANNEALING_MAX_ITER,
+                &mut StdRng::seed_from_u64(seed),
+            )
+        };
+        let (first, second) = (run(42), run(42));
+        assert_eq!(first.path.last(), second.path.last());
+        assert_eq!(first.actions.len(), second.actions.len());
+        assert_eq!(first.expanded_nodes, second.expanded_nodes);
+    }
+
+    /// A queen on (0, 0) attacks its row, column and main diagonal; a second
+    /// one on (7, 1) doubles up on (7, 7), (1, 1) and (6, 0).
+    #[test]
+    fn attacked_cells_follow_rows_columns_and_diagonals() {
+        let single = board(&[(0, 0)]).attacked_cells();
+        assert_eq!(single.iter().filter(|&&attacked| attacked).count(), 21);
+        assert!(!single[0]);
+        assert!(single[7] && single[7 * 8] && single[7 * 8 + 7]);
+        assert!(!single[8 + 2]);
+
+        let counts = board(&[(0, 0), (7, 1)]).attack_counts();
+        assert_eq!(counts[7 * 8 + 7], 2);
+        assert_eq!(counts[8 + 1], 2);
+        assert_eq!(counts[6 * 8], 2);
+        assert_eq!(counts[8 + 2], 0);
+    }
+}
@@ -0,0 +1,252 @@
+use crate::search::SearchState;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Smallest board with an open tour.
+pub const MIN_SIZE: u8 = 5;
+/// Largest board whose squares fit the `visited` bitboard.
+pub const MAX_SIZE: u8 = 8;
+pub const DEFAULT_SIZE: u8 = 8;
+
+const KNIGHT_JUMPS: [(i8, i8); 8] = [
+    (-2, -1),
+    (-2, 1),
+    (-1, -2),
+    (-1, 2),
+    (1, -2),
+    (1, 2),
+    (2, -1),
+    (2, 1),
+];
+
+/// Estimate A* orders partial tours by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TourHeuristic {
+    /// Squares left, weighted so the deepest tour comes first, plus the
+    /// onward move count so Warnsdorff's rule picks among its jumps.
+    #[default]
+    Warnsdorff,
+    /// Squares left to visit. Never overestimates, but every partial tour
+    /// of the same length ties, so A* spreads out among them.
+    SquaresLeft,
+}
+
+impl TourHeuristic {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TourHeuristic::Warnsdorff => "Warnsdorff",
+            TourHeuristic::SquaresLeft => "Squares left",
+        }
+    }
+
+    /// Name of an A* run with this heuristic in the comparison table.
+    pub fn search_label(&self) -> &'static str {
+        match self {
+            TourHeuristic::Warnsdorff => "A* (Warnsdorff)",
+            TourHeuristic::SquaresLeft => "A* (Squares left)",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            TourHeuristic::Warnsdorff => TourHeuristic::SquaresLeft,
+            TourHeuristic::SquaresLeft => TourHeuristic::Warnsdorff,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct KnightsTourState {
+    // Bit `row * size + col` is set once the knight has visited that square
+    pub visited: u64,
+    pub position: (u8, u8),
+    /// Squares per side of the board.
+    pub size: u8,
+    pub heuristic: TourHeuristic,
+}
+
+impl Default for KnightsTourState {
+    fn default() -> Self {
+        Self::new(DEFAULT_SIZE, 0, 0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KnightMove {
+    pub row: u8,
+    pub col: u8,
+}
+
+impl KnightsTourState {
+    /// Knight standing on its first square of a `size`×`size` board.
+    pub fn new(size: u8, row: u8, col: u8) -> Self {
+        Self {
+            visited: square_bit(size, row, col),
+            position: (row, col),
+            size,
+            heuristic: TourHeuristic::default(),
+        }
+    }
+
+    pub fn with_heuristic(self, heuristic: TourHeuristic) -> Self {
+        Self { heuristic, ..self }
+    }
+
+    pub fn squares(&self) -> u32 {
+        self.size as u32 * self.size as u32
+    }
+
+    pub fn is_visited(&self, row: u8, col: u8) -> bool {
+        self.visited & square_bit(self.size, row, col) != 0
+    }
+
+    pub fn visited_count(&self) -> u32 {
+        self.visited.count_ones()
+    }
+
+    /// Unvisited squares one knight jump away from `(row, col)`.
+    pub fn jumps_from(&self, row: u8, col: u8) -> Vec<KnightMove> {
+        KNIGHT_JUMPS
+            .iter()
+            .filter_map(|&(dr, dc)| {
+                let row = row as i8 + dr;
+                let col = col as i8 + dc;
+                let size = self.size as i8;
+                if (0..size).contains(&row) && (0..size).contains(&col) {
+                    Some(KnightMove {
+                        row: row as u8,
+                        col: col as u8,
+                    })
+                } else {
+                    None
+                }
+            })
+            .filter(|mv| !self.is_visited(mv.row, mv.col))
+            .collect()
+    }
+
+    pub fn apply_move(&self, mv: KnightMove) -> Option<Self> {
+        let (row, col) = self.position;
+        if !self.jumps_from(row, col).contains(&mv) {
+            return None;
+        }
+        Some(Self {
+            visited: self.visited | square_bit(self.size, mv.row, mv.col),
+            position: (mv.row, mv.col),
+            ..*self
+        })
+    }
+
+    /// Number of onward jumps from the knight's square (Warnsdorff's degree).
+    pub fn onward_moves(&self) -> u32 {
+        let (row, col) = self.position;
+        self.jumps_from(row, col).len() as u32
+    }
+
+    pub fn heuristic(&self) -> u32 {
+        let remaining = self.squares() - self.visited_count();
+        match self.heuristic {
+            // Every complete tour has the same length, so weighting the remaining
+            // squares above the largest possible onward count makes A* always extend
+            // the deepest partial tour first, and the onward count then applies
+            // Warnsdorff's rule among its moves. This overestimates, but since all
+            // tours are equally long any tour found is still a shortest one.
+            TourHeuristic::Warnsdorff => remaining * (KNIGHT_JUMPS.len() as u32 + 1) + self.onward_moves(),
+            TourHeuristic::SquaresLeft => remaining,
+        }
+    }
+}
+
+fn square_bit(size: u8, row: u8, col: u8) -> u64 {
+    1u64 << (row as u32 * size as u32 + col as u32)
+}
+
+impl Display for KnightsTourState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.size {
+            for col in 0..self.size {
+                let symbol = if self.position == (row, col) {
+                    'N'
+                } else if self.is_visited(row, col) {
+                    'x'
+                } else {
+                    '.'
+                };
+                write!(f, "{} ", symbol)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl SearchState for KnightsTourState {
+    type Move = KnightMove;
+
+    fn is_goal(&self) -> bool {
+        self.visited.count_ones() == self.squares()
+    }
+
+    fn heuristic(&self) -> u32 {
+        self.heuristic()
+    }
+
+    fn successors(&self) -> Vec<(Self::Move, Self)> {
+        let (row, col) = self.position;
+        self.jumps_from(row, col)
+            .into_iter()
+            .filter_map(|mv| self.apply_move(mv).map(|next| (mv, next)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::solver::{astar, SearchOutcome};
+
+    #[test]
+    fn tour_visits_every_square_once() {
+        for (row, col) in [(0, 0), (3, 4), (7, 2)] {
+            let report = astar(KnightsTourState::new(8, row, col));
+            assert_eq!(report.outcome, SearchOutcome::Solved);
+            let squares: Vec<(u8, u8)> = report.path.iter().map(|state| state.position).collect();
+            assert_eq!(squares.len(), 64);
+            let mut seen = 0u64;
+            for &(row, col) in &squares {
+                assert_eq!(seen & square_bit(8, row, col), 0, "square {:?} visited twice", (row, col));
+                seen |= square_bit(8, row, col);
+            }
+            for pair in squares.windows(2) {
+                let jump = (pair[1].0 as i8 - pair[0].0 as i8, pair[1].1 as i8 - pair[0].1 as i8);
+                assert!(KNIGHT_JUMPS.contains(&jump));
+            }
+        }
+    }
+
+    /// The heuristic overestimates from the first square, yet the tour is as
+    /// short as any can be: every tour takes one jump per square after the first.
+    #[test]
+    fn overestimating_heuristic_still_finds_a_shortest_tour() {
+        let start = KnightsTourState::new(8, 0, 0);
+        assert!(start.heuristic() > start.squares() - 1);
+        // A deeper partial tour always looks at least as good as a shallower one
+        let step = start.successors()[0].1;
+        let f = |state: &KnightsTourState| state.visited_count() - 1 + state.heuristic();
+        assert!(f(&step) <= f(&start));
+        assert_eq!(astar(start).actions.len(), 63);
+    }
+
+    /// Squares left is exact while a tour exists, so it is admissible too.
+    #[test]
+    fn both_heuristics_tour_a_small_board() {
+        for heuristic in [TourHeuristic::Warnsdorff, TourHeuristic::SquaresLeft] {
+            let start = KnightsTourState::new(MIN_SIZE, 0, 0).with_heuristic(heuristic);
+            let report = astar(start);
+            assert_eq!(report.outcome, SearchOutcome::Solved, "{}", heuristic.label());
+            assert_eq!(report.actions.len(), 24);
+            assert!(report.path.iter().all(|state| state.size == MIN_SIZE));
+        }
+        assert_eq!(KnightsTourState::new(MIN_SIZE, 0, 0).with_heuristic(TourHeuristic::SquaresLeft).heuristic(), 24);
+    }
+}
@@ -0,0 +1,235 @@
+use crate::search::SearchState;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+pub const MIN_SIZE: u8 = 3;
+pub const MAX_SIZE: u8 = 6;
+pub const DEFAULT_SIZE: u8 = 5;
+
+/// Lights a single press can switch: the cell and its four neighbours.
+const LIGHTS_PER_PRESS: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LightsOutState {
+    // Bit `row * size + col` is set while that light is on; a u64 so 6×6 fits
+    pub grid: u64,
+    pub size: u8,
+}
+
+impl Default for LightsOutState {
+    fn default() -> Self {
+        Self::new(DEFAULT_SIZE)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PressCell {
+    pub index: usize,
+}
+
+impl LightsOutState {
+    /// Every light off.
+    pub fn new(size: u8) -> Self {
+        Self { grid: 0, size }
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.size as usize * self.size as usize
+    }
+
+    pub fn is_lit(&self, index: usize) -> bool {
+        self.grid & (1 << index) != 0
+    }
+
+    pub fn lit_count(&self) -> u32 {
+        self.grid.count_ones()
+    }
+
+    /// Bits flipped by pressing `index`.
+    fn press_mask(&self, index: usize) -> u64 {
+        let size = self.size as usize;
+        let (row, col) = (index / size, index % size);
+        let mut mask = 1 << index;
+        if row > 0 {
+            mask |= 1 << (index - size);
+        }
+        if row + 1 < size {
+            mask |= 1 << (index + size);
+        }
+        if col > 0 {
+            mask |= 1 << (index - 1);
+        }
+        if col + 1 < size {
+            mask |= 1 << (index + 1);
+        }
+        mask
+    }
+
+    pub fn press(&self, index: usize) -> Option<Self> {
+        if index >= self.cell_count() {
+            return None;
+        }
+        Some(Self {
+            grid: self.grid ^ self.press_mask(index),
+            size: self.size,
+        })
+    }
+
+    /// Board reached by pressing random cells, so it is always solvable.
+    pub fn random_solvable(size: u8, rng: &mut impl Rng) -> Self {
+        let mut state = Self::new(size);
+        while state.grid == 0 {
+            for index in 0..state.cell_count() {
+                if rng.gen_bool(0.5) {
+                    state = state.press(index).unwrap_or(state);
+                }
+            }
+        }
+        state
+    }
+
+    /// Fewest presses that switch every light off, found by solving the press
+    /// system over GF(2). Returns `None` when the pattern cannot be cleared.
+    pub fn solve_gaussian_elimination(&self) -> Option<Vec<usize>> {
+        let cells = self.cell_count();
+        // Row i: which presses toggle light i (bits 0..cells), plus whether light
+        // i is on (bit `cells`).
+        let mut rows: Vec<u64> = (0..cells)
+            .map(|light| {
+                let presses = self.press_mask(light);
+                let lit = (self.grid >> light) & 1;
+                presses | (lit << cells)
+            })
+            .collect();
+
+        let mut pivot_cols = Vec::new();
+        let mut pivot_row = 0;
+        for col in 0..cells {
+            let Some(found) = (pivot_row..cells).find(|&row| rows[row] & (1 << col) != 0) else {
+                continue;
+            };
+            rows.swap(pivot_row, found);
+            for row in 0..cells {
+                if row != pivot_row && rows[row] & (1 << col) != 0 {
+                    rows[row] ^= rows[pivot_row];
+                }
+            }
+            pivot_cols.push(col);
+            pivot_row += 1;
+        }
+
+        // A zero row with the lit bit set means the system is inconsistent.
+        if rows[pivot_row..].iter().any(|&row| row != 0) {
+            return None;
+        }
+
+        let free_cols: Vec<usize> = (0..cells).filter(|col| !pivot_cols.contains(col)).collect();
+        let mut best: Option<u64> = None;
+        for assignment in 0..(1u64 << free_cols.len()) {
+            let mut presses = 0u64;
+            for (bit, &col) in free_cols.iter().enumerate() {
+                if assignment & (1 << bit) != 0 {
+                    presses |= 1 << col;
+                }
+            }
+            for (row, &col) in pivot_cols.iter().enumerate() {
+                let rhs = (rows[row] >> cells) & 1;
+                let free_terms = (rows[row] & presses).count_ones() as u64 & 1;
+                if rhs ^ free_terms == 1 {
+                    presses |= 1 << col;
+                }
+            }
+            if best.is_none_or(|current| presses.count_ones() < current.count_ones()) {
+                best = Some(presses);
+            }
+        }
+
+        best.map(|presses| {
+            (0..cells)
+                .filter(|&idx| presses & (1 << idx) != 0)
+                .collect()
+        })
+    }
+
+    pub fn heuristic(&self) -> u32 {
+        // One press switches at most five lights off.
+        self.lit_count().div_ceil(LIGHTS_PER_PRESS)
+    }
+}
+
+impl Display for LightsOutState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let size = self.size as usize;
+        for row in 0..size {
+            for col in 0..size {
+                write!(
+                    f,
+                    "{} ",
+                    if self.is_lit(row * size + col) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                )?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl SearchState for LightsOutState {
+    type Move = PressCell;
+
+    fn is_goal(&self) -> bool {
+        self.grid == 0
+    }
+
+    fn heuristic(&self) -> u32 {
+        self.heuristic()
+    }
+
+    fn successors(&self) -> Vec<(Self::Move, Self)> {
+        (0..self.cell_count())
+            .filter_map(|index| self.press(index).map(|next| (PressCell { index }, next)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::solver::astar;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn elimination_presses_clear_the_board() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for size in MIN_SIZE..=MAX_SIZE {
+            for _ in 0..10 {
+                let state = LightsOutState::random_solvable(size, &mut rng);
+                let presses = state.solve_gaussian_elimination().expect("solvable board");
+                let cleared = presses
+                    .into_iter()
+                    .fold(state, |state, index| state.press(index).expect("press on the board"));
+                assert!(cleared.is_goal());
+            }
+        }
+    }
+
+    /// 4×4 boards have several press sets that clear them, so this also
+    /// checks that elimination picks the smallest.
+    #[test]
+    fn elimination_matches_astar_on_small_boards() {
+        let mut rng = StdRng::seed_from_u64(11);
+        for size in [3, 4] {
+            for _ in 0..5 {
+                let state = LightsOutState::random_solvable(size, &mut rng);
+                let presses = state.solve_gaussian_elimination().expect("solvable board");
+                assert_eq!(astar(state).actions.len(), presses.len());
+            }
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use crate::search::SearchState;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+
+/// Smallest and largest maze, in cells per side; both odd so walls line the border.
+pub const MIN_SIZE: u8 = 7;
+pub const MAX_SIZE: u8 = 75;
+pub const DEFAULT_SIZE: (u8, u8) = (31, 21);
+/// Cells added or removed per side by one press of `+` / `-`.
+pub const SIZE_STEP: u8 = 4;
+/// Path cost of stepping onto a [`Cell::Muddy`] cell; any other floor costs 1.
+pub const MUD_COST: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Cell {
+    Wall,
+    Open,
+    /// Walkable, but costs [`MUD_COST`] to enter.
+    Muddy,
+    Start,
+    End,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MazeState {
+    // Indexed `grid[row][col]`; `player` and `goal` are (row, col) too
+    pub grid: Vec<Vec<Cell>>,
+    pub player: (u8, u8),
+    pub goal: (u8, u8),
+}
+
+// The grid never changes during a search, so hashing the player and goal is
+// enough and saves walking every cell on each lookup
+impl Hash for MazeState {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.player.hash(state);
+        self.goal.hash(state);
+    }
+}
+
+impl Default for MazeState {
+    fn default() -> Self {
+        Self::generate(DEFAULT_SIZE, &mut rand::thread_rng())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MazeMove {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl MazeMove {
+    pub const ALL: [MazeMove; 4] = [MazeMove::Up, MazeMove::Down, MazeMove::Left, MazeMove::Right];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MazeMove::Up => "Up",
+            MazeMove::Down => "Down",
+            MazeMove::Left => "Left",
+            MazeMove::Right => "Right",
+        }
+    }
+
+    fn delta(&self) -> (i16, i16) {
+        match self {
+            MazeMove::Up => (-1, 0),
+            MazeMove::Down => (1, 0),
+            MazeMove::Left => (0, -1),
+            MazeMove::Right => (0, 1),
+        }
+    }
+}
+
+impl MazeState {
+    /// A maze carved by recursive backtracking: a depth-first walk that knocks
+    /// down the wall to a random unvisited neighbour and backs up when there is
+    /// none. A few more walls are then removed so some routes have a detour,
+    /// and about one floor cell in eight turns to mud, so A* has to weigh a
+    /// longer dry route against a shorter muddy one. Even sizes are rounded
+    /// down to odd ones. Start is the top-left corner, End the bottom-right one.
+    pub fn generate((width, height): (u8, u8), rng: &mut impl Rng) -> Self {
+        let width = (width.clamp(MIN_SIZE, MAX_SIZE) - 1) | 1;
+        let height = (height.clamp(MIN_SIZE, MAX_SIZE) - 1) | 1;
+        let mut grid = vec![vec![Cell::Wall; width as usize]; height as usize];
+
+        // Rooms sit on odd coordinates; the walls between them on even ones
+        grid[1][1] = Cell::Open;
+        let mut stack = vec![(1u8, 1u8)];
+        while let Some(&(row, col)) = stack.last() {
+            let mut neighbours: Vec<(u8, u8)> = [(-2i16, 0i16), (2, 0), (0, -2), (0, 2)]
+                .into_iter()
+                .filter_map(|(dr, dc)| {
+                    let next_row = u8::try_from(row as i16 + dr).ok()?;
+                    let next_col = u8::try_from(col as i16 + dc).ok()?;
+                    (next_row > 0 && next_row < height - 1 && next_col > 0 && next_col < width - 1)
+                        .then_some((next_row, next_col))
+                })
+                .filter(|&(next_row, next_col)| grid[next_row as usize][next_col as usize] == Cell::Wall)
+                .collect();
+            neighbours.shuffle(rng);
+            match neighbours.first() {
+                Some(&(next_row, next_col)) => {
+                    let wall_row = (row as usize + next_row as usize) / 2;
+                    let wall_col = (col as usize + next_col as usize) / 2;
+                    grid[wall_row][wall_col] = Cell::Open;
+                    grid[next_row as usize][next_col as usize] = Cell::Open;
+                    stack.push((next_row, next_col));
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+
+        // A wall between two rooms sits on an odd row and even column, or the reverse
+        for _ in 0..width as usize * height as usize / 40 {
+            let row = rng.gen_range(1..height as usize - 1);
+            let col = rng.gen_range(1..width as usize - 1);
+            let joins_rooms = match (row % 2, col % 2) {
+                (1, 0) => grid[row][col - 1] == Cell::Open && grid[row][col + 1] == Cell::Open,
+                (0, 1) => grid[row - 1][col] == Cell::Open && grid[row + 1][col] == Cell::Open,
+                _ => false,
+            };
+            if joins_rooms {
+                grid[row][col] = Cell::Open;
+            }
+        }
+        for cell in grid.iter_mut().flatten() {
+            if *cell == Cell::Open && rng.gen_ratio(1, 8) {
+                *cell = Cell::Muddy;
+            }
+        }
+
+        let start = (1, 1);
+        let goal = (height - 2, width - 2);
+        grid[start.0 as usize][start.1 as usize] = Cell::Start;
+        grid[goal.0 as usize][goal.1 as usize] = Cell::End;
+        Self {
+            grid,
+            player: start,
+            goal,
+        }
+    }
+
+    pub fn width(&self) -> u8 {
+        self.grid.first().map_or(0, |row| row.len() as u8)
+    }
+
+    pub fn height(&self) -> u8 {
+        self.grid.len() as u8
+    }
+
+    pub fn cell(&self, (row, col): (u8, u8)) -> Option<Cell> {
+        self.grid.get(row as usize)?.get(col as usize).copied()
+    }
+
+    /// Where the Start cell is, if the grid has one.
+    pub fn start(&self) -> Option<(u8, u8)> {
+        self.grid.iter().enumerate().find_map(|(row, cells)| {
+            let col = cells.iter().position(|&cell| cell == Cell::Start)?;
+            Some((row as u8, col as u8))
+        })
+    }
+
+    pub fn is_wall(&self, cell: (u8, u8)) -> bool {
+        self.cell(cell).is_none_or(|cell| cell == Cell::Wall)
+    }
+
+    /// Cell `mv` leads the player to, walls included.
+    fn target(&self, mv: MazeMove) -> Option<(u8, u8)> {
+        let (dr, dc) = mv.delta();
+        let row = u8::try_from(self.player.0 as i16 + dr).ok()?;
+        let col = u8::try_from(self.player.1 as i16 + dc).ok()?;
+        Some((row, col))
+    }
+
+    /// Cell of the player after `mv`, or `None` when a wall or the edge is in the way.
+    pub fn apply_move(&self, mv: MazeMove) -> Option<Self> {
+        let target = self.target(mv)?;
+        if self.is_wall(target) {
+            return None;
+        }
+        Some(Self {
+            player: target,
+            ..self.clone()
+        })
+    }
+
+    /// Manhattan distance from the player to the goal. Still a lower bound
+    /// with mud about, since every step costs at least 1.
+    pub fn heuristic(&self) -> u32 {
+        self.player.0.abs_diff(self.goal.0) as u32 + self.player.1.abs_diff(self.goal.1) as u32
+    }
+}
+
+impl Display for MazeState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (row, cells) in self.grid.iter().enumerate() {
+            for (col, cell) in cells.iter().enumerate() {
+                let symbol = match cell {
+                    _ if self.player == (row as u8, col as u8) => '@',
+                    Cell::Wall => '#',
+                    Cell::Open => ' ',
+                    Cell::Muddy => '~',
+                    Cell::Start => 'S',
+                    Cell::End => 'E',
+                };
+                write!(f, "{}", symbol)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl SearchState for MazeState {
+    type Move = MazeMove;
+
+    fn is_goal(&self) -> bool {
+        self.player == self.goal
+    }
+
+    fn heuristic(&self) -> u32 {
+        self.heuristic()
+    }
+
+    /// The four cardinal neighbours that are not walls (Start and End cells
+    /// are open floor too).
+    fn successors(&self) -> Vec<(Self::Move, Self)> {
+        MazeMove::ALL
+            .into_iter()
+            .filter_map(|mv| self.apply_move(mv).map(|next| (mv, next)))
+            .collect()
+    }
+
+    fn cost(&self, mv: &Self::Move) -> u32 {
+        match self.target(*mv).and_then(|cell| self.cell(cell)) {
+            Some(Cell::Muddy) => MUD_COST,
+            _ => 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::{HashMap, VecDeque};
+
+    /// Steps from every floor cell to the goal, by breadth-first search.
+    fn distances_to_goal(maze: &MazeState) -> HashMap<(u8, u8), u32> {
+        let mut distances = HashMap::from([(maze.goal, 0)]);
+        let mut queue = VecDeque::from([maze.goal]);
+        while let Some(cell) = queue.pop_front() {
+            let here = MazeState { player: cell, ..maze.clone() };
+            for (_, next) in here.successors() {
+                if !distances.contains_key(&next.player) {
+                    distances.insert(next.player, distances[&cell] + 1);
+                    queue.push_back(next.player);
+                }
+            }
+        }
+        distances
+    }
+
+    #[test]
+    fn generated_mazes_connect_start_to_end() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for size in [(MIN_SIZE, MIN_SIZE), DEFAULT_SIZE, (MAX_SIZE, 9), (MAX_SIZE, MAX_SIZE)] {
+            for _ in 0..5 {
+                let maze = MazeState::generate(size, &mut rng);
+                let start = maze.start().expect("generated mazes have a start");
+                assert_eq!(maze.cell(maze.goal), Some(Cell::End));
+                assert!(distances_to_goal(&maze).contains_key(&start));
+            }
+        }
+    }
+
+    #[test]
+    fn manhattan_distance_never_exceeds_the_walk() {
+        let mut rng = StdRng::seed_from_u64(9);
+        for _ in 0..5 {
+            let maze = MazeState::generate(DEFAULT_SIZE, &mut rng);
+            for (cell, steps) in distances_to_goal(&maze) {
+                let here = MazeState { player: cell, ..maze.clone() };
+                assert!(here.heuristic() <= steps, "h = {} at {:?}, {} steps away", here.heuristic(), cell, steps);
+            }
+        }
+    }
+
+    #[test]
+    fn entering_mud_costs_more() {
+        let mut maze = MazeState::generate((MIN_SIZE, MIN_SIZE), &mut StdRng::seed_from_u64(2));
+        let (mv, next) = maze.successors()[0].clone();
+        let (row, col) = (next.player.0 as usize, next.player.1 as usize);
+        maze.grid[row][col] = Cell::Open;
+        assert_eq!(maze.cost(&mv), 1);
+        maze.grid[row][col] = Cell::Muddy;
+        assert_eq!(maze.cost(&mv), MUD_COST);
+    }
+}
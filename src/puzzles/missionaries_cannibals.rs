@@ -1,22 +1,29 @@
+use crate::puzzles::MoveError;
 use crate::search::SearchState;
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub const MIN_GROUP_SIZE: u8 = 1;
+pub const MAX_GROUP_SIZE: u8 = 9;
+pub const MIN_BOAT_CAPACITY: u8 = 2;
+pub const MAX_BOAT_CAPACITY: u8 = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct MissionariesCannibalsState {
     // Left side: missionaries, cannibals
     pub left_m: u8,
     pub left_c: u8,
+    // Number of missionaries (and of cannibals) in the whole puzzle
+    pub n: u8,
+    // Maximum number of people in the boat
+    pub boat_capacity: u8,
     // Boat position: true = left, false = right
     pub boat_left: bool,
 }
 
 impl Default for MissionariesCannibalsState {
     fn default() -> Self {
-        Self {
-            left_m: 3,
-            left_c: 3,
-            boat_left: true,
-        }
+        Self::new(3, 2)
     }
 }
 
@@ -26,43 +33,116 @@ pub struct BoatMove {
     pub cannibals: u8,
 }
 
+impl BoatMove {
+    /// Who is aboard, e.g. "2 cannibals" or "1 missionary and 1 cannibal".
+    pub fn passengers(&self) -> String {
+        let group = |count: u8, one: &str, many: &str| match count {
+            0 => None,
+            1 => Some(format!("1 {}", one)),
+            count => Some(format!("{} {}", count, many)),
+        };
+        [
+            group(self.missionaries, "missionary", "missionaries"),
+            group(self.cannibals, "cannibal", "cannibals"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" and ")
+    }
+}
+
 impl MissionariesCannibalsState {
+    /// Everyone (and the boat) on the left bank.
+    pub fn new(n: u8, boat_capacity: u8) -> Self {
+        Self {
+            left_m: n,
+            left_c: n,
+            n,
+            boat_capacity,
+            boat_left: true,
+        }
+    }
+
+    pub fn right_m(&self) -> u8 {
+        self.n - self.left_m
+    }
+
+    pub fn right_c(&self) -> u8 {
+        self.n - self.left_c
+    }
+
     pub fn is_valid(&self) -> bool {
+        if self.left_m > self.n || self.left_c > self.n {
+            return false;
+        }
         // Check left side
         if self.left_m > 0 && self.left_c > self.left_m {
             return false;
         }
         // Check right side
-        let right_m = 3 - self.left_m;
-        let right_c = 3 - self.left_c;
+        let right_m = self.right_m();
+        let right_c = self.right_c();
         if right_m > 0 && right_c > right_m {
             return false;
         }
-        // Check boat capacity
         true
     }
 
-    pub fn apply_move(&self, mv: BoatMove) -> Option<Self> {
-        if mv.missionaries + mv.cannibals == 0 || mv.missionaries + mv.cannibals > 2 {
+    /// Every boat load that fits the boat, whether or not it is legal right now.
+    pub fn possible_moves(&self) -> Vec<BoatMove> {
+        let mut moves = Vec::new();
+        for missionaries in 0..=self.boat_capacity {
+            for cannibals in 0..=(self.boat_capacity - missionaries) {
+                if missionaries + cannibals > 0 {
+                    moves.push(BoatMove {
+                        missionaries,
+                        cannibals,
+                    });
+                }
+            }
+        }
+        moves
+    }
+
+    /// The load the boat carried from `self` to `next`, or `None` when `next`
+    /// is not one crossing away.
+    pub fn crossing_to(&self, next: &Self) -> Option<BoatMove> {
+        if self.boat_left == next.boat_left {
             return None;
         }
+        let (from, to) = if self.boat_left { (self, next) } else { (next, self) };
+        let mv = BoatMove {
+            missionaries: from.left_m.checked_sub(to.left_m)?,
+            cannibals: from.left_c.checked_sub(to.left_c)?,
+        };
+        self.apply_move(mv).ok().filter(|state| state == next).map(|_| mv)
+    }
+
+    /// The banks after `mv`. A load the boat cannot take, or more people than
+    /// wait on the boat's bank, is [`MoveError::InvalidQuantity`]; leaving
+    /// missionaries outnumbered is [`MoveError::ConflictsExist`].
+    pub fn apply_move(&self, mv: BoatMove) -> Result<Self, MoveError> {
+        if mv.missionaries + mv.cannibals == 0
+            || mv.missionaries + mv.cannibals > self.boat_capacity
+        {
+            return Err(MoveError::InvalidQuantity);
+        }
 
         let mut new_state = *self;
 
         if self.boat_left {
             // Moving from left to right
             if mv.missionaries > self.left_m || mv.cannibals > self.left_c {
-                return None;
+                return Err(MoveError::InvalidQuantity);
             }
             new_state.left_m -= mv.missionaries;
             new_state.left_c -= mv.cannibals;
             new_state.boat_left = false;
         } else {
             // Moving from right to left
-            let right_m = 3 - self.left_m;
-            let right_c = 3 - self.left_c;
-            if mv.missionaries > right_m || mv.cannibals > right_c {
-                return None;
+            if mv.missionaries > self.right_m() || mv.cannibals > self.right_c() {
+                return Err(MoveError::InvalidQuantity);
             }
             new_state.left_m += mv.missionaries;
             new_state.left_c += mv.cannibals;
@@ -70,25 +150,40 @@ impl MissionariesCannibalsState {
         }
 
         if new_state.is_valid() {
-            Some(new_state)
+            Ok(new_state)
         } else {
-            None
+            Err(MoveError::ConflictsExist)
         }
     }
 
     pub fn heuristic(&self) -> u32 {
-        // Heuristic: number of people on left side (all need to cross)
-        (self.left_m + self.left_c) as u32
+        // Heuristic: minimum number of crossings needed to ferry everyone on the
+        // left across. Every round trip nets at most `capacity - 1` people, so it
+        // never overestimates.
+        let people = (self.left_m + self.left_c) as u32;
+        let capacity = self.boat_capacity.max(MIN_BOAT_CAPACITY) as u32;
+        let crossings = |people: u32| {
+            if people == 0 {
+                0
+            } else if people <= capacity {
+                1
+            } else {
+                2 * (people - capacity).div_ceil(capacity - 1) + 1
+            }
+        };
+        match (people, self.boat_left) {
+            (0, _) => 0,
+            (_, true) => crossings(people),
+            // The boat has to come back first, carrying at least one person.
+            (_, false) => 1 + crossings(people + 1),
+        }
     }
 }
 
 impl Display for MissionariesCannibalsState {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let right_m = 3 - self.left_m;
-        let right_c = 3 - self.left_c;
-        
         writeln!(f, "Left:  M={} C={}", self.left_m, self.left_c)?;
-        writeln!(f, "Right: M={} C={}", right_m, right_c)?;
+        writeln!(f, "Right: M={} C={}", self.right_m(), self.right_c())?;
         writeln!(f, "Boat:  {}", if self.boat_left { "Left" } else { "Right" })?;
         Ok(())
     }
@@ -107,18 +202,10 @@ impl SearchState for MissionariesCannibalsState {
 
     fn successors(&self) -> Vec<(Self::Move, Self)> {
         let mut moves = Vec::new();
-        
-        // Generate all possible boat moves (1-2 people, at least 1 person)
-        let possible_moves = vec![
-            BoatMove { missionaries: 1, cannibals: 0 },
-            BoatMove { missionaries: 2, cannibals: 0 },
-            BoatMove { missionaries: 0, cannibals: 1 },
-            BoatMove { missionaries: 0, cannibals: 2 },
-            BoatMove { missionaries: 1, cannibals: 1 },
-        ];
-
-        for mv in possible_moves {
-            if let Some(new_state) = self.apply_move(mv) {
+
+        // Generate all possible boat moves (at least 1 person, at most the capacity)
+        for mv in self.possible_moves() {
+            if let Ok(new_state) = self.apply_move(mv) {
                 moves.push((mv, new_state));
             }
         }
@@ -126,4 +213,3 @@ impl SearchState for MissionariesCannibalsState {
         moves
     }
 }
-
@@ -1,29 +1,142 @@
 pub mod eight_puzzle;
 pub mod eight_queens;
+pub mod knights_tour;
+pub mod lights_out;
+pub mod maze;
 pub mod missionaries_cannibals;
+pub mod sokoban;
+pub mod sudoku;
+pub mod tower_of_hanoi;
+pub mod water_jug;
 pub mod xor_tic_tac_toe;
 
-pub use eight_puzzle::{EightPuzzleState, SlideMove};
+pub use eight_puzzle::{EightPuzzleHeuristic, EightPuzzleState, SlideMove};
 pub use eight_queens::{EightQueensState, PlaceQueen};
+pub use knights_tour::{KnightMove, KnightsTourState};
+pub use lights_out::LightsOutState;
+pub use maze::{MazeMove, MazeState};
 pub use missionaries_cannibals::{BoatMove, MissionariesCannibalsState};
-pub use xor_tic_tac_toe::{Player, XorTicTacToeState, WINNING_LINES};
+pub use sokoban::{SokobanMove, SokobanState};
+pub use sudoku::{SudokuMove, SudokuState};
+pub use tower_of_hanoi::{HanoiMove, HanoiState};
+pub use water_jug::{WaterJugMove, WaterJugState};
+pub use xor_tic_tac_toe::{Player, XorTicTacToeState};
 
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Why a puzzle refused a move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum MoveError {
+    #[error("that square is off the board")]
+    OutOfBounds,
+    #[error("that spot is already taken")]
+    Occupied,
+    #[error("the move breaks a rule of the puzzle")]
+    ConflictsExist,
+    #[error("the move carries too many or too few pieces")]
+    InvalidQuantity,
+    #[error("no solution is left after that move")]
+    DeadEnd,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PuzzleId {
+    #[default]
     EightPuzzle,
     XorTicTacToe,
     MissionariesCannibals,
     EightQueens,
+    TowerOfHanoi,
+    KnightsTour,
+    WaterJug,
+    LightsOut,
+    Sokoban,
+    Sudoku,
+    Maze,
+    /// Not a puzzle of its own: opens today's board of one of the others.
+    DailyChallenge,
     About,
 }
 
+impl PuzzleId {
+    /// Short identifier used in file names and on the command line.
+    pub fn slug(&self) -> &'static str {
+        match self {
+            PuzzleId::EightPuzzle => "eight_puzzle",
+            PuzzleId::XorTicTacToe => "xor_tic_tac_toe",
+            PuzzleId::MissionariesCannibals => "missionaries_cannibals",
+            PuzzleId::EightQueens => "eight_queens",
+            PuzzleId::TowerOfHanoi => "tower_of_hanoi",
+            PuzzleId::KnightsTour => "knights_tour",
+            PuzzleId::WaterJug => "water_jug",
+            PuzzleId::LightsOut => "lights_out",
+            PuzzleId::Sokoban => "sokoban",
+            PuzzleId::Sudoku => "sudoku",
+            PuzzleId::Maze => "maze",
+            PuzzleId::DailyChallenge => "daily_challenge",
+            PuzzleId::About => "about",
+        }
+    }
+
+    /// Parses a puzzle name as typed on the command line, e.g. `eight` or `queens`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase().replace('-', "_");
+        match name.as_str() {
+            "eight" | "8puzzle" | "eight_puzzle" | "puzzle" => Some(PuzzleId::EightPuzzle),
+            "xor" | "tictactoe" | "tic_tac_toe" | "xor_tic_tac_toe" => Some(PuzzleId::XorTicTacToe),
+            "missionaries" | "mc" | "missionaries_cannibals" => {
+                Some(PuzzleId::MissionariesCannibals)
+            }
+            "queens" | "8queens" | "eight_queens" => Some(PuzzleId::EightQueens),
+            "hanoi" | "tower_of_hanoi" => Some(PuzzleId::TowerOfHanoi),
+            "knight" | "knights" | "knights_tour" => Some(PuzzleId::KnightsTour),
+            "jug" | "jugs" | "water_jug" => Some(PuzzleId::WaterJug),
+            "lights" | "lights_out" => Some(PuzzleId::LightsOut),
+            "sokoban" | "boxes" => Some(PuzzleId::Sokoban),
+            "sudoku" => Some(PuzzleId::Sudoku),
+            "maze" | "labyrinth" => Some(PuzzleId::Maze),
+            "daily" | "daily_challenge" => Some(PuzzleId::DailyChallenge),
+            "about" => Some(PuzzleId::About),
+            _ => None,
+        }
+    }
+}
+
+/// How hard a puzzle is to solve by hand, shown as a badge in the menu.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Difficulty {
+    #[default]
+    Beginner,
+    Intermediate,
+    Advanced,
+    Expert,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Beginner,
+        Difficulty::Intermediate,
+        Difficulty::Advanced,
+        Difficulty::Expert,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Beginner => "Beginner",
+            Difficulty::Intermediate => "Intermediate",
+            Difficulty::Advanced => "Advanced",
+            Difficulty::Expert => "Expert",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PuzzleDescriptor {
     pub id: PuzzleId,
     pub name: &'static str,
     pub summary: &'static str,
+    pub difficulty: Difficulty,
 }
 
 #[derive(Debug, Clone)]
@@ -43,16 +156,11 @@ impl Default for PuzzleDescriptor {
             id: PuzzleId::EightPuzzle,
             name: "8-Puzzle Solver",
             summary: "Classic sliding puzzle solved with A* and Manhattan heuristic.",
+            difficulty: Difficulty::Intermediate,
         }
     }
 }
 
-impl Default for PuzzleId {
-    fn default() -> Self {
-        PuzzleId::EightPuzzle
-    }
-}
-
 impl PuzzleRegistry {
     pub fn initialize() -> Self {
         let descriptors = vec![
@@ -60,26 +168,79 @@ impl PuzzleRegistry {
                 id: PuzzleId::EightPuzzle,
                 name: "8-Puzzle Solver",
                 summary: "Slide tiles into place, observe heuristic-driven search stats.",
+                difficulty: Difficulty::Intermediate,
             },
             PuzzleDescriptor {
                 id: PuzzleId::XorTicTacToe,
                 name: "XOR Tic-Tac-Toe",
                 summary: "Play optimally with A* hints in an unusual variant of tic-tac-toe.",
+                difficulty: Difficulty::Beginner,
             },
             PuzzleDescriptor {
                 id: PuzzleId::MissionariesCannibals,
                 name: "Missionaries & Cannibals",
                 summary: "Get 3 missionaries and 3 cannibals across the river safely using A* search.",
+                difficulty: Difficulty::Beginner,
             },
             PuzzleDescriptor {
                 id: PuzzleId::EightQueens,
                 name: "8 Queens Problem",
                 summary: "Place 8 queens on a chessboard so none attack each other. Watch A* solve it!",
+                difficulty: Difficulty::Advanced,
+            },
+            PuzzleDescriptor {
+                id: PuzzleId::TowerOfHanoi,
+                name: "Tower of Hanoi",
+                summary: "Move the whole stack to the last peg, never placing a larger disk on a smaller one.",
+                difficulty: Difficulty::Beginner,
+            },
+            PuzzleDescriptor {
+                id: PuzzleId::KnightsTour,
+                name: "Knight's Tour",
+                summary: "Visit every square of the chessboard exactly once with a knight, guided by Warnsdorff's rule.",
+                difficulty: Difficulty::Expert,
+            },
+            PuzzleDescriptor {
+                id: PuzzleId::WaterJug,
+                name: "Water Jug Problem",
+                summary: "Fill, empty and pour between jugs until one holds exactly the target amount.",
+                difficulty: Difficulty::Beginner,
+            },
+            PuzzleDescriptor {
+                id: PuzzleId::LightsOut,
+                name: "Lights Out",
+                summary: "Pressing a light toggles it and its neighbours. Switch every light off, solved exactly by Gaussian elimination.",
+                difficulty: Difficulty::Intermediate,
+            },
+            PuzzleDescriptor {
+                id: PuzzleId::Sokoban,
+                name: "Sokoban",
+                summary: "Push every box onto a goal square. A* plans the pushes using box-to-goal distances.",
+                difficulty: Difficulty::Expert,
+            },
+            PuzzleDescriptor {
+                id: PuzzleId::Sudoku,
+                name: "Sudoku",
+                summary: "Fill the grid so every row, column and box holds 1-9. A* guesses only after constraint propagation runs dry.",
+                difficulty: Difficulty::Advanced,
+            },
+            PuzzleDescriptor {
+                id: PuzzleId::Maze,
+                name: "Maze",
+                summary: "Find the way from S to E through a randomly carved maze. A* follows the Manhattan distance to the exit.",
+                difficulty: Difficulty::Beginner,
+            },
+            PuzzleDescriptor {
+                id: PuzzleId::DailyChallenge,
+                name: "Daily Challenge",
+                summary: "Today's board, the same for everyone on this date. Solve it by hand and compare your moves with par.",
+                difficulty: Difficulty::Intermediate,
             },
             PuzzleDescriptor {
                 id: PuzzleId::About,
                 name: "About This Program",
                 summary: "Learn about this AI Puzzle Suite, the A* algorithm, and acknowledgments.",
+                difficulty: Difficulty::Beginner,
             },
         ];
 
@@ -0,0 +1,354 @@
+use crate::search::SearchState;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Largest level, in cells, accepted from a saved session.
+pub const MAX_SIZE: u8 = 20;
+/// Most boxes [`SokobanState::heuristic`] pairs with goals exactly.
+const MAX_MATCHED_BOXES: usize = 10;
+
+/// Built-in levels, easiest first: `#` wall, `$` box, `.` goal, `*` box on
+/// a goal, `@` player, `+` player on a goal. Levels 1-5 of David W. Skinner's
+/// Microban set.
+pub const LEVELS: [&str; 5] = [
+    r"
+####
+# .#
+#  ###
+#*@  #
+#  $ #
+#  ###
+####
+",
+    r"
+######
+#    #
+# #@ #
+# $* #
+# .* #
+#    #
+######
+",
+    r"
+  ####
+###  ####
+#     $ #
+# #  #$ #
+# . .#@ #
+#########
+",
+    r"
+########
+#      #
+# .**$@#
+#      #
+#####  #
+    ####
+",
+    r"
+ #######
+ #     #
+ # .$. #
+## $@$ #
+#  .$. #
+#      #
+########
+",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SokobanState {
+    // Cells are (row, col); `boxes` is kept sorted so equal boards compare equal
+    pub player: (u8, u8),
+    pub boxes: Vec<(u8, u8)>,
+    pub goals: Vec<(u8, u8)>,
+    pub walls: Vec<(u8, u8)>,
+    pub width: u8,
+    pub height: u8,
+}
+
+impl Default for SokobanState {
+    fn default() -> Self {
+        Self::level(0).expect("built-in levels parse")
+    }
+}
+
+/// One step of the player, pushing the box in front of it if there is one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SokobanMove {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl SokobanMove {
+    pub const ALL: [SokobanMove; 4] = [
+        SokobanMove::Up,
+        SokobanMove::Down,
+        SokobanMove::Left,
+        SokobanMove::Right,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SokobanMove::Up => "Up",
+            SokobanMove::Down => "Down",
+            SokobanMove::Left => "Left",
+            SokobanMove::Right => "Right",
+        }
+    }
+
+    fn delta(&self) -> (i16, i16) {
+        match self {
+            SokobanMove::Up => (-1, 0),
+            SokobanMove::Down => (1, 0),
+            SokobanMove::Left => (0, -1),
+            SokobanMove::Right => (0, 1),
+        }
+    }
+}
+
+impl SokobanState {
+    /// Built-in level `index` (0-based) from [`LEVELS`].
+    pub fn level(index: usize) -> Option<Self> {
+        LEVELS.get(index).and_then(|map| Self::parse(map))
+    }
+
+    /// Reads a level map. Returns `None` without exactly one player, or when
+    /// the box and goal counts differ.
+    pub fn parse(map: &str) -> Option<Self> {
+        let rows: Vec<&str> = map
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        let mut player = None;
+        let mut boxes = Vec::new();
+        let mut goals = Vec::new();
+        let mut walls = Vec::new();
+        for (row, line) in rows.iter().enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                let cell = (u8::try_from(row).ok()?, u8::try_from(col).ok()?);
+                match ch {
+                    '#' => walls.push(cell),
+                    '$' => boxes.push(cell),
+                    '.' => goals.push(cell),
+                    '*' => {
+                        boxes.push(cell);
+                        goals.push(cell);
+                    }
+                    '@' | '+' => {
+                        if player.replace(cell).is_some() {
+                            return None;
+                        }
+                        if ch == '+' {
+                            goals.push(cell);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if boxes.len() != goals.len() {
+            return None;
+        }
+        boxes.sort_unstable();
+        goals.sort_unstable();
+        walls.sort_unstable();
+        let width = rows.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+        Some(Self {
+            player: player?,
+            boxes,
+            goals,
+            walls,
+            width: u8::try_from(width).ok()?,
+            height: u8::try_from(rows.len()).ok()?,
+        })
+    }
+
+    pub fn is_wall(&self, cell: (u8, u8)) -> bool {
+        self.walls.binary_search(&cell).is_ok()
+    }
+
+    pub fn is_box(&self, cell: (u8, u8)) -> bool {
+        self.boxes.binary_search(&cell).is_ok()
+    }
+
+    pub fn is_goal_cell(&self, cell: (u8, u8)) -> bool {
+        self.goals.binary_search(&cell).is_ok()
+    }
+
+    pub fn boxes_on_goals(&self) -> usize {
+        self.boxes.iter().filter(|&&cell| self.is_goal_cell(cell)).count()
+    }
+
+    fn step(&self, (row, col): (u8, u8), mv: SokobanMove) -> Option<(u8, u8)> {
+        let (dr, dc) = mv.delta();
+        let row = u8::try_from(row as i16 + dr).ok()?;
+        let col = u8::try_from(col as i16 + dc).ok()?;
+        (row < self.height && col < self.width).then_some((row, col))
+    }
+
+    pub fn apply_move(&self, mv: SokobanMove) -> Option<Self> {
+        let target = self.step(self.player, mv)?;
+        if self.is_wall(target) {
+            return None;
+        }
+        let mut next = self.clone();
+        if let Ok(idx) = self.boxes.binary_search(&target) {
+            let beyond = self.step(target, mv)?;
+            if self.is_wall(beyond) || self.is_box(beyond) {
+                return None;
+            }
+            next.boxes[idx] = beyond;
+            next.boxes.sort_unstable();
+        }
+        next.player = target;
+        Some(next)
+    }
+
+    /// Whether `mv` would push a box rather than just walk.
+    pub fn is_push(&self, mv: SokobanMove) -> bool {
+        self.step(self.player, mv).is_some_and(|target| self.is_box(target))
+    }
+
+    /// A box wedged into a corner off its goal can never move again, so the
+    /// board is lost.
+    fn has_cornered_box(&self) -> bool {
+        self.boxes.iter().any(|&cell| {
+            if self.is_goal_cell(cell) {
+                return false;
+            }
+            let blocked = |mv| self.step(cell, mv).is_none_or(|next| self.is_wall(next));
+            (blocked(SokobanMove::Up) || blocked(SokobanMove::Down))
+                && (blocked(SokobanMove::Left) || blocked(SokobanMove::Right))
+        })
+    }
+
+    /// Smallest sum of box-to-goal Manhattan distances over every way of
+    /// giving each box its own goal. Each push moves one box one cell, so
+    /// this never overestimates. Found by dynamic programming over the sets
+    /// of goals already taken; past [`MAX_MATCHED_BOXES`] boxes that gets too
+    /// costly and each box is measured to its nearest goal instead, which is
+    /// lower still.
+    pub fn heuristic(&self) -> u32 {
+        let distance = |(box_row, box_col): (u8, u8), (goal_row, goal_col): (u8, u8)| {
+            box_row.abs_diff(goal_row) as u32 + box_col.abs_diff(goal_col) as u32
+        };
+        if self.boxes.len() > MAX_MATCHED_BOXES || self.boxes.len() != self.goals.len() {
+            return self
+                .boxes
+                .iter()
+                .map(|&cell| self.goals.iter().map(|&goal| distance(cell, goal)).min().unwrap_or(0))
+                .sum();
+        }
+        // best[taken] is the cheapest way to place the first popcount(taken)
+        // boxes on exactly the goals in `taken`
+        let mut best = vec![u32::MAX; 1 << self.goals.len()];
+        best[0] = 0;
+        for taken in 0..best.len() {
+            if best[taken] == u32::MAX {
+                continue;
+            }
+            let Some(&cell) = self.boxes.get(taken.count_ones() as usize) else {
+                continue;
+            };
+            for (g, &goal) in self.goals.iter().enumerate() {
+                if taken & (1 << g) == 0 {
+                    let next = taken | (1 << g);
+                    best[next] = best[next].min(best[taken] + distance(cell, goal));
+                }
+            }
+        }
+        best[best.len() - 1]
+    }
+}
+
+impl Display for SokobanState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let cell = (row, col);
+                let symbol = match (self.is_goal_cell(cell), self.is_box(cell)) {
+                    _ if self.is_wall(cell) => '#',
+                    _ if self.player == cell => '@',
+                    (true, true) => '*',
+                    (false, true) => '$',
+                    (true, false) => '.',
+                    (false, false) => ' ',
+                };
+                write!(f, "{}", symbol)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl SearchState for SokobanState {
+    type Move = SokobanMove;
+
+    fn is_goal(&self) -> bool {
+        self.boxes == self.goals
+    }
+
+    fn heuristic(&self) -> u32 {
+        self.heuristic()
+    }
+
+    fn successors(&self) -> Vec<(Self::Move, Self)> {
+        SokobanMove::ALL
+            .into_iter()
+            .filter_map(|mv| self.apply_move(mv).map(|next| (mv, next)))
+            .filter(|(_, next)| !next.has_cornered_box())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::solver::{astar, SearchOutcome};
+
+    #[test]
+    fn box_and_goal_counts_must_match() {
+        assert!(SokobanState::parse("#####\n#@$ #\n#####").is_none());
+        assert!(SokobanState::parse("#####\n#@$.#\n#####").is_some());
+    }
+
+    #[test]
+    fn boxes_do_not_move_into_walls_or_other_boxes() {
+        let against_wall = SokobanState::parse("####\n#@$#\n#. #\n####").expect("valid level");
+        assert_eq!(against_wall.apply_move(SokobanMove::Right), None);
+        let against_box = SokobanState::parse("######\n#@$$ #\n#..  #\n######").expect("valid level");
+        assert_eq!(against_box.apply_move(SokobanMove::Right), None);
+        assert!(against_box.apply_move(SokobanMove::Down).is_some());
+    }
+
+    #[test]
+    fn pushes_into_a_corner_are_pruned() {
+        let state = SokobanState::parse("#####\n# $@#\n#   #\n#.  #\n#####").expect("valid level");
+        assert!(state.apply_move(SokobanMove::Left).is_some());
+        let moves: Vec<SokobanMove> = state.successors().into_iter().map(|(mv, _)| mv).collect();
+        assert!(!moves.contains(&SokobanMove::Left));
+        assert!(moves.contains(&SokobanMove::Down));
+    }
+
+    /// Closest pair first would give each box the nearer goal and score 1 + 5.
+    #[test]
+    fn heuristic_uses_the_cheapest_matching() {
+        let state = SokobanState::parse("#########\n#$ .$ .@#\n#########").expect("valid level");
+        assert_eq!(state.heuristic(), 4);
+    }
+
+    #[test]
+    fn first_level_is_solved() {
+        let report = astar(SokobanState::level(0).expect("built-in level"));
+        assert_eq!(report.outcome, SearchOutcome::Solved);
+        assert!(report.path.last().is_some_and(SearchState::is_goal));
+        let moves = report.path.len() - 1;
+        for (step, state) in report.path.iter().enumerate() {
+            assert!(state.heuristic() as usize <= moves - step);
+        }
+    }
+}
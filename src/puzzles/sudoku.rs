@@ -0,0 +1,293 @@
+use crate::search::SearchState;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{Display, Formatter};
+
+/// Built-in puzzles, easiest first, as 81 digits read row by row (0 = empty).
+pub const PUZZLES: [(&str, &str); 5] = [
+    (
+        "Easy",
+        "530070000600195000098000060800060003400803001700020006060000280000419005000080079",
+    ),
+    (
+        "Medium",
+        "000260701680070090190004500820100040004602900050003028009300074040050036703018000",
+    ),
+    (
+        "Hard",
+        "000000907000420180000705026100904000050000040000507009920108000034059000507000000",
+    ),
+    (
+        "Very hard",
+        "000600400700003600000091080000000000050180003000306045040200060903000000020000100",
+    ),
+    (
+        "Expert",
+        "800000000003600000070090200050007000000045700000100030001000068008500010090000400",
+    ),
+];
+
+/// Bitmask with bits 1-9 set: every digit still possible.
+const ALL_DIGITS: u16 = 0b11_1111_1110;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SudokuState {
+    // Cell `row * 9 + col`; 0 marks an empty cell
+    #[serde(serialize_with = "write_digits", deserialize_with = "read_digits")]
+    pub grid: [u8; 81],
+}
+
+impl Default for SudokuState {
+    fn default() -> Self {
+        Self::puzzle(0).expect("built-in puzzles parse")
+    }
+}
+
+/// Writes a digit into an empty cell. During a search this is the guess
+/// made on the most constrained cell; forced cells follow from propagation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SudokuMove {
+    pub index: usize,
+    pub digit: u8,
+}
+
+impl SudokuMove {
+    pub fn label(&self) -> String {
+        format!("{} at row {}, column {}", self.digit, self.index / 9 + 1, self.index % 9 + 1)
+    }
+}
+
+impl SudokuState {
+    /// Built-in puzzle `index` (0-based) from [`PUZZLES`].
+    pub fn puzzle(index: usize) -> Option<Self> {
+        PUZZLES.get(index).and_then(|(_, digits)| Self::parse(digits))
+    }
+
+    /// Reads 81 digits, with `0` or `.` for an empty cell.
+    pub fn parse(digits: &str) -> Option<Self> {
+        let mut grid = [0u8; 81];
+        let mut cells = digits.chars().filter(|ch| !ch.is_whitespace());
+        for cell in grid.iter_mut() {
+            *cell = match cells.next()? {
+                '.' => 0,
+                ch => ch.to_digit(10)? as u8,
+            };
+        }
+        cells.next().is_none().then_some(Self { grid })
+    }
+
+    pub fn empty_cells(&self) -> u32 {
+        self.grid.iter().filter(|&&digit| digit == 0).count() as u32
+    }
+
+    /// Cells sharing a row, column or 3×3 box with `index`, `index` excluded.
+    pub fn peers(index: usize) -> impl Iterator<Item = usize> {
+        let (row, col) = (index / 9, index % 9);
+        let (box_row, box_col) = (row / 3 * 3, col / 3 * 3);
+        (0..81).filter(move |&other| {
+            other != index
+                && (other / 9 == row
+                    || other % 9 == col
+                    || (other / 9 / 3 * 3 == box_row && other % 9 / 3 * 3 == box_col))
+        })
+    }
+
+    /// Whether `digit` could go in `index` without repeating a peer.
+    pub fn allows(&self, index: usize, digit: u8) -> bool {
+        Self::peers(index).all(|peer| self.grid[peer] != digit)
+    }
+
+    /// Bitmask of the digits `index` could still take (bit `d` for digit `d`).
+    fn candidates(&self, index: usize) -> u16 {
+        Self::peers(index).fold(ALL_DIGITS, |mask, peer| mask & !(1 << self.grid[peer]))
+    }
+
+    /// Whether no row, column or box repeats a digit.
+    pub fn is_consistent(&self) -> bool {
+        (0..81).all(|index| self.grid[index] == 0 || self.allows(index, self.grid[index]))
+    }
+
+    /// Fills every forced cell: naked singles (a cell with one candidate) and
+    /// hidden singles (a digit with one possible cell in a row, column or
+    /// box), until neither applies. Returns `None` on a contradiction.
+    pub fn propagate(&self) -> Option<Self> {
+        let mut state = *self;
+        loop {
+            let mut changed = false;
+            for index in 0..81 {
+                if state.grid[index] != 0 {
+                    continue;
+                }
+                let mask = state.candidates(index);
+                match mask.count_ones() {
+                    0 => return None,
+                    1 => {
+                        state.grid[index] = mask.trailing_zeros() as u8;
+                        changed = true;
+                    }
+                    _ => {}
+                }
+            }
+            for unit in units() {
+                for digit in 1..=9u8 {
+                    if unit.iter().any(|&index| state.grid[index] == digit) {
+                        continue;
+                    }
+                    let mut spots = unit
+                        .iter()
+                        .copied()
+                        .filter(|&index| state.grid[index] == 0 && state.candidates(index) & (1 << digit) != 0);
+                    match (spots.next(), spots.next()) {
+                        (None, _) => return None,
+                        (Some(index), None) => {
+                            state.grid[index] = digit;
+                            changed = true;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            if !changed {
+                return Some(state);
+            }
+        }
+    }
+
+    /// Empty cell with the fewest candidates, and those candidates.
+    fn most_constrained(&self) -> Option<(usize, Vec<u8>)> {
+        (0..81)
+            .filter(|&index| self.grid[index] == 0)
+            .map(|index| (index, self.candidates(index)))
+            .min_by_key(|(_, mask)| mask.count_ones())
+            .map(|(index, mask)| (index, (1..=9).filter(|digit| mask & (1 << digit) != 0).collect()))
+    }
+
+    /// Empty cells left once propagation has filled the forced ones. Not a
+    /// lower bound: one guess often fills many cells through propagation, so
+    /// this overestimates and A* runs close to greedy best-first, diving
+    /// into the most promising branch. That is deliberate; every completed
+    /// grid is equally good, so there is no shorter solution to miss.
+    pub fn heuristic(&self) -> u32 {
+        self.propagate()
+            .map_or(self.empty_cells(), |state| state.empty_cells())
+    }
+}
+
+/// The 27 rows, columns and boxes as lists of cell indices.
+fn units() -> impl Iterator<Item = [usize; 9]> {
+    let rows = (0..9).map(|row| std::array::from_fn(|i| row * 9 + i));
+    let cols = (0..9).map(|col| std::array::from_fn(|i| i * 9 + col));
+    let boxes = (0..9).map(|b| std::array::from_fn(|i| (b / 3 * 3 + i / 3) * 9 + b % 3 * 3 + i % 3));
+    rows.chain(cols).chain(boxes)
+}
+
+fn write_digits<S: Serializer>(grid: &[u8; 81], serializer: S) -> Result<S::Ok, S::Error> {
+    let digits: String = grid.iter().map(|&digit| char::from(b'0' + digit.min(9))).collect();
+    serializer.serialize_str(&digits)
+}
+
+fn read_digits<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 81], D::Error> {
+    let digits = String::deserialize(deserializer)?;
+    SudokuState::parse(&digits)
+        .map(|state| state.grid)
+        .ok_or_else(|| serde::de::Error::custom("expected 81 digits"))
+}
+
+impl Display for SudokuState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in 0..9 {
+            if row > 0 && row % 3 == 0 {
+                writeln!(f, "------+-------+------")?;
+            }
+            for col in 0..9 {
+                if col > 0 && col % 3 == 0 {
+                    write!(f, "| ")?;
+                }
+                match self.grid[row * 9 + col] {
+                    0 => write!(f, ". ")?,
+                    digit => write!(f, "{} ", digit)?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl SearchState for SudokuState {
+    type Move = SudokuMove;
+
+    fn is_goal(&self) -> bool {
+        self.empty_cells() == 0 && self.is_consistent()
+    }
+
+    fn heuristic(&self) -> u32 {
+        self.heuristic()
+    }
+
+    fn successors(&self) -> Vec<(Self::Move, Self)> {
+        let Some(forced) = self.propagate() else {
+            return Vec::new();
+        };
+        let Some((index, digits)) = forced.most_constrained() else {
+            // Propagation alone finished the grid; report the first cell it filled
+            return (0..81)
+                .find(|&index| self.grid[index] != forced.grid[index])
+                .map(|index| {
+                    let mv = SudokuMove {
+                        index,
+                        digit: forced.grid[index],
+                    };
+                    vec![(mv, forced)]
+                })
+                .unwrap_or_default();
+        };
+        digits
+            .into_iter()
+            .filter_map(|digit| {
+                let mut next = forced;
+                next.grid[index] = digit;
+                next.propagate().map(|next| (SudokuMove { index, digit }, next))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::solver::{astar, SearchOutcome};
+
+    #[test]
+    fn built_in_puzzles_parse() {
+        for index in 0..PUZZLES.len() {
+            let state = SudokuState::puzzle(index).expect("built-in puzzle");
+            assert!(state.is_consistent());
+        }
+    }
+
+    /// The first cell of the top row has every digit but 9 in its row and a
+    /// 9 below it, so nothing fits.
+    #[test]
+    fn propagation_finds_contradictions() {
+        let mut grid = [0u8; 81];
+        grid[1..9].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        grid[9] = 9;
+        let state = SudokuState { grid };
+        assert!(state.is_consistent());
+        assert_eq!(state.propagate(), None);
+    }
+
+    #[test]
+    fn built_in_puzzles_are_solved_around_their_givens() {
+        for index in 0..PUZZLES.len() {
+            let start = SudokuState::puzzle(index).expect("built-in puzzle");
+            let report = astar(start);
+            assert_eq!(report.outcome, SearchOutcome::Solved, "puzzle {}", index);
+            let solved = report.path.last().expect("solution path");
+            assert!(solved.is_goal() && solved.is_consistent());
+            for (given, digit) in start.grid.iter().zip(solved.grid) {
+                assert!(*given == 0 || *given == digit);
+            }
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use crate::search::SearchState;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+pub const MIN_JUGS: usize = 2;
+pub const MAX_JUGS: usize = 4;
+pub const MIN_CAPACITY: u32 = 1;
+pub const MAX_CAPACITY: u32 = 20;
+
+/// Stand-in for the tap (as `from`) or the drain (as `to`) in a [`WaterJugMove`].
+pub const RIVER: usize = usize::MAX;
+
+/// Classic setups selectable from the puzzle screen: (capacities, target).
+pub const PRESETS: [(&[u32], u32); 3] = [(&[3, 5], 4), (&[3, 5, 8], 4), (&[4, 9], 6)];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WaterJugState {
+    // Liters currently in each jug
+    pub jugs: Vec<u32>,
+    pub capacities: Vec<u32>,
+    pub target: u32,
+}
+
+impl Default for WaterJugState {
+    fn default() -> Self {
+        let (capacities, target) = PRESETS[0];
+        Self::new(capacities.to_vec(), target)
+    }
+}
+
+/// Pour from one jug into another until the source is empty or the
+/// destination is full. [`RIVER`] as `from` fills `to` from the tap; as `to`
+/// it empties `from`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaterJugMove {
+    pub from: usize,
+    pub to: usize,
+}
+
+impl WaterJugMove {
+    pub fn label(&self) -> String {
+        match (self.from, self.to) {
+            (RIVER, to) => format!("Fill jug {}", to + 1),
+            (from, RIVER) => format!("Empty jug {}", from + 1),
+            (from, to) => format!("Pour jug {} → jug {}", from + 1, to + 1),
+        }
+    }
+}
+
+impl WaterJugState {
+    /// Every jug empty.
+    pub fn new(capacities: Vec<u32>, target: u32) -> Self {
+        Self {
+            jugs: vec![0; capacities.len()],
+            capacities,
+            target,
+        }
+    }
+
+    pub fn preset(index: usize) -> Option<Self> {
+        PRESETS
+            .get(index)
+            .map(|(capacities, target)| Self::new(capacities.to_vec(), *target))
+    }
+
+    /// Smallest gap in liters between any jug and the target.
+    pub fn min_distance_to_target(&self) -> u32 {
+        self.jugs
+            .iter()
+            .map(|&liters| liters.abs_diff(self.target))
+            .min()
+            .unwrap_or(self.target)
+    }
+
+    pub fn apply_move(&self, mv: WaterJugMove) -> Option<Self> {
+        let jug_count = self.jugs.len();
+        let mut next = self.clone();
+        match (mv.from, mv.to) {
+            (RIVER, RIVER) => return None,
+            (RIVER, to) => {
+                if to >= jug_count || self.jugs[to] == self.capacities[to] {
+                    return None;
+                }
+                next.jugs[to] = self.capacities[to];
+            }
+            (from, RIVER) => {
+                if from >= jug_count || self.jugs[from] == 0 {
+                    return None;
+                }
+                next.jugs[from] = 0;
+            }
+            (from, to) => {
+                if from == to || from >= jug_count || to >= jug_count {
+                    return None;
+                }
+                let amount = self.jugs[from].min(self.capacities[to] - self.jugs[to]);
+                if amount == 0 {
+                    return None;
+                }
+                next.jugs[from] -= amount;
+                next.jugs[to] += amount;
+            }
+        }
+        Some(next)
+    }
+
+    pub fn heuristic(&self) -> u32 {
+        // A single fill or pour can close any gap in liters, so the distance
+        // itself would overestimate; only "solved or not" is a safe bound.
+        self.min_distance_to_target().min(1)
+    }
+}
+
+impl Display for WaterJugState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (idx, (liters, capacity)) in self.jugs.iter().zip(&self.capacities).enumerate() {
+            writeln!(f, "Jug {}: {}/{} L", idx + 1, liters, capacity)?;
+        }
+        writeln!(f, "Target: {} L", self.target)?;
+        Ok(())
+    }
+}
+
+impl SearchState for WaterJugState {
+    type Move = WaterJugMove;
+
+    fn is_goal(&self) -> bool {
+        self.jugs.contains(&self.target)
+    }
+
+    fn heuristic(&self) -> u32 {
+        self.heuristic()
+    }
+
+    fn successors(&self) -> Vec<(Self::Move, Self)> {
+        let mut moves = Vec::new();
+        let ends = (0..self.jugs.len()).chain(std::iter::once(RIVER));
+        for from in ends.clone() {
+            for to in ends.clone() {
+                let mv = WaterJugMove { from, to };
+                if let Some(next) = self.apply_move(mv) {
+                    moves.push((mv, next));
+                }
+            }
+        }
+        moves
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::solver::{astar, solve_optimal_length, SearchOutcome};
+
+    #[test]
+    fn classic_jugs_measure_four_liters() {
+        let start = WaterJugState::new(vec![3, 5], 4);
+        let report = astar(start.clone());
+        assert_eq!(report.outcome, SearchOutcome::Solved);
+        assert_eq!(Some(report.actions.len()), solve_optimal_length(start));
+        assert_eq!(report.actions.len(), 6);
+    }
+
+    /// Every amount the jugs can hold is a multiple of gcd(2, 4) = 2.
+    #[test]
+    fn unreachable_target_has_no_solution() {
+        let report = astar(WaterJugState::new(vec![2, 4], 3));
+        assert_eq!(report.outcome, SearchOutcome::NoSolution);
+        assert!(report.path.is_empty());
+    }
+}
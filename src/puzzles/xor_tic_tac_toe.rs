@@ -1,5 +1,17 @@
 use crate::search::SearchState;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Base score for a decided game; the number of empty cells is added so faster
+/// wins (and slower losses) are preferred.
+const WIN_SCORE: i32 = 10;
+
+/// Search depth that always reaches the end of the game.
+pub const FULL_DEPTH: u8 = 9;
+
+pub type TranspositionTable = HashMap<(XorTicTacToeState, Player), i32>;
 
 pub const WINNING_LINES: [[usize; 3]; 8] = [
     [0, 1, 2],
@@ -27,7 +39,7 @@ impl Player {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct XorTicTacToeState {
     pub cells: [Option<Player>; 9],
     pub to_move: Player,
@@ -42,14 +54,73 @@ impl Default for XorTicTacToeState {
     }
 }
 
-#[allow(dead_code)]
+/// Why a position string could not be read by [`XorTicTacToeState::from_str`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParsePositionError {
+    #[error("unexpected '{0}': use X, O and . or space for an empty cell")]
+    InvalidChar(char),
+    #[error("expected 9 cells, found {0}")]
+    WrongLength(usize),
+    #[error("{x} X and {o} O cannot happen: X moves first, so X has as many pieces as O or one more")]
+    ImpossibleCounts { x: usize, o: usize },
+    #[error("both players have completed a line, but the game ends at the first one")]
+    TwoLines,
+}
+
+/// Reads 9 cells row by row, e.g. `"X.O.X...O"`: `X` and `O` (either case)
+/// for pieces, `.` or a space for an empty cell. `/`, `|` and line breaks
+/// only separate rows. The player to move follows from the piece counts.
+impl FromStr for XorTicTacToeState {
+    type Err = ParsePositionError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let cells = text
+            .chars()
+            .filter(|ch| !matches!(ch, '/' | '|' | '\n' | '\r'))
+            .map(|ch| match ch.to_ascii_uppercase() {
+                'X' => Ok(Some(Player::X)),
+                'O' => Ok(Some(Player::O)),
+                '.' | ' ' => Ok(None),
+                _ => Err(ParsePositionError::InvalidChar(ch)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let cells: [Option<Player>; 9] = cells
+            .as_slice()
+            .try_into()
+            .map_err(|_| ParsePositionError::WrongLength(cells.len()))?;
+        let count = |player| cells.iter().filter(|cell| **cell == Some(player)).count();
+        let (x, o) = (count(Player::X), count(Player::O));
+        let to_move = match x.checked_sub(o) {
+            Some(0) => Player::X,
+            Some(1) => Player::O,
+            _ => return Err(ParsePositionError::ImpossibleCounts { x, o }),
+        };
+        let owns_line = |player| {
+            WINNING_LINES
+                .iter()
+                .any(|line| line.iter().all(|&idx| cells[idx] == Some(player)))
+        };
+        if owns_line(Player::X) && owns_line(Player::O) {
+            return Err(ParsePositionError::TwoLines);
+        }
+        Ok(Self { cells, to_move })
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct PlaceMove {
     pub index: usize,
 }
 
 impl XorTicTacToeState {
+    /// XOR rule: completing a line of your own pieces loses, so the winner is the
+    /// opponent of whoever owns the line.
     pub fn winner(&self) -> Option<Player> {
+        self.line_owner().map(Player::opponent)
+    }
+
+    /// Player whose pieces fill a complete winning line, if any.
+    pub fn line_owner(&self) -> Option<Player> {
         for line in WINNING_LINES {
             if let (Some(a), Some(b), Some(c)) = (
                 self.cells[line[0]],
@@ -67,27 +138,114 @@ impl XorTicTacToeState {
     pub fn is_full(&self) -> bool {
         self.cells.iter().all(|cell| cell.is_some())
     }
+
+    pub fn empty_cells(&self) -> usize {
+        self.cells.iter().filter(|cell| cell.is_none()).count()
+    }
+
+    /// Negamax value of every legal move for the player to move (higher is better).
+    pub fn move_scores(&self, depth: u8) -> [Option<i32>; 9] {
+        self.move_scores_with_table(depth, &mut TranspositionTable::new())
+    }
+
+    /// Like [`Self::move_scores`], reusing `table` across calls. Cached values depend
+    /// on the search depth, so a table should only be shared between equal depths.
+    pub fn move_scores_with_table(
+        &self,
+        depth: u8,
+        table: &mut TranspositionTable,
+    ) -> [Option<i32>; 9] {
+        let mut scores = [None; 9];
+        if self.winner().is_some() || depth == 0 {
+            return scores;
+        }
+        for (mv, next) in self.successors() {
+            let score = -negamax(
+                &next,
+                depth - 1,
+                -i32::MAX,
+                i32::MAX,
+                self.to_move.opponent(),
+                table,
+            );
+            scores[mv.index] = Some(score);
+        }
+        scores
+    }
+
+    /// Best move for the player to move, preferring the lowest cell index on ties.
+    pub fn best_move(&self, depth: u8) -> Option<usize> {
+        self.best_move_with_table(depth, &mut TranspositionTable::new())
+    }
+
+    pub fn best_move_with_table(&self, depth: u8, table: &mut TranspositionTable) -> Option<usize> {
+        let scores = self.move_scores_with_table(depth, table);
+        (0..9)
+            .filter_map(|idx| scores[idx].map(|score| (idx, score)))
+            .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+            .map(|(idx, _)| idx)
+    }
+}
+
+/// Negamax with alpha-beta pruning, scored from `player`'s point of view.
+/// Only exact values are cached in `table`; cut-off bounds are not.
+pub fn negamax(
+    state: &XorTicTacToeState,
+    depth: u8,
+    mut alpha: i32,
+    beta: i32,
+    player: Player,
+    table: &mut TranspositionTable,
+) -> i32 {
+    if let Some(&score) = table.get(&(*state, player)) {
+        return score;
+    }
+    if let Some(winner) = state.winner() {
+        let score = WIN_SCORE + state.empty_cells() as i32;
+        return if winner == player { score } else { -score };
+    }
+    if state.is_full() || depth == 0 {
+        return 0;
+    }
+
+    let alpha_orig = alpha;
+    let mut best = -i32::MAX;
+    for (_, next) in state.successors() {
+        let score = -negamax(&next, depth - 1, -beta, -alpha, player.opponent(), table);
+        best = best.max(score);
+        alpha = alpha.max(score);
+        if alpha >= beta {
+            break;
+        }
+    }
+
+    if best > alpha_orig && best < beta {
+        table.insert((*state, player), best);
+    }
+    best
 }
 
 impl SearchState for XorTicTacToeState {
     type Move = PlaceMove;
 
+    /// X has won, which under the XOR rule means O completed a line.
     fn is_goal(&self) -> bool {
         matches!(self.winner(), Some(Player::X))
     }
 
+    /// O pieces still missing from the line X has left open that O is
+    /// closest to filling. Each needs a move of its own, so this never
+    /// overestimates; 100 once X can no longer win.
     fn heuristic(&self) -> u32 {
         match self.winner() {
             Some(Player::X) => 0,
             Some(Player::O) => 100,
-            None => {
-                let center_bonus = match self.cells[4] {
-                    Some(Player::X) => 0,
-                    Some(Player::O) => 4,
-                    None => 2,
-                };
-                center_bonus
-            }
+            None => WINNING_LINES
+                .iter()
+                .filter(|line| line.iter().all(|&idx| self.cells[idx] != Some(Player::X)))
+                .map(|line| line.iter().filter(|&&idx| self.cells[idx].is_none()).count() as u32)
+                .min()
+                .unwrap_or(100),
         }
     }
 
@@ -112,3 +270,75 @@ impl SearchState for XorTicTacToeState {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position_strings_infer_the_player_to_move() {
+        let state: XorTicTacToeState = "X.O/.X./...".parse().unwrap();
+        assert_eq!(state.cells[0], Some(Player::X));
+        assert_eq!(state.cells[2], Some(Player::O));
+        assert_eq!(state.to_move, Player::O);
+        assert_eq!("x o      ".parse::<XorTicTacToeState>().unwrap().to_move, Player::X);
+    }
+
+    #[test]
+    fn impossible_positions_are_rejected() {
+        assert_eq!(
+            "XX.......".parse::<XorTicTacToeState>(),
+            Err(ParsePositionError::ImpossibleCounts { x: 2, o: 0 })
+        );
+        assert_eq!(
+            "O........".parse::<XorTicTacToeState>(),
+            Err(ParsePositionError::ImpossibleCounts { x: 0, o: 1 })
+        );
+        assert_eq!("XXXOOO...".parse::<XorTicTacToeState>(), Err(ParsePositionError::TwoLines));
+        assert_eq!("X.O".parse::<XorTicTacToeState>(), Err(ParsePositionError::WrongLength(3)));
+        assert_eq!("X.O.Z....".parse::<XorTicTacToeState>(), Err(ParsePositionError::InvalidChar('Z')));
+    }
+
+    /// Plays `ai` with `depth`-ply search against every possible sequence of
+    /// opponent moves and returns whether the AI ever loses.
+    fn ai_can_lose(state: XorTicTacToeState, ai: Player, depth: u8) -> bool {
+        if let Some(winner) = state.winner() {
+            return winner != ai;
+        }
+        if state.is_full() {
+            return false;
+        }
+        if state.to_move == ai {
+            let index = state.best_move(depth).expect("a move while the board has room");
+            let mut next = state;
+            next.cells[index] = Some(ai);
+            next.to_move = ai.opponent();
+            ai_can_lose(next, ai, depth)
+        } else {
+            state.successors().into_iter().any(|(_, next)| ai_can_lose(next, ai, depth))
+        }
+    }
+
+    /// The centre no longer helps X: only O lines X has left open count.
+    #[test]
+    fn heuristic_counts_o_pieces_missing_from_open_lines() {
+        let mut state = XorTicTacToeState::default();
+        assert_eq!(state.heuristic(), 3);
+        state.cells[4] = Some(Player::X);
+        assert_eq!(state.heuristic(), 3);
+        state.cells[0] = Some(Player::O);
+        state.cells[1] = Some(Player::O);
+        assert_eq!(state.heuristic(), 1);
+        state.cells[2] = Some(Player::O);
+        assert!(state.is_goal());
+        assert_eq!(state.heuristic(), 0);
+    }
+
+    /// The Perfect difficulty searches to `FULL_DEPTH`, so it should at least
+    /// draw whichever side it plays.
+    #[test]
+    fn perfect_ai_never_loses_from_empty_board() {
+        assert!(!ai_can_lose(XorTicTacToeState::default(), Player::O, FULL_DEPTH));
+        assert!(!ai_can_lose(XorTicTacToeState::default(), Player::X, FULL_DEPTH));
+    }
+}
@@ -0,0 +1,310 @@
+use std::mem::size_of;
+
+/// Where an item sits in a [`FibHeap`]. Returned by [`FibHeap::insert`] and
+/// valid until that item is extracted; the slot is reused after that.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Handle(usize);
+
+struct Node<T> {
+    /// `None` once the item has been extracted and the slot is free.
+    item: Option<T>,
+    parent: Option<usize>,
+    child: Option<usize>,
+    // Neighbours in the circular list of roots or of siblings; a lone node
+    // points at itself
+    left: usize,
+    right: usize,
+    degree: usize,
+    /// Lost a child since it last became a child itself.
+    marked: bool,
+}
+
+/// Min-heap with O(1) amortized [`FibHeap::decrease_key`], so an item whose
+/// priority improves moves up in place instead of being pushed again.
+/// Nodes live in one vector and refer to each other by index.
+pub struct FibHeap<T: Ord> {
+    nodes: Vec<Node<T>>,
+    free: Vec<usize>,
+    min: Option<usize>,
+    len: usize,
+    /// Scratch space for [`FibHeap::consolidate`], kept between calls.
+    roots: Vec<usize>,
+    by_degree: Vec<Option<usize>>,
+}
+
+impl<T: Ord> Default for FibHeap<T> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            min: None,
+            len: 0,
+            roots: Vec::new(),
+            by_degree: Vec::new(),
+        }
+    }
+}
+
+impl<T: Ord> FibHeap<T> {
+    /// Bytes one item takes in the heap, links included.
+    pub const ENTRY_BYTES: usize = size_of::<Node<T>>();
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn insert(&mut self, item: T) -> Handle {
+        let node = Node {
+            item: Some(item),
+            parent: None,
+            child: None,
+            left: 0,
+            right: 0,
+            degree: 0,
+            marked: false,
+        };
+        let idx = match self.free.pop() {
+            Some(idx) => {
+                self.nodes[idx] = node;
+                idx
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        self.nodes[idx].left = idx;
+        self.nodes[idx].right = idx;
+        self.add_root(idx);
+        self.len += 1;
+        Handle(idx)
+    }
+
+    #[allow(dead_code)]
+    pub fn find_min(&self) -> Option<&T> {
+        self.min.map(|idx| self.item(idx))
+    }
+
+    pub fn extract_min(&mut self) -> Option<T> {
+        let min = self.min?;
+        // The children of the minimum join the roots
+        while let Some(child) = self.nodes[min].child {
+            let next = self.nodes[child].right;
+            self.nodes[min].child = (next != child).then_some(next);
+            self.unlink(child);
+            self.nodes[child].parent = None;
+            self.nodes[child].marked = false;
+            self.splice(min, child);
+        }
+        let next_root = self.nodes[min].right;
+        self.unlink(min);
+        self.min = (next_root != min).then_some(next_root);
+        if self.min.is_some() {
+            self.consolidate();
+        }
+        self.len -= 1;
+        self.free.push(min);
+        self.nodes[min].item.take()
+    }
+
+    /// Replaces the item at `handle` with `item`, which must not be greater.
+    ///
+    /// # Panics
+    ///
+    /// If `item` is greater than the item it replaces, or `handle` belongs
+    /// to an item already extracted.
+    pub fn decrease_key(&mut self, handle: Handle, item: T) {
+        let idx = handle.0;
+        let slot = self.nodes[idx].item.as_mut().expect("handle of an item still in the heap");
+        assert!(item <= *slot, "decrease_key must not increase the key");
+        *slot = item;
+        if let Some(parent) = self.nodes[idx].parent {
+            if self.item(idx) < self.item(parent) {
+                self.cut(idx, parent);
+                self.cascading_cut(parent);
+            }
+        }
+        let min = self.min.expect("a heap holding the item is not empty");
+        if self.item(idx) < self.item(min) {
+            self.min = Some(idx);
+        }
+    }
+
+    fn item(&self, idx: usize) -> &T {
+        self.nodes[idx].item.as_ref().expect("linked nodes hold an item")
+    }
+
+    /// Puts the lone node `idx` into the circular list right of `anchor`.
+    fn splice(&mut self, anchor: usize, idx: usize) {
+        let right = self.nodes[anchor].right;
+        self.nodes[idx].left = anchor;
+        self.nodes[idx].right = right;
+        self.nodes[anchor].right = idx;
+        self.nodes[right].left = idx;
+    }
+
+    /// Takes `idx` out of its circular list, leaving it a lone node.
+    fn unlink(&mut self, idx: usize) {
+        let (left, right) = (self.nodes[idx].left, self.nodes[idx].right);
+        self.nodes[left].right = right;
+        self.nodes[right].left = left;
+        self.nodes[idx].left = idx;
+        self.nodes[idx].right = idx;
+    }
+
+    /// Adds the lone node `idx` to the roots, updating the minimum.
+    fn add_root(&mut self, idx: usize) {
+        self.nodes[idx].parent = None;
+        self.nodes[idx].marked = false;
+        match self.min {
+            Some(min) => {
+                self.splice(min, idx);
+                if self.item(idx) < self.item(min) {
+                    self.min = Some(idx);
+                }
+            }
+            None => self.min = Some(idx),
+        }
+    }
+
+    /// Makes the lone root `child` a child of `parent`.
+    fn link(&mut self, child: usize, parent: usize) {
+        self.nodes[child].parent = Some(parent);
+        self.nodes[child].marked = false;
+        match self.nodes[parent].child {
+            Some(first) => self.splice(first, child),
+            None => self.nodes[parent].child = Some(child),
+        }
+        self.nodes[parent].degree += 1;
+    }
+
+    /// Links roots of equal degree until no two share one, then rebuilds
+    /// the root list and finds the new minimum.
+    fn consolidate(&mut self) {
+        let Some(start) = self.min else {
+            return;
+        };
+        let mut roots = std::mem::take(&mut self.roots);
+        let mut by_degree = std::mem::take(&mut self.by_degree);
+        roots.push(start);
+        let mut idx = self.nodes[start].right;
+        while idx != start {
+            roots.push(idx);
+            idx = self.nodes[idx].right;
+        }
+
+        for mut root in roots.drain(..) {
+            self.unlink(root);
+            loop {
+                let degree = self.nodes[root].degree;
+                if by_degree.len() <= degree {
+                    by_degree.resize(degree + 1, None);
+                }
+                match by_degree[degree].take() {
+                    Some(other) => {
+                        let (parent, child) = if self.item(other) < self.item(root) {
+                            (other, root)
+                        } else {
+                            (root, other)
+                        };
+                        self.link(child, parent);
+                        root = parent;
+                    }
+                    None => {
+                        by_degree[degree] = Some(root);
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.min = None;
+        for root in by_degree.drain(..).flatten() {
+            self.add_root(root);
+        }
+        self.roots = roots;
+        self.by_degree = by_degree;
+    }
+
+    /// Moves `idx` from `parent`'s children to the roots.
+    fn cut(&mut self, idx: usize, parent: usize) {
+        if self.nodes[parent].child == Some(idx) {
+            let next = self.nodes[idx].right;
+            self.nodes[parent].child = (next != idx).then_some(next);
+        }
+        self.unlink(idx);
+        self.nodes[parent].degree -= 1;
+        self.add_root(idx);
+    }
+
+    /// Marks `idx` after it lost a child, or cuts it too if it had already
+    /// lost one, and so on up the tree. Keeps trees bushy enough for the
+    /// logarithmic bound on degrees.
+    fn cascading_cut(&mut self, mut idx: usize) {
+        while let Some(parent) = self.nodes[idx].parent {
+            if !self.nodes[idx].marked {
+                self.nodes[idx].marked = true;
+                return;
+            }
+            self.cut(idx, parent);
+            idx = parent;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    #[test]
+    fn extracts_in_ascending_order() {
+        let mut values: Vec<u32> = (0..500).map(|value| value % 97).collect();
+        values.shuffle(&mut StdRng::seed_from_u64(1));
+        let mut heap = FibHeap::new();
+        for &value in &values {
+            heap.insert(value);
+        }
+        values.sort_unstable();
+
+        let mut extracted = Vec::new();
+        while let Some(value) = heap.extract_min() {
+            extracted.push(value);
+        }
+        assert_eq!(extracted, values);
+        assert!(heap.is_empty());
+    }
+
+    /// Decreases keys deep inside consolidated trees, so cuts and cascading
+    /// cuts both run, and checks the order still holds afterwards.
+    #[test]
+    fn decrease_key_moves_items_forward() {
+        let mut heap = FibHeap::new();
+        let handles: Vec<Handle> = (0..200u32).map(|value| heap.insert(value * 10 + 1000)).collect();
+        assert_eq!(heap.extract_min(), Some(1000));
+        for (value, &handle) in (1..200u32).zip(&handles[1..]).rev().step_by(3) {
+            heap.decrease_key(handle, value);
+        }
+        assert_eq!(heap.find_min(), Some(&1));
+
+        let mut previous = 0;
+        let mut count = 0;
+        while let Some(value) = heap.extract_min() {
+            assert!(value >= previous);
+            previous = value;
+            count += 1;
+        }
+        assert_eq!(count, 199);
+    }
+}
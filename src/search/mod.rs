@@ -1,3 +1,4 @@
+mod fibonacci_heap;
 pub mod solver;
 mod state;
-pub use state::SearchState;
+pub use state::{LocalSearchState, SearchState};
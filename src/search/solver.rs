@@ -1,28 +1,163 @@
 use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::mem::size_of;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use super::fibonacci_heap::{FibHeap, Handle};
 use super::SearchState;
 
+/// Parent, move from the parent, best known path cost, and the open-list
+/// entry while one is waiting, for every discovered state.
+pub type CameFrom<S> = HashMap<S, (Option<S>, Option<<S as SearchState>::Move>, u32, Option<Handle>)>;
+
+/// Expansions after which the puzzle screens give up on an A* search. Far
+/// above anything the built-in puzzles need, but it stops a pathological
+/// start from filling memory for the full hour the clock allows.
+pub const DEFAULT_NODE_LIMIT: usize = 2_000_000;
+
+/// Search steps between two looks at a [`CancellationToken`]; frequent
+/// enough to stop within a blink, rare enough to cost nothing.
+const CANCEL_CHECK_INTERVAL: usize = 1_000;
+
+/// Shared flag that asks a search on another thread to stop. Clones share
+/// the flag, so the UI keeps one and hands the other to the search.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, AtomicOrdering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(AtomicOrdering::Relaxed)
+    }
+}
+
+/// How a search ended.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchOutcome {
+    Solved,
+    /// Every reachable state was searched (or every restart used up)
+    /// without reaching the goal.
+    #[default]
+    NoSolution,
+    /// Stopped after [`AstarConfig::node_limit`] expansions.
+    NodeLimit,
+    /// Stopped by the one-hour wall clock.
+    TimedOut,
+    Cancelled,
+}
+
+impl SearchOutcome {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SearchOutcome::Solved => "solved",
+            SearchOutcome::NoSolution => "no solution",
+            SearchOutcome::NodeLimit => "node limit reached",
+            SearchOutcome::TimedOut => "timed out",
+            SearchOutcome::Cancelled => "cancelled",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SearchReport<S: SearchState> {
     pub path: Vec<S>,
+    /// Move taken between consecutive `path` states (`path.len() - 1` entries).
+    pub actions: Vec<S::Move>,
     pub expanded_nodes: usize,
     pub visited_states: usize,
     pub goal_found: bool,
+    /// Why the search stopped; tells a node limit or timeout apart from a
+    /// search that proved there is no solution.
+    pub outcome: SearchOutcome,
     pub elapsed: Duration,
+    /// Largest estimated size of the visited map plus the open list, in bytes.
+    /// Counts the entries themselves, not heap data they point to.
+    pub peak_memory_bytes: usize,
+    /// Most entries the open list held at once.
+    pub max_open_size: usize,
+    /// Moves on the solution path, `path.len() - 1`; 0 without a solution.
+    pub solution_depth: usize,
+    /// The b* solving N = b* + b*² + … + b*^d, where N counts the nodes
+    /// generated besides the start and d is the solution depth. Only A*-style
+    /// searches fill it in; 0 otherwise.
+    pub effective_branching_factor: f64,
+    /// Every (parent, child) edge A* discovered, in order. Only filled when
+    /// [`AstarConfig::record_edges`] is set.
+    pub edges: Vec<(S, S)>,
+    /// When A* stopped without the goal, the path to the expanded state
+    /// that looked closest to it (lowest h): the best incomplete answer.
+    /// Empty once the goal is found, and for the other searches.
+    pub partial_path: Vec<S>,
+    /// Path cost of [`SearchReport::partial_path`]; 0 when it is empty.
+    pub partial_path_cost: u32,
+}
+
+impl<S: SearchState> SearchReport<S> {
+    /// Sum of [`SearchState::cost`] along the solution; equals the number of
+    /// moves when every move costs 1.
+    pub fn path_cost(&self) -> u32 {
+        self.path
+            .iter()
+            .zip(&self.actions)
+            .map(|(state, mv)| state.cost(mv))
+            .sum()
+    }
 }
 
 impl<S: SearchState> Default for SearchReport<S> {
     fn default() -> Self {
         Self {
             path: Vec::new(),
+            actions: Vec::new(),
             expanded_nodes: 0,
             visited_states: 0,
             goal_found: false,
+            outcome: SearchOutcome::NoSolution,
             elapsed: Duration::default(),
+            peak_memory_bytes: 0,
+            max_open_size: 0,
+            solution_depth: 0,
+            effective_branching_factor: 0.0,
+            edges: Vec::new(),
+            partial_path: Vec::new(),
+            partial_path_cost: 0,
+        }
+    }
+}
+
+/// Solves N = b + b² + … + b^d for b by bisection. A depth of 0 has no
+/// branching to measure and yields 0.
+pub fn effective_branching_factor(generated: usize, depth: usize) -> f64 {
+    if depth == 0 {
+        return 0.0;
+    }
+    let target = generated as f64;
+    let generated_with = |b: f64| (1..=depth).try_fold(0.0, |sum: f64, power| {
+        let sum = sum + b.powi(power as i32);
+        // Past the target already; stop before the powers overflow
+        if sum > target { None } else { Some(sum) }
+    });
+    let (mut low, mut high) = (0.0, target.max(1.0));
+    for _ in 0..100 {
+        let mid = (low + high) / 2.0;
+        match generated_with(mid) {
+            Some(_) => low = mid,
+            None => high = mid,
         }
     }
+    (low + high) / 2.0
 }
 
 #[derive(Clone)]
@@ -30,29 +165,24 @@ struct FrontierEntry<S: SearchState> {
     state: S,
     g_cost: u32,
     h_cost: u32,
-}
-
-impl<S: SearchState> FrontierEntry<S> {
-    fn f_cost(&self) -> u32 {
-        self.g_cost + self.h_cost
-    }
+    /// Priority on the open list: g + h for A*, h alone for greedy search.
+    f_cost: u32,
 }
 
 impl<S: SearchState> Eq for FrontierEntry<S> {}
 
 impl<S: SearchState> PartialEq for FrontierEntry<S> {
     fn eq(&self, other: &Self) -> bool {
-        self.f_cost() == other.f_cost() && self.h_cost == other.h_cost
+        self.f_cost == other.f_cost && self.h_cost == other.h_cost
     }
 }
 
 impl<S: SearchState> Ord for FrontierEntry<S> {
+    /// Lowest f first, ties going to the entry with the lower h.
     fn cmp(&self, other: &Self) -> Ordering {
-        // Reverse ordering because BinaryHeap is a max-heap by default.
-        other
-            .f_cost()
-            .cmp(&self.f_cost())
-            .then_with(|| other.h_cost.cmp(&self.h_cost))
+        self.f_cost
+            .cmp(&other.f_cost)
+            .then_with(|| self.h_cost.cmp(&other.h_cost))
     }
 }
 
@@ -62,96 +192,697 @@ impl<S: SearchState> PartialOrd for FrontierEntry<S> {
     }
 }
 
+/// Bytes held by `visited` map entries and `open` frontier entries of an A* run.
+fn search_memory<S: SearchState>(visited: usize, open: usize) -> usize {
+    visited * size_of::<(S, (Option<S>, Option<S::Move>, u32, Option<Handle>))>()
+        + open * FibHeap::<FrontierEntry<S>>::ENTRY_BYTES
+}
+
+/// Tuning knobs for an A* run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AstarConfig {
+    /// Stop with `goal_found: false` once this many nodes have been expanded.
+    pub node_limit: Option<usize>,
+    /// Treat every heuristic as 0, which turns A* into uniform-cost search.
+    pub ignore_heuristic: bool,
+    /// Order the frontier by h(n) alone and never reopen a discovered state,
+    /// which turns A* into greedy best-first search.
+    pub ignore_path_cost: bool,
+    /// Keep every discovered edge in [`SearchReport::edges`] for [`to_dot`].
+    /// Off by default: the list grows with every state the search reaches.
+    pub record_edges: bool,
+}
+
+impl AstarConfig {
+    /// Plain A* that stops after `max_nodes` expansions.
+    pub fn with_node_limit(max_nodes: usize) -> Self {
+        Self {
+            node_limit: Some(max_nodes),
+            ..Self::default()
+        }
+    }
+}
+
 pub fn astar<S: SearchState>(start: S) -> SearchReport<S> {
-    const MAX_TIME: Duration = Duration::from_secs(3600); // 1 hour timeout
-    
-    let start_time = Instant::now();
-    let mut open = BinaryHeap::new();
-    let mut came_from: HashMap<S, (Option<S>, u32)> = HashMap::new();
-
-    open.push(FrontierEntry {
-        g_cost: 0,
-        h_cost: start.heuristic(),
-        state: start.clone(),
-    });
-    came_from.insert(start.clone(), (None, 0));
+    astar_with_config(start, AstarConfig::default(), None, None)
+}
+
+/// [`astar`] for a background thread: gives up with `goal_found: false`
+/// shortly after `cancel` fires or as soon as `config` says to, and keeps
+/// `progress` at the expanded-node count so another thread can watch the
+/// search.
+pub fn astar_cancellable<S: SearchState>(
+    start: S,
+    config: AstarConfig,
+    cancel: &CancellationToken,
+    progress: &AtomicUsize,
+) -> SearchReport<S> {
+    astar_with_config(start, config, Some(cancel), Some(progress))
+}
+
+/// [`astar`] run with `config`'s limits and tuning.
+pub fn astar_with_limits<S: SearchState>(start: S, config: AstarConfig) -> SearchReport<S> {
+    astar_with_config(start, config, None, None)
+}
+
+/// [`astar`] that gives up with [`SearchOutcome::NodeLimit`] after `max_nodes` expansions.
+pub fn astar_limited<S: SearchState>(start: S, max_nodes: usize) -> SearchReport<S> {
+    astar_with_limits(start, AstarConfig::with_node_limit(max_nodes))
+}
+
+/// Uniform-cost (Dijkstra) search: A* ordering the frontier by g(n) alone.
+/// Finds the same path length as [`astar`] but, without a heuristic to steer
+/// it, usually expands far more nodes.
+pub fn uniform_cost<S: SearchState>(start: S) -> SearchReport<S> {
+    astar_with_config(
+        start,
+        AstarConfig {
+            ignore_heuristic: true,
+            ..AstarConfig::default()
+        },
+        None,
+        None,
+    )
+}
 
-    let mut expanded = 0usize;
+/// Greedy best-first search: always expands the node that looks closest to
+/// the goal, ignoring how far it is from the start. Usually expands far fewer
+/// nodes than [`astar`], but the path it returns is not guaranteed optimal.
+pub fn greedy_best_first<S: SearchState>(start: S) -> SearchReport<S> {
+    astar_with_config(
+        start,
+        AstarConfig {
+            ignore_path_cost: true,
+            ..AstarConfig::default()
+        },
+        None,
+        None,
+    )
+}
+
+fn astar_with_config<S: SearchState>(
+    start: S,
+    config: AstarConfig,
+    cancel: Option<&CancellationToken>,
+    progress: Option<&AtomicUsize>,
+) -> SearchReport<S> {
+    const MAX_TIME: Duration = Duration::from_secs(3600); // 1 hour timeout
 
-    while let Some(entry) = open.pop() {
-        // Check timeout (1 hour max)
-        if start_time.elapsed() >= MAX_TIME {
+    let mut search = AStarSearch::with_config(start, config);
+    let mut steps = 0usize;
+    loop {
+        // Check cancellation, timeout (1 hour max) and the node limit
+        let cancelled = steps.is_multiple_of(CANCEL_CHECK_INTERVAL) && cancel.is_some_and(CancellationToken::is_cancelled);
+        steps += 1;
+        let stopped = if cancelled {
+            Some(SearchOutcome::Cancelled)
+        } else if config.node_limit.is_some_and(|limit| search.expanded >= limit) {
+            Some(SearchOutcome::NodeLimit)
+        } else if search.start_time.elapsed() >= MAX_TIME {
+            Some(SearchOutcome::TimedOut)
+        } else {
+            None
+        };
+        if let Some(outcome) = stopped {
             return SearchReport {
-                path: Vec::new(),
-                expanded_nodes: expanded,
-                visited_states: came_from.len(),
-                goal_found: false,
-                elapsed: start_time.elapsed(),
+                outcome,
+                ..search.report(None)
             };
         }
-        
-        let current_state = entry.state;
+        match search.step() {
+            StepResult::Expanded => {
+                if let Some(progress) = progress {
+                    progress.store(search.expanded, AtomicOrdering::Relaxed);
+                }
+            }
+            StepResult::Finished(report) => {
+                debug_assert!(verify_solution(&report.path), "A* returned a path with an illegal step");
+                return report;
+            }
+        }
+    }
+}
+
+/// What a single [`AStarSearch::step`] did.
+pub enum StepResult<S: SearchState> {
+    /// One node came off the frontier and its successors went on; the search goes on.
+    Expanded,
+    /// The goal came off the frontier, or the frontier ran dry: the final report.
+    Finished(SearchReport<S>),
+}
 
-        let (_, recorded_cost) = came_from
-            .get(&current_state)
-            .cloned()
-            .unwrap_or((None, u32::MAX));
+/// A* as a resumable search: each [`AStarSearch::step`] pops one frontier node,
+/// so a UI can show the search as it runs. [`astar`] simply steps it to the end.
+pub struct AStarSearch<S: SearchState> {
+    /// Each waiting state appears once; a cheaper path to it lowers its
+    /// entry in place.
+    open: FibHeap<FrontierEntry<S>>,
+    came_from: CameFrom<S>,
+    config: AstarConfig,
+    expanded: usize,
+    start_time: Instant,
+    max_open: usize,
+    peak_memory: usize,
+    /// Last node taken off the open list with its g and h costs.
+    current: Option<(S, u32, u32)>,
+    /// Expanded node with the lowest h so far, and that h.
+    best_so_far: Option<(S, u32)>,
+    edges: Vec<(S, S)>,
+}
 
-        if entry.g_cost > recorded_cost {
-            continue;
+impl<S: SearchState> std::fmt::Debug for AStarSearch<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AStarSearch")
+            .field("open", &self.open.len())
+            .field("visited", &self.came_from.len())
+            .field("expanded", &self.expanded)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: SearchState> AStarSearch<S> {
+    pub fn new(start: S) -> Self {
+        Self::with_config(start, AstarConfig::default())
+    }
+
+    /// A search tuned by `config`. Its node limit is left to the caller, who
+    /// decides when to stop stepping.
+    pub fn with_config(start: S, config: AstarConfig) -> Self {
+        let mut search = Self {
+            open: FibHeap::new(),
+            came_from: HashMap::new(),
+            config,
+            expanded: 0,
+            start_time: Instant::now(),
+            max_open: 0,
+            peak_memory: 0,
+            current: None,
+            best_so_far: None,
+            edges: Vec::new(),
+        };
+        let handle = search.open.insert(search.frontier_entry(start.clone(), 0));
+        search.came_from.insert(start, (None, None, 0, Some(handle)));
+        search.max_open = search.open.len();
+        search.peak_memory = search_memory::<S>(search.came_from.len(), search.open.len());
+        search
+    }
+
+    fn frontier_entry(&self, state: S, g_cost: u32) -> FrontierEntry<S> {
+        let h_cost = if self.config.ignore_heuristic { 0 } else { state.heuristic() };
+        let f_cost = if self.config.ignore_path_cost { h_cost } else { g_cost + h_cost };
+        FrontierEntry {
+            state,
+            g_cost,
+            h_cost,
+            f_cost,
         }
+    }
 
-        if current_state.is_goal() {
-            return SearchReport {
-                path: reconstruct_path(&came_from, current_state),
-                expanded_nodes: expanded,
-                visited_states: came_from.len(),
-                goal_found: true,
-                elapsed: start_time.elapsed(),
-            };
+    /// Expands one node.
+    pub fn step(&mut self) -> StepResult<S> {
+        let Some(entry) = self.open.extract_min() else {
+            return StepResult::Finished(self.report(None));
+        };
+        if let Some((_, _, _, handle)) = self.came_from.get_mut(&entry.state) {
+            *handle = None;
         }
 
-        expanded += 1;
+        let current_state = entry.state;
+        self.current = Some((current_state.clone(), entry.g_cost, entry.h_cost));
+        if self.best_so_far.as_ref().is_none_or(|(_, best_h)| entry.h_cost < *best_h) {
+            self.best_so_far = Some((current_state.clone(), entry.h_cost));
+        }
+        if current_state.is_goal() {
+            return StepResult::Finished(self.report(Some(current_state)));
+        }
 
-        for (_, successor) in current_state.successors() {
-            let tentative_cost = entry.g_cost.saturating_add(1);
-            let needs_update = match came_from.get(&successor) {
-                Some((_, known_cost)) => tentative_cost < *known_cost,
+        self.expanded += 1;
+        if current_state.is_dead_end() {
+            return StepResult::Expanded;
+        }
+        for (mv, successor) in current_state.successors() {
+            let tentative_cost = entry.g_cost.saturating_add(current_state.cost(&mv));
+            let known = self
+                .came_from
+                .get(&successor)
+                .map(|(_, _, known_cost, handle)| (*known_cost, *handle));
+            let needs_update = match known {
+                Some(_) if self.config.ignore_path_cost => false,
+                Some((known_cost, _)) => tentative_cost < known_cost,
                 None => true,
             };
-
             if needs_update {
-                came_from.insert(
-                    successor.clone(),
-                    (Some(current_state.clone()), tentative_cost),
+                if self.config.record_edges {
+                    self.edges.push((current_state.clone(), successor.clone()));
+                }
+                // A waiting state moves up the open list; a closed one is reopened
+                let entry = self.frontier_entry(successor.clone(), tentative_cost);
+                let handle = match known.and_then(|(_, handle)| handle) {
+                    Some(handle) => {
+                        self.open.decrease_key(handle, entry);
+                        handle
+                    }
+                    None => self.open.insert(entry),
+                };
+                self.came_from.insert(
+                    successor,
+                    (Some(current_state.clone()), Some(mv), tentative_cost, Some(handle)),
                 );
-                open.push(FrontierEntry {
-                    h_cost: successor.heuristic(),
-                    g_cost: tentative_cost,
-                    state: successor,
-                });
             }
         }
+        self.max_open = self.max_open.max(self.open.len());
+        self.peak_memory = self
+            .peak_memory
+            .max(search_memory::<S>(self.came_from.len(), self.open.len()));
+        StepResult::Expanded
     }
 
+    /// The report for a search ending at `goal`, or a failed one for `None`.
+    fn report(&self, goal: Option<S>) -> SearchReport<S> {
+        let (path, actions) = match goal {
+            Some(goal) => reconstruct_path(&self.came_from, goal),
+            None => (Vec::new(), Vec::new()),
+        };
+        let depth = actions.len();
+        let goal_found = !path.is_empty();
+        let (partial_path, partial_path_cost) = match &self.best_so_far {
+            Some((best, _)) if !goal_found => {
+                let cost = self.came_from.get(best).map_or(0, |(_, _, cost, _)| *cost);
+                (reconstruct_path(&self.came_from, best.clone()).0, cost)
+            }
+            _ => (Vec::new(), 0),
+        };
+        SearchReport {
+            goal_found,
+            outcome: if goal_found {
+                SearchOutcome::Solved
+            } else {
+                SearchOutcome::NoSolution
+            },
+            path,
+            actions,
+            expanded_nodes: self.expanded,
+            visited_states: self.came_from.len(),
+            elapsed: self.start_time.elapsed(),
+            peak_memory_bytes: self.peak_memory,
+            max_open_size: self.max_open,
+            solution_depth: depth,
+            effective_branching_factor: if depth > 0 {
+                effective_branching_factor(self.came_from.len() - 1, depth)
+            } else {
+                0.0
+            },
+            edges: self.edges.clone(),
+            partial_path,
+            partial_path_cost,
+        }
+    }
+
+    /// The node taken off the frontier by the latest step, with its g and h costs.
+    pub fn current(&self) -> Option<&(S, u32, u32)> {
+        self.current.as_ref()
+    }
+
+    /// Nodes waiting on the open list.
+    pub fn frontier_len(&self) -> usize {
+        self.open.len()
+    }
+
+    /// Nodes expanded so far: the closed list.
+    pub fn closed_len(&self) -> usize {
+        self.expanded
+    }
+}
+
+/// What one bounded depth-first pass of [`ida_star`] ended with.
+enum IdaProbe {
+    Found,
+    /// Smallest f = g + h seen past the bound; `u32::MAX` when nothing was cut off.
+    Exceeded(u32),
+    Stopped(SearchOutcome),
+}
+
+/// The current path of an [`ida_star`] run and its counters.
+struct IdaSearch<S: SearchState> {
+    path: Vec<S>,
+    actions: Vec<S::Move>,
+    max_nodes: usize,
+    expanded: usize,
+    generated: usize,
+    longest_path: usize,
+    start_time: Instant,
+}
+
+impl<S: SearchState> IdaSearch<S> {
+    fn probe(&mut self, g_cost: u32, bound: u32) -> IdaProbe {
+        const MAX_TIME: Duration = Duration::from_secs(3600);
+
+        let current = self.path.last().expect("path starts with the start state").clone();
+        let f_cost = g_cost.saturating_add(current.heuristic());
+        if f_cost > bound {
+            return IdaProbe::Exceeded(f_cost);
+        }
+        if current.is_goal() {
+            return IdaProbe::Found;
+        }
+        if self.expanded >= self.max_nodes {
+            return IdaProbe::Stopped(SearchOutcome::NodeLimit);
+        }
+        if self.start_time.elapsed() >= MAX_TIME {
+            return IdaProbe::Stopped(SearchOutcome::TimedOut);
+        }
+
+        self.expanded += 1;
+        if current.is_dead_end() {
+            return IdaProbe::Exceeded(u32::MAX);
+        }
+        let mut next_bound = u32::MAX;
+        for (mv, successor) in current.successors() {
+            // Only the current path is remembered, so only cycles along it are cut
+            if self.path.contains(&successor) {
+                continue;
+            }
+            self.generated += 1;
+            let step_cost = current.cost(&mv);
+            self.path.push(successor);
+            self.actions.push(mv);
+            self.longest_path = self.longest_path.max(self.path.len());
+            match self.probe(g_cost.saturating_add(step_cost), bound) {
+                IdaProbe::Exceeded(f_cost) => next_bound = next_bound.min(f_cost),
+                done => return done,
+            }
+            self.path.pop();
+            self.actions.pop();
+        }
+        IdaProbe::Exceeded(next_bound)
+    }
+}
+
+/// Iterative-deepening A*: depth-first passes cut off at an f = g + h bound,
+/// raised after each pass to the smallest f that went over it. Finds a path
+/// as cheap as [`astar`]'s while holding only the current path, at the cost
+/// of expanding shallow nodes again on every pass. `visited_states` counts
+/// generated nodes, repeats included, since no visited set is kept. Gives up
+/// with [`SearchOutcome::NodeLimit`] after `max_nodes` expansions.
+pub fn ida_star<S: SearchState>(start: S, max_nodes: usize) -> SearchReport<S> {
+    let mut bound = start.heuristic();
+    let mut search = IdaSearch {
+        path: vec![start],
+        actions: Vec::new(),
+        max_nodes,
+        expanded: 0,
+        generated: 0,
+        longest_path: 1,
+        start_time: Instant::now(),
+    };
+    let outcome = loop {
+        match search.probe(0, bound) {
+            IdaProbe::Found => break SearchOutcome::Solved,
+            IdaProbe::Stopped(outcome) => break outcome,
+            IdaProbe::Exceeded(u32::MAX) => break SearchOutcome::NoSolution,
+            IdaProbe::Exceeded(next_bound) => bound = next_bound,
+        }
+    };
+
+    let goal_found = outcome == SearchOutcome::Solved;
+    let (path, actions) = if goal_found {
+        (search.path, search.actions)
+    } else {
+        (Vec::new(), Vec::new())
+    };
+    let depth = actions.len();
     SearchReport {
-        path: Vec::new(),
-        expanded_nodes: expanded,
-        visited_states: came_from.len(),
-        goal_found: false,
-        elapsed: start_time.elapsed(),
+        path,
+        actions,
+        expanded_nodes: search.expanded,
+        visited_states: search.generated + 1,
+        goal_found,
+        outcome,
+        elapsed: search.start_time.elapsed(),
+        peak_memory_bytes: search.longest_path * size_of::<S>(),
+        max_open_size: 0,
+        solution_depth: depth,
+        effective_branching_factor: if depth > 0 {
+            effective_branching_factor(search.generated, depth)
+        } else {
+            0.0
+        },
+        edges: Vec::new(),
+        partial_path: Vec::new(),
+        partial_path_cost: 0,
     }
 }
 
+/// Fewest moves from `start` to a goal, by breadth-first search, or `None`
+/// when no goal is reachable. Ignores [`SearchState::cost`] and keeps every
+/// reached state in memory, so it is only a reference to check [`astar`]
+/// against on small puzzles.
+#[cfg(test)]
+pub fn solve_optimal_length<S: SearchState>(start: S) -> Option<usize> {
+    use std::collections::VecDeque;
+
+    if start.is_goal() {
+        return Some(0);
+    }
+    let mut depths = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(state) = queue.pop_front() {
+        let depth = depths[&state] + 1;
+        for (_, next) in state.successors() {
+            // Testing on discovery spares the whole last layer
+            if next.is_goal() {
+                return Some(depth);
+            }
+            if !depths.contains_key(&next) {
+                depths.insert(next.clone(), depth);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Whether every state on `path` is one move from the one before it, i.e.
+/// appears among its predecessor's successors. An empty path passes.
+pub fn verify_solution<S: SearchState>(path: &[S]) -> bool {
+    path.windows(2)
+        .all(|pair| pair[0].successors().iter().any(|(_, next)| *next == pair[1]))
+}
+
 fn reconstruct_path<S: SearchState>(
-    came_from: &HashMap<S, (Option<S>, u32)>,
+    came_from: &CameFrom<S>,
     mut current: S,
-) -> Vec<S> {
+) -> (Vec<S>, Vec<S::Move>) {
     let mut path = vec![current.clone()];
-    while let Some((Some(parent), _)) = came_from.get(&current) {
+    let mut actions = Vec::new();
+    while let Some((Some(parent), mv, _, _)) = came_from.get(&current) {
+        if let Some(mv) = mv {
+            actions.push(mv.clone());
+        }
         current = parent.clone();
         path.push(current.clone());
     }
     path.reverse();
-    path
+    actions.reverse();
+    (path, actions)
+}
+
+/// Graphviz DOT digraph of a recorded search: one box per state, labelled
+/// with its `Display` output, and one arrow per discovered edge. States and
+/// edges on `solution` are drawn in red. Render with `dot -Tsvg search.dot`.
+pub fn to_dot<S: SearchState + Display>(edges: &[(S, S)], solution: &[S]) -> String {
+    let on_path: HashSet<&S> = solution.iter().collect();
+    let path_edges: HashSet<(&S, &S)> = solution.windows(2).map(|pair| (&pair[0], &pair[1])).collect();
+    let mut dot = String::from("digraph search {\n    node [shape=box, fontname=\"monospace\"];\n");
+
+    let mut ids: HashMap<&S, usize> = HashMap::new();
+    for state in edges.iter().flat_map(|(parent, child)| [parent, child]).chain(solution) {
+        if ids.contains_key(state) {
+            continue;
+        }
+        let id = ids.len();
+        ids.insert(state, id);
+        // `\l` ends a left-aligned line inside a DOT label
+        let label: String = state
+            .to_string()
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .lines()
+            .map(|line| format!("{}\\l", line.trim_end()))
+            .collect();
+        let color = if on_path.contains(state) { ", color=red, fontcolor=red" } else { "" };
+        dot.push_str(&format!("    n{} [label=\"{}\"{}];\n", id, label, color));
+    }
+
+    let mut drawn = HashSet::new();
+    for (parent, child) in edges {
+        let (from, to) = (ids[parent], ids[child]);
+        if !drawn.insert((from, to)) {
+            continue;
+        }
+        let color = if path_edges.contains(&(parent, child)) { " [color=red, penwidth=2]" } else { "" };
+        dot.push_str(&format!("    n{} -> n{}{};\n", from, to, color));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzles::EightPuzzleState;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// The frontier only orders entries by f and h, never by the state, so
+    /// ties must not cost A* its optimality.
+    #[test]
+    fn astar_paths_are_as_short_as_breadth_first_ones() {
+        let mut rng = StdRng::seed_from_u64(11);
+        for _ in 0..100 {
+            let start = EightPuzzleState::random_solvable(&mut rng);
+            let report = astar(start);
+            assert_eq!(Some(report.actions.len()), solve_optimal_length(start), "from {:?}", start);
+        }
+    }
+
+    #[test]
+    fn cancelled_search_stops_without_a_path() {
+        let start = EightPuzzleState::random_solvable(&mut StdRng::seed_from_u64(3));
+        let token = CancellationToken::new();
+        token.clone().cancel();
+        let report = astar_cancellable(start, AstarConfig::default(), &token, &AtomicUsize::new(0));
+        assert_eq!(report.outcome, SearchOutcome::Cancelled);
+        assert!(!report.goal_found && report.path.is_empty());
+        assert_eq!(report.expanded_nodes, 0);
+    }
+
+    #[test]
+    fn node_limit_keeps_the_path_to_the_closest_state() {
+        let start = EightPuzzleState::random_solvable(&mut StdRng::seed_from_u64(3));
+        let report = astar_limited(start, 20);
+        assert_eq!(report.outcome, SearchOutcome::NodeLimit);
+        assert_eq!(report.partial_path.first(), Some(&start));
+        assert!(verify_solution(&report.partial_path));
+        let closest = report.partial_path.last().expect("the start is always expanded");
+        assert!(closest.heuristic() < start.heuristic());
+        assert_eq!(report.partial_path_cost as usize, report.partial_path.len() - 1);
+
+        assert!(astar(start).partial_path.is_empty());
+    }
+
+    /// Without a heuristic the search still finds an optimal path, just with
+    /// more work than A* with Manhattan distance.
+    #[test]
+    fn uniform_cost_matches_astar_path_length_but_expands_more() {
+        for seed in 0..3 {
+            let start = EightPuzzleState::random_solvable(&mut StdRng::seed_from_u64(seed));
+            let guided = astar(start);
+            let blind = uniform_cost(start);
+            assert!(guided.goal_found && blind.goal_found);
+            assert_eq!(blind.path.len(), guided.path.len());
+            assert!(blind.expanded_nodes > guided.expanded_nodes);
+        }
+    }
+
+    /// The textbook example: 52 nodes generated for a solution at depth 5.
+    #[test]
+    fn effective_branching_factor_matches_textbook_example() {
+        assert!((effective_branching_factor(52, 5) - 1.92).abs() < 0.01);
+        assert_eq!(effective_branching_factor(10, 0), 0.0);
+    }
+
+    /// Greedy search trades optimality for speed: summed over a handful of
+    /// boards it takes longer paths than A* but expands fewer nodes.
+    #[test]
+    fn greedy_takes_longer_paths_with_fewer_expansions() {
+        let (mut greedy_moves, mut astar_moves) = (0, 0);
+        let (mut greedy_nodes, mut astar_nodes) = (0, 0);
+        for seed in 0..5 {
+            let start = EightPuzzleState::random_solvable(&mut StdRng::seed_from_u64(seed));
+            let optimal = astar(start);
+            let greedy = greedy_best_first(start);
+            assert!(optimal.goal_found && greedy.goal_found);
+            assert!(greedy.actions.len() >= optimal.actions.len());
+            greedy_moves += greedy.actions.len();
+            astar_moves += optimal.actions.len();
+            greedy_nodes += greedy.expanded_nodes;
+            astar_nodes += optimal.expanded_nodes;
+        }
+        assert!(greedy_moves > astar_moves);
+        assert!(greedy_nodes < astar_nodes);
+    }
+
+    #[test]
+    fn ida_star_matches_astar_path_length() {
+        for seed in 0..3 {
+            let start = EightPuzzleState::random_solvable(&mut StdRng::seed_from_u64(seed));
+            let optimal = astar(start);
+            let deepening = ida_star(start, DEFAULT_NODE_LIMIT);
+            assert_eq!(deepening.outcome, SearchOutcome::Solved);
+            assert_eq!(deepening.actions.len(), optimal.actions.len());
+            assert_eq!(deepening.path.len(), deepening.actions.len() + 1);
+            assert!(deepening.path.last().unwrap().is_goal());
+        }
+        let start = EightPuzzleState::random_solvable(&mut StdRng::seed_from_u64(0));
+        assert_eq!(ida_star(start, 5).outcome, SearchOutcome::NodeLimit);
+    }
+
+    #[test]
+    fn recorded_edges_render_with_the_solution_in_red() {
+        let start = EightPuzzleState::random_solvable(&mut StdRng::seed_from_u64(2));
+        assert!(astar(start).edges.is_empty());
+        let config = AstarConfig {
+            record_edges: true,
+            ..AstarConfig::default()
+        };
+        let report = astar_with_limits(start, config);
+        // A state reached again by a cheaper path adds a second edge into it
+        assert!(report.edges.len() >= report.visited_states - 1);
+        let dot = to_dot(&report.edges, &report.path);
+        assert!(dot.starts_with("digraph search {") && dot.ends_with("}\n"));
+        assert_eq!(dot.matches("color=red, penwidth").count(), report.actions.len());
+        assert_eq!(dot.matches("fontcolor=red").count(), report.path.len());
+        assert!(dot.matches(" -> ").count() <= report.edges.len());
+    }
+
+    /// Three muddy cells on the straight route cost more than the dry detour.
+    #[test]
+    fn weighted_moves_steer_the_search_around_mud() {
+        use crate::puzzles::maze::{Cell, MazeState};
+        let grid = ["#######", "#S~~~E#", "# ### #", "#     #", "#######"]
+            .iter()
+            .map(|row| {
+                row.chars()
+                    .map(|ch| match ch {
+                        '#' => Cell::Wall,
+                        '~' => Cell::Muddy,
+                        'S' => Cell::Start,
+                        'E' => Cell::End,
+                        _ => Cell::Open,
+                    })
+                    .collect()
+            })
+            .collect();
+        let start = MazeState {
+            grid,
+            player: (1, 1),
+            goal: (1, 5),
+        };
+        for report in [astar(start.clone()), ida_star(start, DEFAULT_NODE_LIMIT)] {
+            assert_eq!(report.actions.len(), 8);
+            assert_eq!(report.path_cost(), 8);
+        }
+    }
+
+    /// Hitting the cap is reported as such, not as an unsolvable board.
+    #[test]
+    fn node_limit_stops_with_its_own_outcome() {
+        let start = EightPuzzleState::random_solvable(&mut StdRng::seed_from_u64(0));
+        let capped = astar_limited(start, 5);
+        assert!(!capped.goal_found);
+        assert_eq!(capped.outcome, SearchOutcome::NodeLimit);
+        assert_eq!(capped.expanded_nodes, 5);
+        assert_eq!(astar_limited(start, DEFAULT_NODE_LIMIT).outcome, SearchOutcome::Solved);
+    }
 }
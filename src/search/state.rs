@@ -1,3 +1,4 @@
+use rand::Rng;
 use std::hash::Hash;
 
 /// Trait implemented by every puzzle state that can be explored by A*.
@@ -7,4 +8,29 @@ pub trait SearchState: Clone + Eq + Hash {
     fn is_goal(&self) -> bool;
     fn heuristic(&self) -> u32;
     fn successors(&self) -> Vec<(Self::Move, Self)>;
+
+    /// What taking `mv` from this state adds to the path cost. Every move
+    /// costs 1 unless a puzzle weighs them differently; the heuristic must
+    /// then stay below the cheapest remaining cost for A* to remain optimal.
+    fn cost(&self, _mv: &Self::Move) -> u32 {
+        1
+    }
+
+    /// Whether no goal can be reached from here whatever the moves. A* counts
+    /// such a state as expanded but never builds its successors.
+    fn is_dead_end(&self) -> bool {
+        false
+    }
+
+    /// Rough number of successors per state, for predicting how many nodes
+    /// a depth-first pass to depth d visits (about b^d).
+    fn branching_factor_estimate(&self) -> f32 {
+        2.0
+    }
+}
+
+/// Complete-assignment states that local search can start from.
+pub trait LocalSearchState: SearchState {
+    /// Fill every unassigned variable with a random value, keeping existing assignments.
+    fn randomized<R: Rng>(&self, rng: &mut R) -> Self;
 }
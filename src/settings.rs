@@ -0,0 +1,117 @@
+use crate::app::{QueensAlgorithm, DEFAULT_FRAME_INTERVAL, MAX_FPS, MIN_FPS};
+use crate::i18n::Language;
+use crate::persistence;
+use crate::theme::ThemeKind;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Redraw intervals offered for [`Settings::animation_speed_ms`], fastest first.
+pub const ANIMATION_SPEEDS_MS: [u64; 4] = [20, 50, 100, 250];
+
+/// Preferences edited on the Settings screen and kept in `config.toml`.
+/// Keys missing from the file keep their defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Algorithm the 8 Queens solver starts on.
+    pub default_algorithm: QueensAlgorithm,
+    /// Milliseconds between redraws while something animates.
+    pub animation_speed_ms: u64,
+    pub theme: ThemeKind,
+    /// Ask "Quit? (y/n)" before exiting.
+    pub confirm_quit: bool,
+    pub language: Language,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            default_algorithm: QueensAlgorithm::AStar,
+            animation_speed_ms: DEFAULT_FRAME_INTERVAL.as_millis() as u64,
+            theme: ThemeKind::Default,
+            confirm_quit: true,
+            language: Language::English,
+        }
+    }
+}
+
+/// One row of the Settings screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsField {
+    DefaultAlgorithm,
+    AnimationSpeed,
+    Theme,
+    ConfirmQuit,
+    Language,
+}
+
+impl SettingsField {
+    pub const ALL: [SettingsField; 5] = [
+        SettingsField::DefaultAlgorithm,
+        SettingsField::AnimationSpeed,
+        SettingsField::Theme,
+        SettingsField::ConfirmQuit,
+        SettingsField::Language,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingsField::DefaultAlgorithm => "Default 8 Queens algorithm",
+            SettingsField::AnimationSpeed => "Animation frame interval",
+            SettingsField::Theme => "Color theme",
+            SettingsField::ConfirmQuit => "Confirm before quitting",
+            SettingsField::Language => "Language",
+        }
+    }
+}
+
+impl Settings {
+    /// `config.toml` inside the platform config directory.
+    pub fn config_path() -> Option<PathBuf> {
+        persistence::config_file("config.toml")
+    }
+
+    /// The saved settings, or the defaults when there is no readable file.
+    pub fn load() -> Self {
+        Self::config_path()
+            .and_then(|path| persistence::load_config(&path).ok())
+            .unwrap_or_default()
+    }
+
+    /// [`Settings::animation_speed_ms`] as an event-loop wait, kept within
+    /// the frame rates `--fps` accepts.
+    pub fn frame_interval(&self) -> Duration {
+        let fastest = 1_000 / u64::from(MAX_FPS);
+        let slowest = 1_000 / u64::from(MIN_FPS);
+        Duration::from_millis(self.animation_speed_ms.clamp(fastest, slowest))
+    }
+
+    /// Current value of `field`, as shown on the Settings screen.
+    pub fn value(&self, field: SettingsField) -> String {
+        match field {
+            SettingsField::DefaultAlgorithm => self.default_algorithm.label().into(),
+            SettingsField::AnimationSpeed => format!("{} ms", self.animation_speed_ms),
+            SettingsField::Theme => self.theme.label().into(),
+            SettingsField::ConfirmQuit => if self.confirm_quit { "On" } else { "Off" }.into(),
+            SettingsField::Language => self.language.label().into(),
+        }
+    }
+
+    /// Moves `field` on to its next value, wrapping after the last one.
+    pub fn cycle(&mut self, field: SettingsField) {
+        match field {
+            SettingsField::DefaultAlgorithm => self.default_algorithm = self.default_algorithm.next(),
+            SettingsField::AnimationSpeed => {
+                // A hand-edited value off the list moves to the next larger preset
+                self.animation_speed_ms = ANIMATION_SPEEDS_MS
+                    .into_iter()
+                    .find(|speed| *speed > self.animation_speed_ms)
+                    .unwrap_or(ANIMATION_SPEEDS_MS[0]);
+            }
+            SettingsField::Theme => self.theme = self.theme.next(),
+            SettingsField::ConfirmQuit => self.confirm_quit = !self.confirm_quit,
+            SettingsField::Language => self.language = self.language.next(),
+        }
+    }
+}
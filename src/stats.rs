@@ -0,0 +1,194 @@
+use crate::persistence;
+use crate::puzzles::PuzzleId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum StatsError {
+    #[error("could not access {path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("invalid stats file {path}: {source}")]
+    Format {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+}
+
+/// One finished puzzle, waiting for [`crate::app::App::collect_solves`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolveRecord {
+    /// `None` for puzzles that do not count hand moves.
+    pub moves: Option<usize>,
+    /// Search time; hand solves are not timed.
+    pub time: Option<Duration>,
+}
+
+impl SolveRecord {
+    pub fn by_hand(moves: Option<usize>) -> Self {
+        Self { moves, time: None }
+    }
+
+    pub fn by_solver(moves: usize, time: Duration) -> Self {
+        Self {
+            moves: Some(moves),
+            time: Some(time),
+        }
+    }
+}
+
+/// All-time records for one puzzle.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BestStats {
+    pub min_moves: Option<usize>,
+    pub min_time: Option<Duration>,
+    pub total_solves: usize,
+    /// Time the puzzle has been open, over every run.
+    #[serde(default)]
+    pub time_spent: Duration,
+}
+
+impl BestStats {
+    /// Counts the solve and keeps whichever values are lower. Returns whether
+    /// an earlier record was beaten; the first solve sets records without
+    /// beating anything.
+    pub fn record(&mut self, solve: SolveRecord) -> bool {
+        self.total_solves += 1;
+        let moves_beaten = improve(&mut self.min_moves, solve.moves);
+        let time_beaten = improve(&mut self.min_time, solve.time);
+        moves_beaten || time_beaten
+    }
+
+    /// One-line record for the main menu, such as "8-Puzzle solved 14 times,
+    /// best 22 moves."; `None` before the first solve.
+    pub fn summary(&self, name: &str) -> Option<String> {
+        let times = match self.total_solves {
+            0 => return None,
+            1 => "once".to_string(),
+            total => format!("{} times", total),
+        };
+        Some(match self.min_moves {
+            Some(moves) => format!("{} solved {}, best {} moves.", name, times, moves),
+            None => format!("{} solved {}.", name, times),
+        })
+    }
+}
+
+fn improve<T: Ord + Copy>(best: &mut Option<T>, value: Option<T>) -> bool {
+    match (*best, value) {
+        (_, None) => false,
+        (None, Some(value)) => {
+            *best = Some(value);
+            false
+        }
+        (Some(current), Some(value)) if value < current => {
+            *best = Some(value);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// `stats.json` next to `config.toml` in the platform config directory.
+pub fn stats_path() -> Option<PathBuf> {
+    persistence::config_file("stats.json")
+}
+
+/// Where `stats.json` was kept before it moved next to `config.toml`.
+fn old_stats_path() -> Option<PathBuf> {
+    persistence::data_file("stats.json")
+}
+
+/// Loads the saved records, or none when nothing was saved yet. A file left
+/// in the data directory by an older version is moved over first.
+pub fn load() -> Result<HashMap<PuzzleId, BestStats>, StatsError> {
+    let Some(path) = stats_path() else {
+        return Ok(HashMap::new());
+    };
+    if let Some(old_path) = old_stats_path() {
+        move_old_file(&old_path, &path)?;
+    }
+    read_file(&path)
+}
+
+/// Moves `from` to `to` unless `to` already exists or there is nothing to move.
+/// Copies and deletes when a rename is not possible, e.g. across file systems.
+pub fn move_old_file(from: &Path, to: &Path) -> Result<(), StatsError> {
+    if to.exists() || !from.exists() {
+        return Ok(());
+    }
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| StatsError::Io { path, source }
+    };
+    if let Some(dir) = to.parent() {
+        fs::create_dir_all(dir).map_err(io_error(to))?;
+    }
+    if fs::rename(from, to).is_err() {
+        fs::copy(from, to).map_err(io_error(from))?;
+        fs::remove_file(from).map_err(io_error(from))?;
+    }
+    Ok(())
+}
+
+/// Writes the records for the next start; does nothing without a config directory.
+pub fn save(stats: &HashMap<PuzzleId, BestStats>) -> Result<(), StatsError> {
+    match stats_path() {
+        Some(path) => write_file(stats, &path),
+        None => Ok(()),
+    }
+}
+
+/// Moves an unreadable `stats.json` aside to `stats.json.bak` so saving on exit
+/// does not overwrite it; returns the new path.
+pub fn back_up() -> Result<Option<PathBuf>, StatsError> {
+    match stats_path() {
+        Some(path) => back_up_file(&path).map(Some),
+        None => Ok(None),
+    }
+}
+
+pub fn back_up_file(path: &Path) -> Result<PathBuf, StatsError> {
+    let backup = path.with_extension("json.bak");
+    fs::rename(path, &backup).map_err(|source| StatsError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(backup)
+}
+
+pub fn read_file(path: &Path) -> Result<HashMap<PuzzleId, BestStats>, StatsError> {
+    let json = match fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(source) => {
+            return Err(StatsError::Io {
+                path: path.to_path_buf(),
+                source,
+            })
+        }
+    };
+    serde_json::from_str(&json).map_err(|source| StatsError::Format {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+pub fn write_file(stats: &HashMap<PuzzleId, BestStats>, path: &Path) -> Result<(), StatsError> {
+    let json = serde_json::to_string_pretty(stats).map_err(|source| StatsError::Format {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let io_error = |source| StatsError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    fs::write(path, json).map_err(io_error)
+}
@@ -0,0 +1,185 @@
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeKind {
+    #[default]
+    Default,
+    HighContrast,
+    Monochrome,
+    Solarized,
+}
+
+impl ThemeKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ThemeKind::Default => "Default",
+            ThemeKind::HighContrast => "High contrast",
+            ThemeKind::Monochrome => "Monochrome",
+            ThemeKind::Solarized => "Solarized",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ThemeKind::Default => ThemeKind::HighContrast,
+            ThemeKind::HighContrast => ThemeKind::Monochrome,
+            ThemeKind::Monochrome => ThemeKind::Solarized,
+            ThemeKind::Solarized => ThemeKind::Default,
+        }
+    }
+}
+
+// Solarized palette, https://ethanschoonover.com/solarized/
+const BASE03: Color = Color::Rgb(0x00, 0x2b, 0x36);
+const BASE01: Color = Color::Rgb(0x58, 0x6e, 0x75);
+const BASE0: Color = Color::Rgb(0x83, 0x94, 0x96);
+const BASE1: Color = Color::Rgb(0x93, 0xa1, 0xa1);
+const SOL_YELLOW: Color = Color::Rgb(0xb5, 0x89, 0x00);
+const SOL_RED: Color = Color::Rgb(0xdc, 0x32, 0x2f);
+const SOL_MAGENTA: Color = Color::Rgb(0xd3, 0x36, 0x82);
+const SOL_VIOLET: Color = Color::Rgb(0x6c, 0x71, 0xc4);
+const SOL_BLUE: Color = Color::Rgb(0x26, 0x8b, 0xd2);
+const SOL_CYAN: Color = Color::Rgb(0x2a, 0xa1, 0x98);
+const SOL_GREEN: Color = Color::Rgb(0x85, 0x99, 0x00);
+
+/// Colors shared by every puzzle screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub kind: ThemeKind,
+    /// Cursor and selected cells.
+    pub highlight: Style,
+    /// Tiles, marks and other board contents.
+    pub board_fg: Color,
+    pub board_bg: Color,
+    /// Main menu and About page titles; puzzle headers go through [`Theme::accent`].
+    pub header_fg: Color,
+    pub queen: Color,
+    /// Background of a square attacked by a queen.
+    pub conflict: Color,
+    pub footer: Style,
+    pub x: Color,
+    pub o: Color,
+    /// Empty cells and other de-emphasised text.
+    pub dim: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::from_kind(ThemeKind::Default)
+    }
+}
+
+impl Theme {
+    pub fn from_kind(kind: ThemeKind) -> Self {
+        match kind {
+            ThemeKind::Default => Self {
+                kind,
+                highlight: Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+                board_fg: Color::White,
+                board_bg: Color::Reset,
+                header_fg: Color::Cyan,
+                queen: Color::Red,
+                conflict: Color::Red,
+                footer: Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::DIM),
+                x: Color::Magenta,
+                o: Color::Cyan,
+                dim: Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::DIM),
+            },
+            // Bright white on black, with selections inverted and bold
+            ThemeKind::HighContrast => Self {
+                kind,
+                highlight: Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+                board_fg: Color::White,
+                board_bg: Color::Black,
+                header_fg: Color::White,
+                queen: Color::White,
+                conflict: Color::LightRed,
+                footer: Style::default().fg(Color::White),
+                x: Color::White,
+                o: Color::White,
+                dim: Style::default().fg(Color::White),
+            },
+            ThemeKind::Monochrome => Self {
+                kind,
+                highlight: Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Gray)
+                    .add_modifier(Modifier::BOLD),
+                board_fg: Color::White,
+                board_bg: Color::Reset,
+                header_fg: Color::White,
+                queen: Color::White,
+                conflict: Color::DarkGray,
+                footer: Style::default().fg(Color::DarkGray),
+                x: Color::White,
+                o: Color::Gray,
+                dim: Style::default().fg(Color::DarkGray),
+            },
+            ThemeKind::Solarized => Self {
+                kind,
+                highlight: Style::default()
+                    .fg(BASE03)
+                    .bg(SOL_BLUE)
+                    .add_modifier(Modifier::BOLD),
+                board_fg: BASE1,
+                board_bg: BASE03,
+                header_fg: SOL_GREEN,
+                queen: SOL_RED,
+                conflict: SOL_RED,
+                footer: Style::default().fg(BASE01),
+                x: SOL_MAGENTA,
+                o: SOL_CYAN,
+                dim: Style::default().fg(BASE01),
+            },
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        Self::from_kind(self.kind.next())
+    }
+
+    /// Maps a puzzle's own accent color (a header, a disk, the water in a
+    /// jug, …) into this theme's palette.
+    pub fn accent(&self, color: Color) -> Color {
+        match self.kind {
+            ThemeKind::Default => color,
+            ThemeKind::HighContrast => match color {
+                Color::Black | Color::DarkGray => Color::Black,
+                _ => Color::White,
+            },
+            ThemeKind::Monochrome => match color {
+                Color::Black | Color::DarkGray => Color::DarkGray,
+                Color::Gray | Color::Red | Color::Green | Color::Blue | Color::Magenta | Color::Cyan => {
+                    Color::Gray
+                }
+                _ => Color::White,
+            },
+            ThemeKind::Solarized => match color {
+                Color::Red | Color::LightRed => SOL_RED,
+                Color::Green | Color::LightGreen => SOL_GREEN,
+                Color::Yellow | Color::LightYellow => SOL_YELLOW,
+                Color::Blue | Color::LightBlue => SOL_BLUE,
+                Color::Cyan | Color::LightCyan => SOL_CYAN,
+                Color::Magenta => SOL_MAGENTA,
+                Color::LightMagenta => SOL_VIOLET,
+                Color::Black => BASE03,
+                Color::DarkGray => BASE01,
+                Color::Gray => BASE0,
+                Color::White => BASE1,
+                other => other,
+            },
+        }
+    }
+}
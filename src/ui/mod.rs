@@ -3,180 +3,822 @@ use std::time::Duration;
 
 use color_eyre::Result;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+        KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Alignment, Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{block::Title, Block, Borders, Cell, Clear, List, ListItem, ListState, Paragraph, Row, Table, TableState, Wrap},
     Frame, Terminal,
 };
 
 use crate::{
-    app::{App, AppRoute, XorTicTacToeSession},
-    puzzles::{EightPuzzleState, EightQueensState, MissionariesCannibalsState, Player, PuzzleId},
-    search::SearchState,
+    app::{App, AppRoute, CompareReports, GameMode, KnightsTourSession, LightsOutSession, MazeSession, MoveTimer, QueensAlgorithm, SudokuSession, WaterJugSession, XorTicTacToeSession},
+    keymap::Action,
+    persistence,
+    settings::{Settings, SettingsField},
+    theme::Theme,
+    puzzles::{
+        eight_queens, knights_tour::TourHeuristic, maze, tower_of_hanoi, EightPuzzleState, EightQueensState, HanoiState,
+        MazeMove, MissionariesCannibalsState, Player, Difficulty, PuzzleDescriptor, PuzzleId, SokobanMove, SokobanState,
+        sudoku,
+    },
+    search::{solver::SearchReport, SearchState},
 };
 
 pub fn run(app: &mut App) -> Result<()> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     let mut menu_state = MenuState::default();
+    let mut settings_screen = SettingsScreen::default();
+    let mut statistics_screen = StatisticsScreen::default();
+    let mut layout_cache = LayoutCache::default();
+    let mut too_small = false;
+    let mut redraw = true;
 
     while !app.should_exit() {
-        terminal.draw(|frame| match app.route {
-            AppRoute::MainMenu => render_main_menu(frame, app, &menu_state),
-            AppRoute::Puzzle(id) => render_puzzle_shell(frame, app, id),
-            AppRoute::Quit => {}
-        })?;
-
-        if let Some(event) = poll_event()? {
-            match app.route {
+        app.eight_puzzle.poll_solver();
+        app.poll_autoplay();
+        app.collect_solves();
+        app.track_time_spent();
+        app.stop_solved_timers();
+        app.log_status();
+        let animating = app.is_animating();
+        // An idle screen is left alone until the next event
+        if redraw || animating {
+            terminal.draw(|frame| {
+                too_small = !check_minimum_size(frame);
+                if too_small {
+                    render_too_small(frame, &app.theme);
+                    return;
+                }
+                match app.current_route() {
+                    AppRoute::MainMenu => render_main_menu(frame, app, &menu_state),
+                    AppRoute::Settings => render_settings(frame, app, &settings_screen),
+                    AppRoute::Statistics => render_statistics(frame, app, &mut statistics_screen),
+                    AppRoute::Puzzle(id) => render_puzzle_shell(frame, app, id, &mut layout_cache),
+                    AppRoute::Quit => {}
+                }
+                if app.show_help {
+                    render_help(frame, app);
+                }
+                if app.confirm_quit {
+                    render_quit_confirm(frame, app.strings().quit_prompt, &app.theme);
+                }
+            })?;
+        }
+        // One more frame after an animation ends clears it from the screen
+        redraw = animating;
+
+        if let Some(event) = poll_event(app.frame_interval)? {
+            redraw = true;
+            // Only a resize can get the app out of the too-small screen
+            if too_small && !matches!(event, Event::Resize(..)) {
+                continue;
+            }
+            if handle_global_input(&event, app) {
+                continue;
+            }
+            match app.current_route() {
                 AppRoute::MainMenu => handle_main_menu_input(event, app, &mut menu_state),
-                AppRoute::Puzzle(id) => handle_puzzle_input(event, app, id),
+                AppRoute::Settings => handle_settings_input(event, app, &mut settings_screen),
+                AppRoute::Statistics => handle_statistics_input(event, app, &mut statistics_screen),
+                AppRoute::Puzzle(id) => handle_puzzle_input(event, app, id, &layout_cache),
                 AppRoute::Quit => break,
             }
         }
     }
 
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
     terminal.show_cursor()?;
 
     Ok(())
 }
 
-fn poll_event() -> Result<Option<Event>> {
-    if event::poll(Duration::from_millis(50))? {
+fn poll_event(timeout: Duration) -> Result<Option<Event>> {
+    if event::poll(timeout)? {
         Ok(Some(event::read()?))
     } else {
         Ok(None)
     }
 }
 
+/// Whether the locale asks for UTF-8 output, checked the way the C library
+/// does: the first of `LC_ALL`, `LC_CTYPE` and `LANG` that is set wins.
+pub fn unicode_locale() -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .map(|value| {
+            let value = value.to_ascii_lowercase();
+            value.contains("utf-8") || value.contains("utf8")
+        })
+        .unwrap_or(false)
+}
+
+/// Smallest terminal the puzzle layouts are designed for.
+const MIN_WIDTH: u16 = 80;
+const MIN_HEIGHT: u16 = 24;
+
+/// Whether the frame is large enough to draw the screens without overlap.
+fn check_minimum_size(frame: &Frame) -> bool {
+    let size = frame.size();
+    size.width >= MIN_WIDTH && size.height >= MIN_HEIGHT
+}
+
+fn render_too_small(frame: &mut Frame, theme: &Theme) {
+    let size = frame.size();
+    let message = format!(
+        "Terminal too small: {}×{}\nNeed at least {}×{}.\nResize the window to continue.",
+        size.width, size.height, MIN_WIDTH, MIN_HEIGHT
+    );
+    let area = centered_rect(size.width, 3, size);
+    let paragraph = Paragraph::new(message)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.accent(Color::Red)))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, area);
+}
+
+/// Keys that work on every screen: `?`/`F1` for the help popup, `Ctrl+T` to
+/// cycle the theme and `Ctrl+A` to switch ASCII boards on or off. While the
+/// popup or the quit dialog is open every other key is swallowed, so returns
+/// `true` when the event has been dealt with.
+fn handle_global_input(event: &Event, app: &mut App) -> bool {
+    let (code, modifiers) = match event {
+        Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) => (*code, *modifiers),
+        _ => return app.show_help || app.confirm_quit,
+    };
+    if app.confirm_quit {
+        match code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => app.quit(),
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app.cancel_quit(),
+            _ => {}
+        }
+        return true;
+    }
+    match code {
+        KeyCode::Char('t') | KeyCode::Char('T') if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.cycle_theme();
+            true
+        }
+        KeyCode::Char('a') | KeyCode::Char('A') if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.toggle_ascii_mode();
+            true
+        }
+        KeyCode::Char('w') | KeyCode::Char('W') if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.toggle_wrap_cursor();
+            true
+        }
+        KeyCode::Char('?') | KeyCode::F(1) => {
+            app.toggle_help();
+            true
+        }
+        KeyCode::Esc if app.show_help => {
+            app.toggle_help();
+            true
+        }
+        _ => app.show_help,
+    }
+}
+
 #[derive(Default)]
 struct MenuState {
+    /// Index into the filtered list, not the registry.
+    selected: usize,
+    /// Typed text the menu is narrowed to, matched case-insensitively against puzzle names.
+    filter: String,
+    /// Whether keys type into `filter` (entered with `/` or F3) instead of acting as menu keys.
+    filtering: bool,
+    /// Only puzzles of this difficulty are listed; `None` lists them all.
+    difficulty: Option<Difficulty>,
+}
+
+impl MenuState {
+    fn visible<'a>(&self, descriptors: &'a [PuzzleDescriptor]) -> Vec<&'a PuzzleDescriptor> {
+        let filter = self.filter.to_lowercase();
+        descriptors
+            .iter()
+            .filter(|descriptor| descriptor.name.to_lowercase().contains(&filter))
+            // The About page and the daily challenge are not puzzles of their own,
+            // so they stay listed at every difficulty
+            .filter(|descriptor| {
+                matches!(descriptor.id, PuzzleId::About | PuzzleId::DailyChallenge)
+                    || self.difficulty.is_none_or(|difficulty| descriptor.difficulty == difficulty)
+            })
+            .collect()
+    }
+
+    /// Steps the difficulty filter through All, then each [`Difficulty`] in turn.
+    fn cycle_difficulty(&mut self, descriptors: &[PuzzleDescriptor]) {
+        self.difficulty = match self.difficulty {
+            None => Difficulty::ALL.first().copied(),
+            Some(current) => Difficulty::ALL
+                .iter()
+                .skip_while(|difficulty| **difficulty != current)
+                .nth(1)
+                .copied(),
+        };
+        let filter = std::mem::take(&mut self.filter);
+        self.set_filter(filter, descriptors);
+    }
+
+    /// Narrows the list to `filter` and selects the first puzzle whose name
+    /// starts with it, or the first entry left when none does.
+    fn set_filter(&mut self, filter: String, descriptors: &[PuzzleDescriptor]) {
+        self.filter = filter;
+        let prefix = self.filter.to_lowercase();
+        self.selected = self
+            .visible(descriptors)
+            .iter()
+            .position(|descriptor| descriptor.name.to_lowercase().starts_with(&prefix))
+            .unwrap_or(0);
+    }
+
+    /// Selects the first listed puzzle whose name starts with `letter`, ignoring
+    /// case; leaves the selection alone when none does.
+    fn jump_to_letter(&mut self, letter: char, descriptors: &[PuzzleDescriptor]) {
+        let prefix = letter.to_lowercase().to_string();
+        if let Some(index) = self
+            .visible(descriptors)
+            .iter()
+            .position(|descriptor| descriptor.name.to_lowercase().starts_with(&prefix))
+        {
+            self.selected = index;
+        }
+    }
+
+    /// Leaves filter mode with the full list showing and `keep` selected in it.
+    fn stop_filtering(&mut self, keep: Option<PuzzleId>, descriptors: &[PuzzleDescriptor]) {
+        self.filtering = false;
+        self.filter.clear();
+        self.selected = keep
+            .and_then(|id| descriptors.iter().position(|descriptor| descriptor.id == id))
+            .unwrap_or(0);
+    }
+}
+
+/// Cursor and latest message of the Settings screen.
+#[derive(Default)]
+struct SettingsScreen {
+    /// Index into [`SettingsField::ALL`].
     selected: usize,
+    status: String,
+}
+
+/// Table cursor and latest message of the Statistics screen.
+struct StatisticsScreen {
+    /// Selected row of [`statistics_puzzles`] and how far the table scrolled.
+    table: TableState,
+    /// "Reset all statistics? (y/n)" is waiting for an answer.
+    confirm_reset: bool,
+    status: String,
+}
+
+impl Default for StatisticsScreen {
+    fn default() -> Self {
+        Self {
+            table: TableState::default().with_selected(Some(0)),
+            confirm_reset: false,
+            status: String::new(),
+        }
+    }
+}
+
+/// Screen areas from the last drawn frame, so a mouse click can be mapped back
+/// to the board cell or list entry under it.
+#[derive(Default)]
+struct LayoutCache {
+    eight_puzzle_board: Option<Rect>,
+    eight_puzzle_goal: Option<Rect>,
+    queens_board: Option<Rect>,
+    mc_moves: Option<Rect>,
+}
+
+/// Offset of `(column, row)` from the top-left corner of `text_width`-wide
+/// lines drawn centred inside a bordered `area`.
+fn centered_text_offset(area: Rect, text_width: u16, column: u16, row: u16) -> Option<(u16, u16)> {
+    let inner_x = area.x + 1;
+    let inner_y = area.y + 1;
+    let inner_width = area.width.saturating_sub(2);
+    let inner_height = area.height.saturating_sub(2);
+    // Same rounding as ratatui's centre alignment
+    let left = inner_x + (inner_width / 2).saturating_sub(text_width / 2);
+    if column < left || row < inner_y || row >= inner_y + inner_height {
+        return None;
+    }
+    Some((column - left, row - inner_y))
+}
+
+/// 8-puzzle cell under a click, given the 7-wide, 1-high cells of `render_eight_puzzle_board`.
+fn eight_puzzle_cell_at(area: Rect, column: u16, row: u16) -> Option<usize> {
+    let (x, y) = centered_text_offset(area, 25, column, row)?;
+    if y % 2 == 0 || x % 8 == 0 || x >= 24 || y >= 6 {
+        return None;
+    }
+    Some((y / 2 * 3 + x / 8) as usize)
+}
+
+/// Square under a click on the board drawn by `render_queens_board`.
+fn queens_square_at(area: Rect, column: u16, row: u16) -> Option<(usize, usize)> {
+    // Square rows are 20 columns wide (border, row label, 8 two-column squares,
+    // border) and centred on their own, below three header lines
+    let (x, y) = centered_text_offset(area, 20, column, row)?;
+    if !(3..11).contains(&y) || !(3..19).contains(&x) {
+        return None;
+    }
+    Some(((y - 3) as usize, ((x - 3) / 2) as usize))
+}
+
+fn handle_puzzle_click(app: &mut App, puzzle_id: PuzzleId, layout: &LayoutCache, column: u16, row: u16) {
+    match puzzle_id {
+        PuzzleId::EightPuzzle => {
+            if let Some(index) = layout
+                .eight_puzzle_board
+                .and_then(|area| eight_puzzle_cell_at(area, column, row))
+            {
+                app.eight_puzzle.select_cell(index, false);
+            } else if let Some(index) = layout
+                .eight_puzzle_goal
+                .and_then(|area| eight_puzzle_cell_at(area, column, row))
+            {
+                app.eight_puzzle.select_cell(index, true);
+            }
+        }
+        PuzzleId::EightQueens => {
+            if let Some((square_row, square_col)) = layout
+                .queens_board
+                .and_then(|area| queens_square_at(area, column, row))
+            {
+                app.eight_queens.selected_row = square_row;
+                app.eight_queens.selected_col = square_col;
+                app.eight_queens.toggle_queen();
+            }
+        }
+        PuzzleId::MissionariesCannibals => {
+            let Some(area) = layout.mc_moves else {
+                return;
+            };
+            let Some((_, line)) = centered_text_offset(area, area.width.saturating_sub(2), column, row) else {
+                return;
+            };
+            let moves = app.missionaries_cannibals.get_valid_moves();
+            if let Some(mv) = moves.get(line as usize) {
+                app.missionaries_cannibals.selected_move = line as usize;
+                app.missionaries_cannibals.apply_move(*mv);
+            }
+        }
+        _ => {}
+    }
 }
 
+/// `/` or F3 opens the filter: from then on printable keys narrow the list by
+/// name and only non-character keys (arrows, Enter) act as menu keys, until
+/// Esc clears the filter or Enter opens a puzzle.
 fn handle_main_menu_input(event: Event, app: &mut App, menu_state: &mut MenuState) {
+    let visible_ids: Vec<PuzzleId> = menu_state
+        .visible(&app.registry.descriptors)
+        .iter()
+        .map(|descriptor| descriptor.id)
+        .collect();
+    if let Event::Resize(..) = event {
+        menu_state.selected = menu_state.selected.min(visible_ids.len().saturating_sub(1));
+        return;
+    }
     if let Event::Key(KeyEvent {
         code,
+        modifiers,
         kind: KeyEventKind::Press,
         ..
     }) = event
     {
-        match code {
-            KeyCode::Char('q') | KeyCode::Char('Q') => app.request_quit(),
-            KeyCode::Up => {
-                if menu_state.selected > 0 {
-                    menu_state.selected -= 1;
+        if menu_state.filtering {
+            match code {
+                KeyCode::Char(ch) if !modifiers.contains(KeyModifiers::CONTROL) => {
+                    let mut filter = std::mem::take(&mut menu_state.filter);
+                    filter.push(ch);
+                    menu_state.set_filter(filter, &app.registry.descriptors);
                 }
-            }
-            KeyCode::Down => {
-                if menu_state.selected + 1 < app.registry.descriptors.len() {
-                    menu_state.selected += 1;
+                KeyCode::Backspace => {
+                    let mut filter = std::mem::take(&mut menu_state.filter);
+                    filter.pop();
+                    menu_state.set_filter(filter, &app.registry.descriptors);
                 }
-            }
-            KeyCode::Enter => {
-                if let Some(descriptor) = app.registry.descriptors.get(menu_state.selected) {
-                    app.select_puzzle(descriptor.id);
+                KeyCode::Esc => {
+                    let keep = visible_ids.get(menu_state.selected).copied();
+                    menu_state.stop_filtering(keep, &app.registry.descriptors);
+                }
+                KeyCode::Enter => {
+                    if let Some(&id) = visible_ids.get(menu_state.selected) {
+                        menu_state.stop_filtering(Some(id), &app.registry.descriptors);
+                        app.select_puzzle(id);
+                    }
+                }
+                KeyCode::Up if !visible_ids.is_empty() => {
+                    menu_state.selected = menu_state
+                        .selected
+                        .checked_sub(1)
+                        .unwrap_or(visible_ids.len() - 1);
                 }
+                KeyCode::Down if !visible_ids.is_empty() => {
+                    menu_state.selected = (menu_state.selected + 1) % visible_ids.len();
+                }
+                KeyCode::Home => menu_state.selected = 0,
+                KeyCode::End => menu_state.selected = visible_ids.len().saturating_sub(1),
+                _ => {}
+            }
+            return;
+        }
+        match code {
+            KeyCode::Char('/') | KeyCode::F(3) => menu_state.filtering = true,
+            KeyCode::Char('s') | KeyCode::Char('S') if modifiers.contains(KeyModifiers::CONTROL) => {
+                app.push_route(AppRoute::Settings)
             }
+            // Most terminals send Ctrl+I as Tab
+            KeyCode::Char('i') | KeyCode::Char('I') if modifiers.contains(KeyModifiers::CONTROL) => {
+                app.push_route(AppRoute::Statistics)
+            }
+            KeyCode::Tab => app.push_route(AppRoute::Statistics),
+            KeyCode::Char('f') | KeyCode::Char('F') => menu_state.cycle_difficulty(&app.registry.descriptors),
             KeyCode::Char(digit) if digit.is_ascii_digit() => {
-                let index = digit.to_string().parse::<usize>().ok();
+                let index = digit.to_digit(10).map(|n| n as usize);
                 if let Some(idx) = index.and_then(|n| n.checked_sub(1)) {
-                    if idx < app.registry.descriptors.len() {
+                    if let Some(id) = visible_ids.get(idx) {
                         menu_state.selected = idx;
-                        if let Some(descriptor) = app.registry.descriptors.get(menu_state.selected)
-                        {
-                            app.select_puzzle(descriptor.id);
-                        }
+                        app.select_puzzle(*id);
+                    }
+                }
+            }
+            // Letters bound to a menu action keep it; the rest jump to a puzzle
+            KeyCode::Char(letter)
+                if letter.is_alphabetic()
+                    && !modifiers.contains(KeyModifiers::CONTROL)
+                    && !matches!(
+                        app.keymap.action(code),
+                        Some(Action::Quit | Action::MoveUp | Action::MoveDown | Action::Select)
+                    ) =>
+            {
+                menu_state.jump_to_letter(letter, &app.registry.descriptors)
+            }
+            KeyCode::Esc => app.request_quit(),
+            KeyCode::Home => menu_state.selected = 0,
+            KeyCode::End => menu_state.selected = visible_ids.len().saturating_sub(1),
+            _ => match app.keymap.action(code) {
+                Some(Action::Quit) => app.request_quit(),
+                // Both ends wrap around to the other
+                Some(Action::MoveUp) if !visible_ids.is_empty() => {
+                    menu_state.selected = menu_state
+                        .selected
+                        .checked_sub(1)
+                        .unwrap_or(visible_ids.len() - 1);
+                }
+                Some(Action::MoveDown) if !visible_ids.is_empty() => {
+                    menu_state.selected = (menu_state.selected + 1) % visible_ids.len();
+                }
+                Some(Action::Select) => {
+                    if let Some(id) = visible_ids.get(menu_state.selected) {
+                        app.select_puzzle(*id);
                     }
                 }
+                _ => {}
+            },
+        }
+    }
+}
+
+/// Up and Down pick a setting, Enter moves it on to its next value and B or
+/// Esc goes back to the previous screen.
+fn handle_settings_input(event: Event, app: &mut App, screen: &mut SettingsScreen) {
+    let Event::Key(KeyEvent {
+        code,
+        kind: KeyEventKind::Press,
+        ..
+    }) = event
+    else {
+        return;
+    };
+    let count = SettingsField::ALL.len();
+    match app.keymap.action(code) {
+        Some(Action::MoveUp) => screen.selected = screen.selected.checked_sub(1).unwrap_or(count - 1),
+        Some(Action::MoveDown) => screen.selected = (screen.selected + 1) % count,
+        Some(Action::Select) => {
+            if let Some(&field) = SettingsField::ALL.get(screen.selected) {
+                screen.status = app.cycle_setting(field);
+            }
+        }
+        Some(Action::Back) => {
+            screen.status.clear();
+            app.pop_route();
+        }
+        Some(Action::Quit) => app.request_quit(),
+        _ => {}
+    }
+}
+
+/// Puzzles listed on the Statistics screen: all but About.
+fn statistics_puzzles(app: &App) -> Vec<&PuzzleDescriptor> {
+    app.registry
+        .descriptors
+        .iter()
+        .filter(|descriptor| descriptor.id != PuzzleId::About)
+        .collect()
+}
+
+/// Up and Down pick a puzzle, Enter opens it, R resets every record once
+/// confirmed with Y, and B or Esc goes back to the previous screen.
+fn handle_statistics_input(event: Event, app: &mut App, screen: &mut StatisticsScreen) {
+    let Event::Key(KeyEvent {
+        code,
+        kind: KeyEventKind::Press,
+        ..
+    }) = event
+    else {
+        return;
+    };
+    if screen.confirm_reset {
+        match code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                screen.confirm_reset = false;
+                screen.status = match app.reset_stats() {
+                    Ok(()) => "Statistics reset.".into(),
+                    Err(err) => format!("Statistics reset (not saved: {}).", err),
+                };
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                screen.confirm_reset = false;
+                screen.status = "Reset cancelled.".into();
             }
             _ => {}
         }
+        return;
+    }
+    let puzzles: Vec<PuzzleId> = statistics_puzzles(app).iter().map(|descriptor| descriptor.id).collect();
+    let selected = screen.table.selected().unwrap_or(0);
+    match app.keymap.action(code) {
+        Some(Action::MoveUp) => screen
+            .table
+            .select(Some(selected.checked_sub(1).unwrap_or(puzzles.len() - 1))),
+        Some(Action::MoveDown) => screen.table.select(Some((selected + 1) % puzzles.len())),
+        Some(Action::Select) => {
+            if let Some(&puzzle) = puzzles.get(selected) {
+                screen.status.clear();
+                app.select_puzzle(puzzle);
+            }
+        }
+        Some(Action::Reset) => {
+            screen.confirm_reset = true;
+            screen.status = "Reset all statistics? (y/n)".into();
+        }
+        Some(Action::Back) => {
+            screen.status.clear();
+            app.pop_route();
+        }
+        Some(Action::Quit) => app.request_quit(),
+        _ => {}
     }
 }
 
-fn handle_puzzle_input(event: Event, app: &mut App, puzzle_id: PuzzleId) {
+fn handle_puzzle_input(event: Event, app: &mut App, puzzle_id: PuzzleId, layout: &LayoutCache) {
+    // Only the press counts; drags and releases would repeat the same click
+    if let Event::Mouse(MouseEvent {
+        kind: MouseEventKind::Down(MouseButton::Left),
+        column,
+        row,
+        ..
+    }) = event
+    {
+        if app.seed_prompt.is_none() {
+            app.stop_autoplay();
+            handle_puzzle_click(app, puzzle_id, layout, column, row);
+        }
+        return;
+    }
+
     if let Event::Key(KeyEvent {
         code,
+        modifiers,
         kind: KeyEventKind::Press,
         ..
     }) = event
     {
+        if app.seed_prompt.is_some() {
+            match code {
+                KeyCode::Enter => app.submit_seed_prompt(),
+                KeyCode::Esc => app.cancel_seed_prompt(),
+                KeyCode::Backspace => app.seed_prompt_pop(),
+                KeyCode::Char(digit) if digit.is_ascii_digit() => app.seed_prompt_push(digit),
+                _ => {}
+            }
+            return;
+        }
+
+        if puzzle_id == PuzzleId::EightPuzzle && app.eight_puzzle.board_prompt.is_some() {
+            match code {
+                KeyCode::Enter => app.eight_puzzle.submit_board_prompt(),
+                KeyCode::Esc => app.eight_puzzle.cancel_board_prompt(),
+                KeyCode::Backspace => app.eight_puzzle.board_prompt_pop(),
+                KeyCode::Char(ch) => app.eight_puzzle.board_prompt_push(ch),
+                _ => {}
+            }
+            return;
+        }
+
+        if puzzle_id == PuzzleId::XorTicTacToe && app.xor_ttt.board_prompt.is_some() {
+            match code {
+                KeyCode::Enter => app.xor_ttt.submit_board_prompt(),
+                KeyCode::Esc => app.xor_ttt.cancel_board_prompt(),
+                KeyCode::Backspace => app.xor_ttt.board_prompt_pop(),
+                KeyCode::Char(ch) => app.xor_ttt.board_prompt_push(ch),
+                _ => {}
+            }
+            return;
+        }
+
+        // While a solution auto-plays, + and - change its speed and any other
+        // key stops it before doing its usual job
         match code {
-            KeyCode::Esc | KeyCode::Char('b') | KeyCode::Char('B') => {
-                app.select_main_menu();
+            KeyCode::Char('p') | KeyCode::Char('P') if modifiers.contains(KeyModifiers::CONTROL) => {
+                app.toggle_autoplay();
+                return;
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') if app.change_autoplay_speed(true) => return,
+            KeyCode::Char('-') | KeyCode::Char('_') if app.change_autoplay_speed(false) => return,
+            _ => app.stop_autoplay(),
+        }
+
+        if puzzle_id == PuzzleId::EightPuzzle
+            && app.eight_puzzle.is_solving()
+            && matches!(code, KeyCode::Esc | KeyCode::Char('x') | KeyCode::Char('X'))
+        {
+            app.eight_puzzle.cancel_solve();
+            return;
+        }
+
+        match app.keymap.action(code) {
+            Some(Action::Back) => {
+                app.pop_route();
                 return;
             }
-            KeyCode::Char('q') | KeyCode::Char('Q') => {
+            Some(Action::Quit) => {
                 app.request_quit();
                 return;
             }
             _ => {}
         }
 
+        match code {
+            KeyCode::Char('[') => {
+                app.scroll_status_log(true);
+                return;
+            }
+            KeyCode::Char(']') => {
+                app.scroll_status_log(false);
+                return;
+            }
+            KeyCode::Char('k') | KeyCode::Char('K') if modifiers.contains(KeyModifiers::CONTROL) => {
+                app.clear_status_log();
+                return;
+            }
+            KeyCode::F(2) => {
+                app.save_session(&persistence::session_path(puzzle_id));
+                return;
+            }
+            KeyCode::F(3) => {
+                app.load_session(&persistence::session_path(puzzle_id));
+                return;
+            }
+            KeyCode::Char('s') | KeyCode::Char('S') if modifiers.contains(KeyModifiers::CONTROL) => {
+                match persistence::state_path(puzzle_id) {
+                    Some(path) => app.save_session(&path),
+                    None => app.report_no_data_dir(),
+                }
+                return;
+            }
+            KeyCode::Char('l') | KeyCode::Char('L') if modifiers.contains(KeyModifiers::CONTROL) => {
+                match persistence::state_path(puzzle_id) {
+                    Some(path) => app.load_session(&path),
+                    None => app.report_no_data_dir(),
+                }
+                return;
+            }
+            KeyCode::Char('c') | KeyCode::Char('C')
+                if !matches!(puzzle_id, PuzzleId::LightsOut | PuzzleId::XorTicTacToe) =>
+            {
+                app.toggle_comparison();
+                return;
+            }
+            // The water jug screen uses E for editing and Lights Out has no search trace
+            KeyCode::Char('e') | KeyCode::Char('E')
+                if !matches!(puzzle_id, PuzzleId::WaterJug | PuzzleId::LightsOut | PuzzleId::XorTicTacToe) =>
+            {
+                app.export_solution();
+                return;
+            }
+            _ => {}
+        }
+
         match puzzle_id {
-            PuzzleId::EightPuzzle => handle_eight_puzzle_key(code, app),
+            PuzzleId::EightPuzzle => handle_eight_puzzle_key(code, modifiers, app),
             PuzzleId::XorTicTacToe => handle_xor_ttt_key(code, app),
-            PuzzleId::MissionariesCannibals => handle_missionaries_cannibals_key(code, app),
+            PuzzleId::MissionariesCannibals => handle_missionaries_cannibals_key(code, modifiers, app),
             PuzzleId::EightQueens => handle_eight_queens_key(code, app),
-            PuzzleId::About => {
+            PuzzleId::TowerOfHanoi => handle_tower_of_hanoi_key(code, app),
+            PuzzleId::KnightsTour => handle_knights_tour_key(code, app),
+            PuzzleId::WaterJug => handle_water_jug_key(code, app),
+            PuzzleId::LightsOut => handle_lights_out_key(code, app),
+            PuzzleId::Sokoban => handle_sokoban_key(code, app),
+            PuzzleId::Sudoku => handle_sudoku_key(code, app),
+            PuzzleId::Maze => handle_maze_key(code, app),
+            PuzzleId::DailyChallenge | PuzzleId::About => {
                 // About page only needs back/quit, handled by common keys above
             }
         }
     }
 }
 
-fn handle_eight_puzzle_key(code: KeyCode, app: &mut App) {
-    match code {
-        KeyCode::Tab => app.eight_puzzle.toggle_editing_goal(),
-        KeyCode::Char('r') | KeyCode::Char('R') => app.eight_puzzle.reset(),
-        KeyCode::Char('n') | KeyCode::Char('N') => app.eight_puzzle.new_board(),
-        KeyCode::Char('h') | KeyCode::Char('H') => app.eight_puzzle.shuffle(),
-        KeyCode::Char('s') | KeyCode::Char('S') => app.eight_puzzle.solve_current(),
-        KeyCode::Char(' ') | KeyCode::Enter => {
-            app.eight_puzzle.advance_solution();
-        }
-        KeyCode::Up | KeyCode::Char('w') | KeyCode::Char('W') => {
-            app.eight_puzzle.move_cursor(-1, 0);
-        }
-        KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => {
-            app.eight_puzzle.move_cursor(1, 0);
+fn handle_eight_puzzle_key(code: KeyCode, modifiers: KeyModifiers, app: &mut App) {
+    // The board stays as it is until the background search reports back
+    if app.eight_puzzle.is_solving() {
+        return;
+    }
+    // Step mode only listens to Space / Enter (expand a node) and I (leave)
+    if app.eight_puzzle.is_stepping() {
+        match code {
+            KeyCode::Enter | KeyCode::Char(' ') => app.eight_puzzle.step_search(),
+            KeyCode::Char('i') | KeyCode::Char('I') => app.eight_puzzle.toggle_step_mode(),
+            _ => {}
         }
-        KeyCode::Left | KeyCode::Char('a') | KeyCode::Char('A') => {
-            app.eight_puzzle.move_cursor(0, -1);
+        return;
+    }
+    if modifiers.contains(KeyModifiers::CONTROL) && matches!(code, KeyCode::Char('r') | KeyCode::Char('R')) {
+        app.eight_puzzle.redo();
+        return;
+    }
+    match app.keymap.action(code) {
+        Some(Action::Reset) => app.eight_puzzle.reset(),
+        Some(Action::Shuffle) => app.eight_puzzle.shuffle(),
+        Some(Action::Solve) => app.eight_puzzle.solve_current(),
+        Some(Action::StepForward | Action::Select) => {
+            app.eight_puzzle.advance_solution();
         }
-        KeyCode::Right | KeyCode::Char('d') | KeyCode::Char('D') => {
-            app.eight_puzzle.move_cursor(0, 1);
+        Some(Action::StepBack) => {
+            app.eight_puzzle.rewind_solution();
         }
-        KeyCode::Char(digit) if digit.is_ascii_digit() => {
-            if let Some(num) = digit.to_digit(10) {
-                if num >= 1 && num <= 8 {
-                    app.eight_puzzle.place_number(num as u8);
+        Some(Action::MoveUp) => app.eight_puzzle.move_cursor(-1, 0, app.wrap_cursor),
+        Some(Action::MoveDown) => app.eight_puzzle.move_cursor(1, 0, app.wrap_cursor),
+        Some(Action::MoveLeft) => app.eight_puzzle.move_cursor(0, -1, app.wrap_cursor),
+        Some(Action::MoveRight) => app.eight_puzzle.move_cursor(0, 1, app.wrap_cursor),
+        _ => match code {
+            KeyCode::Char('g') | KeyCode::Char('G') => app.open_seed_prompt(),
+            KeyCode::Char('u') | KeyCode::Char('U') => {
+                app.eight_puzzle.undo();
+            }
+            KeyCode::Tab => app.eight_puzzle.toggle_editing_goal(),
+            KeyCode::Char('t') | KeyCode::Char('T') => app.eight_puzzle.show_hint(),
+            KeyCode::Char('i') | KeyCode::Char('I') => app.eight_puzzle.toggle_step_mode(),
+            KeyCode::Char('v') | KeyCode::Char('V') => app.eight_puzzle.open_board_prompt(),
+            KeyCode::Char('m') | KeyCode::Char('M') => app.eight_puzzle.cycle_heuristic(),
+            KeyCode::PageUp => app.eight_puzzle.scroll_history_up(),
+            KeyCode::PageDown => app.eight_puzzle.scroll_history_down(),
+            // N cycles equally short solutions while one is loaded
+            KeyCode::Char('n') | KeyCode::Char('N') if app.eight_puzzle.solution.is_some() => {
+                app.eight_puzzle.next_path();
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') => app.eight_puzzle.new_board(),
+            KeyCode::Char(digit) if digit.is_ascii_digit() => {
+                if let Some(num) = digit.to_digit(10) {
+                    if (1..=8).contains(&num) {
+                        app.eight_puzzle.place_number(num as u8);
+                    }
                 }
             }
-        }
-        _ => {}
+            _ => {}
+        },
     }
 }
 
 fn handle_xor_ttt_key(code: KeyCode, app: &mut App) {
-    match code {
-        KeyCode::Tab => app.xor_ttt.toggle_setup_mode(),
-        KeyCode::Char('r') | KeyCode::Char('R') => app.xor_ttt.reset(),
-        KeyCode::Char('h') | KeyCode::Char('H') => app.xor_ttt.shuffle(),
-        KeyCode::Char('s') | KeyCode::Char('S') => {
+    match app.keymap.action(code) {
+        Some(Action::Reset) => app.xor_ttt.reset(),
+        Some(Action::Shuffle) => app.xor_ttt.shuffle(),
+        Some(Action::Solve) => {
             if app.xor_ttt.setup_mode {
                 // In setup mode, S doesn't make sense
                 app.xor_ttt.status = "Exit setup mode (Tab) to use auto-move.".into();
@@ -184,37 +826,57 @@ fn handle_xor_ttt_key(code: KeyCode, app: &mut App) {
                 app.xor_ttt.auto_player_move();
             }
         }
-        KeyCode::Up | KeyCode::Char('w') | KeyCode::Char('W') => app.xor_ttt.move_cursor(-1, 0),
-        KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => app.xor_ttt.move_cursor(1, 0),
-        KeyCode::Left | KeyCode::Char('a') | KeyCode::Char('A') => app.xor_ttt.move_cursor(0, -1),
-        KeyCode::Right | KeyCode::Char('d') | KeyCode::Char('D') => app.xor_ttt.move_cursor(0, 1),
-        KeyCode::Char('x') | KeyCode::Char('X') => {
-            app.xor_ttt.place_manual(Player::X);
-        }
-        KeyCode::Char('o') | KeyCode::Char('O') => {
-            app.xor_ttt.place_manual(Player::O);
-        }
-        KeyCode::Enter | KeyCode::Char(' ') => {
+        Some(Action::MoveUp) => app.xor_ttt.move_cursor(-1, 0, app.wrap_cursor),
+        Some(Action::MoveDown) => app.xor_ttt.move_cursor(1, 0, app.wrap_cursor),
+        Some(Action::MoveLeft) => app.xor_ttt.move_cursor(0, -1, app.wrap_cursor),
+        Some(Action::MoveRight) => app.xor_ttt.move_cursor(0, 1, app.wrap_cursor),
+        Some(Action::Select | Action::StepForward) => {
             app.xor_ttt.place_cursor();
         }
-        KeyCode::Char(digit) if digit.is_ascii_digit() => {
-            if let Some(index) = digit_to_index(digit) {
-                app.xor_ttt.quick_place(index);
+        _ => match code {
+            KeyCode::Tab => app.xor_ttt.toggle_setup_mode(),
+            KeyCode::Char('v') | KeyCode::Char('V') => app.xor_ttt.open_board_prompt(),
+            KeyCode::Char('l') | KeyCode::Char('L') => app.xor_ttt.cycle_difficulty(),
+            KeyCode::Char('f') | KeyCode::Char('F') => app.xor_ttt.swap_sides(),
+            KeyCode::Char('m') | KeyCode::Char('M') => app.xor_ttt.toggle_mode(),
+            KeyCode::Char('t') | KeyCode::Char('T') => app.xor_ttt.start_tournament(),
+            KeyCode::Char('x') | KeyCode::Char('X') => {
+                app.xor_ttt.place_manual(Player::X);
             }
-        }
-        _ => {}
+            KeyCode::Char('o') | KeyCode::Char('O') => {
+                app.xor_ttt.place_manual(Player::O);
+            }
+            KeyCode::Char(digit) if digit.is_ascii_digit() => {
+                if let Some(index) = digit_to_index(digit) {
+                    app.xor_ttt.quick_place(index);
+                }
+            }
+            _ => {}
+        },
     }
 }
 
-fn handle_missionaries_cannibals_key(code: KeyCode, app: &mut App) {
-    match code {
-        KeyCode::Char('r') | KeyCode::Char('R') => app.missionaries_cannibals.reset(),
-        KeyCode::Char('h') | KeyCode::Char('H') => app.missionaries_cannibals.shuffle(),
-        KeyCode::Char('s') | KeyCode::Char('S') => app.missionaries_cannibals.solve(),
-        KeyCode::Char(' ') | KeyCode::Enter => {
+fn handle_missionaries_cannibals_key(code: KeyCode, modifiers: KeyModifiers, app: &mut App) {
+    // Step mode only listens to Space / Enter (expand a node) and I (leave)
+    if app.missionaries_cannibals.is_stepping() {
+        match code {
+            KeyCode::Enter | KeyCode::Char(' ') => app.missionaries_cannibals.step_search(),
+            KeyCode::Char('i') | KeyCode::Char('I') => app.missionaries_cannibals.toggle_step_mode(),
+            _ => {}
+        }
+        return;
+    }
+    match app.keymap.action(code) {
+        Some(Action::Reset) => app.missionaries_cannibals.reset(),
+        Some(Action::Shuffle) => app.missionaries_cannibals.shuffle(),
+        Some(Action::Solve) => app.missionaries_cannibals.solve(),
+        Some(Action::StepForward | Action::Select) => {
             app.missionaries_cannibals.advance_solution();
         }
-        KeyCode::Up | KeyCode::Char('w') | KeyCode::Char('W') => {
+        Some(Action::StepBack) => {
+            app.missionaries_cannibals.rewind_solution();
+        }
+        Some(Action::MoveUp) => {
             let moves = app.missionaries_cannibals.get_valid_moves();
             if !moves.is_empty() {
                 app.missionaries_cannibals.selected_move = app.missionaries_cannibals.selected_move.saturating_sub(1);
@@ -223,47 +885,50 @@ fn handle_missionaries_cannibals_key(code: KeyCode, app: &mut App) {
                 }
             }
         }
-        KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => {
+        Some(Action::MoveDown) => {
             let moves = app.missionaries_cannibals.get_valid_moves();
             if !moves.is_empty() {
                 app.missionaries_cannibals.selected_move = (app.missionaries_cannibals.selected_move + 1).min(moves.len().saturating_sub(1));
             }
         }
-        KeyCode::Char('1') => {
-            let moves = app.missionaries_cannibals.get_valid_moves();
-            if moves.len() > 0 {
-                app.missionaries_cannibals.apply_move(moves[0]);
+        _ => match code {
+            KeyCode::Char('z') | KeyCode::Char('Z') if modifiers.contains(KeyModifiers::CONTROL) => {
+                app.missionaries_cannibals.undo();
             }
-        }
-        KeyCode::Char('2') => {
-            let moves = app.missionaries_cannibals.get_valid_moves();
-            if moves.len() > 1 {
-                app.missionaries_cannibals.apply_move(moves[1]);
+            KeyCode::Char('g') | KeyCode::Char('G') => app.open_seed_prompt(),
+            KeyCode::Char('i') | KeyCode::Char('I') => app.missionaries_cannibals.toggle_step_mode(),
+            KeyCode::Char('v') | KeyCode::Char('V') => app.missionaries_cannibals.export_search_graph(),
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                let n = app.missionaries_cannibals.state.n;
+                app.missionaries_cannibals.set_group_size(n.saturating_add(1));
             }
-        }
-        KeyCode::Char('3') => {
-            let moves = app.missionaries_cannibals.get_valid_moves();
-            if moves.len() > 2 {
-                app.missionaries_cannibals.apply_move(moves[2]);
+            KeyCode::Char('-') | KeyCode::Char('_') => {
+                let n = app.missionaries_cannibals.state.n;
+                app.missionaries_cannibals.set_group_size(n.saturating_sub(1));
             }
-        }
-        KeyCode::Char('4') => {
-            let moves = app.missionaries_cannibals.get_valid_moves();
-            if moves.len() > 3 {
-                app.missionaries_cannibals.apply_move(moves[3]);
+            KeyCode::Char('>') | KeyCode::Char('.') => {
+                let capacity = app.missionaries_cannibals.state.boat_capacity;
+                app.missionaries_cannibals.set_boat_capacity(capacity.saturating_add(1));
             }
-        }
-        KeyCode::Char('5') => {
-            let moves = app.missionaries_cannibals.get_valid_moves();
-            if moves.len() > 4 {
-                app.missionaries_cannibals.apply_move(moves[4]);
+            KeyCode::Char('<') | KeyCode::Char(',') => {
+                let capacity = app.missionaries_cannibals.state.boat_capacity;
+                app.missionaries_cannibals.set_boat_capacity(capacity.saturating_sub(1));
             }
-        }
-        _ => {}
+            KeyCode::Char(digit) if digit.is_ascii_digit() => {
+                if let Some(index) = digit_to_index(digit) {
+                    let moves = app.missionaries_cannibals.get_valid_moves();
+                    if let Some(mv) = moves.get(index) {
+                        app.missionaries_cannibals.apply_move(*mv);
+                    }
+                }
+            }
+            _ => {}
+        },
     }
 }
 
 fn render_main_menu(frame: &mut Frame, app: &App, menu_state: &MenuState) {
+    let theme = &app.theme;
     let layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -273,66 +938,287 @@ fn render_main_menu(frame: &mut Frame, app: &App, menu_state: &MenuState) {
         ])
         .split(frame.size());
 
-    let title = Paragraph::new("AI Puzzle Suite (TUI)")
+    let strings = app.strings();
+    let title = Paragraph::new(strings.app_title)
         .alignment(Alignment::Center)
         .style(Style::default().add_modifier(Modifier::BOLD));
     frame.render_widget(title, layout[0]);
 
-    let list_items: Vec<ListItem> = app
-        .registry
-        .descriptors
-        .iter()
-        .enumerate()
-        .map(|(idx, descriptor)| {
-            let prefix = format!("{}. {}", idx + 1, descriptor.name);
-            ListItem::new(Line::from(vec![Span::raw(prefix)]))
-        })
-        .collect();
+    let visible = menu_state.visible(&app.registry.descriptors);
+    let list_items: Vec<ListItem> = if visible.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(strings.no_match, theme.dim)).alignment(reading_alignment(app)))]
+    } else {
+        visible
+            .iter()
+            .enumerate()
+            .map(|(idx, descriptor)| {
+                let prefix = format!("{}. {}", idx + 1, app.language.puzzle_name(descriptor));
+                let mut spans = vec![Span::raw(prefix)];
+                if !matches!(descriptor.id, PuzzleId::About | PuzzleId::DailyChallenge) {
+                    spans.push(Span::raw(" "));
+                    spans.push(difficulty_badge(app.language.difficulty(descriptor.difficulty), descriptor.difficulty, theme));
+                }
+                ListItem::new(Line::from(spans).alignment(reading_alignment(app)))
+            })
+            .collect()
+    };
     let mut list_state = ratatui::widgets::ListState::default();
-    list_state.select(Some(menu_state.selected));
+    list_state.select((!visible.is_empty()).then_some(menu_state.selected));
 
+    let list_title = if menu_state.filtering {
+        format!("{} ({}: \"{}\")", strings.puzzles, strings.filtering, menu_state.filter)
+    } else {
+        format!("{} — {}", strings.puzzles, strings.filter_hint)
+    };
+    let list_block = titled_block(app, list_title);
+    let list_area = list_block.inner(layout[1]);
+    frame.render_widget(list_block, layout[1]);
+    let list_area = if menu_state.filtering {
+        let parts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(list_area);
+        let input = Paragraph::new(Line::from(vec![
+            Span::styled("/ ", theme.dim),
+            Span::raw(menu_state.filter.as_str()),
+            Span::styled("_", Style::default().add_modifier(Modifier::SLOW_BLINK)),
+        ]));
+        frame.render_widget(input, parts[0]);
+        parts[1]
+    } else {
+        list_area
+    };
     let list = List::new(list_items)
-        .block(Block::default().title("Puzzles").borders(Borders::ALL))
         .highlight_style(
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.header_fg)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol("> ");
-    frame.render_stateful_widget(list, layout[1], &mut list_state);
+    frame.render_stateful_widget(list, list_area, &mut list_state);
 
-    if let Some(current) = app.registry.descriptors.get(menu_state.selected) {
-        let details_area = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Min(0), Constraint::Length(1)])
-            .split(layout[2]);
-        
-        let details = Paragraph::new(current.summary)
-            .block(Block::default().title("Description").borders(Borders::ALL));
+    let details_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(layout[2]);
+    if let Some(current) = visible.get(menu_state.selected) {
+        let mut text = vec![Line::from(app.language.puzzle_summary(current))];
+        if let Some(record) = app.best_stats(current.id).summary(current.name) {
+            text.push(Line::from(""));
+            text.push(Line::from(Span::styled(record, app.theme.dim)));
+        }
+        for warning in &app.startup_warnings {
+            text.push(Line::from(""));
+            text.push(Line::from(Span::styled(
+                warning.as_str(),
+                Style::default().fg(theme.accent(Color::Red)),
+            )));
+        }
+        let details = Paragraph::new(text)
+            .wrap(Wrap { trim: true })
+            .alignment(reading_alignment(app))
+            .block(titled_block(app, strings.description));
         frame.render_widget(details, details_area[0]);
-        
-        // Add author name
-        let footer = Paragraph::new("Adel Enazi")
-            .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM));
-        frame.render_widget(footer, details_area[1]);
     }
+
+    // Author name, and the difficulty filter in use
+    let footer = Paragraph::new(format!(
+        "Adel Enazi · {}: {} ({})",
+        strings.difficulty,
+        menu_state.difficulty.map_or(strings.all, |difficulty| app.language.difficulty(difficulty)),
+        strings.change_difficulty
+    ))
+    .alignment(Alignment::Center)
+    .style(theme.footer);
+    frame.render_widget(footer, details_area[1]);
+}
+
+fn render_statistics(frame: &mut Frame, app: &App, screen: &mut StatisticsScreen) {
+    let theme = &app.theme;
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(6),
+            Constraint::Length(4),
+            Constraint::Length(4),
+        ])
+        .split(frame.size());
+
+    let header = Paragraph::new(app.strings().statistics)
+        .alignment(Alignment::Center)
+        .style(
+            Style::default()
+                .fg(theme.header_fg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(header, layout[0]);
+
+    let rows: Vec<Row> = statistics_puzzles(app)
+        .into_iter()
+        .map(|descriptor| {
+            let best = app.best_stats(descriptor.id);
+            Row::new(vec![
+                Cell::from(app.language.puzzle_name(descriptor)),
+                Cell::from(best.total_solves.to_string()),
+                Cell::from(best.min_time.map_or("—".to_string(), format_duration)),
+                Cell::from(best.min_moves.map_or("—".to_string(), |moves| moves.to_string())),
+                Cell::from(format_hours(best.time_spent)),
+            ])
+        })
+        .collect();
+    let strings = app.strings();
+    let header_row = Row::new(vec![
+        strings.puzzle,
+        strings.times_solved,
+        strings.fastest_solve,
+        strings.fewest_moves,
+        strings.total_time_spent,
+    ])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+    let widths = [
+        Constraint::Min(22),
+        Constraint::Length(13),
+        Constraint::Length(14),
+        Constraint::Length(13),
+        Constraint::Length(17),
+    ];
+    let table_title = match crate::stats::stats_path() {
+        Some(path) => format!("All time — saved to {}", persistence::display_path(&path)),
+        None => "All time — not saved: no data directory".to_string(),
+    };
+    let table = Table::new(rows, widths)
+        .header(header_row)
+        .block(titled_block(app, table_title))
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .highlight_symbol("> ");
+    frame.render_stateful_widget(table, layout[1], &mut screen.table);
+
+    let all_time: usize = app.stats.values().map(|best| best.total_solves).sum();
+    let session = Paragraph::new(format!(
+        "Running for: {}\nSolves: {} this session, {} all time",
+        format_hours(app.app_start.elapsed()),
+        app.session_solves,
+        all_time
+    ))
+    .block(Block::default().title("Session Stats").borders(Borders::ALL));
+    frame.render_widget(session, layout[2]);
+
+    let status = if screen.status.is_empty() {
+        "Times and move counts are the best over every run."
+    } else {
+        screen.status.as_str()
+    };
+    let footer = Paragraph::new(format!("{}\n↑↓ choose • Enter open puzzle • R reset • B back • Q quit", status))
+        .block(Block::default().borders(Borders::ALL))
+        .style(if screen.confirm_reset { Style::default().fg(theme.accent(Color::Red)) } else { theme.footer })
+        .wrap(Wrap { trim: true });
+    frame.render_widget(footer, layout[3]);
+}
+
+/// `[Beginner]`-style tag, green through magenta as puzzles get harder.
+fn difficulty_badge(label: &str, difficulty: Difficulty, theme: &Theme) -> Span<'static> {
+    let color = match difficulty {
+        Difficulty::Beginner => Color::Green,
+        Difficulty::Intermediate => Color::Yellow,
+        Difficulty::Advanced => Color::Red,
+        Difficulty::Expert => Color::Magenta,
+    };
+    Span::styled(
+        format!("[{}]", label),
+        Style::default().fg(theme.accent(color)),
+    )
+}
+
+fn render_settings(frame: &mut Frame, app: &App, screen: &SettingsScreen) {
+    let theme = &app.theme;
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(6),
+            Constraint::Length(4),
+        ])
+        .split(frame.size());
+
+    let header = Paragraph::new(app.strings().settings)
+        .alignment(Alignment::Center)
+        .style(
+            Style::default()
+                .fg(theme.header_fg)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(header, layout[0]);
+
+    let label_width = SettingsField::ALL
+        .iter()
+        .map(|field| field.label().chars().count())
+        .max()
+        .unwrap_or(0);
+    let items: Vec<ListItem> = SettingsField::ALL
+        .iter()
+        .map(|field| {
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{:<width$}  ", field.label(), width = label_width)),
+                Span::styled(
+                    app.settings.value(*field),
+                    Style::default().fg(theme.accent(Color::Cyan)),
+                ),
+            ]))
+        })
+        .collect();
+    let mut list_state = ratatui::widgets::ListState::default();
+    list_state.select(Some(screen.selected));
+    let list_title = match Settings::config_path() {
+        Some(path) => format!("Saved to {}", persistence::display_path(&path)),
+        None => "Not saved: no config directory".to_string(),
+    };
+    let list = List::new(items)
+        .block(Block::default().title(list_title).borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .highlight_symbol("> ");
+    frame.render_stateful_widget(list, layout[1], &mut list_state);
+
+    let status = if screen.status.is_empty() {
+        "Changes apply immediately."
+    } else {
+        screen.status.as_str()
+    };
+    let footer = Paragraph::new(format!("{}\n↑↓ choose • Enter change • B back • Q quit", status))
+        .block(Block::default().borders(Borders::ALL))
+        .style(theme.footer)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(footer, layout[2]);
 }
 
-fn render_puzzle_shell(frame: &mut Frame, app: &App, puzzle_id: PuzzleId) {
+fn render_puzzle_shell(frame: &mut Frame, app: &App, puzzle_id: PuzzleId, layout: &mut LayoutCache) {
     match puzzle_id {
-        PuzzleId::EightPuzzle => render_eight_puzzle(frame, app),
+        PuzzleId::EightPuzzle => render_eight_puzzle(frame, app, layout),
         PuzzleId::XorTicTacToe => render_xor_ttt(frame, app),
-        PuzzleId::MissionariesCannibals => render_missionaries_cannibals(frame, app),
-        PuzzleId::EightQueens => render_eight_queens(frame, app),
+        PuzzleId::MissionariesCannibals => render_missionaries_cannibals(frame, app, layout),
+        PuzzleId::EightQueens => render_eight_queens(frame, app, layout),
+        PuzzleId::TowerOfHanoi => render_tower_of_hanoi(frame, app),
+        PuzzleId::KnightsTour => render_knights_tour(frame, app),
+        PuzzleId::WaterJug => render_water_jug(frame, app),
+        PuzzleId::LightsOut => render_lights_out(frame, app),
+        PuzzleId::Sokoban => render_sokoban(frame, app),
+        PuzzleId::Sudoku => render_sudoku(frame, app),
+        PuzzleId::Maze => render_maze(frame, app),
         PuzzleId::About => render_about(frame, app),
+        // Opening the challenge routes to its puzzle, so this is never on the stack
+        PuzzleId::DailyChallenge => {}
     }
 }
 
-fn render_eight_puzzle(frame: &mut Frame, app: &App) {
+fn render_eight_puzzle(frame: &mut Frame, app: &App, layout: &mut LayoutCache) {
+    let theme = &app.theme;
     let descriptor = app.registry.descriptor(PuzzleId::EightPuzzle);
-    let title = descriptor.map(|d| d.name).unwrap_or("8-Puzzle Solver");
-    let summary = descriptor.map(|d| d.summary).unwrap_or("");
+    let title = descriptor.map(|d| app.language.puzzle_name(d)).unwrap_or("8-Puzzle Solver");
+    let daily_title = app.daily_in_play(PuzzleId::EightPuzzle).map(|daily| daily.title());
+    let title = daily_title.as_deref().unwrap_or(title);
+    let summary = descriptor.map(|d| app.language.puzzle_summary(d)).unwrap_or("");
     let session = &app.eight_puzzle;
 
     let outer = Layout::default()
@@ -348,15 +1234,15 @@ fn render_eight_puzzle(frame: &mut Frame, app: &App) {
         "{} — {}",
         title,
         if session.is_solved() {
-            "Solved"
+            app.strings().solved
         } else {
-            "In progress"
+            app.strings().in_progress
         }
     ))
     .alignment(Alignment::Center)
     .style(
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme.accent(Color::Yellow))
             .add_modifier(Modifier::BOLD),
     )
     .block(Block::default().borders(Borders::ALL));
@@ -373,7 +1259,23 @@ fn render_eight_puzzle(frame: &mut Frame, app: &App) {
         .split(body[0]);
 
     let current_selection = if session.editing_goal { 10 } else { session.selected_cell };
-    let board_lines = render_eight_puzzle_board(&session.current, current_selection);
+    let mut board_lines = render_eight_puzzle_board(&session.current, current_selection, session.active_hint(), theme, app.ascii_mode);
+    // While replaying, name the move that produced the board shown
+    if let Some(solution) = session
+        .solution
+        .as_ref()
+        .filter(|solution| solution.report.path.get(solution.step) == Some(&session.current))
+    {
+        if let Some(label) = solution.move_label(solution.step) {
+            board_lines.insert(
+                0,
+                Line::from(Span::styled(
+                    format!("Step {}: Slide {}", solution.step, label),
+                    Style::default().fg(theme.accent(Color::Yellow)).add_modifier(Modifier::BOLD),
+                )),
+            );
+        }
+    }
     let board_title = if session.editing_goal {
         if session.is_solved() { "Current Board (Solved)" } else { "Current Board" }
     } else {
@@ -387,9 +1289,10 @@ fn render_eight_puzzle(frame: &mut Frame, app: &App) {
                 .borders(Borders::ALL),
         );
     frame.render_widget(board_block, board_area[0]);
+    layout.eight_puzzle_board = Some(board_area[0]);
 
     let goal_selection = if session.editing_goal { session.goal_selected_cell } else { 10 };
-    let goal_lines = render_eight_puzzle_board(&session.goal_state, goal_selection);
+    let goal_lines = render_eight_puzzle_board(&session.goal_state, goal_selection, None, theme, app.ascii_mode);
     let goal_title = if session.editing_goal {
         "Goal Board [EDITING]"
     } else {
@@ -399,13 +1302,15 @@ fn render_eight_puzzle(frame: &mut Frame, app: &App) {
         .alignment(Alignment::Center)
         .block(Block::default().title(goal_title).borders(Borders::ALL));
     frame.render_widget(goal_block, board_area[1]);
+    layout.eight_puzzle_goal = Some(board_area[1]);
 
     let info_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(4),
-            Constraint::Length(5),
+            Constraint::Length(6),
             Constraint::Min(8),
+            Constraint::Length(3),
             Constraint::Length(4),
         ])
         .split(body[1]);
@@ -415,44 +1320,133 @@ fn render_eight_puzzle(frame: &mut Frame, app: &App) {
         .wrap(Wrap { trim: true });
     frame.render_widget(summary_block, info_chunks[0]);
 
-    let stats_text = format!(
-        "Moves made: {}\nHeuristic: {}\nSolved: {}",
-        session.moves_made,
-        session.current.manhattan_distance(),
-        if session.is_solved() { "Yes" } else { "No" }
-    );
+    // Odd against the goal's parity means no sequence of slides reaches it
+    let inversions = if session.current.is_solvable_to(&session.goal_state) {
+        Span::raw(format!("Inversions: {}", session.current.inversion_count()))
+    } else {
+        Span::styled(
+            format!("Inversions: {} (Unsolvable!)", session.current.inversion_count()),
+            Style::default().fg(theme.accent(Color::Red)).add_modifier(Modifier::BOLD),
+        )
+    };
+    let stats_text = vec![
+        Line::from(format!("Moves made: {}", session.moves_made)),
+        Line::from(format!(
+            "Heuristic: {} ({})",
+            session.heuristic_estimate(),
+            session.heuristic.label()
+        )),
+        Line::from(inversions),
+        Line::from(format!("Solved: {}", if session.is_solved() { "Yes" } else { "No" })),
+    ];
     let stats_block =
-        Paragraph::new(stats_text).block(Block::default().title("State").borders(Borders::ALL));
+        Paragraph::new(stats_text).block(Block::default().title(state_title(&session.timer)).borders(Borders::ALL));
     frame.render_widget(stats_block, info_chunks[1]);
 
     let solver_content = match &session.solution {
+        _ if session.is_stepping() => {
+            let (frontier, closed) = session.step_counts().unwrap_or_default();
+            let node = match session.step_node() {
+                Some((board, g, h)) => format!(
+                    "Last expanded (f = {} = {} + {}):\n{}",
+                    g + h,
+                    g,
+                    h,
+                    board
+                ),
+                None => "Nothing expanded yet.".into(),
+            };
+            format!(
+                "Step mode: watching A*\n\nFrontier (open): {}\nClosed (expanded): {}\n\n{}\nSpace/Enter expands the next\nnode, I leaves step mode.",
+                frontier, closed, node
+            )
+        }
+        _ if session.is_solving() => {
+            let elapsed = session.solve_start.map(|start| start.elapsed()).unwrap_or_default();
+            format!(
+                "Expanding… {} nodes\nElapsed: {}\n\nPress X or Esc to cancel.",
+                format_thousands(session.expanded_so_far()),
+                format_clock(elapsed)
+            )
+        }
         Some(solution) => {
+            let moves = solution
+                .report
+                .actions
+                .iter()
+                .enumerate()
+                .map(|(idx, mv)| {
+                    if idx + 1 == solution.step {
+                        format!("[{}]", mv.label())
+                    } else {
+                        mv.label().to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
             let stats = format!(
-                "Steps total: {}\nCurrent step: {}\nExpanded nodes: {}\nVisited states: {}\nElapsed: {}",
+                "Steps total: {}\nCurrent step: {}\nExpanded nodes: {}\nVisited states: {}\n{}\nElapsed: {}\n{}\n\nMoves: {}",
                 solution.total_steps(),
                 solution.step,
                 solution.report.expanded_nodes,
                 solution.report.visited_states,
-                format_duration(solution.report.elapsed)
+                branching_stats(&solution.report),
+                format_duration(solution.report.elapsed),
+                memory_stats(&solution.report),
+                if moves.is_empty() { "—".to_string() } else { moves }
             );
             let explanation = format!(
-                "\n\nA* Algorithm Explanation:\n\nA* finds the shortest path\nusing: f(n) = g(n) + h(n)\n\n• g(n) = actual moves\n  from start to here\n• h(n) = estimated moves\n  to goal (Manhattan)\n\nMetrics:\n• Expanded nodes: {}\n  States we fully explored\n  (checked all neighbors)\n\n• Visited states: {}\n  All states we've seen\n  (in queue + explored)",
+                "\n\nA* Algorithm Explanation:\n\nA* finds the shortest path\nusing: f(n) = g(n) + h(n)\n\n• g(n) = actual moves\n  from start to here\n• h(n) = estimated moves\n  to goal ({})\n\nMetrics:\n• Expanded nodes: {}\n  States we fully explored\n  (checked all neighbors)\n\n• Visited states: {}\n  All states we've seen\n  (in queue + explored)",
+                solution.heuristic.label(),
                 solution.report.expanded_nodes,
                 solution.report.visited_states
             );
-            format!("{}{}", stats, explanation)
+            let partial = if solution.is_partial() {
+                format!("Partial solution ({} steps, not optimal)\n\n", solution.total_steps())
+            } else {
+                String::new()
+            };
+            format!("{}{}{}", partial, stats, explanation)
         },
-        None => "Press S to run the A* solver.\n\nA* Algorithm:\nFinds optimal paths using:\nf(n) = g(n) + h(n)\n\n• g(n) = actual cost\n  from start\n• h(n) = heuristic\n  (Manhattan distance)\n\nExpanded nodes: States\nwe fully explored.\nVisited states: All states\nwe've encountered.".into(),
+        None => format!(
+            "Press S to run the A* solver.\n\nA* Algorithm:\nFinds optimal paths using:\nf(n) = g(n) + h(n)\n\n• g(n) = actual cost\n  from start\n• h(n) = heuristic\n  ({}; M cycles\n  Manhattan, Misplaced,\n  Linear conflict,\n  Pattern database)\n\nExpanded nodes: States\nwe fully explored.\nVisited states: All states\nwe've encountered.",
+            session.heuristic.label()
+        ),
     };
+    let solver_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(info_chunks[2]);
     let solver_block = Paragraph::new(solver_content)
-        .block(Block::default().title("Solver").borders(Borders::ALL))
+        .block(titled_block(app, app.strings().solver))
         .wrap(Wrap { trim: true });
-    frame.render_widget(solver_block, info_chunks[2]);
+    if session.compare_reports.showing_table() {
+        render_comparison(frame, &app.theme, &session.compare_reports, solver_area[0]);
+    } else {
+        frame.render_widget(solver_block, solver_area[0]);
+    }
 
-    let status_block = Paragraph::new(session.status.as_str())
-        .block(Block::default().title("Status").borders(Borders::ALL))
-        .wrap(Wrap { trim: true });
-    frame.render_widget(status_block, info_chunks[3]);
+    let history_items: Vec<ListItem> = session
+        .move_history
+        .iter()
+        .map(|entry| ListItem::new(entry.as_str()))
+        .collect();
+    let mut history_state = ListState::default();
+    if !session.move_history.is_empty() {
+        history_state.select(Some(session.history_scroll));
+    }
+    let history_list = List::new(history_items)
+        .block(Block::default().title("Move History (PgUp/PgDn)").borders(Borders::ALL))
+        .highlight_style(Style::default().fg(theme.accent(Color::Yellow)));
+    frame.render_stateful_widget(history_list, solver_area[1], &mut history_state);
+
+    let status_text = match session.spinner() {
+        Some(spinner) => format!("{} {}", spinner, session.status),
+        None => session.status.clone(),
+    };
+    render_best(frame, app, PuzzleId::EightPuzzle, info_chunks[3]);
+
+    render_status(frame, app, PuzzleId::EightPuzzle, &status_text, info_chunks[4]);
 
     let instructions_area = Layout::default()
         .direction(Direction::Vertical)
@@ -460,7 +1454,7 @@ fn render_eight_puzzle(frame: &mut Frame, app: &App) {
         .split(outer[2]);
     
     let instructions = Paragraph::new(
-        "Controls: Tab switch boards • ←→↑↓ select cell • 1-8 place number • H shuffle current/goal • S solve • Space step • R reset • N new board • B back • Q quit",
+        "Controls: Tab switch boards • ←→↑↓ select cell • 1-8 place number • H shuffle • G seed current/goal • V paste board • T hint • S solve • I step A* • Space/Backspace step fwd/back • U undo • Ctrl+R redo • PgUp/PgDn history • R reset • N new board • B back • Q quit",
     )
     .alignment(Alignment::Center)
     .block(Block::default().borders(Borders::ALL))
@@ -469,37 +1463,166 @@ fn render_eight_puzzle(frame: &mut Frame, app: &App) {
     
     let footer = Paragraph::new("Adel Enazi")
         .alignment(Alignment::Center)
-        .style(Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM));
+        .style(theme.footer);
     frame.render_widget(footer, instructions_area[1]);
 }
 
-fn render_eight_puzzle_board(state: &EightPuzzleState, selected_cell: usize) -> Vec<Line<'static>> {
+/// "Best" row with the all-time records for `puzzle`.
+fn render_best(frame: &mut Frame, app: &App, puzzle: PuzzleId, area: Rect) {
+    let best = app.best_stats(puzzle);
+    let text = format!(
+        "Min Moves: {}, Min Time: {}, Total: {}",
+        best.min_moves.map_or("—".to_string(), |moves| moves.to_string()),
+        best.min_time.map_or("—".to_string(), |time| format!("{:.2}s", time.as_secs_f64())),
+        best.total_solves
+    );
+    let best_block = Paragraph::new(text).block(titled_block(app, app.strings().best));
+    frame.render_widget(best_block, area);
+}
+
+/// Status block: the newest message in bold, or an older one from the log
+/// while the user is scrolling back with `[`.
+fn render_status(frame: &mut Frame, app: &App, puzzle: PuzzleId, current: &str, area: Rect) {
+    let log = app.status_logs.get(&puzzle);
+    let (text, style, title) = match log.and_then(|log| log.scrolled_entry().map(|entry| (log.len(), entry))) {
+        Some((total, (back, entry))) => (
+            entry,
+            app.theme.dim,
+            format!("Status — {} of {} ([ ] scroll)", total - back, total),
+        ),
+        None => (
+            current,
+            status_style(app).add_modifier(Modifier::BOLD),
+            match app.autoplay().filter(|autoplay| autoplay.playing) {
+                Some(autoplay) => format!("{} — {}", app.strings().status, autoplay.label()),
+                None => app.strings().status.to_string(),
+            },
+        ),
+    };
+    let status_block = Paragraph::new(text)
+        .block(titled_block(app, title))
+        .style(style)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(status_block, area);
+}
+
+/// Side text starts from in the active language: the right for Arabic.
+fn reading_alignment(app: &App) -> Alignment {
+    if app.language.is_rtl() {
+        Alignment::Right
+    } else {
+        Alignment::Left
+    }
+}
+
+/// Bordered block with `title` on the reading side of the top border.
+fn titled_block<'a>(app: &App, title: impl Into<Title<'a>>) -> Block<'a> {
+    Block::default()
+        .title(title)
+        .title_alignment(reading_alignment(app))
+        .borders(Borders::ALL)
+}
+
+/// Status text turns green for a few seconds after a personal best.
+fn status_style(app: &App) -> Style {
+    if app.best_flash_active() {
+        Style::default().fg(Color::LightGreen)
+    } else {
+        Style::default()
+    }
+}
+
+/// Tile or square suggested by a hint; kept apart from the cursor highlight.
+fn hint_style(theme: &Theme) -> Style {
+    Style::default()
+        .fg(theme.accent(Color::Black))
+        .bg(theme.accent(Color::Green))
+        .add_modifier(Modifier::BOLD)
+}
+
+/// Characters for board borders; every one is a single column wide, so the
+/// ASCII set draws boards with exactly the same geometry.
+struct BoxChars {
+    horizontal: char,
+    vertical: &'static str,
+    /// Left corner, column join and right corner of each border row.
+    top: [char; 3],
+    middle: [char; 3],
+    bottom: [char; 3],
+}
+
+const UNICODE_BOX: BoxChars = BoxChars {
+    horizontal: '─',
+    vertical: "│",
+    top: ['┌', '┬', '┐'],
+    middle: ['├', '┼', '┤'],
+    bottom: ['└', '┴', '┘'],
+};
+
+const ASCII_BOX: BoxChars = BoxChars {
+    horizontal: '-',
+    vertical: "|",
+    top: ['+', '+', '+'],
+    middle: ['+', '+', '+'],
+    bottom: ['+', '+', '+'],
+};
+
+fn box_chars(ascii_mode: bool) -> &'static BoxChars {
+    if ascii_mode {
+        &ASCII_BOX
+    } else {
+        &UNICODE_BOX
+    }
+}
+
+impl BoxChars {
+    /// Border row over `cells` cells that are `width` columns wide.
+    fn rule(&self, [left, join, right]: [char; 3], cells: usize, width: usize) -> String {
+        let segment = self.horizontal.to_string().repeat(width);
+        let mut line = String::from(left);
+        for cell in 0..cells {
+            if cell > 0 {
+                line.push(join);
+            }
+            line.push_str(&segment);
+        }
+        line.push(right);
+        line
+    }
+}
+
+fn render_eight_puzzle_board(
+    state: &EightPuzzleState,
+    selected_cell: usize,
+    hint_cell: Option<usize>,
+    theme: &Theme,
+    ascii_mode: bool,
+) -> Vec<Line<'static>> {
+    let chars = box_chars(ascii_mode);
     let mut lines = Vec::new();
     
     // Top border - each cell is 7 characters wide
-    lines.push(Line::from("┌───────┬───────┬───────┐"));
+    lines.push(Line::from(chars.rule(chars.top, 3, 7)));
     
     for row in 0..3 {
         let mut cell_spans = Vec::new();
-        cell_spans.push(Span::raw("│"));
+        cell_spans.push(Span::raw(chars.vertical));
         
         for col in 0..3 {
             let idx = row * 3 + col;
-            let tile = state.tiles[idx];
+            let tile = state.get_tile(idx);
             let is_selected = idx == selected_cell;
             
-            let style = if is_selected {
-                Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD)
+            let style = if hint_cell == Some(idx) {
+                hint_style(theme)
+            } else if is_selected {
+                theme.highlight
             } else if tile == 0 {
-                Style::default()
-                    .fg(Color::DarkGray)
-                    .add_modifier(Modifier::DIM)
+                theme.dim
             } else {
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme.board_fg)
+                    .bg(theme.board_bg)
             };
             
             // Center the content in a 7-character wide cell
@@ -511,16 +1634,16 @@ fn render_eight_puzzle_board(state: &EightPuzzleState, selected_cell: usize) ->
             };
             
             cell_spans.push(Span::styled(content, style));
-            cell_spans.push(Span::raw("│"));
+            cell_spans.push(Span::raw(chars.vertical));
         }
         
         lines.push(Line::from(cell_spans));
         
         // Middle or bottom border
         if row < 2 {
-            lines.push(Line::from("├───────┼───────┼───────┤"));
+            lines.push(Line::from(chars.rule(chars.middle, 3, 7)));
         } else {
-            lines.push(Line::from("└───────┴───────┴───────┘"));
+            lines.push(Line::from(chars.rule(chars.bottom, 3, 7)));
         }
     }
     
@@ -528,38 +1651,285 @@ fn render_eight_puzzle_board(state: &EightPuzzleState, selected_cell: usize) ->
 }
 
 fn handle_eight_queens_key(code: KeyCode, app: &mut App) {
-    match code {
-        KeyCode::Char('r') | KeyCode::Char('R') => app.eight_queens.reset(),
-        KeyCode::Char('h') | KeyCode::Char('H') => app.eight_queens.shuffle(),
-        KeyCode::Char('s') | KeyCode::Char('S') => app.eight_queens.solve(),
-        KeyCode::Char(' ') | KeyCode::Enter => {
-            // If solution exists, step through it; otherwise toggle queen
-            if app.eight_queens.solution.is_some() {
-                app.eight_queens.advance_solution();
-            } else {
-                app.eight_queens.toggle_queen();
+    // P pages through the solution list here, so it wins over its default StepBack binding
+    if matches!(code, KeyCode::Char('p') | KeyCode::Char('P')) {
+        app.eight_queens.previous_solution();
+        return;
+    }
+    // Likewise A runs simulated annealing rather than moving left
+    if matches!(code, KeyCode::Char('a') | KeyCode::Char('A')) {
+        app.eight_queens.solve_with(QueensAlgorithm::SimulatedAnnealing);
+        return;
+    }
+    match app.keymap.action(code) {
+        Some(Action::Reset) => app.eight_queens.reset(),
+        Some(Action::Shuffle) => app.eight_queens.shuffle(),
+        Some(Action::Solve) => app.eight_queens.solve(),
+        Some(Action::StepBack) => {
+            app.eight_queens.rewind_solution();
+        }
+        // Space only replays and Enter only edits, so a loaded solution
+        // never swallows a placement
+        Some(Action::StepForward) => {
+            app.eight_queens.advance_solution();
+        }
+        Some(Action::Select) => {
+            app.eight_queens.toggle_queen();
+        }
+        Some(Action::MoveUp) => app.eight_queens.move_cursor(-1, 0, app.wrap_cursor),
+        Some(Action::MoveDown) => app.eight_queens.move_cursor(1, 0, app.wrap_cursor),
+        Some(Action::MoveLeft) => app.eight_queens.move_cursor(0, -1, app.wrap_cursor),
+        Some(Action::MoveRight) => app.eight_queens.move_cursor(0, 1, app.wrap_cursor),
+        _ => match code {
+            KeyCode::Char('g') | KeyCode::Char('G') => app.open_seed_prompt(),
+            KeyCode::Char('t') | KeyCode::Char('T') => app.eight_queens.show_hint(),
+            KeyCode::Char('l') | KeyCode::Char('L') => app.eight_queens.cycle_node_limit(),
+            KeyCode::Char('m') | KeyCode::Char('M') => app.eight_queens.toggle_algorithm(),
+            KeyCode::Char('x') | KeyCode::Char('X') => app.eight_queens.toggle_attack_overlay(),
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                app.eight_queens.next_solution();
+            }
+            _ => {}
+        },
+    }
+}
+
+fn handle_tower_of_hanoi_key(code: KeyCode, app: &mut App) {
+    match app.keymap.action(code) {
+        Some(Action::Reset) => app.tower_of_hanoi.reset(),
+        Some(Action::Solve) => app.tower_of_hanoi.solve(),
+        Some(Action::StepForward) => {
+            app.tower_of_hanoi.advance_solution();
+        }
+        Some(Action::StepBack) => {
+            app.tower_of_hanoi.rewind_solution();
+        }
+        Some(Action::MoveLeft) => app.tower_of_hanoi.move_selection(-1),
+        Some(Action::MoveRight) => app.tower_of_hanoi.move_selection(1),
+        Some(Action::Select) => {
+            let peg = app.tower_of_hanoi.selected_peg;
+            app.tower_of_hanoi.select_peg(peg);
+        }
+        _ => match code {
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                let disks = app.tower_of_hanoi.state.disks;
+                app.tower_of_hanoi.set_disks(disks.saturating_add(1));
+            }
+            KeyCode::Char('-') | KeyCode::Char('_') => {
+                let disks = app.tower_of_hanoi.state.disks;
+                app.tower_of_hanoi.set_disks(disks.saturating_sub(1));
             }
+            KeyCode::Char(digit) if digit.is_ascii_digit() => {
+                if let Some(index) = digit_to_index(digit) {
+                    app.tower_of_hanoi.select_peg(index);
+                }
+            }
+            _ => {}
+        },
+    }
+}
+
+fn handle_knights_tour_key(code: KeyCode, app: &mut App) {
+    match app.keymap.action(code) {
+        Some(Action::Reset) => app.knights_tour.reset(),
+        Some(Action::Solve) => app.knights_tour.solve(),
+        Some(Action::Select | Action::StepForward) => app.knights_tour.select_cursor(),
+        Some(Action::MoveUp) => app.knights_tour.move_cursor(-1, 0, app.wrap_cursor),
+        Some(Action::MoveDown) => app.knights_tour.move_cursor(1, 0, app.wrap_cursor),
+        Some(Action::MoveLeft) => app.knights_tour.move_cursor(0, -1, app.wrap_cursor),
+        Some(Action::MoveRight) => app.knights_tour.move_cursor(0, 1, app.wrap_cursor),
+        _ => match code {
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                let size = app.knights_tour.state.size;
+                app.knights_tour.set_size(size.saturating_add(1));
+            }
+            KeyCode::Char('-') | KeyCode::Char('_') => {
+                let size = app.knights_tour.state.size;
+                app.knights_tour.set_size(size.saturating_sub(1));
+            }
+            KeyCode::Char('m') | KeyCode::Char('M') => app.knights_tour.cycle_heuristic(),
+            _ => {}
+        },
+    }
+}
+
+fn handle_water_jug_key(code: KeyCode, app: &mut App) {
+    let action = app.keymap.action(code);
+    let session = &mut app.water_jug;
+    if session.editing {
+        match action {
+            Some(Action::Select) => session.toggle_editing(),
+            Some(Action::MoveLeft) => session.move_selection(-1),
+            Some(Action::MoveRight) => session.move_selection(1),
+            Some(Action::MoveUp) => session.adjust_field(1),
+            Some(Action::MoveDown) => session.adjust_field(-1),
+            _ => match code {
+                KeyCode::Char('e') | KeyCode::Char('E') => session.toggle_editing(),
+                KeyCode::Char('+') | KeyCode::Char('=') => session.add_jug(),
+                KeyCode::Char('-') | KeyCode::Char('_') => session.remove_jug(),
+                _ => {}
+            },
         }
-        KeyCode::Up | KeyCode::Char('w') | KeyCode::Char('W') => {
-            app.eight_queens.move_cursor(-1, 0);
+        return;
+    }
+    match action {
+        Some(Action::Reset) => session.reset(),
+        Some(Action::Solve) => session.solve(),
+        Some(Action::StepForward) => {
+            session.advance_solution();
         }
-        KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => {
-            app.eight_queens.move_cursor(1, 0);
+        Some(Action::StepBack) => {
+            session.rewind_solution();
         }
-        KeyCode::Left | KeyCode::Char('a') | KeyCode::Char('A') => {
-            app.eight_queens.move_cursor(0, -1);
+        Some(Action::MoveLeft) => session.move_selection(-1),
+        Some(Action::MoveRight) => session.move_selection(1),
+        Some(Action::Select) => {
+            session.pour_selected();
         }
-        KeyCode::Right | KeyCode::Char('d') | KeyCode::Char('D') => {
-            app.eight_queens.move_cursor(0, 1);
+        _ => match code {
+            KeyCode::Char('e') | KeyCode::Char('E') => session.toggle_editing(),
+            KeyCode::Char('f') | KeyCode::Char('F') => {
+                session.fill_selected();
+            }
+            KeyCode::Char('x') | KeyCode::Char('X') => {
+                session.empty_selected();
+            }
+            KeyCode::Char(digit) if digit.is_ascii_digit() => {
+                if let Some(index) = digit_to_index(digit) {
+                    session.load_preset(index);
+                }
+            }
+            _ => {}
+        },
+    }
+}
+
+fn handle_lights_out_key(code: KeyCode, app: &mut App) {
+    match app.keymap.action(code) {
+        Some(Action::Reset) => app.lights_out.reset(),
+        Some(Action::Solve) => app.lights_out.solve(),
+        Some(Action::Shuffle) => app.lights_out.shuffle(),
+        Some(Action::Select | Action::StepForward) => {
+            app.lights_out.press_cursor();
         }
-        _ => {}
+        Some(Action::MoveUp) => app.lights_out.move_cursor(-1, 0, app.wrap_cursor),
+        Some(Action::MoveDown) => app.lights_out.move_cursor(1, 0, app.wrap_cursor),
+        Some(Action::MoveLeft) => app.lights_out.move_cursor(0, -1, app.wrap_cursor),
+        Some(Action::MoveRight) => app.lights_out.move_cursor(0, 1, app.wrap_cursor),
+        _ => match code {
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                let size = app.lights_out.state.size;
+                app.lights_out.set_size(size.saturating_add(1));
+            }
+            KeyCode::Char('-') | KeyCode::Char('_') => {
+                let size = app.lights_out.state.size;
+                app.lights_out.set_size(size.saturating_sub(1));
+            }
+            _ => {}
+        },
+    }
+}
+
+fn handle_sokoban_key(code: KeyCode, app: &mut App) {
+    let session = &mut app.sokoban;
+    match app.keymap.action(code) {
+        Some(Action::Reset) => session.reset(),
+        Some(Action::Solve) => session.solve(),
+        Some(Action::StepForward) => {
+            session.advance_solution();
+        }
+        Some(Action::StepBack) => {
+            session.rewind_solution();
+        }
+        Some(Action::MoveUp) => {
+            session.move_player(SokobanMove::Up);
+        }
+        Some(Action::MoveDown) => {
+            session.move_player(SokobanMove::Down);
+        }
+        Some(Action::MoveLeft) => {
+            session.move_player(SokobanMove::Left);
+        }
+        Some(Action::MoveRight) => {
+            session.move_player(SokobanMove::Right);
+        }
+        _ => {
+            if let KeyCode::Char(digit) = code {
+                if let Some(index) = digit_to_index(digit) {
+                    session.load_level(index);
+                }
+            }
+        }
+    }
+}
+
+fn handle_sudoku_key(code: KeyCode, app: &mut App) {
+    let session = &mut app.sudoku;
+    match app.keymap.action(code) {
+        Some(Action::Reset) => session.reset(),
+        Some(Action::Solve) => session.solve(),
+        Some(Action::StepForward) => {
+            session.advance_solution();
+        }
+        Some(Action::StepBack) => {
+            session.rewind_solution();
+        }
+        Some(Action::MoveUp) => session.move_cursor(-1, 0, app.wrap_cursor),
+        Some(Action::MoveDown) => session.move_cursor(1, 0, app.wrap_cursor),
+        Some(Action::MoveLeft) => session.move_cursor(0, -1, app.wrap_cursor),
+        Some(Action::MoveRight) => session.move_cursor(0, 1, app.wrap_cursor),
+        _ => match code {
+            KeyCode::Char('n') | KeyCode::Char('N') => session.next_puzzle(),
+            KeyCode::Delete => {
+                session.set_digit(0);
+            }
+            KeyCode::Char(digit) if digit.is_ascii_digit() => {
+                if let Some(digit) = digit.to_digit(10) {
+                    session.set_digit(digit as u8);
+                }
+            }
+            _ => {}
+        },
+    }
+}
+
+fn handle_maze_key(code: KeyCode, app: &mut App) {
+    let session = &mut app.maze;
+    match app.keymap.action(code) {
+        Some(Action::Reset) => session.reset(),
+        Some(Action::Solve) => session.solve(),
+        Some(Action::StepForward) => {
+            session.advance_solution();
+        }
+        Some(Action::StepBack) => {
+            session.rewind_solution();
+        }
+        Some(Action::MoveUp) => {
+            session.move_player(MazeMove::Up);
+        }
+        Some(Action::MoveDown) => {
+            session.move_player(MazeMove::Down);
+        }
+        Some(Action::MoveLeft) => {
+            session.move_player(MazeMove::Left);
+        }
+        Some(Action::MoveRight) => {
+            session.move_player(MazeMove::Right);
+        }
+        _ => match code {
+            KeyCode::Char('g') | KeyCode::Char('G') => session.generate(),
+            KeyCode::Char('+') | KeyCode::Char('=') => session.resize(1),
+            KeyCode::Char('-') | KeyCode::Char('_') => session.resize(-1),
+            _ => {}
+        },
     }
 }
 
 fn render_xor_ttt(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
     let descriptor = app.registry.descriptor(PuzzleId::XorTicTacToe);
-    let title = descriptor.map(|d| d.name).unwrap_or("XOR Tic-Tac-Toe");
-    let summary = descriptor.map(|d| d.summary).unwrap_or("");
+    let title = descriptor.map(|d| app.language.puzzle_name(d)).unwrap_or("XOR Tic-Tac-Toe");
+    let summary = descriptor.map(|d| app.language.puzzle_summary(d)).unwrap_or("");
     let session = &app.xor_ttt;
 
     let outer = Layout::default()
@@ -579,11 +1949,22 @@ fn render_xor_ttt(frame: &mut Frame, app: &App) {
         " [PLAYING]"
     };
     
-    let header = Paragraph::new(format!("{}{}", title, mode_indicator))
+    let players = match session.mode {
+        GameMode::VsAi => format!(
+            "You: {} — AI: {}",
+            format_player(session.human_symbol),
+            session.ai_difficulty.label()
+        ),
+        GameMode::TwoPlayer => format!(
+            "Two players — {} to move",
+            format_player(session.state.to_move)
+        ),
+    };
+    let header = Paragraph::new(format!("{}{} — {}", title, mode_indicator, players))
         .alignment(Alignment::Center)
         .style(
             Style::default()
-                .fg(Color::Green)
+                .fg(theme.accent(Color::Green))
                 .add_modifier(Modifier::BOLD),
         )
         .block(Block::default().borders(Borders::ALL));
@@ -594,7 +1975,7 @@ fn render_xor_ttt(frame: &mut Frame, app: &App) {
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(outer[1]);
 
-    let board_lines = render_tic_tac_toe_board(session);
+    let board_lines = render_tic_tac_toe_board(session, theme, app.ascii_mode);
     let board_title = if session.setup_mode {
         "Board [SETUP MODE]"
     } else {
@@ -620,9 +2001,13 @@ fn render_xor_ttt(frame: &mut Frame, app: &App) {
     frame.render_widget(summary_block, info_chunks[0]);
 
     let info_text = format!(
-        "Next player: {}\nCursor cell: {}\nWinner: {}\nBoard full: {}",
+        "Next player: {}\nCursor cell: {} (minimax {})\nWinner: {}\nBoard full: {}",
         format_player(session.state.to_move),
         session.cursor + 1,
+        session
+            .cell_score(session.cursor)
+            .map(|score| format!("{:+}", score))
+            .unwrap_or_else(|| "—".into()),
         session.state.winner().map(format_player).unwrap_or("—"),
         if session.state.is_full() { "Yes" } else { "No" }
     );
@@ -630,10 +2015,7 @@ fn render_xor_ttt(frame: &mut Frame, app: &App) {
         Paragraph::new(info_text).block(Block::default().title("State").borders(Borders::ALL));
     frame.render_widget(info_block, info_chunks[1]);
 
-    let status_block = Paragraph::new(session.status.as_str())
-        .block(Block::default().title("Status").borders(Borders::ALL))
-        .wrap(Wrap { trim: true });
-    frame.render_widget(status_block, info_chunks[2]);
+    render_status(frame, app, PuzzleId::XorTicTacToe, &session.status, info_chunks[2]);
 
     let instructions_area = Layout::default()
         .direction(Direction::Vertical)
@@ -641,7 +2023,7 @@ fn render_xor_ttt(frame: &mut Frame, app: &App) {
         .split(outer[2]);
     
     let instructions = Paragraph::new(
-        "Controls: Tab setup mode • ←→↑↓ move cursor • X/O place pieces • 1-9 quick place • Space toggle • H shuffle • S auto-move • R restart • B back • Q quit",
+        "Controls: Tab setup mode • ←→↑↓ move cursor • X/O place pieces • 1-9 quick place • Space toggle • H shuffle • S auto-move • L AI difficulty • F swap sides • M two-player • T tournament • R restart • B back • Q quit",
     )
     .alignment(Alignment::Center)
     .block(Block::default().borders(Borders::ALL))
@@ -650,19 +2032,20 @@ fn render_xor_ttt(frame: &mut Frame, app: &App) {
     
     let footer = Paragraph::new("Adel Enazi")
         .alignment(Alignment::Center)
-        .style(Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM));
+        .style(theme.footer);
     frame.render_widget(footer, instructions_area[1]);
 }
 
-fn render_tic_tac_toe_board(session: &XorTicTacToeSession) -> Vec<Line<'static>> {
+fn render_tic_tac_toe_board(session: &XorTicTacToeSession, theme: &Theme, ascii_mode: bool) -> Vec<Line<'static>> {
+    let chars = box_chars(ascii_mode);
     let mut lines = Vec::new();
     
     // Top border
-    lines.push(Line::from("┌───────┬───────┬───────┐"));
+    lines.push(Line::from(chars.rule(chars.top, 3, 7)));
     
     for row in 0..3 {
         let mut cell_spans = Vec::new();
-        cell_spans.push(Span::raw("│"));
+        cell_spans.push(Span::raw(chars.vertical));
         
         for col in 0..3 {
             let idx = row * 3 + col;
@@ -672,84 +2055,1737 @@ fn render_tic_tac_toe_board(session: &XorTicTacToeSession) -> Vec<Line<'static>>
             let symbol = match cell_value {
                 Some(Player::X) => "X",
                 Some(Player::O) => "O",
+                None if ascii_mode => ".",
                 None => "·",
             };
             
             let style = if is_selected {
                 if cell_value.is_none() && (!session.is_locked() || session.setup_mode) {
-                    Style::default()
-                        .fg(Color::Black)
-                        .bg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD)
+                    theme.highlight
                 } else {
-                    Style::default()
-                        .fg(Color::Black)
-                        .bg(Color::Yellow)
+                    theme.highlight.remove_modifier(Modifier::BOLD)
                 }
             } else if cell_value == Some(Player::O) {
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.o)
                     .add_modifier(Modifier::BOLD)
             } else if cell_value == Some(Player::X) {
                 Style::default()
-                    .fg(Color::Magenta)
+                    .fg(theme.x)
                     .add_modifier(Modifier::BOLD)
             } else {
-                Style::default()
-                    .fg(Color::DarkGray)
-                    .add_modifier(Modifier::DIM)
+                theme.dim
             };
             
-            // Center the symbol in a 7-character wide cell
-            let content = format!("   {}   ", symbol);
+            // Center the symbol in a 7-character wide cell; the hovered empty
+            // cell shows its minimax score instead.
+            let content = match session.cell_score(idx) {
+                Some(score) if is_selected => format!("{:^7}", format!("{:+}", score)),
+                _ => format!("   {}   ", symbol),
+            };
             
             cell_spans.push(Span::styled(content, style));
-            cell_spans.push(Span::raw("│"));
+            cell_spans.push(Span::raw(chars.vertical));
         }
         
         lines.push(Line::from(cell_spans));
         
         // Middle or bottom border
         if row < 2 {
-            lines.push(Line::from("├───────┼───────┼───────┤"));
+            lines.push(Line::from(chars.rule(chars.middle, 3, 7)));
         } else {
-            lines.push(Line::from("└───────┴───────┴───────┘"));
+            lines.push(Line::from(chars.rule(chars.bottom, 3, 7)));
         }
     }
     
     lines
 }
 
-fn format_duration(duration: Duration) -> String {
-    if duration.as_secs_f64() < 1.0 {
-        format!("{:.2} ms", duration.as_secs_f64() * 1_000.0)
+fn format_duration(duration: Duration) -> String {
+    if duration.as_secs_f64() < 1.0 {
+        format!("{:.2} ms", duration.as_secs_f64() * 1_000.0)
+    } else {
+        format!("{:.2} s", duration.as_secs_f64())
+    }
+}
+
+/// Longer spans as `h:mm:ss`.
+fn format_hours(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{}:{:02}:{:02}", secs / 3_600, secs / 60 % 60, secs % 60)
+}
+
+/// Running time as `mm:ss.mmm`.
+fn format_clock(duration: Duration) -> String {
+    let millis = duration.as_millis();
+    format!("{:02}:{:02}.{:03}", millis / 60_000, millis / 1_000 % 60, millis % 1_000)
+}
+
+/// Move timer as `mm:ss`, `00:00` until the first move.
+fn format_timer(timer: &MoveTimer) -> String {
+    let secs = timer.elapsed().unwrap_or_default().as_secs();
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+fn state_title(timer: &MoveTimer) -> String {
+    format!("State — Time: {}", format_timer(timer))
+}
+
+/// `1234567` as `1,234,567`.
+fn format_thousands(count: usize) -> String {
+    let digits = count.to_string();
+    let mut formatted = String::new();
+    for (idx, digit) in digits.chars().enumerate() {
+        if idx > 0 && (digits.len() - idx).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
+/// Byte count in the largest whole unit, e.g. `45 MB`.
+fn format_bytes(bytes: usize) -> String {
+    const KB: usize = 1024;
+    const MB: usize = KB * 1024;
+    if bytes >= MB {
+        format!("{} MB", (bytes + MB / 2) / MB)
+    } else if bytes >= KB {
+        format!("{} KB", (bytes + KB / 2) / KB)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+/// Solver panel in comparison mode: one striped row per run from the current
+/// start state, with the run that expanded the fewest nodes in green.
+fn render_comparison<S: SearchState>(frame: &mut Frame, theme: &Theme, compare: &CompareReports<S>, area: Rect) {
+    let block = Block::default()
+        .title("Comparison (C for details)")
+        .borders(Borders::ALL);
+    if compare.rows.is_empty() {
+        let empty = Paragraph::new("No runs from this start state yet.\nPress S to solve; every run adds a row.")
+            .block(block)
+            .style(theme.dim)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let fewest = compare
+        .rows
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, (_, report))| report.expanded_nodes)
+        .map(|(idx, _)| idx);
+    let rows: Vec<Row> = compare
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(idx, (label, report))| {
+            let steps = if report.goal_found {
+                report.path.len().saturating_sub(1).to_string()
+            } else {
+                "—".to_string()
+            };
+            let mut style = if idx % 2 == 1 {
+                Style::default().bg(theme.accent(Color::DarkGray))
+            } else {
+                Style::default()
+            };
+            if Some(idx) == fewest {
+                style = style.fg(theme.accent(Color::Green));
+            }
+            Row::new(vec![
+                Cell::from(*label),
+                Cell::from(steps),
+                Cell::from(format_thousands(report.expanded_nodes)),
+                Cell::from(format_bytes(report.peak_memory_bytes)),
+                Cell::from(format_duration(report.elapsed)),
+            ])
+            .style(style)
+        })
+        .collect();
+    let header = Row::new(vec!["Algorithm", "Steps", "Nodes Expanded", "Peak RAM", "Time"])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+    let widths = [
+        Constraint::Min(12),
+        Constraint::Length(6),
+        Constraint::Length(15),
+        Constraint::Length(9),
+        Constraint::Length(10),
+    ];
+    let table = Table::new(rows, widths).header(header).block(block);
+    frame.render_widget(table, area);
+}
+
+/// Peak memory and frontier lines shared by the solver panels.
+fn memory_stats<S: SearchState>(report: &SearchReport<S>) -> String {
+    format!(
+        "Peak RAM: {}\nMax frontier: {} nodes",
+        format_bytes(report.peak_memory_bytes),
+        format_thousands(report.max_open_size)
+    )
+}
+
+fn branching_stats<S: SearchState>(report: &SearchReport<S>) -> String {
+    format!(
+        "Solution depth: {}\nBranching factor (EBF): {:.2}",
+        report.solution_depth, report.effective_branching_factor
+    )
+}
+
+fn format_player(player: Player) -> &'static str {
+    match player {
+        Player::X => "X",
+        Player::O => "O",
+    }
+}
+
+fn digit_to_index(ch: char) -> Option<usize> {
+    ch.to_digit(10).and_then(|d| {
+        if (1..=9).contains(&d) {
+            Some((d - 1) as usize)
+        } else {
+            None
+        }
+    })
+}
+
+fn render_missionaries_cannibals(frame: &mut Frame, app: &App, layout: &mut LayoutCache) {
+    let theme = &app.theme;
+    let descriptor = app.registry.descriptor(PuzzleId::MissionariesCannibals);
+    let title = descriptor.map(|d| app.language.puzzle_name(d)).unwrap_or("Missionaries & Cannibals");
+    let summary = descriptor.map(|d| app.language.puzzle_summary(d)).unwrap_or("");
+    let session = &app.missionaries_cannibals;
+
+    let outer = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(20),
+            Constraint::Length(4),
+        ])
+        .split(frame.size());
+
+    let header = Paragraph::new(format!(
+        "{} — {}",
+        title,
+        if session.is_solved() {
+            app.strings().solved
+        } else {
+            app.strings().in_progress
+        }
+    ))
+    .alignment(Alignment::Center)
+    .style(
+        Style::default()
+            .fg(theme.accent(Color::Cyan))
+            .add_modifier(Modifier::BOLD),
+    )
+    .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(header, outer[0]);
+
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(outer[1]);
+
+    let state_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(12), Constraint::Min(8)])
+        .split(body[0]);
+
+    let mut state_lines = render_mc_state(&session.state, app.ascii_mode);
+    if let Some((mv, rightward)) = session.last_crossing {
+        let arrow = match (rightward, app.ascii_mode) {
+            (true, false) => "→",
+            (false, false) => "←",
+            (true, true) => "->",
+            (false, true) => "<-",
+        };
+        let mut annotation = format!("{} crossing {}", mv.passengers(), arrow);
+        // While replaying, say which step of the solution this is
+        if let Some(solution) = session
+            .solution
+            .as_ref()
+            .filter(|solution| solution.report.path.get(solution.step) == Some(&session.state))
+        {
+            annotation = format!("Step {}: {}", solution.step, annotation);
+        }
+        state_lines.insert(
+            0,
+            Line::from(Span::styled(
+                annotation,
+                Style::default().fg(theme.accent(Color::Cyan)).add_modifier(Modifier::BOLD),
+            )),
+        );
+    }
+    let mut counter = vec![Span::raw(format!(
+        "Crossings: {} · Optimal: {}",
+        session.moves_made,
+        session.optimal_moves.map_or("? (press S)".to_string(), |moves| moves.to_string())
+    ))];
+    if session.is_optimal() {
+        counter.push(Span::styled(
+            "  Optimal!",
+            Style::default().fg(theme.accent(Color::Green)).add_modifier(Modifier::BOLD),
+        ));
+    }
+    state_lines.push(Line::from(""));
+    state_lines.push(Line::from(counter));
+    let state_block = Paragraph::new(state_lines)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .title(format!(
+                    "Current State ({} pairs, boat holds {}) — Time: {}",
+                    session.state.n,
+                    session.state.boat_capacity,
+                    format_timer(&session.timer)
+                ))
+                .borders(Borders::ALL),
+        );
+    frame.render_widget(state_block, state_area[0]);
+
+    let valid_moves = session.get_valid_moves();
+    let moves_text = if valid_moves.is_empty() {
+        "No valid moves available.".into()
+    } else {
+        valid_moves
+            .iter()
+            .enumerate()
+            .map(|(idx, mv)| {
+                let marker = if idx == session.selected_move { ">" } else { " " };
+                format!("{} {}. Move {}M {}C", marker, idx + 1, mv.missionaries, mv.cannibals)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let moves_block = Paragraph::new(moves_text)
+        .block(Block::default().title("Valid Moves").borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(moves_block, state_area[1]);
+    layout.mc_moves = Some(state_area[1]);
+
+    let info_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4),
+            Constraint::Min(8),
+            Constraint::Length(3),
+            Constraint::Length(4),
+        ])
+        .split(body[1]);
+
+    let summary_block = Paragraph::new(summary)
+        .block(Block::default().title("Summary").borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(summary_block, info_chunks[0]);
+
+    let solver_text = match &session.solution {
+        _ if session.is_stepping() => {
+            let (frontier, closed) = session
+                .step_solver
+                .as_ref()
+                .map_or((0, 0), |search| (search.frontier_len(), search.closed_len()));
+            let node = match session.step_solver.as_ref().and_then(|search| search.current()) {
+                Some((state, g, h)) => format!(
+                    "Last expanded (f = {} = {} + {}):\nLeft bank: {}M {}C\nBoat on the {} bank",
+                    g + h,
+                    g,
+                    h,
+                    state.left_m,
+                    state.left_c,
+                    if state.boat_left { "left" } else { "right" }
+                ),
+                None => "Nothing expanded yet.".into(),
+            };
+            format!(
+                "Step mode: watching A*\n\nFrontier (open): {}\nClosed (expanded): {}\n\n{}\n\nSpace/Enter expands the next\nnode, I leaves step mode.",
+                frontier, closed, node
+            )
+        }
+        Some(solution) => {
+            let stats = format!(
+                "Steps total: {}\nCurrent step: {}\nExpanded nodes: {}\nVisited states: {}\n{}\nElapsed: {}\n{}",
+                solution.total_steps(),
+                solution.step,
+                solution.report.expanded_nodes,
+                solution.report.visited_states,
+                branching_stats(&solution.report),
+                format_duration(solution.report.elapsed),
+                memory_stats(&solution.report)
+            );
+            let explanation = format!(
+                "\n\nA* Algorithm Explanation:\n\nA* finds the shortest path\nusing: f(n) = g(n) + h(n)\n\n• g(n) = actual moves\n  from start to here\n• h(n) = estimated moves\n  to goal (boat crossings)\n\nMetrics:\n• Expanded nodes: {}\n  States we fully explored\n  (checked all neighbors)\n\n• Visited states: {}\n  All states we've seen\n  (in queue + explored)",
+                solution.report.expanded_nodes,
+                solution.report.visited_states
+            );
+            format!("{}{}", stats, explanation)
+        },
+        None => "Press S to run the A* solver.\n\nA* Algorithm:\nFinds optimal paths using:\nf(n) = g(n) + h(n)\n\n• g(n) = actual cost\n  from start\n• h(n) = heuristic\n  (boat crossings left)\n\nExpanded nodes: States\nwe fully explored.\nVisited states: All states\nwe've encountered.".into(),
+    };
+    let solver_block = Paragraph::new(solver_text)
+        .block(titled_block(app, app.strings().solver))
+        .wrap(Wrap { trim: true });
+    if session.compare_reports.showing_table() {
+        render_comparison(frame, &app.theme, &session.compare_reports, info_chunks[1]);
+    } else {
+        frame.render_widget(solver_block, info_chunks[1]);
+    }
+
+    render_best(frame, app, PuzzleId::MissionariesCannibals, info_chunks[2]);
+
+    render_status(frame, app, PuzzleId::MissionariesCannibals, &session.status, info_chunks[3]);
+
+    let instructions_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(outer[2]);
+    
+    let instructions = Paragraph::new(
+        "Controls: 1-9 apply move • ↑↓ navigate moves • S solve • Space/Backspace step fwd/back • +/- group size • </> boat size • H shuffle • G seed • R reset • B back • Q quit",
+    )
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL))
+    .wrap(Wrap { trim: true });
+    frame.render_widget(instructions, instructions_area[0]);
+    
+    let footer = Paragraph::new("Adel Enazi")
+        .alignment(Alignment::Center)
+        .style(theme.footer);
+    frame.render_widget(footer, instructions_area[1]);
+}
+
+fn render_mc_state(state: &MissionariesCannibalsState, ascii_mode: bool) -> Vec<Line<'static>> {
+    let right_m = state.right_m();
+    let right_c = state.right_c();
+    let chars = box_chars(ascii_mode);
+    let side = |content: String| format!("{}{:<25}{}", chars.vertical, content, chars.vertical);
+    let (boat, no_boat) = if ascii_mode { ("[B]", "   ") } else { ("🚤", "  ") };
+    let water = if ascii_mode { "===============" } else { "═══════════════" };
+    
+    let mut lines = Vec::new();
+    
+    // All lines are exactly 27 characters wide (including borders)
+    // Content width is 25 (excluding │ on each side)
+    
+    // Top border
+    lines.push(Line::from(chars.rule(chars.top, 1, 25)));
+    
+    // Empty line
+    lines.push(Line::from(side(String::new())));
+    
+    // Left side - ensure exact width
+    let boat_left_str = if state.boat_left { boat } else { no_boat };
+    let left_content = format!("LEFT:  M={} C={} {}", state.left_m, state.left_c, boat_left_str);
+    lines.push(Line::from(side(left_content)));
+    
+    // Empty line
+    lines.push(Line::from(side(String::new())));
+    
+    // River separator
+    lines.push(Line::from(side(format!("{:^25}", water))));
+    
+    // River label - centered
+    lines.push(Line::from(side(format!("{:^25}", "RIVER"))));
+    
+    // River separator
+    lines.push(Line::from(side(format!("{:^25}", water))));
+    
+    // Empty line
+    lines.push(Line::from(side(String::new())));
+    
+    // Right side - ensure exact width
+    let boat_right_str = if !state.boat_left { boat } else { no_boat };
+    let right_content = format!("RIGHT: M={} C={} {}", right_m, right_c, boat_right_str);
+    lines.push(Line::from(side(right_content)));
+    
+    // Empty line
+    lines.push(Line::from(side(String::new())));
+    
+    // Bottom border
+    lines.push(Line::from(chars.rule(chars.bottom, 1, 25)));
+    
+    lines
+}
+
+fn render_eight_queens(frame: &mut Frame, app: &App, layout: &mut LayoutCache) {
+    let theme = &app.theme;
+    let descriptor = app.registry.descriptor(PuzzleId::EightQueens);
+    let title = descriptor.map(|d| app.language.puzzle_name(d)).unwrap_or("8 Queens Problem");
+    let summary = descriptor.map(|d| app.language.puzzle_summary(d)).unwrap_or("");
+    let session = &app.eight_queens;
+
+    let outer = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(20),
+            Constraint::Length(4),
+        ])
+        .split(frame.size());
+
+    let header = Paragraph::new(format!(
+        "{} — {}",
+        title,
+        if session.is_solved() {
+            app.strings().solved
+        } else {
+            app.strings().in_progress
+        }
+    ))
+    .alignment(Alignment::Center)
+    .style(
+        Style::default()
+            .fg(theme.accent(Color::Magenta))
+            .add_modifier(Modifier::BOLD),
+    )
+    .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(header, outer[0]);
+
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(outer[1]);
+
+    let board_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(18), Constraint::Length(4)])
+        .split(body[0]);
+
+    let queen_color = match session.current_solution_number() {
+        Some(number) => theme.accent(QUEEN_PALETTE[(number - 1) % QUEEN_PALETTE.len()]),
+        None => theme.queen,
+    };
+    let board_lines = render_queens_board(
+        &session.state,
+        (session.selected_row, session.selected_col),
+        session.active_hint(),
+        session.show_attacks,
+        queen_color,
+        theme,
+        app.ascii_mode,
+    );
+    let board_block = Paragraph::new(board_lines)
+        .alignment(Alignment::Center)
+        .block(Block::default().title("Chessboard").borders(Borders::ALL));
+    frame.render_widget(board_block, board_area[0]);
+    layout.queens_board = Some(board_area[0]);
+
+    let stats_text = format!(
+        "Queens placed: {}/8\nConflicts: {}\nHeuristic: {}",
+        session.state.queens.iter().filter(|q| q.is_some()).count(),
+        session.state.count_conflicts(),
+        session.state.heuristic()
+    );
+    let stats_block = Paragraph::new(stats_text)
+        .block(Block::default().title(state_title(&session.timer)).borders(Borders::ALL));
+    frame.render_widget(stats_block, board_area[1]);
+
+    let info_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4),
+            Constraint::Min(8),
+            Constraint::Length(3),
+            Constraint::Length(4),
+        ])
+        .split(body[1]);
+
+    let summary_block = Paragraph::new(summary)
+        .block(Block::default().title("Summary").borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(summary_block, info_chunks[0]);
+
+    let solver_text = match &session.solution {
+        Some(solution) if solution.algorithm == QueensAlgorithm::MinConflicts => {
+            format!(
+                "Algorithm: {}\nRepair steps: {}\nCurrent step: {}\nElapsed: {}\n\nMin-conflicts Explanation:\n\nStart with one queen per\nrow, then repeatedly move\nthe most attacked queen to\nthe column with the fewest\nattackers, for at most\n{} steps.",
+                solution.algorithm.label(),
+                solution.report.expanded_nodes,
+                solution.step,
+                format_duration(solution.report.elapsed),
+                eight_queens::MIN_CONFLICTS_MAX_STEPS
+            )
+        }
+        Some(solution) if solution.algorithm == QueensAlgorithm::SimulatedAnnealing => {
+            let temperature = eight_queens::annealing_temperature(
+                eight_queens::ANNEALING_INITIAL_TEMP,
+                eight_queens::ANNEALING_COOLING,
+                solution.report.expanded_nodes,
+            );
+            let conflicts = solution
+                .report
+                .path
+                .get(solution.step)
+                .map_or(0, |state| state.count_conflicts());
+            format!(
+                "Algorithm: {}\nExpanded nodes: {}\nAccepted moves: {}\nCurrent step: {}\nTemperature: {:.4}\nConflicts: {}\nElapsed: {}\n\nSimulated Annealing:\n\nMove a random queen to a\nrandom column. Better boards\nare always kept; worse ones\nwith chance e^(-ΔE/T), which\nshrinks as T cools. Each\nrun differs - press A again.",
+                solution.algorithm.label(),
+                solution.report.expanded_nodes,
+                solution.total_steps(),
+                solution.step,
+                temperature,
+                conflicts,
+                format_duration(solution.report.elapsed)
+            )
+        }
+        Some(solution) => {
+            let stats = format!(
+                "Algorithm: {}{}\nSteps total: {}\nCurrent step: {}\nExpanded nodes: {}\nVisited states: {}\n{}\nElapsed: {}\n{}",
+                solution.algorithm.label(),
+                if solution.algorithm.is_optimal() { "" } else { " (not guaranteed optimal)" },
+                solution.total_steps(),
+                solution.step,
+                solution.report.expanded_nodes,
+                solution.report.visited_states,
+                branching_stats(&solution.report),
+                format_duration(solution.report.elapsed),
+                memory_stats(&solution.report)
+            );
+            let explanation = if solution.algorithm == QueensAlgorithm::UniformCost {
+                format!(
+                    "\n\nUniform-cost Explanation:\n\nA* with h(n) = 0: the open\nlist is ordered by g(n), the\nqueens placed so far, alone.\nIt finds the same path\nlength as A* but expands\nmore nodes without the\nheuristic to guide it.\n\nMetrics:\n• Expanded nodes: {}\n• Visited states: {}",
+                    solution.report.expanded_nodes,
+                    solution.report.visited_states
+                )
+            } else if solution.algorithm == QueensAlgorithm::IdaStar {
+                format!(
+                    "\n\nIDA* Explanation:\n\nDepth-first passes cut off\nat an f(n) bound that rises\nafter each pass, holding\nonly the current path.\n\nEstimated nodes at final\nthreshold: {}\n(b^d with b = {:.2} safe\nsquares per empty row)",
+                    format_thousands(solution.estimated_final_pass_nodes() as usize),
+                    solution.report.path.first().map_or(0.0, |start| start.branching_factor_estimate())
+                )
+            } else if solution.algorithm == QueensAlgorithm::Greedy {
+                format!(
+                    "\n\nGreedy Explanation:\n\nThe open list is ordered by\nh(n) alone, so the search\nrushes toward boards that\nlook closest to the goal,\nignoring g(n). It expands\nfew nodes, but the path it\nfinds may not be the\nshortest.\n\nMetrics:\n• Expanded nodes: {}\n• Visited states: {}",
+                    solution.report.expanded_nodes,
+                    solution.report.visited_states
+                )
+            } else {
+                format!(
+                    "\n\nA* Algorithm Explanation:\n\nA* finds the shortest path\nusing: f(n) = g(n) + h(n)\n\n• g(n) = actual moves\n  from start to here\n• h(n) = estimated cost\n  (conflicts + missing)\n\nMetrics:\n• Expanded nodes: {}\n  States we fully explored\n  (checked all neighbors)\n\n• Visited states: {}\n  All states we've seen\n  (in queue + explored)",
+                    solution.report.expanded_nodes,
+                    solution.report.visited_states
+                )
+            };
+            format!("{}{}", stats, explanation)
+        },
+        None => match session.algorithm {
+            QueensAlgorithm::AStar => "Press S to run the A* solver.\n\nA* Algorithm:\nFinds optimal paths using:\nf(n) = g(n) + h(n)\n\n• g(n) = actual cost\n  from start\n• h(n) = heuristic\n  (conflicts + missing)\n\nExpanded nodes: States\nwe fully explored.\nVisited states: All states\nwe've encountered.".into(),
+            QueensAlgorithm::MinConflicts => "Press S to run min-conflicts.\n\nMin-conflicts:\nLocal search over complete\nboards. Each repair step\nmoves the most attacked\nqueen to its safest column.\n\nPress M for uniform-cost\nsearch.".into(),
+            QueensAlgorithm::UniformCost => "Press S to run uniform-cost search.\n\nUniform cost:\nA* with the heuristic set\nto 0, so the open list is\nordered by g(n) alone. Run\nA* on the same board and\npress C to compare them.\n\nPress M for greedy\nbest-first search.".into(),
+            QueensAlgorithm::Greedy => "Press S to run greedy best-first search\n(not guaranteed optimal).\n\nGreedy best-first:\nThe open list is ordered\nby h(n) alone. Fast, but\nthe path may be longer\nthan the one A* finds.\n\nPress M for IDA*.".into(),
+            QueensAlgorithm::IdaStar => "Press S to run IDA*.\n\nIDA*:\nIterative-deepening A*:\ndepth-first passes bounded\nby f(n) = g(n) + h(n), the\nbound raised after every\npass. Little memory, some\nnodes expanded again.\n\nPress M to switch back\nto A*.".into(),
+            QueensAlgorithm::SimulatedAnnealing => "Press A to run simulated annealing.".into(),
+        },
+    };
+    // A (simulated annealing) runs without switching the selected algorithm
+    let shown_algorithm = session
+        .solution
+        .as_ref()
+        .map_or(session.algorithm, |solution| solution.algorithm);
+    let solver_text = match shown_algorithm {
+        QueensAlgorithm::AStar | QueensAlgorithm::IdaStar => format!(
+            "Node limit: {}\n{}",
+            session
+                .astar_config
+                .node_limit
+                .map(format_thousands)
+                .unwrap_or_else(|| "unlimited".into()),
+            solver_text
+        ),
+        QueensAlgorithm::MinConflicts
+        | QueensAlgorithm::UniformCost
+        | QueensAlgorithm::Greedy
+        | QueensAlgorithm::SimulatedAnnealing => {
+            solver_text
+        }
+    };
+    let solver_text = match session.current_solution_number() {
+        Some(number) => format!(
+            "Solution {} / {}\n\n{}",
+            number,
+            session.all_solutions.len(),
+            solver_text
+        ),
+        None => solver_text,
+    };
+    let solver_block = Paragraph::new(solver_text)
+        .block(titled_block(app, app.strings().solver))
+        .wrap(Wrap { trim: true });
+    if session.compare_reports.showing_table() {
+        render_comparison(frame, &app.theme, &session.compare_reports, info_chunks[1]);
+    } else {
+        frame.render_widget(solver_block, info_chunks[1]);
+    }
+
+    render_best(frame, app, PuzzleId::EightQueens, info_chunks[2]);
+
+    render_status(frame, app, PuzzleId::EightQueens, &session.status, info_chunks[3]);
+
+    let instructions_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(outer[2]);
+    
+    let instructions = Paragraph::new(
+        "Controls: ←→↑↓ select cell • Enter place/remove queen • T hint • X attacked squares • S solve • M switch algorithm • L node limit • Space/Backspace step fwd/back • N/P next/prev solution • H shuffle • G seed • R reset • B back • Q quit",
+    )
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL))
+    .wrap(Wrap { trim: true });
+    frame.render_widget(instructions, instructions_area[0]);
+    
+    let footer = Paragraph::new("Adel Enazi")
+        .alignment(Alignment::Center)
+        .style(theme.footer);
+    frame.render_widget(footer, instructions_area[1]);
+}
+
+/// Queen colors used while cycling through the enumerated solutions.
+const QUEEN_PALETTE: [Color; 6] = [
+    Color::Red,
+    Color::Cyan,
+    Color::Green,
+    Color::Magenta,
+    Color::Blue,
+    Color::LightYellow,
+];
+
+fn render_queens_board(
+    state: &EightQueensState,
+    selected: (usize, usize),
+    hint: Option<(usize, usize)>,
+    show_attacks: bool,
+    queen_color: Color,
+    theme: &Theme,
+    ascii_mode: bool,
+) -> Vec<Line<'static>> {
+    let chars = box_chars(ascii_mode);
+    let (queen, empty, attacked_mark) = if ascii_mode { ("Q", ".", "x") } else { ("♛", "·", "×") };
+    let mut lines = Vec::new();
+    let attacked = state.attacked_cells();
+    let attack_counts = state.attack_counts();
+    
+    // Top border
+    lines.push(Line::from(chars.rule(chars.top, 1, 31)));
+    
+    // Column numbers
+    let mut col_header = format!("{}   ", chars.vertical);
+    for col in 0..8 {
+        col_header.push_str(&format!("{} ", col + 1));
+    }
+    col_header.push_str(chars.vertical);
+    lines.push(Line::from(col_header));
+    
+    // Separator
+    lines.push(Line::from(chars.rule(chars.middle, 1, 31)));
+    
+    for row in 0..8 {
+        let mut row_spans = Vec::new();
+        row_spans.push(Span::raw(chars.vertical));
+        row_spans.push(Span::raw(format!("{} ", row + 1)));
+        
+        for col in 0..8 {
+            let is_selected = selected == (row, col);
+            let has_queen = state.queens[row].map(|q| q == col as u8).unwrap_or(false);
+            
+            let (symbol, style) = if has_queen {
+                if is_selected {
+                    (queen, theme.highlight)
+                } else {
+                    (queen, Style::default().fg(queen_color).add_modifier(Modifier::BOLD))
+                }
+            } else {
+                if hint == Some((row, col)) {
+                    (queen, hint_style(theme))
+                } else if is_selected {
+                    (empty, theme.highlight)
+                } else if show_attacks && attack_counts[row * 8 + col] >= 2 {
+                    (attacked_mark, Style::default().bg(theme.accent(Color::LightRed)).fg(theme.accent(Color::DarkGray)))
+                } else if show_attacks && attacked[row * 8 + col] {
+                    (attacked_mark, Style::default().bg(theme.conflict).fg(theme.accent(Color::DarkGray)))
+                } else {
+                    // Alternate colors for chessboard pattern
+                    let is_light = (row + col) % 2 == 0;
+                    if is_light {
+                        (empty, Style::default().fg(theme.accent(Color::DarkGray)))
+                    } else {
+                        (empty, Style::default().fg(theme.accent(Color::Gray)))
+                    }
+                }
+            };
+            
+            row_spans.push(Span::styled(format!("{} ", symbol), style));
+        }
+        
+        row_spans.push(Span::raw(chars.vertical));
+        lines.push(Line::from(row_spans));
+    }
+    
+    // Bottom border
+    lines.push(Line::from(chars.rule(chars.bottom, 1, 31)));
+    
+    lines
+}
+
+fn render_tower_of_hanoi(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let descriptor = app.registry.descriptor(PuzzleId::TowerOfHanoi);
+    let title = descriptor.map(|d| app.language.puzzle_name(d)).unwrap_or("Tower of Hanoi");
+    let summary = descriptor.map(|d| app.language.puzzle_summary(d)).unwrap_or("");
+    let session = &app.tower_of_hanoi;
+    let disks = session.state.disks;
+
+    let outer = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(20),
+            Constraint::Length(4),
+        ])
+        .split(frame.size());
+
+    let header = Paragraph::new(format!(
+        "{} — {}",
+        title,
+        if session.is_solved() {
+            app.strings().solved
+        } else {
+            app.strings().in_progress
+        }
+    ))
+    .alignment(Alignment::Center)
+    .style(
+        Style::default()
+            .fg(theme.accent(Color::LightBlue))
+            .add_modifier(Modifier::BOLD),
+    )
+    .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(header, outer[0]);
+
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(outer[1]);
+
+    let board_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(14), Constraint::Length(6)])
+        .split(body[0]);
+
+    let peg_lines = render_hanoi_pegs(&session.state, session.selected_peg, session.held_peg, theme);
+    let pegs_block = Paragraph::new(peg_lines)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .title(format!("Pegs ({} disks)", disks))
+                .borders(Borders::ALL),
+        );
+    frame.render_widget(pegs_block, board_area[0]);
+
+    let stats_text = format!(
+        "Moves made: {}\nOptimal from the start: 2^{} - 1 = {}\nHeuristic: {}\nHolding: {}",
+        session.moves_made,
+        disks,
+        HanoiState::optimal_moves(disks),
+        session.state.heuristic(),
+        session
+            .held_peg
+            .map(|peg| format!("top disk of peg {}", peg + 1))
+            .unwrap_or_else(|| "—".into())
+    );
+    let stats_block = Paragraph::new(stats_text)
+        .block(Block::default().title(state_title(&session.timer)).borders(Borders::ALL));
+    frame.render_widget(stats_block, board_area[1]);
+
+    let info_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4),
+            Constraint::Min(8),
+            Constraint::Length(3),
+            Constraint::Length(4),
+        ])
+        .split(body[1]);
+
+    let summary_block = Paragraph::new(summary)
+        .block(Block::default().title("Summary").borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(summary_block, info_chunks[0]);
+
+    let solver_text = match &session.solution {
+        Some(solution) => format!(
+            "A* steps: {}\nKnown optimum: 2^{} - 1 = {}\nCurrent step: {}\nLast move: {}\nExpanded nodes: {}\nVisited states: {}\nElapsed: {}\n{}\n\nh(n): the largest disk k not yet\non peg 3 still needs 2^(k-1) moves\n(itself plus the smaller disks\nfollowing it), so A* stays optimal.",
+            solution.total_steps(),
+            disks,
+            HanoiState::optimal_moves(disks),
+            solution.step,
+            solution.move_label(solution.step).unwrap_or_else(|| "—".into()),
+            solution.report.expanded_nodes,
+            solution.report.visited_states,
+            format_duration(solution.report.elapsed),
+            memory_stats(&solution.report)
+        ),
+        None => format!(
+            "Press S to run the A* solver.\n\nFor {} disks the optimal solution\nalways takes 2^{} - 1 = {} moves.\n\nh(n) = 2^(k-1), where k is the\nlargest disk not on peg 3.",
+            disks,
+            disks,
+            HanoiState::optimal_moves(disks)
+        ),
+    };
+    let solver_block = Paragraph::new(solver_text)
+        .block(titled_block(app, app.strings().solver))
+        .wrap(Wrap { trim: true });
+    if session.compare_reports.showing_table() {
+        render_comparison(frame, &app.theme, &session.compare_reports, info_chunks[1]);
+    } else {
+        frame.render_widget(solver_block, info_chunks[1]);
+    }
+
+    render_best(frame, app, PuzzleId::TowerOfHanoi, info_chunks[2]);
+
+    render_status(frame, app, PuzzleId::TowerOfHanoi, &session.status, info_chunks[3]);
+
+    let instructions_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(outer[2]);
+
+    let instructions = Paragraph::new(
+        "Controls: 1-3 pick up/drop • ←→ select peg • Enter pick up/drop • S solve • Space/Backspace step fwd/back • +/- disks • R reset • B back • Q quit",
+    )
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL))
+    .wrap(Wrap { trim: true });
+    frame.render_widget(instructions, instructions_area[0]);
+
+    let footer = Paragraph::new("Adel Enazi")
+        .alignment(Alignment::Center)
+        .style(theme.footer);
+    frame.render_widget(footer, instructions_area[1]);
+}
+
+const DISK_PALETTE: [Color; 8] = [
+    Color::Red,
+    Color::LightRed,
+    Color::Yellow,
+    Color::Green,
+    Color::Cyan,
+    Color::Blue,
+    Color::Magenta,
+    Color::LightMagenta,
+];
+
+fn render_hanoi_pegs(
+    state: &HanoiState,
+    selected_peg: usize,
+    held_peg: Option<usize>,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let disks = state.disks as usize;
+    // Room for the widest disk plus a space on either side
+    let width = 2 * disks + 3;
+    let mut lines = Vec::new();
+
+    for level in (0..=disks).rev() {
+        let mut spans = Vec::new();
+        for (peg, stack) in state.pegs.iter().enumerate() {
+            match stack.get(level) {
+                Some(&disk) => {
+                    let disk_width = 2 * disk as usize + 1;
+                    let pad = (width - disk_width) / 2;
+                    let is_held = held_peg == Some(peg) && level + 1 == stack.len();
+                    let mut style = Style::default().fg(theme.accent(DISK_PALETTE[(disk as usize - 1) % DISK_PALETTE.len()]));
+                    if is_held {
+                        style = style.add_modifier(Modifier::REVERSED);
+                    }
+                    spans.push(Span::raw(" ".repeat(pad)));
+                    spans.push(Span::styled("█".repeat(disk_width), style));
+                    spans.push(Span::raw(" ".repeat(pad)));
+                }
+                None => {
+                    let pad = (width - 1) / 2;
+                    spans.push(Span::raw(" ".repeat(pad)));
+                    spans.push(Span::styled("│", Style::default().fg(theme.accent(Color::Gray))));
+                    spans.push(Span::raw(" ".repeat(pad)));
+                }
+            }
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::from("▀".repeat(width * tower_of_hanoi::PEG_COUNT)));
+
+    let labels: Vec<Span> = (0..tower_of_hanoi::PEG_COUNT)
+        .map(|peg| {
+            let label = format!("{:^width$}", format!("Peg {}", peg + 1), width = width);
+            if peg == selected_peg {
+                Span::styled(
+                    label,
+                    theme.highlight,
+                )
+            } else {
+                Span::raw(label)
+            }
+        })
+        .collect();
+    lines.push(Line::from(labels));
+
+    lines
+}
+
+fn render_knights_tour(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let descriptor = app.registry.descriptor(PuzzleId::KnightsTour);
+    let title = descriptor.map(|d| app.language.puzzle_name(d)).unwrap_or("Knight's Tour");
+    let title = format!("{} ({1}×{1})", title, app.knights_tour.state.size);
+    let summary = descriptor.map(|d| app.language.puzzle_summary(d)).unwrap_or("");
+    let session = &app.knights_tour;
+
+    let outer = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(20),
+            Constraint::Length(4),
+        ])
+        .split(frame.size());
+
+    let header = Paragraph::new(format!(
+        "{} — {}",
+        title,
+        if session.is_solved() {
+            app.strings().solved
+        } else {
+            app.strings().in_progress
+        }
+    ))
+    .alignment(Alignment::Center)
+    .style(
+        Style::default()
+            .fg(theme.accent(Color::LightGreen))
+            .add_modifier(Modifier::BOLD),
+    )
+    .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(header, outer[0]);
+
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(outer[1]);
+
+    let board_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(20), Constraint::Length(5)])
+        .split(body[0]);
+
+    let board_block = Paragraph::new(render_knights_board(session, theme))
+        .alignment(Alignment::Center)
+        .block(Block::default().title("Chessboard").borders(Borders::ALL));
+    frame.render_widget(board_block, board_area[0]);
+
+    let (row, col) = session.state.position;
+    let stats_text = format!(
+        "Squares visited: {}/{}\nKnight on: row {}, column {}\nOnward moves: {}",
+        session.state.visited_count(),
+        session.state.squares(),
+        row + 1,
+        col + 1,
+        session.state.onward_moves()
+    );
+    let stats_block = Paragraph::new(stats_text)
+        .block(Block::default().title(state_title(&session.timer)).borders(Borders::ALL));
+    frame.render_widget(stats_block, board_area[1]);
+
+    let info_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4),
+            Constraint::Min(8),
+            Constraint::Length(3),
+            Constraint::Length(4),
+        ])
+        .split(body[1]);
+
+    let summary_block = Paragraph::new(summary)
+        .block(Block::default().title("Summary").borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(summary_block, info_chunks[0]);
+
+    let explanation = match session.state.heuristic {
+        TourHeuristic::Warnsdorff => "Warnsdorff's rule: always jump to the square\nwith the fewest onward moves, so hard-to-reach\ncorners and edges are visited before they\nbecome dead ends.\n\nh(n) = 9 × squares left + onward moves,\nso A* extends the deepest tour first and\nfollows Warnsdorff's order among its jumps,\nbacktracking only when it gets stuck.\n\nPress M for squares left.",
+        TourHeuristic::SquaresLeft => "h(n) = squares left to visit. It never\noverestimates, but every partial tour of the\nsame length ties on f(n), so A* spreads out\nacross them instead of diving into one. Fine\non 5×5, hopeless on 8×8 before the node limit.\n\nPress M for Warnsdorff's rule.",
+    };
+    let solver_text = match &session.solution {
+        Some(report) => format!(
+            "Result: {}\nExpanded nodes: {}\nVisited states: {}\nElapsed: {}\n{}\n\n{}",
+            report.outcome.label(),
+            report.expanded_nodes,
+            report.visited_states,
+            format_duration(report.elapsed),
+            memory_stats(report),
+            explanation
+        ),
+        None => format!("Press S to complete the tour with A*.\n\n{}", explanation),
+    };
+    let solver_block = Paragraph::new(solver_text)
+        .block(titled_block(app, app.strings().solver))
+        .wrap(Wrap { trim: true });
+    if session.compare_reports.showing_table() {
+        render_comparison(frame, &app.theme, &session.compare_reports, info_chunks[1]);
+    } else {
+        frame.render_widget(solver_block, info_chunks[1]);
+    }
+
+    render_best(frame, app, PuzzleId::KnightsTour, info_chunks[2]);
+
+    render_status(frame, app, PuzzleId::KnightsTour, &session.status, info_chunks[3]);
+
+    let instructions_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(outer[2]);
+
+    let instructions = Paragraph::new(
+        "Controls: ←→↑↓ move cursor • Enter jump / pick start square • S solve • +/- board size • M heuristic • R reset • B back • Q quit",
+    )
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL))
+    .wrap(Wrap { trim: true });
+    frame.render_widget(instructions, instructions_area[0]);
+
+    let footer = Paragraph::new("Adel Enazi")
+        .alignment(Alignment::Center)
+        .style(theme.footer);
+    frame.render_widget(footer, instructions_area[1]);
+}
+
+fn render_knights_board(session: &KnightsTourSession, theme: &Theme) -> Vec<Line<'static>> {
+    let size = session.state.size;
+    let (knight_row, knight_col) = session.state.position;
+    let jumps = session.state.jumps_from(knight_row, knight_col);
+    let mut lines = Vec::new();
+
+    let border = "─".repeat(size as usize * 4 + 3);
+    lines.push(Line::from(format!("┌{}┐", border)));
+    let mut col_header = String::from("│   ");
+    for col in 0..size {
+        col_header.push_str(&format!(" {}  ", col + 1));
+    }
+    col_header.push('│');
+    lines.push(Line::from(col_header));
+    lines.push(Line::from(format!("├{}┤", border)));
+
+    for row in 0..size {
+        let mut row_spans = vec![Span::raw("│"), Span::raw(format!("{}  ", row + 1))];
+        for col in 0..size {
+            let is_cursor = session.cursor == (row, col);
+            let is_knight = (knight_row, knight_col) == (row, col);
+            let is_jump = jumps.iter().any(|mv| (mv.row, mv.col) == (row, col));
+            let text = if is_knight {
+                " ♞  ".to_string()
+            } else {
+                match session.move_number(row, col) {
+                    Some(number) => format!("{:>3} ", number),
+                    None => "  · ".to_string(),
+                }
+            };
+            let base = if (row + col) % 2 == 0 {
+                Style::default().fg(theme.accent(Color::Gray))
+            } else {
+                Style::default().fg(theme.accent(Color::DarkGray))
+            };
+            let style = if is_cursor {
+                theme.highlight
+            } else if is_knight {
+                Style::default().fg(theme.accent(Color::LightGreen)).add_modifier(Modifier::BOLD)
+            } else if is_jump {
+                Style::default().fg(theme.accent(Color::Green))
+            } else if session.state.is_visited(row, col) {
+                base.add_modifier(Modifier::BOLD)
+            } else {
+                base
+            };
+            row_spans.push(Span::styled(text, style));
+        }
+        row_spans.push(Span::raw("│"));
+        lines.push(Line::from(row_spans));
+    }
+
+    lines.push(Line::from(format!("└{}┘", border)));
+    lines
+}
+
+fn render_water_jug(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let descriptor = app.registry.descriptor(PuzzleId::WaterJug);
+    let title = descriptor.map(|d| app.language.puzzle_name(d)).unwrap_or("Water Jug Problem");
+    let summary = descriptor.map(|d| app.language.puzzle_summary(d)).unwrap_or("");
+    let session = &app.water_jug;
+
+    let outer = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(20),
+            Constraint::Length(4),
+        ])
+        .split(frame.size());
+
+    let mode = if session.editing {
+        "Editing"
+    } else if session.is_solved() {
+        app.strings().solved
+    } else {
+        app.strings().in_progress
+    };
+    let header = Paragraph::new(format!("{} — {}", title, mode))
+        .alignment(Alignment::Center)
+        .style(
+            Style::default()
+                .fg(theme.accent(Color::LightCyan))
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(header, outer[0]);
+
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(outer[1]);
+
+    let board_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(16), Constraint::Length(5)])
+        .split(body[0]);
+
+    let jugs_block = Paragraph::new(render_jugs(session, theme))
+        .alignment(Alignment::Center)
+        .block(Block::default().title("Jugs").borders(Borders::ALL));
+    frame.render_widget(jugs_block, board_area[0]);
+
+    let target_style = if session.editing && session.edit_field == session.state.jugs.len() {
+        theme.highlight
+    } else {
+        Style::default().add_modifier(Modifier::BOLD)
+    };
+    let stats_lines = vec![
+        Line::from(vec![
+            Span::raw("Target: "),
+            Span::styled(format!("{} L", session.state.target), target_style),
+        ]),
+        Line::from(format!(
+            "Closest jug is {} L away",
+            session.state.min_distance_to_target()
+        )),
+        Line::from(format!(
+            "Pouring from: {}",
+            session
+                .pour_from
+                .map(|jug| format!("jug {}", jug + 1))
+                .unwrap_or_else(|| "—".into())
+        )),
+    ];
+    let stats_block =
+        Paragraph::new(stats_lines).block(Block::default().title(state_title(&session.timer)).borders(Borders::ALL));
+    frame.render_widget(stats_block, board_area[1]);
+
+    let info_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4),
+            Constraint::Min(8),
+            Constraint::Length(3),
+            Constraint::Length(4),
+        ])
+        .split(body[1]);
+
+    let summary_block = Paragraph::new(summary)
+        .block(Block::default().title("Summary").borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(summary_block, info_chunks[0]);
+
+    let solver_text = match &session.solution {
+        Some(solution) => {
+            let moves: Vec<String> = solution
+                .report
+                .actions
+                .iter()
+                .enumerate()
+                .map(|(idx, mv)| {
+                    let marker = if idx + 1 == solution.step { ">" } else { " " };
+                    format!("{} {}. {}", marker, idx + 1, mv.label())
+                })
+                .collect();
+            format!(
+                "Steps total: {}\nCurrent step: {}\nExpanded nodes: {}\nVisited states: {}\nElapsed: {}\n{}\n\n{}",
+                solution.total_steps(),
+                solution.step,
+                solution.report.expanded_nodes,
+                solution.report.visited_states,
+                format_duration(solution.report.elapsed),
+                memory_stats(&solution.report),
+                moves.join("\n")
+            )
+        }
+        None => "Press S to run the A* solver.\n\nMoves: fill a jug from the tap, empty it,\nor pour until the source is empty or the\ndestination is full.\n\nh(n) = 0 once a jug holds the target,\n1 otherwise: a single pour can close any\ngap in liters, so the distance itself\nwould overestimate.".into(),
+    };
+    let solver_block = Paragraph::new(solver_text)
+        .block(titled_block(app, app.strings().solver))
+        .wrap(Wrap { trim: true });
+    if session.compare_reports.showing_table() {
+        render_comparison(frame, &app.theme, &session.compare_reports, info_chunks[1]);
+    } else {
+        frame.render_widget(solver_block, info_chunks[1]);
+    }
+
+    render_best(frame, app, PuzzleId::WaterJug, info_chunks[2]);
+
+    render_status(frame, app, PuzzleId::WaterJug, &session.status, info_chunks[3]);
+
+    let instructions_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(outer[2]);
+
+    let controls = if session.editing {
+        "Controls: ←→ choose jug/target • ↑↓ adjust liters • +/- add/remove jug • E/Enter done • B back • Q quit"
+    } else {
+        "Controls: ←→ select jug • F fill • X empty • Enter pour • S solve • Space/Backspace step fwd/back • 1-3 presets • E edit • R reset • B back • Q quit"
+    };
+    let instructions = Paragraph::new(controls)
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(instructions, instructions_area[0]);
+
+    let footer = Paragraph::new("Adel Enazi")
+        .alignment(Alignment::Center)
+        .style(theme.footer);
+    frame.render_widget(footer, instructions_area[1]);
+}
+
+/// Rows used by the tallest jug; smaller jugs are scaled to it.
+const JUG_ROWS: u32 = 10;
+
+fn render_jugs(session: &WaterJugSession, theme: &Theme) -> Vec<Line<'static>> {
+    let state = &session.state;
+    let max_capacity = state.capacities.iter().copied().max().unwrap_or(1).max(1);
+    let heights: Vec<u32> = state
+        .capacities
+        .iter()
+        .map(|&capacity| (capacity * JUG_ROWS).div_ceil(max_capacity).max(1))
+        .collect();
+    let fills: Vec<u32> = state
+        .jugs
+        .iter()
+        .zip(&state.capacities)
+        .zip(&heights)
+        .map(|((&liters, &capacity), &height)| {
+            let rows = (liters * height + capacity / 2) / capacity.max(1);
+            if liters > 0 {
+                rows.max(1)
+            } else {
+                rows
+            }
+        })
+        .collect();
+    let water_style = if session.is_solved() {
+        Style::default().fg(theme.accent(Color::Green))
+    } else {
+        Style::default().fg(theme.accent(Color::Blue))
+    };
+
+    let mut lines = Vec::new();
+    for row in (1..=JUG_ROWS).rev() {
+        let mut spans = Vec::new();
+        for (jug, (&height, &fill)) in heights.iter().zip(&fills).enumerate() {
+            if jug > 0 {
+                spans.push(Span::raw("  "));
+            }
+            if row > height {
+                spans.push(Span::raw("       "));
+            } else if row <= fill {
+                spans.push(Span::raw("│"));
+                spans.push(Span::styled("█████", water_style));
+                spans.push(Span::raw("│"));
+            } else {
+                spans.push(Span::raw("│     │"));
+            }
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let join = |cells: Vec<Span<'static>>| {
+        let mut spans = Vec::new();
+        for (idx, cell) in cells.into_iter().enumerate() {
+            if idx > 0 {
+                spans.push(Span::raw("  "));
+            }
+            spans.push(cell);
+        }
+        Line::from(spans)
+    };
+    lines.push(join(state.jugs.iter().map(|_| Span::raw("└─────┘")).collect()));
+    lines.push(join(
+        state
+            .jugs
+            .iter()
+            .zip(&state.capacities)
+            .enumerate()
+            .map(|(jug, (liters, capacity))| {
+                let text = format!("{:^7}", format!("{}/{}L", liters, capacity));
+                if session.editing && session.edit_field == jug {
+                    Span::styled(
+                        text,
+                        theme.highlight,
+                    )
+                } else {
+                    Span::raw(text)
+                }
+            })
+            .collect(),
+    ));
+    lines.push(join(
+        (0..state.jugs.len())
+            .map(|jug| {
+                let text = format!("{:^7}", format!("Jug {}", jug + 1));
+                if !session.editing && session.selected_jug == jug {
+                    Span::styled(
+                        text,
+                        theme.highlight,
+                    )
+                } else if session.pour_from == Some(jug) {
+                    Span::styled(text, Style::default().fg(theme.accent(Color::Cyan)).add_modifier(Modifier::BOLD))
+                } else {
+                    Span::raw(text)
+                }
+            })
+            .collect(),
+    ));
+    lines
+}
+
+fn render_lights_out(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let descriptor = app.registry.descriptor(PuzzleId::LightsOut);
+    let title = descriptor.map(|d| app.language.puzzle_name(d)).unwrap_or("Lights Out");
+    let daily_title = app.daily_in_play(PuzzleId::LightsOut).map(|daily| daily.title());
+    let title = daily_title.as_deref().unwrap_or(title);
+    let summary = descriptor.map(|d| app.language.puzzle_summary(d)).unwrap_or("");
+    let session = &app.lights_out;
+
+    let outer = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(20),
+            Constraint::Length(4),
+        ])
+        .split(frame.size());
+
+    let header = Paragraph::new(format!(
+        "{} — {}",
+        title,
+        if session.is_solved() {
+            app.strings().solved
+        } else {
+            app.strings().in_progress
+        }
+    ))
+    .alignment(Alignment::Center)
+    .style(
+        Style::default()
+            .fg(theme.accent(Color::LightGreen))
+            .add_modifier(Modifier::BOLD),
+    )
+    .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(header, outer[0]);
+
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(outer[1]);
+
+    let board_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(16), Constraint::Length(5)])
+        .split(body[0]);
+
+    let board_block = Paragraph::new(render_lights_grid(session, theme))
+        .alignment(Alignment::Center)
+        .block(Block::default().title("Lights").borders(Borders::ALL));
+    frame.render_widget(board_block, board_area[0]);
+
+    let stats_text = format!(
+        "Board: {}×{}\nLights on: {}/{}\nPresses made: {}",
+        session.state.size,
+        session.state.size,
+        session.state.lit_count(),
+        session.state.cell_count(),
+        session.moves_made
+    );
+    let stats_block = Paragraph::new(stats_text)
+        .block(Block::default().title(state_title(&session.timer)).borders(Borders::ALL));
+    frame.render_widget(stats_block, board_area[1]);
+
+    let info_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4),
+            Constraint::Min(8),
+            Constraint::Length(3),
+            Constraint::Length(4),
+        ])
+        .split(body[1]);
+
+    let summary_block = Paragraph::new(summary)
+        .block(Block::default().title("Summary").borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(summary_block, info_chunks[0]);
+
+    let explanation = "Pressing a cell twice undoes it and the order\nof presses does not matter, so a solution is\njust a set of cells. Each light gives one\nequation over GF(2); Gaussian elimination\nsolves the system, then the free variables\nare tried to find the fewest presses.";
+    let solver_text = match &session.solution {
+        Some(presses) => format!(
+            "Presses needed: {}\nMarked cells: ◆\n\n{}",
+            presses.len(),
+            explanation
+        ),
+        None => format!("Press S to mark the fewest presses.\n\n{}", explanation),
+    };
+    let solver_block = Paragraph::new(solver_text)
+        .block(titled_block(app, app.strings().solver))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(solver_block, info_chunks[1]);
+
+    render_best(frame, app, PuzzleId::LightsOut, info_chunks[2]);
+
+    render_status(frame, app, PuzzleId::LightsOut, &session.status, info_chunks[3]);
+
+    let instructions_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(outer[2]);
+
+    let instructions = Paragraph::new(
+        "Controls: ←→↑↓ move cursor • Enter/Space press • S solve • H shuffle • +/- size • R reset • B back • Q quit",
+    )
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL))
+    .wrap(Wrap { trim: true });
+    frame.render_widget(instructions, instructions_area[0]);
+
+    let footer = Paragraph::new("Adel Enazi")
+        .alignment(Alignment::Center)
+        .style(theme.footer);
+    frame.render_widget(footer, instructions_area[1]);
+}
+
+fn render_lights_grid(session: &LightsOutSession, theme: &Theme) -> Vec<Line<'static>> {
+    let size = session.state.size as usize;
+    // Five-column cells with a one-column gap so neighbouring lights stay distinct
+    let width = size * 6 + 1;
+    let border = "─".repeat(width);
+    let mut lines = Vec::new();
+
+    lines.push(Line::from(format!("┌{}┐", border)));
+    for row in 0..size {
+        let mut row_spans = vec![Span::raw("│ ")];
+        for col in 0..size {
+            let index = row * size + col;
+            let text = if session.is_in_solution(index) {
+                "  ◆  "
+            } else {
+                "     "
+            };
+            let base = if session.state.is_lit(index) {
+                Style::default().fg(theme.accent(Color::Black)).bg(theme.accent(Color::Yellow))
+            } else {
+                Style::default().fg(theme.accent(Color::LightCyan)).bg(theme.accent(Color::DarkGray))
+            };
+            let style = if session.cursor == index {
+                base.add_modifier(Modifier::BOLD | Modifier::REVERSED)
+            } else {
+                base
+            };
+            row_spans.push(Span::styled(text, style));
+            row_spans.push(Span::raw(" "));
+        }
+        row_spans.push(Span::raw("│"));
+        lines.push(Line::from(row_spans));
+        if row + 1 < size {
+            lines.push(Line::from(format!("│{}│", " ".repeat(width))));
+        }
+    }
+    lines.push(Line::from(format!("└{}┘", border)));
+    lines
+}
+
+fn render_sokoban(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let descriptor = app.registry.descriptor(PuzzleId::Sokoban);
+    let title = descriptor.map(|d| app.language.puzzle_name(d)).unwrap_or("Sokoban");
+    let summary = descriptor.map(|d| app.language.puzzle_summary(d)).unwrap_or("");
+    let session = &app.sokoban;
+
+    let outer = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(20),
+            Constraint::Length(4),
+        ])
+        .split(frame.size());
+
+    let level = session
+        .level
+        .map(|index| format!("Level {}", index + 1))
+        .unwrap_or_else(|| "Saved level".into());
+    let header = Paragraph::new(format!(
+        "{} — {} — {}",
+        title,
+        level,
+        if session.is_solved() {
+            app.strings().solved
+        } else {
+            app.strings().in_progress
+        }
+    ))
+    .alignment(Alignment::Center)
+    .style(
+        Style::default()
+            .fg(theme.accent(Color::Yellow))
+            .add_modifier(Modifier::BOLD),
+    )
+    .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(header, outer[0]);
+
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(outer[1]);
+
+    let board_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(16), Constraint::Length(5)])
+        .split(body[0]);
+
+    let board_block = Paragraph::new(render_sokoban_grid(&session.state, theme))
+        .alignment(Alignment::Center)
+        .block(Block::default().title("Warehouse").borders(Borders::ALL));
+    frame.render_widget(board_block, board_area[0]);
+
+    let stats_text = format!(
+        "Boxes on goals: {}/{}\nMoves made: {}\nPushes: {}",
+        session.state.boxes_on_goals(),
+        session.state.boxes.len(),
+        session.moves_made,
+        session.pushes
+    );
+    let stats_block = Paragraph::new(stats_text)
+        .block(Block::default().title(state_title(&session.timer)).borders(Borders::ALL));
+    frame.render_widget(stats_block, board_area[1]);
+
+    let info_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4),
+            Constraint::Min(8),
+            Constraint::Length(3),
+            Constraint::Length(4),
+        ])
+        .split(body[1]);
+
+    let summary_block = Paragraph::new(summary)
+        .block(Block::default().title("Summary").borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(summary_block, info_chunks[0]);
+
+    let solver_text = match &session.solution {
+        Some(solution) => format!(
+            "Steps total: {}\nPushes: {}\nCurrent step: {}\nExpanded nodes: {}\nVisited states: {}\nElapsed: {}\n{}",
+            solution.total_steps(),
+            solution.push_count(),
+            solution.step,
+            solution.report.expanded_nodes,
+            solution.report.visited_states,
+            format_duration(solution.report.elapsed),
+            memory_stats(&solution.report)
+        ),
+        None => format!(
+            "Press S to run the A* solver.\n\nh(n) gives each box its own goal\nso the Manhattan distances add up\nto as little as possible.\nCurrent h(n): {}\n\nA box pushed into a corner away\nfrom a goal can never move again,\nso A* never tries those pushes.",
+            session.state.heuristic()
+        ),
+    };
+    let solver_block = Paragraph::new(solver_text)
+        .block(titled_block(app, app.strings().solver))
+        .wrap(Wrap { trim: true });
+    if session.compare_reports.showing_table() {
+        render_comparison(frame, &app.theme, &session.compare_reports, info_chunks[1]);
     } else {
-        format!("{:.2} s", duration.as_secs_f64())
+        frame.render_widget(solver_block, info_chunks[1]);
     }
-}
 
-fn format_player(player: Player) -> &'static str {
-    match player {
-        Player::X => "X",
-        Player::O => "O",
-    }
+    render_best(frame, app, PuzzleId::Sokoban, info_chunks[2]);
+
+    render_status(frame, app, PuzzleId::Sokoban, &session.status, info_chunks[3]);
+
+    let instructions_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(outer[2]);
+
+    let instructions = Paragraph::new(
+        "Controls: ←→↑↓ move/push • S solve • Space/Backspace step fwd/back • 1-5 level • R restart • B back • Q quit",
+    )
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL))
+    .wrap(Wrap { trim: true });
+    frame.render_widget(instructions, instructions_area[0]);
+
+    let footer = Paragraph::new("Adel Enazi")
+        .alignment(Alignment::Center)
+        .style(theme.footer);
+    frame.render_widget(footer, instructions_area[1]);
 }
 
-fn digit_to_index(ch: char) -> Option<usize> {
-    ch.to_digit(10).and_then(|d| {
-        if (1..=9).contains(&d) {
-            Some((d - 1) as usize)
-        } else {
-            None
-        }
-    })
+/// Level map in the usual Sokoban symbols, two columns per cell.
+fn render_sokoban_grid(state: &SokobanState, theme: &Theme) -> Vec<Line<'static>> {
+    (0..state.height)
+        .map(|row| {
+            let spans: Vec<Span> = (0..state.width)
+                .map(|col| {
+                    let cell = (row, col);
+                    let (symbol, color) = match (state.is_goal_cell(cell), state.is_box(cell)) {
+                        _ if state.is_wall(cell) => ('#', Color::DarkGray),
+                        _ if state.player == cell => ('@', Color::LightCyan),
+                        (true, true) => ('*', Color::Green),
+                        (false, true) => ('$', Color::Yellow),
+                        (true, false) => ('.', Color::Red),
+                        (false, false) => (' ', Color::Reset),
+                    };
+                    Span::styled(
+                        format!("{} ", symbol),
+                        Style::default()
+                            .fg(theme.accent(color))
+                            .add_modifier(Modifier::BOLD),
+                    )
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect()
 }
 
-fn render_missionaries_cannibals(frame: &mut Frame, app: &App) {
-    let descriptor = app.registry.descriptor(PuzzleId::MissionariesCannibals);
-    let title = descriptor.map(|d| d.name).unwrap_or("Missionaries & Cannibals");
-    let summary = descriptor.map(|d| d.summary).unwrap_or("");
-    let session = &app.missionaries_cannibals;
+fn render_sudoku(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let descriptor = app.registry.descriptor(PuzzleId::Sudoku);
+    let title = descriptor.map(|d| app.language.puzzle_name(d)).unwrap_or("Sudoku");
+    let summary = descriptor.map(|d| app.language.puzzle_summary(d)).unwrap_or("");
+    let session = &app.sudoku;
 
     let outer = Layout::default()
         .direction(Direction::Vertical)
@@ -760,19 +3796,24 @@ fn render_missionaries_cannibals(frame: &mut Frame, app: &App) {
         ])
         .split(frame.size());
 
+    let puzzle = session
+        .puzzle
+        .map(|index| sudoku::PUZZLES[index].0)
+        .unwrap_or("Saved grid");
     let header = Paragraph::new(format!(
-        "{} — {}",
+        "{} — {} — {}",
         title,
+        puzzle,
         if session.is_solved() {
-            "Solved"
+            app.strings().solved
         } else {
-            "In progress"
+            app.strings().in_progress
         }
     ))
     .alignment(Alignment::Center)
     .style(
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme.accent(Color::LightBlue))
             .add_modifier(Modifier::BOLD),
     )
     .block(Block::default().borders(Borders::ALL));
@@ -783,41 +3824,32 @@ fn render_missionaries_cannibals(frame: &mut Frame, app: &App) {
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(outer[1]);
 
-    let state_area = Layout::default()
+    let board_area = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(12), Constraint::Min(8)])
+        .constraints([Constraint::Min(16), Constraint::Length(5)])
         .split(body[0]);
 
-    let state_lines = render_mc_state(&session.state);
-    let state_block = Paragraph::new(state_lines)
+    let board_block = Paragraph::new(render_sudoku_grid(session, theme, app.ascii_mode))
         .alignment(Alignment::Center)
-        .block(Block::default().title("Current State").borders(Borders::ALL));
-    frame.render_widget(state_block, state_area[0]);
+        .block(Block::default().title("Grid").borders(Borders::ALL));
+    frame.render_widget(board_block, board_area[0]);
 
-    let valid_moves = session.get_valid_moves();
-    let moves_text = if valid_moves.is_empty() {
-        "No valid moves available.".into()
-    } else {
-        valid_moves
-            .iter()
-            .enumerate()
-            .map(|(idx, mv)| {
-                let marker = if idx == session.selected_move { ">" } else { " " };
-                format!("{} {}. Move {}M {}C", marker, idx + 1, mv.missionaries, mv.cannibals)
-            })
-            .collect::<Vec<_>>()
-            .join("\n")
-    };
-    let moves_block = Paragraph::new(moves_text)
-        .block(Block::default().title("Valid Moves").borders(Borders::ALL))
-        .wrap(Wrap { trim: true });
-    frame.render_widget(moves_block, state_area[1]);
+    let stats_text = format!(
+        "Empty cells: {}\nAfter propagation: {}\nEntries made: {}",
+        session.state.empty_cells(),
+        session.state.heuristic(),
+        session.moves_made
+    );
+    let stats_block = Paragraph::new(stats_text)
+        .block(Block::default().title(state_title(&session.timer)).borders(Borders::ALL));
+    frame.render_widget(stats_block, board_area[1]);
 
     let info_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(4),
             Constraint::Min(8),
+            Constraint::Length(3),
             Constraint::Length(4),
         ])
         .split(body[1]);
@@ -827,111 +3859,100 @@ fn render_missionaries_cannibals(frame: &mut Frame, app: &App) {
         .wrap(Wrap { trim: true });
     frame.render_widget(summary_block, info_chunks[0]);
 
+    let explanation = "Each node is first propagated: a cell with one\ncandidate left (naked single) or the only spot\nfor a digit in its row, column or box (hidden\nsingle) is filled until neither applies. A*\nthen branches on the empty cell with the\nfewest candidates. h(n) = empty cells left.";
     let solver_text = match &session.solution {
-        Some(solution) => {
-            let stats = format!(
-                "Steps total: {}\nCurrent step: {}\nExpanded nodes: {}\nVisited states: {}\nElapsed: {}",
-                solution.total_steps(),
-                solution.step,
-                solution.report.expanded_nodes,
-                solution.report.visited_states,
-                format_duration(solution.report.elapsed)
-            );
-            let explanation = format!(
-                "\n\nA* Algorithm Explanation:\n\nA* finds the shortest path\nusing: f(n) = g(n) + h(n)\n\n• g(n) = actual moves\n  from start to here\n• h(n) = estimated moves\n  to goal (people on left)\n\nMetrics:\n• Expanded nodes: {}\n  States we fully explored\n  (checked all neighbors)\n\n• Visited states: {}\n  All states we've seen\n  (in queue + explored)",
-                solution.report.expanded_nodes,
-                solution.report.visited_states
-            );
-            format!("{}{}", stats, explanation)
-        },
-        None => "Press S to run the A* solver.\n\nA* Algorithm:\nFinds optimal paths using:\nf(n) = g(n) + h(n)\n\n• g(n) = actual cost\n  from start\n• h(n) = heuristic\n  (people on left side)\n\nExpanded nodes: States\nwe fully explored.\nVisited states: All states\nwe've encountered.".into(),
+        Some(solution) => format!(
+            "Guesses: {}\nCurrent step: {}\nExpanded nodes: {}\nVisited states: {}\nElapsed: {}\n{}\n\n{}",
+            solution.total_steps(),
+            solution.step,
+            solution.report.expanded_nodes,
+            solution.report.visited_states,
+            format_duration(solution.report.elapsed),
+            memory_stats(&solution.report),
+            explanation
+        ),
+        None => format!("Press S to run the A* solver.\n\n{}", explanation),
     };
     let solver_block = Paragraph::new(solver_text)
-        .block(Block::default().title("Solver").borders(Borders::ALL))
+        .block(titled_block(app, app.strings().solver))
         .wrap(Wrap { trim: true });
-    frame.render_widget(solver_block, info_chunks[1]);
+    if session.compare_reports.showing_table() {
+        render_comparison(frame, &app.theme, &session.compare_reports, info_chunks[1]);
+    } else {
+        frame.render_widget(solver_block, info_chunks[1]);
+    }
 
-    let status_block = Paragraph::new(session.status.as_str())
-        .block(Block::default().title("Status").borders(Borders::ALL))
-        .wrap(Wrap { trim: true });
-    frame.render_widget(status_block, info_chunks[2]);
+    render_best(frame, app, PuzzleId::Sudoku, info_chunks[2]);
+
+    render_status(frame, app, PuzzleId::Sudoku, &session.status, info_chunks[3]);
 
     let instructions_area = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Min(0), Constraint::Length(1)])
         .split(outer[2]);
-    
+
     let instructions = Paragraph::new(
-        "Controls: 1-5 select/apply move • ↑↓ navigate moves • S solve • Space step solution • H shuffle • R reset • B back • Q quit",
+        "Controls: ←→↑↓ move • 1-9 fill • 0/Del clear • S solve • Space/Backspace step fwd/back • N next puzzle • R reset • B back • Q quit",
     )
     .alignment(Alignment::Center)
     .block(Block::default().borders(Borders::ALL))
     .wrap(Wrap { trim: true });
     frame.render_widget(instructions, instructions_area[0]);
-    
+
     let footer = Paragraph::new("Adel Enazi")
         .alignment(Alignment::Center)
-        .style(Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM));
+        .style(theme.footer);
     frame.render_widget(footer, instructions_area[1]);
 }
 
-fn render_mc_state(state: &MissionariesCannibalsState) -> Vec<Line<'static>> {
-    let right_m = 3 - state.left_m;
-    let right_c = 3 - state.left_c;
-    
+/// 9×9 grid with the 3×3 box separators drawn in the accent color. Givens
+/// are bold; digits entered by hand or by the solver use the theme's O color.
+fn render_sudoku_grid(session: &SudokuSession, theme: &Theme, ascii_mode: bool) -> Vec<Line<'static>> {
+    let (vertical, horizontal, cross) = if ascii_mode { ('|', '-', '+') } else { ('┃', '━', '╋') };
+    let separator_style = Style::default()
+        .fg(theme.accent(Color::Yellow))
+        .add_modifier(Modifier::BOLD);
+    let box_rule: String = std::iter::repeat_n(horizontal, 9).collect();
+    let rule = format!("{}{}{}{}{}", box_rule, cross, box_rule, cross, box_rule);
     let mut lines = Vec::new();
-    
-    // All lines are exactly 27 characters wide (including borders)
-    // Content width is 25 (excluding │ on each side)
-    
-    // Top border
-    lines.push(Line::from("┌─────────────────────────┐"));
-    
-    // Empty line
-    lines.push(Line::from("│                         │"));
-    
-    // Left side - ensure exact width
-    let boat_left_str = if state.boat_left { "🚤" } else { "  " };
-    let left_content = format!("LEFT:  M={} C={} {}", state.left_m, state.left_c, boat_left_str);
-    let left_padded = format!("│{:<25}│", left_content);
-    lines.push(Line::from(left_padded));
-    
-    // Empty line
-    lines.push(Line::from("│                         │"));
-    
-    // River separator
-    lines.push(Line::from("│     ═══════════════     │"));
-    
-    // River label - centered
-    let river_padded = format!("│{:^25}│", "RIVER");
-    lines.push(Line::from(river_padded));
-    
-    // River separator
-    lines.push(Line::from("│     ═══════════════     │"));
-    
-    // Empty line
-    lines.push(Line::from("│                         │"));
-    
-    // Right side - ensure exact width
-    let boat_right_str = if !state.boat_left { "🚤" } else { "  " };
-    let right_content = format!("RIGHT: M={} C={} {}", right_m, right_c, boat_right_str);
-    let right_padded = format!("│{:<25}│", right_content);
-    lines.push(Line::from(right_padded));
-    
-    // Empty line
-    lines.push(Line::from("│                         │"));
-    
-    // Bottom border
-    lines.push(Line::from("└─────────────────────────┘"));
-    
+    for row in 0..9 {
+        if row > 0 && row % 3 == 0 {
+            lines.push(Line::from(Span::styled(rule.clone(), separator_style)));
+        }
+        let mut spans = Vec::new();
+        for col in 0..9 {
+            if col > 0 && col % 3 == 0 {
+                spans.push(Span::styled(vertical.to_string(), separator_style));
+            }
+            let index = row * 9 + col;
+            let (text, style) = match session.state.grid[index] {
+                0 => (" · ".to_string(), theme.dim),
+                digit if session.is_given(index) => (
+                    format!(" {} ", digit),
+                    Style::default().fg(theme.board_fg).add_modifier(Modifier::BOLD),
+                ),
+                digit => (format!(" {} ", digit), Style::default().fg(theme.o)),
+            };
+            let style = if session.cursor == index {
+                theme.highlight
+            } else {
+                style
+            };
+            spans.push(Span::styled(text, style));
+        }
+        lines.push(Line::from(spans));
+    }
     lines
 }
 
-fn render_eight_queens(frame: &mut Frame, app: &App) {
-    let descriptor = app.registry.descriptor(PuzzleId::EightQueens);
-    let title = descriptor.map(|d| d.name).unwrap_or("8 Queens Problem");
-    let summary = descriptor.map(|d| d.summary).unwrap_or("");
-    let session = &app.eight_queens;
+fn render_maze(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let descriptor = app.registry.descriptor(PuzzleId::Maze);
+    let title = descriptor.map(|d| app.language.puzzle_name(d)).unwrap_or("Maze");
+    let daily_title = app.daily_in_play(PuzzleId::Maze).map(|daily| daily.title());
+    let title = daily_title.as_deref().unwrap_or(title);
+    let summary = descriptor.map(|d| app.language.puzzle_summary(d)).unwrap_or("");
+    let session = &app.maze;
 
     let outer = Layout::default()
         .direction(Direction::Vertical)
@@ -943,18 +3964,20 @@ fn render_eight_queens(frame: &mut Frame, app: &App) {
         .split(frame.size());
 
     let header = Paragraph::new(format!(
-        "{} — {}",
+        "{} — {}×{} — {}",
         title,
+        session.state.width(),
+        session.state.height(),
         if session.is_solved() {
-            "Solved"
+            "Exit reached"
         } else {
-            "In progress"
+            app.strings().in_progress
         }
     ))
     .alignment(Alignment::Center)
     .style(
         Style::default()
-            .fg(Color::Magenta)
+            .fg(theme.accent(Color::LightBlue))
             .add_modifier(Modifier::BOLD),
     )
     .block(Block::default().borders(Borders::ALL));
@@ -962,28 +3985,30 @@ fn render_eight_queens(frame: &mut Frame, app: &App) {
 
     let body = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
         .split(outer[1]);
 
     let board_area = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(18), Constraint::Length(4)])
+        .constraints([Constraint::Min(10), Constraint::Length(5)])
         .split(body[0]);
 
-    let board_lines = render_queens_board(&session.state, session.selected_row, session.selected_col);
-    let board_block = Paragraph::new(board_lines)
+    let board_block = Block::default().title("Maze").borders(Borders::ALL);
+    let inner = board_block.inner(board_area[0]);
+    let board = Paragraph::new(render_maze_grid(session, theme, app.ascii_mode, inner))
         .alignment(Alignment::Center)
-        .block(Block::default().title("Chessboard").borders(Borders::ALL));
-    frame.render_widget(board_block, board_area[0]);
+        .block(board_block);
+    frame.render_widget(board, board_area[0]);
 
     let stats_text = format!(
-        "Queens placed: {}/8\nConflicts: {}\nHeuristic: {}",
-        session.state.queens.iter().filter(|q| q.is_some()).count(),
-        session.state.count_conflicts(),
-        session.state.heuristic()
+        "Moves made: {}\nDistance to exit (Manhattan): {}\nNext maze size: {}×{}",
+        session.moves_made,
+        session.state.heuristic(),
+        session.maze_size.0,
+        session.maze_size.1
     );
     let stats_block = Paragraph::new(stats_text)
-        .block(Block::default().title("State").borders(Borders::ALL));
+        .block(Block::default().title(state_title(&session.timer)).borders(Borders::ALL));
     frame.render_widget(stats_block, board_area[1]);
 
     let info_chunks = Layout::default()
@@ -991,6 +4016,7 @@ fn render_eight_queens(frame: &mut Frame, app: &App) {
         .constraints([
             Constraint::Length(4),
             Constraint::Min(8),
+            Constraint::Length(3),
             Constraint::Length(4),
         ])
         .split(body[1]);
@@ -1000,116 +4026,471 @@ fn render_eight_queens(frame: &mut Frame, app: &App) {
         .wrap(Wrap { trim: true });
     frame.render_widget(summary_block, info_chunks[0]);
 
+    let explanation = "The maze is carved by recursive backtracking,\nwith a few extra gaps for detours. Mud costs\n3 to enter, floor 1. A* expands neighbours in\norder of f(n) = g(n) + h(n), with h(n) the\nManhattan distance to E.";
     let solver_text = match &session.solution {
-        Some(solution) => {
-            let stats = format!(
-                "Steps total: {}\nCurrent step: {}\nExpanded nodes: {}\nVisited states: {}\nElapsed: {}",
-                solution.total_steps(),
-                solution.step,
-                solution.report.expanded_nodes,
-                solution.report.visited_states,
-                format_duration(solution.report.elapsed)
-            );
-            let explanation = format!(
-                "\n\nA* Algorithm Explanation:\n\nA* finds the shortest path\nusing: f(n) = g(n) + h(n)\n\n• g(n) = actual moves\n  from start to here\n• h(n) = estimated cost\n  (conflicts + missing)\n\nMetrics:\n• Expanded nodes: {}\n  States we fully explored\n  (checked all neighbors)\n\n• Visited states: {}\n  All states we've seen\n  (in queue + explored)",
-                solution.report.expanded_nodes,
-                solution.report.visited_states
-            );
-            format!("{}{}", stats, explanation)
-        },
-        None => "Press S to run the A* solver.\n\nA* Algorithm:\nFinds optimal paths using:\nf(n) = g(n) + h(n)\n\n• g(n) = actual cost\n  from start\n• h(n) = heuristic\n  (conflicts + missing)\n\nExpanded nodes: States\nwe fully explored.\nVisited states: All states\nwe've encountered.".into(),
+        Some(solution) => format!(
+            "Moves total: {}{}\nCurrent step: {}\nExpanded nodes: {}\nVisited states: {}\nElapsed: {}\n{}\n\n{}",
+            solution.total_steps(),
+            match solution.report.path_cost() as usize {
+                cost if cost != solution.total_steps() => format!(" (path cost {})", cost),
+                _ => String::new(),
+            },
+            solution.step,
+            solution.report.expanded_nodes,
+            solution.report.visited_states,
+            format_duration(solution.report.elapsed),
+            memory_stats(&solution.report),
+            explanation
+        ),
+        None => format!("Press S to run the A* solver.\n\n{}", explanation),
     };
     let solver_block = Paragraph::new(solver_text)
-        .block(Block::default().title("Solver").borders(Borders::ALL))
+        .block(titled_block(app, app.strings().solver))
         .wrap(Wrap { trim: true });
-    frame.render_widget(solver_block, info_chunks[1]);
+    if session.compare_reports.showing_table() {
+        render_comparison(frame, &app.theme, &session.compare_reports, info_chunks[1]);
+    } else {
+        frame.render_widget(solver_block, info_chunks[1]);
+    }
 
-    let status_block = Paragraph::new(session.status.as_str())
-        .block(Block::default().title("Status").borders(Borders::ALL))
-        .wrap(Wrap { trim: true });
-    frame.render_widget(status_block, info_chunks[2]);
+    render_best(frame, app, PuzzleId::Maze, info_chunks[2]);
+
+    render_status(frame, app, PuzzleId::Maze, &session.status, info_chunks[3]);
 
     let instructions_area = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Min(0), Constraint::Length(1)])
         .split(outer[2]);
-    
+
     let instructions = Paragraph::new(
-        "Controls: ←→↑↓ select cell • Space place/remove queen • S solve • Space step solution • H shuffle • R reset • B back • Q quit",
+        "Controls: ←→↑↓ walk • S solve • Space/Backspace step fwd/back • G new maze • +/- size • R restart • B back • Q quit",
     )
     .alignment(Alignment::Center)
     .block(Block::default().borders(Borders::ALL))
     .wrap(Wrap { trim: true });
     frame.render_widget(instructions, instructions_area[0]);
-    
+
     let footer = Paragraph::new("Adel Enazi")
         .alignment(Alignment::Center)
-        .style(Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM));
+        .style(theme.footer);
     frame.render_widget(footer, instructions_area[1]);
 }
 
-fn render_queens_board(state: &EightQueensState, selected_row: usize, selected_col: usize) -> Vec<Line<'static>> {
-    let mut lines = Vec::new();
-    
-    // Top border
-    lines.push(Line::from("┌───────────────────────────────┐"));
-    
-    // Column numbers
-    let mut col_header = String::from("│   ");
-    for col in 0..8 {
-        col_header.push_str(&format!("{} ", col + 1));
+/// First row or column of a `view`-long window onto `len` cells, centered on
+/// `focus` but never running past either end.
+fn viewport_start(focus: u8, len: u8, view: u16) -> u8 {
+    let view = view.min(len as u16) as u8;
+    focus.saturating_sub(view / 2).min(len - view)
+}
+
+/// The part of the maze that fits in `area`, centered on the player. Each
+/// cell takes two columns; cells on the solver's route are dotted and mud is
+/// shaded.
+fn render_maze_grid(session: &MazeSession, theme: &Theme, ascii_mode: bool, area: Rect) -> Vec<Line<'static>> {
+    let state = &session.state;
+    let (width, height) = (state.width(), state.height());
+    if width == 0 || height == 0 {
+        return Vec::new();
     }
-    col_header.push_str("│");
-    lines.push(Line::from(col_header));
-    
-    // Separator
-    lines.push(Line::from("├───────────────────────────────┤"));
-    
-    for row in 0..8 {
-        let mut row_spans = Vec::new();
-        row_spans.push(Span::raw("│"));
-        row_spans.push(Span::raw(format!("{} ", row + 1)));
-        
-        for col in 0..8 {
-            let is_selected = selected_row == row && selected_col == col;
-            let has_queen = state.queens[row].map(|q| q == col as u8).unwrap_or(false);
-            
-            let (symbol, style) = if has_queen {
-                if is_selected {
-                    ("♛", Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD))
-                } else {
-                    ("♛", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
-                }
-            } else {
-                if is_selected {
-                    ("·", Style::default().fg(Color::Black).bg(Color::Yellow))
-                } else {
-                    // Alternate colors for chessboard pattern
-                    let is_light = (row + col) % 2 == 0;
-                    if is_light {
-                        ("·", Style::default().fg(Color::DarkGray))
-                    } else {
-                        ("·", Style::default().fg(Color::Gray))
-                    }
-                }
-            };
-            
-            row_spans.push(Span::styled(format!("{} ", symbol), style));
+    let cols = (area.width / 2).min(width as u16);
+    let rows = area.height.min(height as u16);
+    let first_col = viewport_start(state.player.1, width, cols);
+    let first_row = viewport_start(state.player.0, height, rows);
+    let wall = if ascii_mode { "##" } else { "██" };
+    let route = if ascii_mode { " ." } else { " ·" };
+    let mud = if ascii_mode { "~~" } else { "░░" };
+
+    (first_row..first_row + rows as u8)
+        .map(|row| {
+            let spans: Vec<Span> = (first_col..first_col + cols as u8)
+                .map(|col| {
+                    let cell = (row, col);
+                    let on_route = session.solution.as_ref().is_some_and(|solution| solution.visits(cell));
+                    let (text, style) = match state.cell(cell) {
+                        _ if state.player == cell => ("@ ", theme.highlight),
+                        Some(maze::Cell::Start) => ("S ", Style::default().fg(theme.accent(Color::Green)).add_modifier(Modifier::BOLD)),
+                        Some(maze::Cell::End) => ("E ", Style::default().fg(theme.accent(Color::Red)).add_modifier(Modifier::BOLD)),
+                        Some(maze::Cell::Open) if on_route => (route, Style::default().fg(theme.accent(Color::Yellow))),
+                        Some(maze::Cell::Open) => ("  ", Style::default()),
+                        Some(maze::Cell::Muddy) if on_route => (route, Style::default().fg(theme.accent(Color::Yellow)).bg(theme.accent(Color::DarkGray))),
+                        Some(maze::Cell::Muddy) => (mud, Style::default().fg(theme.accent(Color::Gray))),
+                        Some(maze::Cell::Wall) | None => (wall, Style::default().fg(theme.accent(Color::DarkGray))),
+                    };
+                    Span::styled(text, style)
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// A titled group of (key, description) pairs in the help popup.
+type HelpSection = (&'static str, Vec<(&'static str, &'static str)>);
+
+/// Help popup contents for `route`, using the default key map.
+fn keybindings(route: AppRoute) -> Vec<HelpSection> {
+    let puzzle_id = match route {
+        AppRoute::Puzzle(id) => id,
+        AppRoute::Settings => {
+            return vec![
+                (
+                    "Settings",
+                    vec![
+                        ("↑ ↓", "Choose a setting"),
+                        ("Enter", "Change it (saved right away)"),
+                        ("B / Esc", "Back to the previous screen"),
+                    ],
+                ),
+                (
+                    "General",
+                    vec![
+                        ("? / F1 / Esc", "Close this help"),
+                        ("Ctrl+T", "Cycle color theme"),
+                        ("Q", "Quit"),
+                    ],
+                ),
+            ];
+        }
+        AppRoute::Statistics => {
+            return vec![
+                (
+                    "Statistics",
+                    vec![
+                        ("↑ ↓", "Choose a puzzle"),
+                        ("Enter", "Open the selected puzzle"),
+                        ("R", "Reset all statistics (asks first)"),
+                        ("B / Esc", "Back to the previous screen"),
+                    ],
+                ),
+                (
+                    "General",
+                    vec![
+                        ("? / F1 / Esc", "Close this help"),
+                        ("Ctrl+T", "Cycle color theme"),
+                        ("Q", "Quit"),
+                    ],
+                ),
+            ];
+        }
+        AppRoute::MainMenu | AppRoute::Quit => {
+            return vec![
+                (
+                    "Menu",
+                    vec![
+                        ("↑ ↓", "Choose a puzzle (wraps around)"),
+                        ("Home / End", "First / last puzzle"),
+                        ("Enter", "Open the selected puzzle"),
+                        ("1-9", "Open a listed puzzle by number"),
+                        ("/ / F3", "Filter puzzles by name, jumping to a prefix match"),
+                        ("Letters", "Select the first puzzle starting with that letter"),
+                        ("F", "Cycle the difficulty filter"),
+                        ("Ctrl+S", "Open the settings"),
+                        ("Ctrl+I / Tab", "Open the statistics"),
+                        ("Backspace", "Edit the filter"),
+                        ("Esc", "Clear the filter and stop filtering"),
+                    ],
+                ),
+                (
+                    "General",
+                    vec![
+                        ("? / F1 / Esc", "Close this help"),
+                        ("Ctrl+T", "Cycle color theme"),
+                        ("Ctrl+A", "Toggle ASCII boards"),
+                        ("Esc", "Quit (when not filtering)"),
+                    ],
+                ),
+            ];
+        }
+    };
+    let (board, mut solver) = match puzzle_id {
+        PuzzleId::EightPuzzle => (
+            vec![
+                ("↑ ↓ ← →", "Move cursor"),
+                ("1-8", "Place number in selected cell"),
+                ("Tab", "Switch between current and goal board"),
+                ("U / Ctrl+R", "Undo / redo placement"),
+                ("PgUp / PgDn", "Scroll move history"),
+                ("H / N", "Shuffle / new random board"),
+                ("G", "Type a shuffle seed"),
+                ("V", "Paste a board, e.g. 123456780"),
+                ("R", "Reset to initial state"),
+            ],
+            vec![
+                ("T", "Hint: highlight the next tile to slide"),
+                ("S", "Solve with A*"),
+                ("M", "Cycle heuristic: Manhattan / Misplaced / Linear conflict / Pattern database"),
+                ("X / Esc", "Cancel a running solve"),
+                ("I", "Step mode: expand one A* node per Space / Enter"),
+                ("N", "Next equally short solution (once solved)"),
+                ("Space / Enter", "Step through solution"),
+                ("Backspace / P", "Step backward"),
+            ],
+        ),
+        PuzzleId::XorTicTacToe => (
+            vec![
+                ("↑ ↓ ← →", "Move cursor"),
+                ("Space / Enter", "Place mark"),
+                ("X / O", "Place X or O manually"),
+                ("1-9", "Quick place (number pad layout)"),
+                ("Tab", "Toggle setup mode"),
+                ("V", "Type a board in setup mode (e.g. X.O/.X./...)"),
+                ("H", "Shuffle board"),
+                ("R", "Reset game"),
+            ],
+            vec![
+                ("S", "Auto-move"),
+                ("L", "Cycle AI difficulty"),
+                ("F", "Swap sides (before your first move)"),
+                ("M", "Toggle two-player hotseat mode"),
+                ("T", "Run a 100-game AI tournament"),
+            ],
+        ),
+        PuzzleId::MissionariesCannibals => (
+            vec![
+                ("↑ ↓", "Navigate valid moves"),
+                ("1-9", "Apply move by number"),
+                ("Ctrl+Z", "Undo the last crossing"),
+                ("+ / -", "Change group size"),
+                ("< / >", "Change boat capacity"),
+                ("H", "Shuffle initial state"),
+                ("G", "Type a shuffle seed"),
+                ("R", "Reset to initial state"),
+            ],
+            vec![
+                ("S", "Solve with A*"),
+                ("I", "Step mode: expand one A* node per Space / Enter"),
+                ("V", "Write the A* search graph to search.dot"),
+                ("Space / Backspace", "Step forward / backward"),
+            ],
+        ),
+        PuzzleId::EightQueens => (
+            vec![
+                ("↑ ↓ ← →", "Move cursor"),
+                ("Enter", "Place / remove queen"),
+                ("H", "Shuffle"),
+                ("G", "Type a shuffle seed"),
+                ("R", "Reset board"),
+            ],
+            vec![
+                ("T", "Hint: highlight a safe square"),
+                ("X", "Show / hide attacked squares"),
+                ("S", "Solve with the selected algorithm"),
+                ("M", "Cycle A* / min-conflicts / uniform cost / greedy / IDA*"),
+                ("A", "Solve with simulated annealing"),
+                ("L", "Cycle A* node limit (10k/100k/1M/none)"),
+                ("N / P", "Next / previous solution"),
+                ("Space / Backspace", "Step forward / backward"),
+            ],
+        ),
+        PuzzleId::TowerOfHanoi => (
+            vec![
+                ("← →", "Select a peg"),
+                ("Enter", "Pick up / drop on selected peg"),
+                ("1 2 3", "Pick up / drop on a peg"),
+                ("+ / -", "Change the number of disks"),
+                ("R", "Reset board"),
+            ],
+            vec![
+                ("S", "Solve with A*"),
+                ("Space / Backspace", "Step forward / backward"),
+            ],
+        ),
+        PuzzleId::KnightsTour => (
+            vec![
+                ("↑ ↓ ← →", "Move cursor"),
+                ("Enter", "Jump there, or start a new tour"),
+                ("+ / -", "Change the board size (5×5 to 8×8)"),
+                ("R", "Restart from the first square"),
+            ],
+            vec![
+                ("S", "Complete the tour with A*"),
+                ("M", "Switch heuristic: Warnsdorff / squares left"),
+            ],
+        ),
+        PuzzleId::WaterJug => (
+            vec![
+                ("← →", "Select jug"),
+                ("F / X", "Fill / empty the selected jug"),
+                ("Enter", "Pour: source, then destination"),
+                ("1 2 3", "Load a preset"),
+                ("E", "Edit jugs and target"),
+                ("R", "Empty every jug"),
+            ],
+            vec![
+                ("S", "Solve with A*"),
+                ("Space / Backspace", "Step forward / backward"),
+            ],
+        ),
+        PuzzleId::LightsOut => (
+            vec![
+                ("↑ ↓ ← →", "Move cursor"),
+                ("Enter / Space", "Press the light"),
+                ("H", "New random board"),
+                ("+ / -", "Change board size"),
+                ("R", "Reset to starting pattern"),
+            ],
+            vec![("S", "Mark the fewest presses")],
+        ),
+        PuzzleId::Sokoban => (
+            vec![
+                ("↑ ↓ ← →", "Move, pushing a box ahead"),
+                ("1-5", "Pick a level"),
+                ("R", "Restart the level"),
+            ],
+            vec![
+                ("S", "Solve with A*"),
+                ("Space / Backspace", "Step forward / backward"),
+            ],
+        ),
+        PuzzleId::Sudoku => (
+            vec![
+                ("↑ ↓ ← →", "Move cursor"),
+                ("1-9", "Fill the cell"),
+                ("0 / Delete", "Clear the cell"),
+                ("N", "Next built-in puzzle"),
+                ("R", "Reset to the starting grid"),
+            ],
+            vec![
+                ("S", "Solve with propagation + A*"),
+                ("Space / Backspace", "Step forward / backward"),
+            ],
+        ),
+        PuzzleId::Maze => (
+            vec![
+                ("↑ ↓ ← →", "Walk through the maze"),
+                ("G", "Generate a new maze"),
+                ("+ / -", "Bigger / smaller maze"),
+                ("R", "Back to the start"),
+            ],
+            vec![
+                ("S", "Solve with A*"),
+                ("Space / Backspace", "Step forward / backward"),
+            ],
+        ),
+        PuzzleId::DailyChallenge | PuzzleId::About => (Vec::new(), Vec::new()),
+    };
+    let mut general = Vec::new();
+    if puzzle_id != PuzzleId::About {
+        general.push(("[ / ]", "Scroll back / forward through status messages"));
+        general.push(("Ctrl+K", "Clear the status log"));
+        general.push(("F2 / F3", "Save / load session"));
+        general.push(("Ctrl+S / Ctrl+L", "Save / load state in the data directory"));
+        if !matches!(puzzle_id, PuzzleId::WaterJug | PuzzleId::LightsOut | PuzzleId::XorTicTacToe) {
+            general.push(("E", "Export the solution as a text file"));
         }
-        
-        row_spans.push(Span::raw("│"));
-        lines.push(Line::from(row_spans));
     }
-    
-    // Bottom border
-    lines.push(Line::from("└───────────────────────────────┘"));
-    
-    lines
+    general.extend([
+        ("? / F1 / Esc", "Close this help"),
+        ("Ctrl+T", "Cycle color theme"),
+        ("Ctrl+A", "Toggle ASCII boards"),
+        ("Ctrl+W", "Toggle cursor wrap-around"),
+        ("B / Esc", "Back to menu"),
+        ("Q", "Quit"),
+    ]);
+    if !matches!(puzzle_id, PuzzleId::LightsOut | PuzzleId::XorTicTacToe | PuzzleId::About) {
+        solver.push(("C", "Toggle the algorithm comparison table"));
+    }
+    if !matches!(
+        puzzle_id,
+        PuzzleId::LightsOut | PuzzleId::XorTicTacToe | PuzzleId::KnightsTour | PuzzleId::DailyChallenge | PuzzleId::About
+    ) {
+        solver.push(("Ctrl+P", "Auto-play the solution; + / - change its speed"));
+    }
+    [("Board", board), ("Solver", solver), ("General", general)]
+        .into_iter()
+        .filter(|(_, bindings)| !bindings.is_empty())
+        .collect()
+}
+
+fn render_quit_confirm(frame: &mut Frame, prompt: &str, theme: &Theme) {
+    let area = centered_rect(21, 3, frame.size());
+    frame.render_widget(Clear, area);
+    let dialog = Paragraph::new(prompt)
+        .alignment(Alignment::Center)
+        .style(Style::default().add_modifier(Modifier::BOLD))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.accent(Color::Red))),
+        );
+    frame.render_widget(dialog, area);
+}
+
+/// Rectangle of at most `width` × `height` centred in `area`.
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+fn render_help(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let strings = app.strings();
+    let screen = match app.current_route() {
+        AppRoute::Puzzle(id) => app
+            .registry
+            .descriptor(id)
+            .map(|d| app.language.puzzle_name(d))
+            .unwrap_or(strings.puzzle),
+        AppRoute::Settings => strings.settings,
+        AppRoute::Statistics => strings.statistics,
+        AppRoute::MainMenu | AppRoute::Quit => strings.main_menu,
+    };
+    let sections = keybindings(app.current_route());
+    let key_width = sections
+        .iter()
+        .flat_map(|(_, bindings)| bindings.iter())
+        .map(|(key, _)| key.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut lines = vec![Line::from(Span::styled(
+        screen.to_string(),
+        Style::default().add_modifier(Modifier::BOLD),
+    ))];
+    for (category, bindings) in &sections {
+        lines.push(Line::from(""));
+        let category = match *category {
+            "Board" => strings.board,
+            "Solver" => strings.solver,
+            "General" => strings.general,
+            other => other,
+        };
+        lines.push(Line::from(Span::styled(
+            category.to_string(),
+            Style::default()
+                .fg(theme.header_fg)
+                .add_modifier(Modifier::UNDERLINED),
+        )));
+        for (key, description) in bindings {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{:<width$}  ", key, width = key_width),
+                    Style::default().fg(theme.accent(Color::Yellow)),
+                ),
+                Span::raw(description.to_string()),
+            ]));
+        }
+    }
+
+    // Lines plus the two borders; long lists are cut off by the frame height
+    let height = lines.len() as u16 + 2;
+    let area = centered_rect(60, height, frame.size());
+    frame.render_widget(Clear, area);
+    let popup = Paragraph::new(lines)
+        .block(titled_block(app, strings.help))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(popup, area);
 }
 
 fn render_about(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
     let descriptor = app.registry.descriptor(PuzzleId::About);
-    let title = descriptor.map(|d| d.name).unwrap_or("About This Program");
+    let title = descriptor.map(|d| app.language.puzzle_name(d)).unwrap_or("About This Program");
 
     let outer = Layout::default()
         .direction(Direction::Vertical)
@@ -1124,7 +4505,7 @@ fn render_about(frame: &mut Frame, app: &App) {
         .alignment(Alignment::Center)
         .style(
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.header_fg)
                 .add_modifier(Modifier::BOLD),
         )
         .block(Block::default().borders(Borders::ALL));
@@ -1142,11 +4523,18 @@ fn render_about(frame: &mut Frame, app: &App) {
     // Program Explanation
     let program_text = "AI Puzzle Suite (TUI)\n\n\
 This interactive terminal application demonstrates the A* (A-Star) search algorithm \
-through four classic AI puzzles:\n\n\
+through eleven classic AI puzzles:\n\n\
 • 8-Puzzle: Slide tiles to solve using Manhattan distance heuristic\n\
-• XOR Tic-Tac-Toe: Strategic game with A* hints\n\
+• XOR Tic-Tac-Toe: Completing a line of your own loses; negamax plays the AI\n\
 • Missionaries & Cannibals: River crossing puzzle\n\
-• 8 Queens: Constraint satisfaction problem\n\n\
+• 8 Queens: Constraint satisfaction problem\n\
+• Tower of Hanoi: Recursive disk-moving puzzle\n\
+• Knight's Tour: Warnsdorff-guided search over the chessboard\n\
+• Water Jug: Measure an exact amount with fills, pours and empties\n\
+• Lights Out: Switch every light off, solved by Gaussian elimination\n\
+• Sokoban: Push every box onto a goal square\n\
+• Sudoku: Constraint propagation with A* branching on the tightest cell\n\
+• Maze: weighted A* through a random maze with muddy cells\n\n\
 Each puzzle showcases how A* efficiently finds optimal solutions by exploring \
 the state space using the formula: f(n) = g(n) + h(n)\n\n\
 • g(n) = actual cost from start to current state\n\
@@ -1172,7 +4560,11 @@ The metrics shown:\n\
 • Expanded Nodes: States fully explored (all neighbors checked)\n\
 • Visited States: All states encountered (in queue + explored)\n\
 • Elapsed Time: How long the search took\n\n\
-You can step through the solution to see each move the algorithm found!";
+You can step through the solution to see each move the algorithm found!\n\n\
+Frame rate: the screen is only redrawn after input or while something moves \
+(a running solve, a move timer, a hint). --fps sets how often input is checked, \
+20 by default: higher feels smoother but wakes the CPU more often, lower saves \
+power at the cost of sluggish timers.";
     
     let happening_block = Paragraph::new(happening_text)
         .block(Block::default().title("How A* Works").borders(Borders::ALL))
@@ -1200,9 +4592,11 @@ Adel Enazi";
         .constraints([Constraint::Min(0), Constraint::Length(1)])
         .split(outer[2]);
     
-    let instructions = Paragraph::new(
-        "Controls: B back to menu • Q quit",
-    )
+    let action_names: Vec<&str> = Action::ALL.iter().map(|action| action.name()).collect();
+    let instructions = Paragraph::new(format!(
+        "Controls: B back to menu • Q quit • Remap keys in keybindings.toml: {}",
+        action_names.join(", ")
+    ))
     .alignment(Alignment::Center)
     .block(Block::default().borders(Borders::ALL))
     .wrap(Wrap { trim: true });
@@ -1210,6 +4604,35 @@ Adel Enazi";
     
     let footer = Paragraph::new("Adel Enazi")
         .alignment(Alignment::Center)
-        .style(Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM));
+        .style(theme.footer);
     frame.render_widget(footer, instructions_area[1]);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn letters_outside_filter_mode_jump_to_a_name_prefix() {
+        let mut app = App::default();
+        let mut menu_state = MenuState::default();
+        handle_main_menu_input(press(KeyCode::Char('L')), &mut app, &mut menu_state);
+        let visible = menu_state.visible(&app.registry.descriptors);
+        assert_eq!(visible[menu_state.selected].id, PuzzleId::LightsOut);
+        assert!(menu_state.filter.is_empty());
+
+        // No puzzle starts with Z, so the selection stays put
+        handle_main_menu_input(press(KeyCode::Char('z')), &mut app, &mut menu_state);
+        let visible = menu_state.visible(&app.registry.descriptors);
+        assert_eq!(visible[menu_state.selected].id, PuzzleId::LightsOut);
+
+        // In filter mode the same letters type into the filter instead
+        menu_state.filtering = true;
+        handle_main_menu_input(press(KeyCode::Char('k')), &mut app, &mut menu_state);
+        assert_eq!(menu_state.filter, "k");
+    }
+}